@@ -2,11 +2,11 @@
 
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
-use quote::quote;
+use quote::{quote, quote_spanned};
 use std::time::Duration;
 use syn::{
     FnArg, ItemFn, LitStr, Pat, PatType, Result, ReturnType, Token, Type, parse::Parse,
-    parse::ParseStream, parse_macro_input,
+    parse::ParseStream, parse_macro_input, spanned::Spanned,
 };
 
 /// Attribute arguments for the provider macro
@@ -15,7 +15,35 @@ struct ProviderArgs {
     interval: Option<Duration>,
     cache_expiration: Option<Duration>,
     stale_time: Option<Duration>,
-    compose: Vec<syn::Ident>, // List of provider functions to compose
+    min_refetch_interval: Option<Duration>,
+    schedule: Option<LitStr>, // Cron expression or "daily at HH:MM"
+    next_interval: Option<syn::ExprClosure>, // Computes the next refresh delay from data
+    cache_policy: Option<syn::Expr>, // Computes a per-entry cache policy from data
+    map_param: Option<syn::ExprClosure>, // Normalizes parameters before hashing and execution
+    validate: Option<syn::ExprClosure>, // Rejects parameters before execution
+    skip_if: Option<syn::ExprClosure>, // Resolves parameters to `skip_value` without running
+    skip_value: Option<syn::Expr>, // Value for skipped parameters (defaults to `Default`)
+    map_ok: Option<syn::ExprClosure>, // Transforms successful results before caching
+    map_err: Option<syn::ExprClosure>, // Transforms errors before caching
+    seed_details: Option<syn::Ident>, // Detail provider seeded with the output's items
+    keep_history: Option<usize>, // Number of recent parameterizations retained
+    history_grace: Option<(LitStr, Duration)>, // Staleness exemption after leaving a key
+    serves: Vec<LitStr>,      // Server entities whose changes invalidate the provider
+    normalize: bool,          // Write the output's entities into the normalized store
+    persist: bool,            // Save successful results to the runtime's cache storage
+    paginated: bool,          // Return `(items, next_cursor)` pages for `use_infinite_provider`
+    refetch_on_focus: bool,   // Revalidate when the app regains focus
+    refetch_on_reconnect: bool, // Refetch failed and stale data when the app comes back online
+    worker: bool,             // Run on a background worker thread instead of the UI thread
+    blocking: bool,           // Run a synchronous body on the blocking thread pool
+    compose: Vec<ComposedProvider>, // List of provider functions to compose
+    compose_policy: Option<(LitStr, ComposePolicy)>, // How composed failures are handled
+    cache_errors: Option<ErrorCaching>, // How long errors stay cached
+    cache_not_found: Option<Duration>, // How long not-found errors stay cached
+    not_found: Option<syn::ExprClosure>, // Classifies errors as not-found
+    retry: RetryArgs,         // Retries of failed fetches before the error is stored
+    timeout: Option<(LitStr, Duration)>, // Longest a run may take before it fails
+    timeout_error: Option<syn::ExprClosure>, // Builds the error of timed-out runs
 }
 
 /// Attribute arguments for the mutation macro
@@ -23,6 +51,98 @@ struct ProviderArgs {
 struct MutationArgs {
     invalidates: Vec<syn::Ident>, // List of provider functions to invalidate
     optimistic: Option<syn::ExprClosure>, // Optimistic closure applied to cached data
+    normalize: bool,              // Write the result's entities into the normalized store
+    journal: bool,                // Record inputs in the runtime's mutation journal
+    retry: RetryArgs,             // Retries of failed mutations before rolling back
+}
+
+/// The `retry`, `retry_delay` and `retry_backoff` arguments shared by both macros
+#[derive(Default)]
+struct RetryArgs {
+    retries: Option<u32>,              // Retries after the first attempt
+    delay: Option<(LitStr, Duration)>, // Delay before the first retry
+    backoff: Option<(LitStr, bool)>,   // Whether the delay doubles after every retry
+}
+
+impl RetryArgs {
+    /// Parse the value of a retry argument, returning false for other arguments
+    fn parse_value(&mut self, ident: &str, input: ParseStream) -> Result<bool> {
+        match ident {
+            "retry" => {
+                // Accept both `retry = 3` and `retry = "3"`
+                let retries = if input.peek(LitStr) {
+                    let lit: LitStr = input.parse()?;
+                    lit.value().trim().parse().map_err(|_| {
+                        syn::Error::new_spanned(&lit, "retry must be a number of retries")
+                    })?
+                } else {
+                    let lit: syn::LitInt = input.parse()?;
+                    lit.base10_parse()?
+                };
+                self.retries = Some(retries);
+            }
+            "retry_delay" => {
+                let lit: LitStr = input.parse()?;
+                let duration = humantime::parse_duration(&lit.value()).map_err(|e| {
+                    syn::Error::new_spanned(&lit, format!("Invalid duration format: {e}"))
+                })?;
+                self.delay = Some((lit, duration));
+            }
+            "retry_backoff" => {
+                let lit: LitStr = input.parse()?;
+                let exponential = match lit.value().as_str() {
+                    "fixed" => false,
+                    "exponential" => true,
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            &lit,
+                            "retry_backoff must be \"fixed\" or \"exponential\"",
+                        ));
+                    }
+                };
+                self.backoff = Some((lit, exponential));
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+}
+
+/// How a composed provider handles failures of the providers it composes
+#[derive(Clone, Copy, PartialEq)]
+enum ComposePolicy {
+    All,        // Fail if any composed provider fails, results are plain values
+    Any,        // Wait for the first success, results are `Option`s
+    BestEffort, // Wait for all, results are `Option`s that are `None` for failures
+}
+
+/// How long a provider's errors stay cached
+#[derive(Clone, Copy, PartialEq)]
+enum ErrorCaching {
+    Never,           // Errors aren't served to other consumers
+    Short(Duration), // Errors are cached for the given duration
+    Same,            // Errors are cached like successful results
+}
+
+/// A provider listed in `compose = [...]`
+struct ComposedProvider {
+    provider: syn::Ident,
+    // Arguments it is called with, or None to pass the outer provider's parameters
+    args: Option<syn::punctuated::Punctuated<syn::Expr, Token![,]>>,
+}
+
+impl Parse for ComposedProvider {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let provider: syn::Ident = input.parse()?;
+        let args = if input.peek(syn::token::Paren) {
+            let content;
+            syn::parenthesized!(content in input);
+            Some(content.parse_terminated(syn::Expr::parse, Token![,])?)
+        } else {
+            None
+        };
+        Ok(Self { provider, args })
+    }
 }
 
 impl Parse for ProviderArgs {
@@ -31,6 +151,31 @@ impl Parse for ProviderArgs {
 
         while !input.is_empty() {
             let ident: syn::Ident = input.parse()?;
+
+            // Flags, optionally followed by `= true` or `= false`
+            let flag = match ident.to_string().as_str() {
+                "normalize" => Some(&mut args.normalize),
+                "persist" => Some(&mut args.persist),
+                "paginated" => Some(&mut args.paginated),
+                "refetch_on_focus" => Some(&mut args.refetch_on_focus),
+                "refetch_on_reconnect" => Some(&mut args.refetch_on_reconnect),
+                "worker" => Some(&mut args.worker),
+                "blocking" => Some(&mut args.blocking),
+                _ => None,
+            };
+            if let Some(flag) = flag {
+                *flag = if input.peek(Token![=]) {
+                    input.parse::<Token![=]>()?;
+                    input.parse::<syn::LitBool>()?.value
+                } else {
+                    true
+                };
+                if input.peek(Token![,]) {
+                    input.parse::<Token![,]>()?;
+                }
+                continue;
+            }
+
             input.parse::<Token![=]>()?;
 
             match ident.to_string().as_str() {
@@ -58,12 +203,155 @@ impl Parse for ProviderArgs {
                     })?;
                     args.stale_time = Some(duration);
                 }
+                "min_refetch_interval" => {
+                    let lit: LitStr = input.parse()?;
+                    let duration_str = lit.value();
+                    let duration = humantime::parse_duration(&duration_str).map_err(|e| {
+                        syn::Error::new_spanned(lit, format!("Invalid duration format: {e}"))
+                    })?;
+                    args.min_refetch_interval = Some(duration);
+                }
+                "schedule" => {
+                    let lit: LitStr = input.parse()?;
+                    validate_schedule(&lit)?;
+                    args.schedule = Some(lit);
+                }
+                "next_interval" => {
+                    let expr: syn::ExprClosure = input.parse()?;
+                    args.next_interval = Some(expr);
+                }
+                "map_param" => {
+                    let expr: syn::ExprClosure = input.parse()?;
+                    args.map_param = Some(expr);
+                }
+                "validate" => {
+                    let expr: syn::ExprClosure = input.parse()?;
+                    args.validate = Some(expr);
+                }
+                "skip_if" => {
+                    let expr: syn::ExprClosure = input.parse()?;
+                    args.skip_if = Some(expr);
+                }
+                "skip_value" => {
+                    let expr: syn::Expr = input.parse()?;
+                    args.skip_value = Some(expr);
+                }
+                "map_ok" => {
+                    let expr: syn::ExprClosure = input.parse()?;
+                    args.map_ok = Some(expr);
+                }
+                "map_err" => {
+                    let expr: syn::ExprClosure = input.parse()?;
+                    args.map_err = Some(expr);
+                }
+                "seed_details" => {
+                    let ident: syn::Ident = input.parse()?;
+                    args.seed_details = Some(ident);
+                }
+                "keep_history" => {
+                    let lit: syn::LitInt = input.parse()?;
+                    args.keep_history = Some(lit.base10_parse()?);
+                }
+                "history_grace" => {
+                    let lit: LitStr = input.parse()?;
+                    let duration = humantime::parse_duration(&lit.value()).map_err(|e| {
+                        syn::Error::new_spanned(&lit, format!("Invalid duration format: {e}"))
+                    })?;
+                    args.history_grace = Some((lit, duration));
+                }
+                "compose_policy" => {
+                    let lit: LitStr = input.parse()?;
+                    let policy = match lit.value().as_str() {
+                        "all" => ComposePolicy::All,
+                        "any" => ComposePolicy::Any,
+                        "best_effort" => ComposePolicy::BestEffort,
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                &lit,
+                                "compose_policy must be \"all\", \"any\" or \"best_effort\"",
+                            ));
+                        }
+                    };
+                    args.compose_policy = Some((lit, policy));
+                }
+                "cache_not_found" => {
+                    let lit: LitStr = input.parse()?;
+                    let duration = humantime::parse_duration(&lit.value()).map_err(|e| {
+                        syn::Error::new_spanned(&lit, format!("Invalid duration format: {e}"))
+                    })?;
+                    args.cache_not_found = Some(duration);
+                }
+                "not_found" => {
+                    let expr: syn::ExprClosure = input.parse()?;
+                    args.not_found = Some(expr);
+                }
+                "timeout" => {
+                    let lit: LitStr = input.parse()?;
+                    let duration = humantime::parse_duration(&lit.value()).map_err(|e| {
+                        syn::Error::new_spanned(&lit, format!("Invalid duration format: {e}"))
+                    })?;
+                    args.timeout = Some((lit, duration));
+                }
+                "timeout_error" => {
+                    let expr: syn::ExprClosure = input.parse()?;
+                    args.timeout_error = Some(expr);
+                }
+                name if args.retry.parse_value(name, input)? => {}
+                "cache_errors" => {
+                    let lit: LitStr = input.parse()?;
+                    let value = lit.value();
+                    let caching = match value.as_str() {
+                        "never" => ErrorCaching::Never,
+                        "same" => ErrorCaching::Same,
+                        _ => match value.strip_prefix("short:") {
+                            Some(duration_str) => {
+                                let duration = humantime::parse_duration(duration_str.trim())
+                                    .map_err(|e| {
+                                        syn::Error::new_spanned(
+                                            &lit,
+                                            format!("Invalid duration format: {e}"),
+                                        )
+                                    })?;
+                                ErrorCaching::Short(duration)
+                            }
+                            None => {
+                                return Err(syn::Error::new_spanned(
+                                    &lit,
+                                    "cache_errors must be \"never\", \"same\" or \"short:<duration>\"",
+                                ));
+                            }
+                        },
+                    };
+                    args.cache_errors = Some(caching);
+                }
+                "cache_policy" => {
+                    let expr: syn::Expr = input.parse()?;
+                    args.cache_policy = Some(expr);
+                }
+                "serves" => {
+                    // Parse entity list: serves = ["user", "org", ...]
+                    let content;
+                    syn::bracketed!(content in input);
+                    let entities = content.parse_terminated(<LitStr as Parse>::parse, Token![,])?;
+                    args.serves = entities.into_iter().collect();
+                }
                 "compose" => {
-                    // Parse compose list: compose = [provider1, provider2, ...]
+                    // Parse compose list: compose = [provider1, provider2(arg), ...]
                     let content;
                     syn::bracketed!(content in input);
-                    let providers = content.parse_terminated(syn::Ident::parse, Token![,])?;
+                    let providers = content.parse_terminated(ComposedProvider::parse, Token![,])?;
                     args.compose = providers.into_iter().collect();
+                    for (index, composed) in args.compose.iter().enumerate() {
+                        if args.compose[..index]
+                            .iter()
+                            .any(|earlier| earlier.provider == composed.provider)
+                        {
+                            return Err(syn::Error::new_spanned(
+                                &composed.provider,
+                                "a provider can only be composed once",
+                            ));
+                        }
+                    }
                 }
                 _ => return Err(syn::Error::new_spanned(ident, "Unknown argument")),
             }
@@ -83,6 +371,20 @@ impl Parse for MutationArgs {
 
         while !input.is_empty() {
             let ident: syn::Ident = input.parse()?;
+
+            // Flags without a value
+            if ident == "normalize" || ident == "journal" {
+                if ident == "normalize" {
+                    args.normalize = true;
+                } else {
+                    args.journal = true;
+                }
+                if input.peek(Token![,]) {
+                    input.parse::<Token![,]>()?;
+                }
+                continue;
+            }
+
             input.parse::<Token![=]>()?;
 
             match ident.to_string().as_str() {
@@ -97,6 +399,7 @@ impl Parse for MutationArgs {
                     let expr: syn::ExprClosure = input.parse()?;
                     args.optimistic = Some(expr);
                 }
+                name if args.retry.parse_value(name, input)? => {}
                 _ => return Err(syn::Error::new_spanned(ident, "Unknown argument")),
             }
 
@@ -118,7 +421,105 @@ impl Parse for MutationArgs {
 /// - `interval = "30s"` - Background refresh interval
 /// - `cache_expiration = "5min"` - Cache expiration time  
 /// - `stale_time = "1min"` - Time before data is considered stale
-/// - `compose = [provider1, provider2, ...]` - Compose multiple providers in parallel
+/// - `min_refetch_interval = "2s"` - Minimum time between refetches; invalidations arriving
+///   sooner coalesce into one trailing refetch
+/// - `refetch_on_focus` - Revalidate the data in the background when the app regains focus:
+///   stale data with a `stale_time`, and on every focus without one
+/// - `refetch_on_reconnect` - Refetch failed and stale data when the app comes back online
+///   (see `platform::network`)
+/// - `cache_policy = |data| ...` - Per-entry stale time / expiration computed from data
+///   (applied automatically when the output type is `HttpCached<T>` or `WithTtl<T>`)
+/// - `next_interval = |data| ...` - Compute the next refresh delay from fetched data
+/// - `map_param = |raw: RawQuery| ...` - Normalize parameters before they are hashed and the
+///   provider runs, so equivalent inputs share a cache entry. The closure receives the
+///   provider's parameter (a tuple for several parameters) and returns the function's
+///   parameters; annotate its input to accept a different raw type
+/// - `validate = |param| ...` - Reject parameters before the provider runs. The closure
+///   receives a reference to the provider's parameter (a tuple for several parameters) and
+///   returns `Result<(), E>` where `E` converts into the provider's error; rejected
+///   parameters put consumers into `State::Error` without fetching
+/// - `skip_if = |query| query.is_empty()` - Resolve parameters to `skip_value` without running
+///   the provider or caching anything, e.g. for an empty search box. The closure receives a
+///   reference to the provider's parameter
+/// - `skip_value = Vec::new()` - The success value for skipped parameters (defaults to
+///   `Default::default()`)
+/// - `map_ok = |envelope: Envelope<User>| -> User { envelope.data }` - Transform successful
+///   results before they are cached. Annotate the closure's return type when it differs from
+///   the function's, which makes it the provider's `Output`
+/// - `map_err = |error: reqwest::Error| -> AppError { error.into() }` - Transform errors
+///   before they are cached, likewise changing the provider's `Error` when annotated
+/// - `seed_details = fetch_item` - Write each item of the output into the cache of the detail
+///   provider `fetch_item`, keyed by the item's `Entity::entity_id`, so detail screens render
+///   instantly and revalidate in the background
+/// - `keep_history = 5` - Keep the last 5 parameterizations fully cached, so navigating back
+///   to one is instant; keys navigated away from aren't refetched for being stale during the
+///   grace period
+/// - `history_grace = "2m"` - The grace period of `keep_history` (defaults to 5 minutes)
+/// - `cache_errors = "short:10s"` - How long errors stay cached: `"never"` (consumers that
+///   mount later refetch), `"short:<duration>"` or `"same"` as successful results (default)
+/// - `cache_not_found = "10m"` - Cache not-found errors for 10 minutes, so lookups of ids
+///   that don't exist don't hit the backend on every use
+/// - `not_found = |error| matches!(error, MyError::Missing(_))` - Classify errors as
+///   not-found (defaults to the not-found variants of the `errors` module)
+/// - `retry = 3` - Retry failed fetches up to 3 times before storing the error
+///   (not-found errors are never retried)
+/// - `retry_delay = "500ms"` - Delay before the first retry (defaults to 1 second)
+/// - `retry_backoff = "fixed" | "exponential"` - Keep the retry delay or double it after
+///   every retry (defaults to `"fixed"`)
+/// - `timeout = "10s"` - Fail runs that take longer than 10 seconds with a timeout error
+///   instead of loading forever (see the `timeout` module)
+/// - `timeout_error = |timeout| MyError::TimedOut(timeout)` - The error of timed-out runs,
+///   built from the timeout's `Duration`; required for error types other than `String` and
+///   those of the `errors` module
+/// - `serves = ["user", "org"]` - The server entities the provider's data comes from, so
+///   `ProviderRuntime::invalidate_entity("user", Some(&id))` refetches it (see the `served` module)
+/// - `schedule = "0 */15 * * * *"` - Refresh at wall-clock times (cron or `"daily at HH:MM"`, UTC)
+/// - `normalize` - Write the output's entities into the normalized entity store
+///   (the output type must implement `entity::Normalize`)
+/// - `persist` - Save successful results to the runtime's cache storage and restore them on
+///   the next start (the output type must implement `Serialize` and `Deserialize`)
+/// - `paginated` - The function takes `(param, cursor: Option<C>)` and returns
+///   `(Vec<T>, Option<C>)`, the items and the next page's cursor; the provider outputs an
+///   `infinite::Page<T, C>` for `use_infinite_provider`
+/// - `worker` - Run the provider on a background worker thread so CPU-heavy work doesn't
+///   stall the UI (native only, runs inline on WASM; the function's future must be `Send`)
+/// - `blocking` - Run the body of a synchronous `fn` (file system, rusqlite, ...) on the
+///   blocking thread pool so it doesn't block the async executor (native only, runs inline
+///   with a warning on WASM)
+/// - `compose = [provider1, provider2(arg), ...]` - Compose multiple providers in parallel,
+///   each called with the outer provider's parameters or with the listed arguments
+/// - `compose_policy = "all" | "any" | "best_effort"` - How failures of composed providers
+///   are handled (see Failure Policies below)
+///
+/// # Generated Items
+/// For `async fn fetch_user(...)` the macro generates:
+/// - `struct FetchUser`, the provider, with `FetchUser::key(...)` building its query key
+/// - `fn fetch_user() -> FetchUser`, returning the provider
+/// - `const FETCH_USER: FetchUser`, for match arms and invalidation maps
+/// - `FetchUser::call(...)`, running the function body directly, without caching
+/// - `impl Provider<P> for FetchUser`, where `P` is `()`, the parameter type, or a tuple of
+///   the parameter types, in order
+///
+/// These items are the macro's stable API and only change in a major release. The
+/// expansion is pinned by snapshot tests in this crate. Anything else in it, such as items
+/// starting with `__` other than the documented composition results, is an implementation
+/// detail.
+///
+/// The output and error types must be `Clone + PartialEq + Send + Sync`. A type missing one
+/// of these bounds is reported at the type in the signature, naming the missing bound.
+///
+/// # Reference Parameters
+/// Parameters may be references such as `&str`, `&[T]` or `&T`. The provider then takes the
+/// owned type (`String`, `Vec<T>`, `T`) and the body still sees the reference:
+///
+/// ```rust,ignore
+/// #[provider]
+/// async fn search(query: &str) -> Result<Vec<Hit>, String> {
+///     api::search(query).await
+/// }
+///
+/// let hits = use_provider(search(), "dioxus".to_string());
+/// ```
 ///
 /// # Composition Requirements
 /// When using `compose = [...]`, the following requirements must be met:
@@ -127,7 +528,7 @@ impl Parse for MutationArgs {
 /// **All function parameters MUST implement `Clone`** when using composition.
 /// Parameters are cloned inside async blocks to enable parallel execution.
 ///
-/// ```rust
+/// ```rust,ignore
 /// // ✅ Good - u32 implements Clone
 /// #[provider(compose = [fetch_permissions])]
 /// async fn fetch_user_profile(user_id: u32) -> Result<Profile, Error> {
@@ -154,13 +555,48 @@ impl Parse for MutationArgs {
 /// All providers listed in `compose = [...]` must:
 /// - Be valid Rust identifiers
 /// - Exist in the current scope when the macro is expanded
-/// - Have compatible signatures (same parameter types)
+/// - Accept the outer provider's parameters, unless called with their own arguments
 ///
 /// The macro generates compile-time calls to verify provider existence and
 /// provides clear error messages if providers are not found.
 ///
+/// ## Parameter Mapping
+/// A composed provider listed with arguments, such as `fetch_org(org_id)`, is called with
+/// those expressions instead of the outer parameters. The arguments may use the outer
+/// parameters and are cloned, so composed providers can take any subset of them, in any
+/// order, or none at all with `fetch_defaults()`.
+///
+/// ```rust,ignore
+/// #[provider(compose = [fetch_permissions(user_id), fetch_org(org_id)])]
+/// async fn fetch_membership(user_id: u32, org_id: String) -> Result<Membership, String> {
+///     let permissions = __dioxus_composed_fetch_permissions_result?;
+///     let org = __dioxus_composed_fetch_org_result?;
+///     Ok(Membership { permissions, org })
+/// }
+/// ```
+///
+/// ## Failure Policies
+/// Without `compose_policy`, composed results are `Result`s for the body to handle. With it,
+/// the macro handles failures and the variables hold:
+/// - `"all"` - the values, with the composed provider failing on the first error
+/// - `"best_effort"` - `Option`s, `None` for providers that failed
+/// - `"any"` - `Option`s, once the first provider succeeds; the others are dropped, and
+///   the composed provider fails with the last error if none succeeds
+///
+/// Errors are converted into the outer error type with `From`.
+///
+/// ```rust,ignore
+/// #[provider(compose = [fetch_avatar, fetch_badges], compose_policy = "best_effort")]
+/// async fn fetch_sidebar(user_id: u32) -> Result<Sidebar, String> {
+///     Ok(Sidebar {
+///         avatar: __dioxus_composed_fetch_avatar_result,
+///         badges: __dioxus_composed_fetch_badges_result.unwrap_or_default(),
+///     })
+/// }
+/// ```
+///
 /// # Examples
-/// ```rust
+/// ```rust,ignore
 /// #[provider(cache_expiration = "5min")]
 /// async fn fetch_user(id: u32) -> Result<User, String> {
 ///     // Implementation
@@ -203,6 +639,47 @@ pub fn provider(args: TokenStream, input: TokenStream) -> TokenStream {
     }
 }
 
+/// Stream provider macro for live data such as WebSockets, SSE or live queries
+///
+/// Turns a function returning `impl Stream<Item = Result<T, E>>`, optionally `async`, into
+/// a `StreamProvider` for `use_stream_provider`, which updates its state on every yielded
+/// item. Parameters must be owned types, since the stream outlives the call.
+///
+/// # Generated Items
+/// For `fn live_prices(...)` the macro generates `struct LivePrices`,
+/// `fn live_prices() -> LivePrices`, `const LIVE_PRICES`, `LivePrices::key(...)`,
+/// `LivePrices::call(...)` returning the function's stream, and
+/// `impl StreamProvider<P> for LivePrices` with `P` as for `#[provider]`.
+///
+/// # Example
+/// ```rust,ignore
+/// use dioxus_provider::{prelude::*, stream::Stream};
+///
+/// #[stream_provider]
+/// async fn live_prices(symbol: String) -> impl Stream<Item = Result<f64, String>> {
+///     let socket = api::subscribe(&symbol).await;
+///     socket.prices()
+/// }
+///
+/// let price = use_stream_provider(live_prices(), "ACME".to_string());
+/// ```
+#[proc_macro_attribute]
+pub fn stream_provider(args: TokenStream, input: TokenStream) -> TokenStream {
+    if !args.is_empty() {
+        let args = TokenStream2::from(args);
+        return syn::Error::new_spanned(args, "stream_provider takes no arguments")
+            .to_compile_error()
+            .into();
+    }
+
+    let input_fn = parse_macro_input!(input as ItemFn);
+
+    match generate_stream_provider(input_fn) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
 /// Mutation macro for creating data mutations with cache invalidation
 ///
 /// This macro converts an async function into a Mutation implementation that can
@@ -211,6 +688,12 @@ pub fn provider(args: TokenStream, input: TokenStream) -> TokenStream {
 /// # Supported Arguments
 /// - `invalidates = [provider1, provider2, ...]` - Providers to invalidate after mutation
 /// - `optimistic = |data, ...args| { ... }` - Optimistic update closure (requires MutationContext)
+/// - `normalize` - Write the returned entities into the normalized entity store
+/// - `journal` - Record every input, serialized with serde, in the runtime's mutation journal
+///   until the mutation succeeds (see `ProviderConfig::with_mutation_journal`)
+/// - `retry = 3`, `retry_delay = "500ms"`, `retry_backoff = "exponential"` - Retry a failed
+///   mutation before its error is reported and optimistic updates are rolled back, as for
+///   `#[provider]`
 ///
 /// ## Optimistic Updates
 /// The optimistic closure receives:
@@ -228,8 +711,17 @@ pub fn provider(args: TokenStream, input: TokenStream) -> TokenStream {
 /// - With optimistic updates: replace cache with server response (avoids refetch)
 /// - Without optimistic: cache is invalidated and providers refetch automatically
 ///
+/// Mutations returning `Result<(), E>`, such as deletes and archives whose endpoints return
+/// nothing, only invalidate: they need no payload type, never read the cache, and reject
+/// `optimistic` and `MutationContext`, which need data to apply.
+///
+/// # Generated Items
+/// For `async fn update_user(...)` the macro generates the same stable items as
+/// `#[provider]`: `struct UpdateUser`, `fn update_user()`, `const UPDATE_USER`,
+/// `UpdateUser::call(...)` and `impl Mutation<I> for UpdateUser`.
+///
 /// # Examples
-/// ```rust
+/// ```rust,ignore
 /// // Simple mutation with cache invalidation
 /// #[mutation(invalidates = [fetch_user, fetch_user_list])]
 /// async fn update_user(user: User) -> Result<User, String> {
@@ -237,6 +729,12 @@ pub fn provider(args: TokenStream, input: TokenStream) -> TokenStream {
 ///     // Will automatically invalidate fetch_user and fetch_user_list caches
 /// }
 ///
+/// // Invalidation-only mutation returning nothing
+/// #[mutation(invalidates = [load_items])]
+/// async fn archive_item(id: u64) -> Result<(), Error> {
+///     api::archive(id).await
+/// }
+///
 /// // Optimistic mutation with single argument
 /// #[mutation(
 ///     invalidates = [load_items],
@@ -303,39 +801,172 @@ fn generate_provider(input_fn: ItemFn, provider_args: ProviderArgs) -> Result<To
         output_type,
         error_type,
         struct_name,
+        sig_span,
+        return_span,
         ..
     } = &info;
 
-    // Extract parameters once
-    let params = extract_all_params(&input_fn)?;
+    // Extract parameters once, with reference parameters turned into owned ones
+    let mut params = extract_all_params(&input_fn)?;
+    let reborrows = own_reference_params(&mut params)?;
 
     // Validate composition requirements if compose is used
+    if let Some((lit, _)) = &provider_args.compose_policy
+        && provider_args.compose.is_empty()
+    {
+        return Err(syn::Error::new_spanned(
+            lit,
+            "compose_policy requires compose",
+        ));
+    }
     if !provider_args.compose.is_empty() {
-        validate_composition_requirements(&provider_args.compose, &params)?;
+        let providers: Vec<_> = provider_args
+            .compose
+            .iter()
+            .map(|composed| composed.provider.clone())
+            .collect();
+        validate_composition_requirements(&providers, &params)?;
     }
 
+    // The body sees reference parameters as the references it was written against
+    let reborrowed_fn_block: syn::Block;
+    let fn_block = if reborrows.is_empty() {
+        fn_block
+    } else {
+        reborrowed_fn_block = syn::parse_quote! {{
+            #(#reborrows)*
+            #fn_block
+        }};
+        &reborrowed_fn_block
+    };
+
+    // Synchronous bodies run on the blocking pool, after any composed providers resolved
+    let blocking_fn_block: syn::Block;
+    let fn_block = if provider_args.blocking {
+        blocking_fn_block = syn::parse_quote! {{
+            ::dioxus_provider::platform::run_blocking(move || #fn_block).await
+        }};
+        &blocking_fn_block
+    } else {
+        fn_block
+    };
+
     // Generate enhanced function body with dependency injection and composition
     let enhanced_fn_block =
-        generate_enhanced_function_body(&provider_args.compose, &params, fn_block);
+        generate_enhanced_function_body(&provider_args, &params, error_type, fn_block);
+
+    // Map the function's result into the provider's output and error types
+    let (output_type, error_type, enhanced_fn_block) = generate_result_mapping(
+        &provider_args,
+        output_type,
+        error_type,
+        *return_span,
+        enhanced_fn_block,
+    )?;
+    if provider_args.paginated && params.len() != 2 {
+        return Err(syn::Error::new(
+            *sig_span,
+            "paginated providers take a parameter and a cursor: `(param, cursor: Option<C>)`",
+        ));
+    }
+    let (output_type, error_type) = (&output_type, &error_type);
+
+    // Errors about the result, or about the future not being `Send`, point at the signature
+    let call_output = quote_spanned! {*return_span=> Result<#output_type, #error_type> };
+    let send_future = generate_send_future(*sig_span);
 
     // Generate interval and cache expiration implementations
     let interval_impl = generate_interval_impl(&provider_args);
     let cache_expiration_impl = generate_cache_expiration_impl(&provider_args);
     let stale_time_impl = generate_stale_time_impl(&provider_args);
+    let min_refetch_interval_impl = generate_min_refetch_interval_impl(&provider_args);
+    let schedule_impl = generate_schedule_impl(&provider_args);
+    let next_interval_impl = generate_next_interval_impl(&provider_args);
+    let cache_policy_impl = generate_cache_policy_impl(&provider_args, output_type);
+    let normalize_impl = generate_normalize_impl(provider_args.normalize);
+    let persist_impl = generate_persist_impl(provider_args.persist);
+    let refetch_on_focus_impl = generate_refetch_on_focus_impl(provider_args.refetch_on_focus);
+    let refetch_on_reconnect_impl =
+        generate_refetch_on_reconnect_impl(provider_args.refetch_on_reconnect);
+    let seed_details_impl = generate_seed_details_impl(&provider_args);
+    let history_impl = generate_history_impl(&provider_args)?;
+    let serves_impl = generate_serves_impl(&provider_args);
+    let cache_errors_impl = generate_cache_errors_impl(&provider_args);
+    let not_found_impl = generate_not_found_impl(&provider_args);
+    let retry_impl = generate_retry_impl(&provider_args.retry)?;
+    let timeout_impl = generate_timeout_impl(&provider_args, error_type)?;
 
     // Generate common struct and const
     let common_struct = generate_common_struct_and_const(&info);
+    let bounds_checks = generate_bounds_checks(output_type, error_type);
+    let key_name = info.fn_name.to_string();
+
+    // The type callers pass, and how it is turned into the function's parameters
+    let function_param_type = match params.as_slice() {
+        [] => quote! { () },
+        [param] => {
+            let ty = &param.ty;
+            quote! { #ty }
+        }
+        params => {
+            let types = params.iter().map(|p| &p.ty);
+            quote! { (#(#types,)*) }
+        }
+    };
+    let (raw_param_type, map_param_fn) = match &provider_args.map_param {
+        Some(closure) => {
+            if params.is_empty() {
+                return Err(syn::Error::new_spanned(
+                    closure,
+                    "map_param requires a provider with parameters",
+                ));
+            }
+            let raw_type = match closure.inputs.iter().collect::<Vec<_>>().as_slice() {
+                [Pat::Type(pat_type)] => {
+                    let ty = &pat_type.ty;
+                    quote! { #ty }
+                }
+                [_] => function_param_type.clone(),
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        closure,
+                        "map_param takes exactly one argument",
+                    ));
+                }
+            };
+            let map_param_fn = quote! {
+                fn map_param(raw: #raw_type) -> #function_param_type {
+                    (#closure)(raw)
+                }
+            };
+            (raw_type, map_param_fn)
+        }
+        None => (function_param_type, quote! {}),
+    };
+    let has_map_param = provider_args.map_param.is_some();
+    let validate_impl = generate_validate_impl(&provider_args, &raw_param_type, params.is_empty())?;
+    let skip_impl = generate_skip_impl(&provider_args, &raw_param_type, params.is_empty())?;
 
     // Determine parameter type and implementation based on function parameters
     if params.is_empty() {
         // No parameters - Provider<()>
+        let run_call = generate_run_call(
+            quote_spanned! {*sig_span=> Self::call() },
+            provider_args.worker,
+        );
         Ok(quote! {
             #common_struct
+            #bounds_checks
 
             impl #struct_name {
-                #fn_vis async fn call() -> Result<#output_type, #error_type> {
+                #fn_vis async fn call() -> #call_output {
                     #enhanced_fn_block
                 }
+
+                /// The provider's structured cache key
+                pub fn key() -> ::dioxus_provider::query_key::QueryKey {
+                    ::dioxus_provider::query_key::QueryKey::of(#key_name)
+                }
             }
 
             impl ::dioxus_provider::hooks::Provider<()> for #struct_name {
@@ -343,17 +974,39 @@ fn generate_provider(input_fn: ItemFn, provider_args: ProviderArgs) -> Result<To
                 type Error = #error_type;
 
                 #[cfg(not(target_family = "wasm"))]
-                fn run(&self, _param: ()) -> impl ::std::future::Future<Output = Result<Self::Output, Self::Error>> + ::std::marker::Send {
-                    Self::call()
+                #[allow(refining_impl_trait)]
+                fn run(&self, _param: ()) -> #send_future {
+                    #run_call
                 }
                 #[cfg(target_family = "wasm")]
                 fn run(&self, _param: ()) -> impl ::std::future::Future<Output = Result<Self::Output, Self::Error>> {
-                    Self::call()
+                    #run_call
+                }
+
+                fn query_key(&self, _param: &()) -> Option<::dioxus_provider::query_key::QueryKey> {
+                    Some(Self::key())
                 }
 
                 #interval_impl
                 #cache_expiration_impl
                 #stale_time_impl
+                #min_refetch_interval_impl
+                #schedule_impl
+                #next_interval_impl
+                #cache_policy_impl
+                #normalize_impl
+                #persist_impl
+                #refetch_on_focus_impl
+                #refetch_on_reconnect_impl
+                #validate_impl
+                #skip_impl
+                #seed_details_impl
+                #history_impl
+                #serves_impl
+                #cache_errors_impl
+                #not_found_impl
+                #retry_impl
+                #timeout_impl
             }
         })
     } else if params.len() == 1 {
@@ -361,72 +1014,272 @@ fn generate_provider(input_fn: ItemFn, provider_args: ProviderArgs) -> Result<To
         let param = &params[0];
         let param_name = &param.name;
         let param_type = &param.ty;
+        let run_call = generate_run_call(
+            quote_spanned! {*sig_span=> Self::call(#param_name) },
+            provider_args.worker,
+        );
+        let (map_param, map_param_ref) = if has_map_param {
+            (
+                quote! { let #param_name = Self::map_param(#param_name); },
+                quote! { let #param_name = &Self::map_param(#param_name.clone()); },
+            )
+        } else {
+            (quote! {}, quote! {})
+        };
 
         Ok(quote! {
             #common_struct
+            #bounds_checks
 
             impl #struct_name {
-                #fn_vis async fn call(#param_name: #param_type) -> Result<#output_type, #error_type> {
+                #fn_vis async fn call(#param_name: #param_type) -> #call_output {
                     #enhanced_fn_block
                 }
+
+                /// The provider's structured cache key for the given parameter
+                pub fn key(#param_name: #param_type) -> ::dioxus_provider::query_key::QueryKey {
+                    ::dioxus_provider::query_key::QueryKey::of(#key_name).with_param(&#param_name)
+                }
+
+                #map_param_fn
             }
 
-            impl ::dioxus_provider::hooks::Provider<#param_type> for #struct_name {
+            impl ::dioxus_provider::hooks::Provider<#raw_param_type> for #struct_name {
                 type Output = #output_type;
                 type Error = #error_type;
 
                 #[cfg(not(target_family = "wasm"))]
-                fn run(&self, #param_name: #param_type) -> impl ::std::future::Future<Output = Result<Self::Output, Self::Error>> + ::std::marker::Send {
-                    Self::call(#param_name)
+                #[allow(refining_impl_trait)]
+                fn run(&self, #param_name: #raw_param_type) -> #send_future {
+                    #map_param
+                    #run_call
                 }
                 #[cfg(target_family = "wasm")]
-                fn run(&self, #param_name: #param_type) -> impl ::std::future::Future<Output = Result<Self::Output, Self::Error>> {
-                    Self::call(#param_name)
+                fn run(&self, #param_name: #raw_param_type) -> impl ::std::future::Future<Output = Result<Self::Output, Self::Error>> {
+                    #map_param
+                    #run_call
+                }
+
+                fn query_key(&self, #param_name: &#raw_param_type) -> Option<::dioxus_provider::query_key::QueryKey> {
+                    #map_param_ref
+                    Some(Self::key(#param_name.clone()))
                 }
 
                 #interval_impl
                 #cache_expiration_impl
                 #stale_time_impl
+                #min_refetch_interval_impl
+                #schedule_impl
+                #next_interval_impl
+                #cache_policy_impl
+                #normalize_impl
+                #persist_impl
+                #refetch_on_focus_impl
+                #refetch_on_reconnect_impl
+                #validate_impl
+                #skip_impl
+                #seed_details_impl
+                #history_impl
+                #serves_impl
+                #cache_errors_impl
+                #not_found_impl
+                #retry_impl
+                #timeout_impl
             }
         })
     } else {
         // Multiple parameters - Provider<(Param1, Param2, ...)>
         let param_names: Vec<_> = params.iter().map(|p| &p.name).collect();
         let param_types: Vec<_> = params.iter().map(|p| &p.ty).collect();
-        let tuple_type = quote! { (#(#param_types,)*) };
+        let run_call = generate_run_call(
+            quote_spanned! {*sig_span=> Self::call(#(#param_names,)*) },
+            provider_args.worker,
+        );
+        let map_param = if has_map_param {
+            quote! { let params = Self::map_param(params); }
+        } else {
+            quote! {}
+        };
+        let map_param_ref = if has_map_param {
+            quote! { let params = &Self::map_param(params.clone()); }
+        } else {
+            quote! {}
+        };
 
         Ok(quote! {
             #common_struct
+            #bounds_checks
 
             impl #struct_name {
-                #fn_vis async fn call(#(#param_names: #param_types,)*) -> Result<#output_type, #error_type> {
+                #fn_vis async fn call(#(#param_names: #param_types,)*) -> #call_output {
                     #enhanced_fn_block
                 }
+
+                /// The provider's structured cache key for the given parameters
+                pub fn key(#(#param_names: #param_types,)*) -> ::dioxus_provider::query_key::QueryKey {
+                    ::dioxus_provider::query_key::QueryKey::of(#key_name)
+                        #(.with_param(&#param_names))*
+                }
+
+                #map_param_fn
             }
 
-            impl ::dioxus_provider::hooks::Provider<#tuple_type> for #struct_name {
+            impl ::dioxus_provider::hooks::Provider<#raw_param_type> for #struct_name {
                 type Output = #output_type;
                 type Error = #error_type;
 
                 #[cfg(not(target_family = "wasm"))]
-                fn run(&self, params: #tuple_type) -> impl ::std::future::Future<Output = Result<Self::Output, Self::Error>> + ::std::marker::Send {
+                #[allow(refining_impl_trait)]
+                fn run(&self, params: #raw_param_type) -> #send_future {
+                    #map_param
                     let (#(#param_names,)*) = params;
-                    Self::call(#(#param_names,)*)
+                    #run_call
                 }
                 #[cfg(target_family = "wasm")]
-                fn run(&self, params: #tuple_type) -> impl ::std::future::Future<Output = Result<Self::Output, Self::Error>> {
+                fn run(&self, params: #raw_param_type) -> impl ::std::future::Future<Output = Result<Self::Output, Self::Error>> {
+                    #map_param
                     let (#(#param_names,)*) = params;
-                    Self::call(#(#param_names,)*)
+                    #run_call
+                }
+
+                fn query_key(&self, params: &#raw_param_type) -> Option<::dioxus_provider::query_key::QueryKey> {
+                    #map_param_ref
+                    let (#(#param_names,)*) = params.clone();
+                    Some(Self::key(#(#param_names,)*))
                 }
 
                 #interval_impl
                 #cache_expiration_impl
                 #stale_time_impl
+                #min_refetch_interval_impl
+                #schedule_impl
+                #next_interval_impl
+                #cache_policy_impl
+                #normalize_impl
+                #persist_impl
+                #refetch_on_focus_impl
+                #refetch_on_reconnect_impl
+                #validate_impl
+                #skip_impl
+                #seed_details_impl
+                #history_impl
+                #serves_impl
+                #cache_errors_impl
+                #not_found_impl
+                #retry_impl
+                #timeout_impl
             }
         })
     }
 }
 
+fn generate_stream_provider(input_fn: ItemFn) -> Result<TokenStream2> {
+    let (output_type, error_type) = extract_stream_item_types(&input_fn.sig.output)?;
+    let sig_span = input_fn.sig.span();
+    let fn_name = input_fn.sig.ident.clone();
+    let struct_name = syn::Ident::new(
+        &to_pascal_case(&fn_name.to_string()),
+        proc_macro2::Span::call_site(),
+    );
+    let info = ProviderInfo {
+        fn_vis: input_fn.vis.clone(),
+        fn_attrs: input_fn.attrs.clone(),
+        fn_block: input_fn.block.clone(),
+        output_type,
+        error_type,
+        struct_name,
+        fn_name,
+        sig_span,
+        return_span: sig_span,
+    };
+    let ProviderInfo {
+        fn_vis,
+        fn_block,
+        output_type,
+        error_type,
+        struct_name,
+        ..
+    } = &info;
+
+    let params = extract_all_params(&input_fn)?;
+    if let Some(param) = params
+        .iter()
+        .find(|param| matches!(param.ty, Type::Reference(_)))
+    {
+        return Err(syn::Error::new_spanned(
+            &param.ty,
+            "stream providers take owned parameters, since the stream outlives the call",
+        ));
+    }
+
+    let common_struct = generate_common_struct_and_const(&info);
+    let bounds_checks = generate_bounds_checks(output_type, error_type);
+    let key_name = info.fn_name.to_string();
+    let asyncness = &input_fn.sig.asyncness;
+    let return_type = &input_fn.sig.output;
+    let param_names: Vec<_> = params.iter().map(|p| &p.name).collect();
+    let param_types: Vec<_> = params.iter().map(|p| &p.ty).collect();
+
+    let (param_type, param_pattern, param_clone) = match params.as_slice() {
+        [] => (quote! { () }, quote! { _param }, quote! {}),
+        [param] => {
+            let (name, ty) = (&param.name, &param.ty);
+            (
+                quote! { #ty },
+                quote! { #name },
+                quote! { let #name = #name.clone(); },
+            )
+        }
+        _ => (
+            quote! { (#(#param_types,)*) },
+            quote! { (#(#param_names,)*) },
+            quote! { let (#(#param_names,)*) = params.clone(); },
+        ),
+    };
+    let key_param = if params.len() > 1 {
+        quote! { params }
+    } else {
+        param_pattern.clone()
+    };
+    let stream = if asyncness.is_some() {
+        quote! { ::dioxus_provider::stream::from_future(Self::call(#(#param_names,)*)) }
+    } else {
+        quote! { ::std::boxed::Box::pin(Self::call(#(#param_names,)*)) }
+    };
+
+    Ok(quote! {
+        #common_struct
+        #bounds_checks
+
+        impl #struct_name {
+            #fn_vis #asyncness fn call(#(#param_names: #param_types,)*) #return_type #fn_block
+
+            /// The stream provider's structured cache key for the given parameters
+            pub fn key(#(#param_names: #param_types,)*) -> ::dioxus_provider::query_key::QueryKey {
+                ::dioxus_provider::query_key::QueryKey::of(#key_name)
+                    #(.with_param(&#param_names))*
+            }
+        }
+
+        impl ::dioxus_provider::stream::StreamProvider<#param_type> for #struct_name {
+            type Output = #output_type;
+            type Error = #error_type;
+
+            fn stream(
+                &self,
+                #param_pattern: #param_type,
+            ) -> ::dioxus_provider::stream::ProviderStream<Self::Output, Self::Error> {
+                #stream
+            }
+
+            fn query_key(&self, #key_param: &#param_type) -> Option<::dioxus_provider::query_key::QueryKey> {
+                #param_clone
+                Some(Self::key(#(#param_names,)*))
+            }
+        }
+    })
+}
+
 fn generate_mutation(input_fn: ItemFn, mutation_args: MutationArgs) -> Result<TokenStream2> {
     let info = extract_provider_info(&input_fn)?;
 
@@ -437,18 +1290,40 @@ fn generate_mutation(input_fn: ItemFn, mutation_args: MutationArgs) -> Result<To
         error_type,
         struct_name,
         fn_name: _fn_name,
+        sig_span,
+        return_span,
         ..
     } = &info;
 
-    let enhanced_fn_block = generate_enhanced_function_body(&[], &[], fn_block);
+    let enhanced_fn_block =
+        generate_enhanced_function_body(&ProviderArgs::default(), &[], error_type, fn_block);
     let invalidation_impl = generate_invalidation_impl(&mutation_args);
     let common_struct = generate_common_struct_and_const(&info);
+    let bounds_checks = generate_bounds_checks(output_type, error_type);
 
     let raw_params = extract_all_params(&input_fn)?;
     let has_optimistic = mutation_args.optimistic.is_some();
     let (input_params, context_param, data_param) =
         split_mutation_params(raw_params.clone(), output_type, has_optimistic)?;
 
+    // Mutations returning `()` only invalidate: there is no data to apply optimistically or
+    // to reconcile the cache with
+    let is_unit = is_unit_type(output_type);
+    if is_unit {
+        if let Some(optimistic) = &mutation_args.optimistic {
+            return Err(syn::Error::new_spanned(
+                optimistic,
+                "mutations returning `()` only invalidate; return the updated data to use `optimistic`",
+            ));
+        }
+        if let Some(context) = &context_param {
+            return Err(syn::Error::new_spanned(
+                &context.name,
+                "mutations returning `()` have no cached data for a MutationContext",
+            ));
+        }
+    }
+
     // Detect auto-apply mode: optimistic is present and there's a data parameter
     let is_auto_apply = has_optimistic && data_param.is_some();
 
@@ -468,7 +1343,9 @@ fn generate_mutation(input_fn: ItemFn, mutation_args: MutationArgs) -> Result<To
         })
         .collect();
 
-    let call_signature = quote! { #fn_vis async fn call(#(#call_params),*) -> Result<#output_type, #error_type> {
+    let call_output = quote_spanned! {*return_span=> Result<#output_type, #error_type> };
+    let send_future = generate_send_future(*sig_span);
+    let call_signature = quote! { #fn_vis async fn call(#(#call_params),*) -> #call_output {
         #enhanced_fn_block
     } };
 
@@ -484,18 +1361,17 @@ fn generate_mutation(input_fn: ItemFn, mutation_args: MutationArgs) -> Result<To
             .iter()
             .map(|param| {
                 // If this is the context parameter, use the ctx_ident
-                if let Some(ctx) = ctx_ident {
-                    if param.name == *ctx {
-                        return quote! { #ctx };
-                    }
+                if let Some(ctx) = ctx_ident
+                    && param.name == *ctx
+                {
+                    return quote! { #ctx };
                 }
                 // If this is the data parameter and we have auto-applied data, use that
-                if let Some(data_name) = data_param_name {
-                    if param.name == *data_name {
-                        if let Some(ref data_expr) = auto_apply_data_expr {
-                            return data_expr.clone();
-                        }
-                    }
+                if let Some(data_name) = data_param_name
+                    && param.name == *data_name
+                    && let Some(ref data_expr) = auto_apply_data_expr
+                {
+                    return data_expr.clone();
                 }
                 // Otherwise, use the parameter name as-is
                 let name = &param.name;
@@ -555,14 +1431,14 @@ fn generate_mutation(input_fn: ItemFn, mutation_args: MutationArgs) -> Result<To
         let mut prelude = Vec::<TokenStream2>::new();
 
         // Create MutationContext if needed in manual mode
-        if !is_auto_apply {
-            if let (Some(ctx_ident), Some(data_ty), Some(err_ty)) = (
+        if !is_auto_apply
+            && let (Some(ctx_ident), Some(data_ty), Some(err_ty)) = (
                 context_ident.as_ref(),
                 context_data_ty.as_ref(),
                 context_error_ty.as_ref(),
-            ) {
-                prelude.push(quote! { let #ctx_ident = ::dioxus_provider::mutation::MutationContext::<'static, #data_ty, #err_ty>::new(None); });
-            }
+            )
+        {
+            prelude.push(quote! { let #ctx_ident = ::dioxus_provider::mutation::MutationContext::<'static, #data_ty, #err_ty>::new(None); });
         }
 
         let call_args = if is_auto_apply {
@@ -573,14 +1449,15 @@ fn generate_mutation(input_fn: ItemFn, mutation_args: MutationArgs) -> Result<To
             call_args_builder(context_ident.as_ref(), None)
         };
 
-        let call_expr = quote! { Self::call(#(#call_args),*) };
-        let body = quote! { async move { #(#prelude)* #call_expr.await } };
+        let call_expr = quote_spanned! {*sig_span=> Self::call(#(#call_args),*) };
+        let body = quote_spanned! {*sig_span=> async move { #(#prelude)* #call_expr.await } };
 
         // Generate full function with cfg for Send bound
         let signature_with_body = match input_count {
             0 => quote! {
                 #[cfg(not(target_family = "wasm"))]
-                fn mutate(&self, _input: ()) -> impl ::std::future::Future<Output = Result<Self::Output, Self::Error>> + ::std::marker::Send {
+                #[allow(refining_impl_trait)]
+                fn mutate(&self, _input: ()) -> #send_future {
                     #body
                 }
                 #[cfg(target_family = "wasm")]
@@ -594,7 +1471,8 @@ fn generate_mutation(input_fn: ItemFn, mutation_args: MutationArgs) -> Result<To
                 let ty = &param.ty;
                 quote! {
                     #[cfg(not(target_family = "wasm"))]
-                    fn mutate(&self, #name: #ty) -> impl ::std::future::Future<Output = Result<Self::Output, Self::Error>> + ::std::marker::Send {
+                    #[allow(refining_impl_trait)]
+                    fn mutate(&self, #name: #ty) -> #send_future {
                         #body
                     }
                     #[cfg(target_family = "wasm")]
@@ -607,7 +1485,8 @@ fn generate_mutation(input_fn: ItemFn, mutation_args: MutationArgs) -> Result<To
                 let names: Vec<_> = input_params.iter().map(|p| &p.name).collect();
                 quote! {
                     #[cfg(not(target_family = "wasm"))]
-                    fn mutate(&self, input: #input_type) -> impl ::std::future::Future<Output = Result<Self::Output, Self::Error>> + ::std::marker::Send {
+                    #[allow(refining_impl_trait)]
+                    fn mutate(&self, input: #input_type) -> #send_future {
                         let (#(#names),*) = input;
                         #body
                     }
@@ -622,7 +1501,10 @@ fn generate_mutation(input_fn: ItemFn, mutation_args: MutationArgs) -> Result<To
         (signature_with_body, quote! {})
     };
 
-    let (mutate_with_current_signature, mutate_with_current_body) = {
+    let (mutate_with_current_signature, mutate_with_current_body) = if is_unit {
+        // The trait's default runs `mutate` without reading the cache
+        (quote! {}, quote! {})
+    } else {
         let mut prelude = Vec::<TokenStream2>::new();
 
         let call_args = if is_auto_apply {
@@ -646,18 +1528,19 @@ fn generate_mutation(input_fn: ItemFn, mutation_args: MutationArgs) -> Result<To
             call_args_builder(context_ident.as_ref(), None)
         };
 
-        let call_expr = quote! { Self::call(#(#call_args),*) };
-        let body = quote! { async move { #(#prelude)* #call_expr.await } };
+        let call_expr = quote_spanned! {*sig_span=> Self::call(#(#call_args),*) };
+        let body = quote_spanned! {*sig_span=> async move { #(#prelude)* #call_expr.await } };
 
         // Generate full function with cfg for Send bound
         let signature_with_body = match input_count {
             0 => quote! {
                 #[cfg(not(target_family = "wasm"))]
+                #[allow(refining_impl_trait)]
                 fn mutate_with_current(
                     &self,
                     _input: (),
                     current_data: Option<&Result<Self::Output, Self::Error>>,
-                ) -> impl ::std::future::Future<Output = Result<Self::Output, Self::Error>> + ::std::marker::Send {
+                ) -> #send_future {
                     #body
                 }
                 #[cfg(target_family = "wasm")]
@@ -675,11 +1558,12 @@ fn generate_mutation(input_fn: ItemFn, mutation_args: MutationArgs) -> Result<To
                 let ty = &param.ty;
                 quote! {
                     #[cfg(not(target_family = "wasm"))]
+                    #[allow(refining_impl_trait)]
                     fn mutate_with_current(
                         &self,
                         #name: #ty,
                         current_data: Option<&Result<Self::Output, Self::Error>>,
-                    ) -> impl ::std::future::Future<Output = Result<Self::Output, Self::Error>> + ::std::marker::Send {
+                    ) -> #send_future {
                         #body
                     }
                     #[cfg(target_family = "wasm")]
@@ -696,11 +1580,12 @@ fn generate_mutation(input_fn: ItemFn, mutation_args: MutationArgs) -> Result<To
                 let names: Vec<_> = input_params.iter().map(|p| &p.name).collect();
                 quote! {
                     #[cfg(not(target_family = "wasm"))]
+                    #[allow(refining_impl_trait)]
                     fn mutate_with_current(
                         &self,
                         input: #input_type,
                         current_data: Option<&Result<Self::Output, Self::Error>>,
-                    ) -> impl ::std::future::Future<Output = Result<Self::Output, Self::Error>> + ::std::marker::Send {
+                    ) -> #send_future {
                         let (#(#names),*) = input;
                         #body
                     }
@@ -729,6 +1614,10 @@ fn generate_mutation(input_fn: ItemFn, mutation_args: MutationArgs) -> Result<To
         quote! {}
     };
 
+    let normalize_impl = generate_normalize_impl(mutation_args.normalize);
+    let journal_impl = generate_journal_impl(mutation_args.journal, &input_type);
+    let retry_impl = generate_retry_impl(&mutation_args.retry)?;
+
     let mutation_impl = quote! {
         impl ::dioxus_provider::mutation::Mutation<#input_type> for #struct_name {
             type Output = #output_type;
@@ -743,6 +1632,12 @@ fn generate_mutation(input_fn: ItemFn, mutation_args: MutationArgs) -> Result<To
             #invalidation_impl
 
             #has_optimistic_impl
+
+            #normalize_impl
+
+            #journal_impl
+
+            #retry_impl
         }
     };
 
@@ -760,12 +1655,19 @@ fn generate_mutation(input_fn: ItemFn, mutation_args: MutationArgs) -> Result<To
 /// Generate duration implementation for provider methods
 fn generate_duration_impl(method_name: &str, duration: Option<Duration>) -> TokenStream2 {
     if let Some(duration) = duration {
-        let duration_secs = duration.as_secs();
         let method_ident = syn::Ident::new(method_name, proc_macro2::Span::call_site());
+        // Keep sub-second durations such as "300ms" instead of truncating them to zero
+        let duration = if duration.subsec_nanos() == 0 {
+            let duration_secs = duration.as_secs();
+            quote! { ::std::time::Duration::from_secs(#duration_secs) }
+        } else {
+            let duration_millis = duration.as_millis() as u64;
+            quote! { ::std::time::Duration::from_millis(#duration_millis) }
+        };
 
         quote! {
             fn #method_ident(&self) -> Option<::std::time::Duration> {
-                Some(::std::time::Duration::from_secs(#duration_secs))
+                Some(#duration)
             }
         }
     } else {
@@ -788,13 +1690,383 @@ fn generate_stale_time_impl(provider_args: &ProviderArgs) -> TokenStream2 {
     generate_duration_impl("stale_time", provider_args.stale_time)
 }
 
-/// Generate invalidation implementation for mutations
-fn generate_invalidation_impl(mutation_args: &MutationArgs) -> TokenStream2 {
-    if mutation_args.invalidates.is_empty() {
-        quote! {}
-    } else {
-        let provider_calls: Vec<_> = mutation_args
-            .invalidates
+/// Generate minimum refetch interval implementation
+fn generate_min_refetch_interval_impl(provider_args: &ProviderArgs) -> TokenStream2 {
+    generate_duration_impl("min_refetch_interval", provider_args.min_refetch_interval)
+}
+
+/// Generate schedule implementation
+fn generate_schedule_impl(provider_args: &ProviderArgs) -> TokenStream2 {
+    if let Some(schedule) = &provider_args.schedule {
+        quote! {
+            fn schedule(&self) -> Option<::dioxus_provider::schedule::Schedule> {
+                Some(
+                    ::dioxus_provider::schedule::Schedule::parse(#schedule)
+                        .expect("schedule validated by #[provider]"),
+                )
+            }
+        }
+    } else {
+        quote! {}
+    }
+}
+
+/// Generate data-aligned interval implementation
+fn generate_next_interval_impl(provider_args: &ProviderArgs) -> TokenStream2 {
+    if let Some(closure) = &provider_args.next_interval {
+        quote! {
+            fn next_interval(&self) -> Option<::dioxus_provider::hooks::NextIntervalFn<Self::Output>> {
+                Some(#closure)
+            }
+        }
+    } else {
+        quote! {}
+    }
+}
+
+/// Generate per-entry cache policy implementation
+///
+/// Providers returning `HttpCached<T>` get the response's header-derived policy by default,
+/// and providers returning `WithTtl<T>` get their per-result TTL.
+fn generate_cache_policy_impl(provider_args: &ProviderArgs, output_type: &Type) -> TokenStream2 {
+    let policy_fn = if let Some(expr) = &provider_args.cache_policy {
+        quote! { #expr }
+    } else if last_segment_is(output_type, "HttpCached") {
+        quote! { ::dioxus_provider::http::HttpCached::cache_policy }
+    } else if last_segment_is(output_type, "WithTtl") {
+        quote! { ::dioxus_provider::cache::WithTtl::cache_policy }
+    } else {
+        return quote! {};
+    };
+
+    quote! {
+        fn cache_policy(&self) -> Option<::dioxus_provider::hooks::CachePolicyFn<Self::Output>> {
+            Some(#policy_fn)
+        }
+    }
+}
+
+/// Generate entity normalization implementation, shared by providers and mutations
+/// The `Send` future returned by native `run` and `mutate`, spanned at the user's signature
+/// so that a body holding non-`Send` values across an await is reported there
+fn generate_send_future(sig_span: proc_macro2::Span) -> TokenStream2 {
+    quote_spanned! {sig_span=>
+        impl ::std::future::Future<Output = Result<Self::Output, Self::Error>> + ::std::marker::Send
+    }
+}
+
+fn generate_run_call(call: TokenStream2, worker: bool) -> TokenStream2 {
+    if worker {
+        quote! { ::dioxus_provider::platform::run_on_worker(#call) }
+    } else {
+        call
+    }
+}
+
+fn generate_normalize_impl(normalize: bool) -> TokenStream2 {
+    if normalize {
+        quote! {
+            fn normalize(&self) -> Option<::dioxus_provider::hooks::NormalizeFn<Self::Output>> {
+                Some(<Self::Output as ::dioxus_provider::entity::Normalize>::normalize)
+            }
+        }
+    } else {
+        quote! {}
+    }
+}
+
+fn generate_journal_impl(journal: bool, input_type: &TokenStream2) -> TokenStream2 {
+    if journal {
+        quote! {
+            fn journaling(&self) -> Option<::dioxus_provider::journal::Journaling<#input_type>> {
+                Some(::dioxus_provider::journal::Journaling::json())
+            }
+        }
+    } else {
+        quote! {}
+    }
+}
+
+fn generate_persist_impl(persist: bool) -> TokenStream2 {
+    if persist {
+        quote! {
+            fn persistence(&self) -> Option<::dioxus_provider::persist::Persistence<Self::Output>> {
+                Some(::dioxus_provider::persist::Persistence::json())
+            }
+        }
+    } else {
+        quote! {}
+    }
+}
+
+fn generate_refetch_on_focus_impl(refetch_on_focus: bool) -> TokenStream2 {
+    if refetch_on_focus {
+        quote! {
+            fn refetch_on_focus(&self) -> bool {
+                true
+            }
+        }
+    } else {
+        quote! {}
+    }
+}
+
+fn generate_refetch_on_reconnect_impl(refetch_on_reconnect: bool) -> TokenStream2 {
+    if refetch_on_reconnect {
+        quote! {
+            fn refetch_on_reconnect(&self) -> bool {
+                true
+            }
+        }
+    } else {
+        quote! {}
+    }
+}
+
+fn generate_seed_details_impl(args: &ProviderArgs) -> TokenStream2 {
+    let Some(detail) = &args.seed_details else {
+        return quote! {};
+    };
+    quote! {
+        fn seed_details(&self) -> Option<::dioxus_provider::hooks::SeedFn<Self::Output>> {
+            Some(|list, cache| {
+                ::dioxus_provider::seed::seed_details(&#detail(), list, cache);
+            })
+        }
+    }
+}
+
+fn generate_history_impl(args: &ProviderArgs) -> Result<TokenStream2> {
+    let Some(size) = &args.keep_history else {
+        if let Some((lit, _)) = &args.history_grace {
+            return Err(syn::Error::new_spanned(
+                lit,
+                "history_grace requires keep_history",
+            ));
+        }
+        return Ok(quote! {});
+    };
+    let grace = args.history_grace.as_ref().map(|(_, grace)| {
+        let grace_millis = grace.as_millis() as u64;
+        quote! { .with_grace(::std::time::Duration::from_millis(#grace_millis)) }
+    });
+    Ok(quote! {
+        fn history(&self) -> Option<::dioxus_provider::history::HistoryWindow> {
+            Some(::dioxus_provider::history::HistoryWindow::new(#size)#grace)
+        }
+    })
+}
+
+fn generate_cache_errors_impl(args: &ProviderArgs) -> TokenStream2 {
+    let caching = match args.cache_errors {
+        None => return quote! {},
+        Some(ErrorCaching::Never) => quote! { ::dioxus_provider::cache::ErrorCaching::Never },
+        Some(ErrorCaching::Same) => quote! { ::dioxus_provider::cache::ErrorCaching::Same },
+        Some(ErrorCaching::Short(duration)) => {
+            let millis = duration.as_millis() as u64;
+            quote! {
+                ::dioxus_provider::cache::ErrorCaching::Short(
+                    ::std::time::Duration::from_millis(#millis)
+                )
+            }
+        }
+    };
+    quote! {
+        fn cache_errors(&self) -> ::dioxus_provider::cache::ErrorCaching {
+            #caching
+        }
+    }
+}
+
+fn generate_not_found_impl(args: &ProviderArgs) -> TokenStream2 {
+    let cache_not_found = generate_duration_impl("cache_not_found", args.cache_not_found);
+    let is_not_found = args.not_found.as_ref().map(|closure| {
+        quote! {
+            fn is_not_found(&self, error: &Self::Error) -> bool {
+                (#closure)(error)
+            }
+        }
+    });
+    quote! {
+        #cache_not_found
+        #is_not_found
+    }
+}
+
+/// Generate timeout implementation
+///
+/// Error types that don't implement `TimeoutError` must name one with `timeout_error`, and
+/// fail to compile without it.
+fn generate_timeout_impl(args: &ProviderArgs, error_type: &Type) -> Result<TokenStream2> {
+    let Some((_, timeout)) = &args.timeout else {
+        if let Some(closure) = &args.timeout_error {
+            return Err(syn::Error::new_spanned(
+                closure,
+                "timeout_error requires timeout",
+            ));
+        }
+        return Ok(quote! {});
+    };
+    let timeout_error = match &args.timeout_error {
+        Some(closure) => quote! {
+            fn timeout_error(&self, timeout: ::std::time::Duration) -> Option<Self::Error> {
+                Some((#closure)(timeout))
+            }
+        },
+        None => quote! {
+            fn timeout_error(&self, timeout: ::std::time::Duration) -> Option<Self::Error> {
+                Some(<#error_type as ::dioxus_provider::errors::TimeoutError>::timed_out(timeout))
+            }
+        },
+    };
+    let timeout = generate_duration_impl("timeout", Some(*timeout));
+    Ok(quote! {
+        #timeout
+        #timeout_error
+    })
+}
+
+fn generate_retry_impl(args: &RetryArgs) -> Result<TokenStream2> {
+    let Some(retries) = args.retries else {
+        let option = args
+            .delay
+            .as_ref()
+            .map(|(lit, _)| lit)
+            .or(args.backoff.as_ref().map(|(lit, _)| lit));
+        if let Some(lit) = option {
+            return Err(syn::Error::new_spanned(
+                lit,
+                "retry_delay and retry_backoff require retry",
+            ));
+        }
+        return Ok(quote! {});
+    };
+    let delay = args.delay.as_ref().map(|(_, delay)| {
+        let millis = delay.as_millis() as u64;
+        quote! { .with_delay(::std::time::Duration::from_millis(#millis)) }
+    });
+    let backoff = matches!(args.backoff, Some((_, true))).then(|| quote! { .exponential() });
+    Ok(quote! {
+        fn retry_policy(&self) -> Option<::dioxus_provider::retry::RetryPolicy> {
+            Some(::dioxus_provider::retry::RetryPolicy::new(#retries) #delay #backoff)
+        }
+    })
+}
+
+fn generate_serves_impl(args: &ProviderArgs) -> TokenStream2 {
+    if args.serves.is_empty() {
+        return quote! {};
+    }
+    let entities = &args.serves;
+    quote! {
+        fn serves(&self) -> &'static [&'static str] {
+            &[#(#entities),*]
+        }
+    }
+}
+
+/// Check whether a type is the unit type `()`
+fn is_unit_type(ty: &Type) -> bool {
+    matches!(ty, Type::Tuple(tuple) if tuple.elems.is_empty())
+}
+
+/// Check whether a type path ends in the given identifier (e.g. `http::HttpCached<T>`)
+fn last_segment_is(ty: &Type, ident: &str) -> bool {
+    matches!(
+        ty,
+        Type::Path(type_path)
+            if type_path.path.segments.last().is_some_and(|segment| segment.ident == ident)
+    )
+}
+
+/// Check the shape of a schedule string at compile time
+///
+/// Field values are fully validated by `Schedule::parse` at runtime; this catches the
+/// common mistakes (wrong field count, malformed time) with a compile error instead.
+fn validate_schedule(lit: &LitStr) -> Result<()> {
+    let value = lit.value();
+    let trimmed = value.trim();
+
+    if let Some(time) = trimmed.strip_prefix("daily at ") {
+        let parts: Vec<_> = time.trim().split(':').collect();
+        let valid = matches!(parts.len(), 2 | 3)
+            && parts
+                .iter()
+                .zip([23u32, 59, 59])
+                .all(|(part, max)| part.parse::<u32>().is_ok_and(|v| v <= max));
+        if !valid {
+            return Err(syn::Error::new_spanned(
+                lit,
+                "Invalid schedule: expected \"daily at HH:MM\"",
+            ));
+        }
+        return Ok(());
+    }
+
+    let mut fields: Vec<_> = trimmed.split_whitespace().collect();
+    if fields.len() == 5 {
+        fields.insert(0, "0");
+    }
+    if fields.len() != 6 {
+        return Err(syn::Error::new_spanned(
+            lit,
+            "Invalid schedule: expected a cron expression (\"sec min hour dom month dow\") or \"daily at HH:MM\"",
+        ));
+    }
+
+    // Same field ranges as `Schedule::parse`, so a validated schedule never fails at runtime
+    let ranges = [
+        ("second", 0, 59),
+        ("minute", 0, 59),
+        ("hour", 0, 23),
+        ("day-of-month", 1, 31),
+        ("month", 1, 12),
+        ("day-of-week", 0, 7),
+    ];
+    for (field, (name, min, max)) in fields.iter().zip(ranges) {
+        if !is_valid_cron_field(field, min, max) {
+            return Err(syn::Error::new_spanned(
+                lit,
+                format!("Invalid schedule: {name} field `{field}` must be within {min}-{max}"),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Check a single cron field (`*`, `5`, `1-5`, `*/15`, `0,30`) against its allowed range
+fn is_valid_cron_field(field: &str, min: u32, max: u32) -> bool {
+    if field == "*" {
+        return true;
+    }
+
+    let in_range = |value: &str| value.parse::<u32>().is_ok_and(|v| (min..=max).contains(&v));
+    field.split(',').all(|part| {
+        let range = match part.split_once('/') {
+            Some((range, step)) if step.parse::<u32>().is_ok_and(|step| step > 0) => range,
+            Some(_) => return false,
+            None => part,
+        };
+
+        match range.split_once('-') {
+            _ if range == "*" => true,
+            Some((start, end)) => {
+                in_range(start)
+                    && in_range(end)
+                    && start.parse::<u32>().ok() <= end.parse::<u32>().ok()
+            }
+            None => in_range(range),
+        }
+    })
+}
+
+/// Generate invalidation implementation for mutations
+fn generate_invalidation_impl(mutation_args: &MutationArgs) -> TokenStream2 {
+    if mutation_args.invalidates.is_empty() {
+        quote! {}
+    } else {
+        let provider_calls: Vec<_> = mutation_args
+            .invalidates
             .iter()
             .map(|provider_fn| {
                 quote! {
@@ -820,6 +2092,8 @@ struct ProviderInfo {
     error_type: Type,
     struct_name: syn::Ident,
     fn_name: syn::Ident,
+    sig_span: proc_macro2::Span, // The user's signature, for errors about the function
+    return_span: proc_macro2::Span, // The user's return type, for errors about the result
 }
 
 /// Information about a function parameter
@@ -837,25 +2111,22 @@ struct ContextInfo {
 }
 
 fn parse_context_type(ty: &Type) -> Option<(Type, Type)> {
-    if let Type::Path(type_path) = ty {
-        if let Some(segment) = type_path.path.segments.last() {
-            if segment.ident == "MutationContext" {
-                if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
-                    if args.args.len() == 2 {
-                        let mut iter = args.args.iter();
-                        let data_ty = match iter.next()? {
-                            syn::GenericArgument::Type(ty) => ty.clone(),
-                            _ => return None,
-                        };
-                        let error_ty = match iter.next()? {
-                            syn::GenericArgument::Type(ty) => ty.clone(),
-                            _ => return None,
-                        };
-                        return Some((data_ty, error_ty));
-                    }
-                }
-            }
-        }
+    if let Type::Path(type_path) = ty
+        && let Some(segment) = type_path.path.segments.last()
+        && segment.ident == "MutationContext"
+        && let syn::PathArguments::AngleBracketed(args) = &segment.arguments
+        && args.args.len() == 2
+    {
+        let mut iter = args.args.iter();
+        let data_ty = match iter.next()? {
+            syn::GenericArgument::Type(ty) => ty.clone(),
+            _ => return None,
+        };
+        let error_ty = match iter.next()? {
+            syn::GenericArgument::Type(ty) => ty.clone(),
+            _ => return None,
+        };
+        return Some((data_ty, error_ty));
     }
     None
 }
@@ -915,13 +2186,13 @@ fn split_mutation_params(
     }
 
     // In auto-apply mode (has optimistic but no context), the last param might be the data param
-    if has_optimistic && context_param.is_none() && !input_params.is_empty() {
-        // Check if the last parameter's type matches the output type
-        if let Some(last_param) = input_params.last() {
-            if types_equal(&last_param.ty, output_type) {
-                data_param = input_params.pop();
-            }
-        }
+    // Check if the last parameter's type matches the output type
+    if has_optimistic
+        && context_param.is_none()
+        && let Some(last_param) = input_params.last()
+        && types_equal(&last_param.ty, output_type)
+    {
+        data_param = input_params.pop();
     }
 
     Ok((input_params, context_param, data_param))
@@ -940,6 +2211,11 @@ fn extract_provider_info(input_fn: &ItemFn) -> Result<ProviderInfo> {
     let fn_block = input_fn.block.clone();
 
     let (output_type, error_type) = extract_result_types(&input_fn.sig.output)?;
+    let sig_span = input_fn.sig.span();
+    let return_span = match &input_fn.sig.output {
+        ReturnType::Type(_, ty) => ty.span(),
+        ReturnType::Default => sig_span,
+    };
     let struct_name = syn::Ident::new(
         &to_pascal_case(&fn_name.to_string()),
         proc_macro2::Span::call_site(),
@@ -953,6 +2229,8 @@ fn extract_provider_info(input_fn: &ItemFn) -> Result<ProviderInfo> {
         error_type,
         struct_name,
         fn_name,
+        sig_span,
+        return_span,
     })
 }
 
@@ -961,9 +2239,13 @@ fn generate_common_struct_and_const(info: &ProviderInfo) -> TokenStream2 {
     let struct_name = &info.struct_name;
     let fn_attrs = &info.fn_attrs;
     let fn_name = &info.fn_name;
+    let const_name = syn::Ident::new(
+        &fn_name.to_string().to_uppercase(),
+        proc_macro2::Span::call_site(),
+    );
 
     quote! {
-        #[derive(Clone, PartialEq)]
+        #[derive(Clone, PartialEq, Eq)]
         #(#fn_attrs)*
         pub struct #struct_name;
 
@@ -977,6 +2259,33 @@ fn generate_common_struct_and_const(info: &ProviderInfo) -> TokenStream2 {
         pub fn #fn_name() -> #struct_name {
             #struct_name
         }
+
+        // The same instance as a constant, usable in match arms and const contexts
+        #[allow(dead_code)]
+        pub const #const_name: #struct_name = #struct_name;
+    }
+}
+
+/// Generate checks that the output and error types satisfy the provider bounds
+///
+/// The checks are spanned at the types in the user's signature, so a missing bound is
+/// reported there, once per type and trait, with a message naming both.
+fn generate_bounds_checks(output_type: &Type, error_type: &Type) -> TokenStream2 {
+    let output_check = quote_spanned! {output_type.span()=>
+        ::dioxus_provider::types::__bounds::assert_output::<#output_type>();
+    };
+    let error_check = quote_spanned! {error_type.span()=>
+        ::dioxus_provider::types::__bounds::assert_error::<#error_type>();
+    };
+
+    quote! {
+        const _: () = {
+            #[allow(dead_code)]
+            fn check_bounds() {
+                #output_check
+                #error_check
+            }
+        };
     }
 }
 
@@ -1011,6 +2320,177 @@ fn extract_all_params(input_fn: &ItemFn) -> Result<Vec<ParamInfo>> {
     Ok(params)
 }
 
+/// Wrap a function body so its result goes through `map_ok` and `map_err`
+///
+/// Returns the provider's output and error types, which are the closures' annotated return
+/// types or else the function's own types.
+fn generate_result_mapping(
+    args: &ProviderArgs,
+    output_type: &Type,
+    error_type: &Type,
+    return_span: proc_macro2::Span,
+    block: syn::Block,
+) -> Result<(Type, Type, syn::Block)> {
+    if args.map_ok.is_none() && args.map_err.is_none() && !args.paginated {
+        return Ok((output_type.clone(), error_type.clone(), block));
+    }
+
+    let mapped_type = |closure: &Option<syn::ExprClosure>, original: &Type| match closure {
+        Some(syn::ExprClosure {
+            output: ReturnType::Type(_, ty),
+            ..
+        }) => (**ty).clone(),
+        _ => original.clone(),
+    };
+    let mapped_output = mapped_type(&args.map_ok, output_type);
+    let mapped_error = mapped_type(&args.map_err, error_type);
+    let map_ok = args
+        .map_ok
+        .as_ref()
+        .map(|closure| quote! { .map(#closure) });
+    let map_err = args
+        .map_err
+        .as_ref()
+        .map(|closure| quote! { .map_err(#closure) });
+
+    // Paginated providers turn `(items, next_cursor)` into a page
+    let (mapped_output, into_page) = if args.paginated {
+        let (item_type, cursor_type) = extract_page_types(&mapped_output)?;
+        (
+            syn::parse_quote! { ::dioxus_provider::infinite::Page<#item_type, #cursor_type> },
+            Some(quote! { .map(::dioxus_provider::infinite::Page::from) }),
+        )
+    } else {
+        (mapped_output, None)
+    };
+
+    let result_type = quote_spanned! {return_span=> Result<#output_type, #error_type> };
+    let block = syn::parse_quote! {{
+        let result: #result_type = async move #block.await;
+        result #map_ok #map_err #into_page
+    }};
+    Ok((mapped_output, mapped_error, block))
+}
+
+/// Extract `T` and `C` from a paginated provider's `(Vec<T>, Option<C>)` output
+fn extract_page_types(output_type: &Type) -> Result<(Type, Type)> {
+    let error = || {
+        syn::Error::new_spanned(
+            output_type,
+            "paginated providers must return `(Vec<T>, Option<C>)`, the items and the next cursor",
+        )
+    };
+    let Type::Tuple(tuple) = output_type else {
+        return Err(error());
+    };
+    let [items, cursor] = tuple.elems.iter().collect::<Vec<_>>()[..] else {
+        return Err(error());
+    };
+    Ok((
+        extract_single_generic(items, "Vec").ok_or_else(error)?,
+        extract_single_generic(cursor, "Option").ok_or_else(error)?,
+    ))
+}
+
+/// The type argument of `Wrapper<T>`, if `ty` is one
+fn extract_single_generic(ty: &Type, wrapper: &str) -> Option<Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != wrapper {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.iter().collect::<Vec<_>>()[..] {
+        [syn::GenericArgument::Type(inner)] => Some(inner.clone()),
+        _ => None,
+    }
+}
+
+/// Generate the validate implementation, if `validate` is set
+fn generate_validate_impl(
+    args: &ProviderArgs,
+    param_type: &TokenStream2,
+    no_params: bool,
+) -> Result<TokenStream2> {
+    let Some(closure) = &args.validate else {
+        return Ok(quote! {});
+    };
+    if no_params {
+        return Err(syn::Error::new_spanned(
+            closure,
+            "validate requires a provider with parameters",
+        ));
+    }
+    Ok(quote! {
+        fn validate(&self, param: &#param_type) -> Result<(), Self::Error> {
+            (#closure)(param).map_err(::std::convert::Into::into)
+        }
+    })
+}
+
+/// Generate the skip implementation, if `skip_if` is set
+fn generate_skip_impl(
+    args: &ProviderArgs,
+    param_type: &TokenStream2,
+    no_params: bool,
+) -> Result<TokenStream2> {
+    let Some(closure) = &args.skip_if else {
+        if let Some(value) = &args.skip_value {
+            return Err(syn::Error::new_spanned(
+                value,
+                "skip_value requires skip_if",
+            ));
+        }
+        return Ok(quote! {});
+    };
+    if no_params {
+        return Err(syn::Error::new_spanned(
+            closure,
+            "skip_if requires a provider with parameters",
+        ));
+    }
+    let value = match &args.skip_value {
+        Some(value) => quote! { #value },
+        None => quote! { ::std::default::Default::default() },
+    };
+    Ok(quote! {
+        fn skip(&self, param: &#param_type) -> Option<Self::Output> {
+            (#closure)(param).then(|| #value)
+        }
+    })
+}
+
+/// Replace reference parameters (`&str`, `&[T]`, `&T`) with their owned types
+///
+/// Provider parameters are stored in cache keys and moved into tasks, so they must be owned.
+/// The owned type is the referenced type's `ToOwned::Owned` (`String`, `Vec<T>`, `T`).
+/// Returns the statements that re-borrow the owned values under their original names.
+fn own_reference_params(params: &mut [ParamInfo]) -> Result<Vec<syn::Stmt>> {
+    let mut reborrows = Vec::new();
+    for param in params {
+        let Type::Reference(reference) = &param.ty else {
+            continue;
+        };
+        if reference.mutability.is_some() {
+            return Err(syn::Error::new_spanned(
+                &param.ty,
+                "Provider parameters can't be mutable references",
+            ));
+        }
+        let name = &param.name;
+        let referenced = &reference.elem;
+        reborrows.push(syn::parse_quote! {
+            let #name: &#referenced = ::std::borrow::Borrow::borrow(&#name);
+        });
+        param.ty = syn::parse_quote! { <#referenced as ::std::borrow::ToOwned>::Owned };
+    }
+    Ok(reborrows)
+}
+
 /// Build the input type: () for 0 params, T for 1 param, (T1, T2, ...) for N params
 fn build_input_type(params: &[ParamInfo]) -> TokenStream2 {
     match params.len() {
@@ -1027,6 +2507,33 @@ fn build_input_type(params: &[ParamInfo]) -> TokenStream2 {
 }
 
 /// Extract result types from the function return type
+/// Extract `T` and `E` from a return type of `impl Stream<Item = Result<T, E>>`
+fn extract_stream_item_types(return_type: &ReturnType) -> Result<(Type, Type)> {
+    if let ReturnType::Type(arrow, ty) = return_type
+        && let Type::ImplTrait(impl_trait) = &**ty
+    {
+        for bound in &impl_trait.bounds {
+            if let syn::TypeParamBound::Trait(trait_bound) = bound
+                && let Some(segment) = trait_bound.path.segments.last()
+                && segment.ident == "Stream"
+                && let syn::PathArguments::AngleBracketed(args) = &segment.arguments
+                && let Some(item) = args.args.iter().find_map(|arg| match arg {
+                    syn::GenericArgument::AssocType(assoc) if assoc.ident == "Item" => {
+                        Some(&assoc.ty)
+                    }
+                    _ => None,
+                })
+            {
+                return extract_result_types(&ReturnType::Type(*arrow, Box::new(item.clone())));
+            }
+        }
+    }
+    Err(syn::Error::new_spanned(
+        return_type,
+        "Stream provider functions must return impl Stream<Item = Result<T, E>>",
+    ))
+}
+
 fn extract_result_types(return_type: &ReturnType) -> Result<(Type, Type)> {
     match return_type {
         ReturnType::Default => Err(syn::Error::new_spanned(
@@ -1034,38 +2541,35 @@ fn extract_result_types(return_type: &ReturnType) -> Result<(Type, Type)> {
             "Provider functions must return Result<T, E>",
         )),
         ReturnType::Type(_, ty) => {
-            if let Type::Path(type_path) = &**ty {
-                if let Some(segment) = type_path.path.segments.last() {
-                    if segment.ident == "Result" {
-                        if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
-                            if args.args.len() == 2 {
-                                let mut args_iter = args.args.iter();
-
-                                let output_type = match args_iter.next().unwrap() {
-                                    syn::GenericArgument::Type(ty) => ty.clone(),
-                                    _ => {
-                                        return Err(syn::Error::new_spanned(
-                                            args,
-                                            "Result must have type arguments",
-                                        ));
-                                    }
-                                };
-
-                                let error_type = match args_iter.next().unwrap() {
-                                    syn::GenericArgument::Type(ty) => ty.clone(),
-                                    _ => {
-                                        return Err(syn::Error::new_spanned(
-                                            args,
-                                            "Result must have type arguments",
-                                        ));
-                                    }
-                                };
-
-                                return Ok((output_type, error_type));
-                            }
-                        }
+            if let Type::Path(type_path) = &**ty
+                && let Some(segment) = type_path.path.segments.last()
+                && segment.ident == "Result"
+                && let syn::PathArguments::AngleBracketed(args) = &segment.arguments
+                && args.args.len() == 2
+            {
+                let mut args_iter = args.args.iter();
+
+                let output_type = match args_iter.next().unwrap() {
+                    syn::GenericArgument::Type(ty) => ty.clone(),
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            args,
+                            "Result must have type arguments",
+                        ));
                     }
-                }
+                };
+
+                let error_type = match args_iter.next().unwrap() {
+                    syn::GenericArgument::Type(ty) => ty.clone(),
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            args,
+                            "Result must have type arguments",
+                        ));
+                    }
+                };
+
+                return Ok((output_type, error_type));
             }
 
             Err(syn::Error::new_spanned(
@@ -1155,15 +2659,18 @@ fn validate_provider_existence(compose_providers: &[syn::Ident]) -> Result<()> {
 
 /// Generate enhanced function body with composition
 fn generate_enhanced_function_body(
-    compose_providers: &[syn::Ident],
+    args: &ProviderArgs,
     params: &[ParamInfo],
+    error_type: &Type,
     original_block: &syn::Block,
 ) -> syn::Block {
     let mut statements = Vec::new();
 
     // Add composition statements
-    if !compose_providers.is_empty() {
-        let composition_statements = generate_composition_statements(compose_providers, params);
+    if !args.compose.is_empty() {
+        let policy = args.compose_policy.as_ref().map(|(_, policy)| *policy);
+        let composition_statements =
+            generate_composition_statements(&args.compose, policy, params, error_type);
         statements.extend(composition_statements);
     }
 
@@ -1178,17 +2685,20 @@ fn generate_enhanced_function_body(
 
 /// Generate composition statements that can be directly added to a statement list
 fn generate_composition_statements(
-    compose_providers: &[syn::Ident],
+    compose: &[ComposedProvider],
+    policy: Option<ComposePolicy>,
     params: &[ParamInfo],
+    error_type: &Type,
 ) -> Vec<syn::Stmt> {
-    if compose_providers.is_empty() {
+    if compose.is_empty() {
         return vec![];
     }
 
     let mut statements = Vec::new();
+    let compose_providers: Vec<_> = compose.iter().map(|c| c.provider.clone()).collect();
 
     // Add compile-time validation checks for better error messages
-    statements.extend(generate_validation_statements(compose_providers, params));
+    statements.extend(generate_validation_statements(&compose_providers, params));
 
     // Generate variable names for composed results with unique prefix to avoid collisions
     let result_vars: Vec<_> = compose_providers
@@ -1201,77 +2711,143 @@ fn generate_composition_statements(
         })
         .collect();
 
-    // Generate provider calls based on parameter count
-    if params.is_empty() {
-        // No parameters - call providers with ()
-        let provider_calls: Vec<_> = compose_providers
-            .iter()
-            .map(|provider| {
-                quote! {
-                    async { #provider().run(()).await }
+    // Each composed provider gets its own arguments, or a clone of the outer parameters
+    let provider_calls: Vec<_> = compose
+        .iter()
+        .map(|ComposedProvider { provider, args }| {
+            let param = match args {
+                Some(args) => {
+                    let args: Vec<_> = args.iter().collect();
+                    match args.as_slice() {
+                        [] => quote! { () },
+                        [arg] => quote! { ::std::clone::Clone::clone(&(#arg)) },
+                        args => quote! { (#(::std::clone::Clone::clone(&(#args)),)*) },
+                    }
                 }
-            })
-            .collect();
-
-        let join_stmt: syn::Stmt = syn::parse_quote! {
-            let (#(#result_vars,)*) = ::futures::join!(
-                #(#provider_calls,)*
-            );
-        };
-        statements.push(join_stmt);
-    } else if params.len() == 1 {
-        // Single parameter - clone it inside each async block
-        let param_name = &params[0].name;
-        let param_type = &params[0].ty;
-
-        let provider_calls: Vec<_> = compose_providers
-            .iter()
-            .map(|provider| {
-                quote! {
-                    async {
+                None => match params {
+                    [] => quote! { () },
+                    [param] => {
+                        let param_name = &param.name;
+                        let param_type = &param.ty;
                         // Explicit clone with helpful error context
-                        let param: #param_type = #param_name.clone();
-                        #provider().run(param).await
+                        quote! { { let param: #param_type = #param_name.clone(); param } }
                     }
-                }
-            })
-            .collect();
-
-        let join_stmt: syn::Stmt = syn::parse_quote! {
-            let (#(#result_vars,)*) = ::futures::join!(
-                #(#provider_calls,)*
-            );
-        };
-        statements.push(join_stmt);
-    } else {
-        // Multiple parameters - clone each parameter inside each async block
-        let param_names: Vec<_> = params.iter().map(|p| &p.name).collect();
-        let param_types: Vec<_> = params.iter().map(|p| &p.ty).collect();
-
-        let provider_calls: Vec<_> = compose_providers
-            .iter()
-            .map(|provider| {
-                quote! {
-                    async {
+                    params => {
+                        let param_names = params.iter().map(|p| &p.name);
+                        let param_types = params.iter().map(|p| &p.ty);
                         // Explicit clone with helpful error context for each parameter
-                        let params: (#(#param_types,)*) = (#(#param_names.clone(),)*);
-                        #provider().run(params).await
+                        quote! {{
+                            let params: (#(#param_types,)*) = (#(#param_names.clone(),)*);
+                            params
+                        }}
                     }
+                },
+            };
+            quote! {
+                async {
+                    let param = #param;
+                    #provider().run(param).await
                 }
-            })
-            .collect();
+            }
+        })
+        .collect();
 
-        let join_stmt: syn::Stmt = syn::parse_quote! {
-            let (#(#result_vars,)*) = ::futures::join!(
-                #(#provider_calls,)*
-            );
-        };
-        statements.push(join_stmt);
+    if policy == Some(ComposePolicy::Any) {
+        statements.extend(generate_first_success_statements(
+            &result_vars,
+            &provider_calls,
+            error_type,
+        ));
+        return statements;
+    }
+
+    let join_stmt: syn::Stmt = syn::parse_quote! {
+        let (#(#result_vars,)*) = ::futures::join!(
+            #(#provider_calls,)*
+        );
+    };
+    statements.push(join_stmt);
+
+    // Without a policy, the body receives the composed results as they are
+    for result_var in &result_vars {
+        match policy {
+            Some(ComposePolicy::All) => {
+                statements.push(syn::parse_quote! { let #result_var = #result_var?; });
+            }
+            Some(ComposePolicy::BestEffort) => {
+                statements.push(syn::parse_quote! { let #result_var = #result_var.ok(); });
+            }
+            _ => {}
+        }
     }
 
     statements
 }
 
+/// Generate statements that run the composed providers until the first one succeeds
+///
+/// Each result variable becomes an `Option` that holds the value of a provider that
+/// succeeded, and the providers still running when the first one succeeds are dropped. If
+/// every provider fails, the composed provider fails with the last error.
+fn generate_first_success_statements(
+    result_vars: &[syn::Ident],
+    provider_calls: &[TokenStream2],
+    error_type: &Type,
+) -> Vec<syn::Stmt> {
+    let ident = |prefix: &str, index: usize| {
+        syn::Ident::new(
+            &format!("__dioxus_composed_{prefix}_{index}"),
+            proc_macro2::Span::call_site(),
+        )
+    };
+    let futures: Vec<_> = (0..result_vars.len()).map(|i| ident("future", i)).collect();
+    let done: Vec<_> = (0..result_vars.len()).map(|i| ident("done", i)).collect();
+
+    let mut statements: Vec<syn::Stmt> = result_vars
+        .iter()
+        .map(|result_var| syn::parse_quote! { let mut #result_var = ::std::option::Option::None; })
+        .collect();
+    statements.push(syn::parse_quote! {{
+        #(let mut #futures = ::std::pin::pin!(#provider_calls);)*
+        #(let mut #done = false;)*
+        let mut __dioxus_composed_error: ::std::option::Option<#error_type> =
+            ::std::option::Option::None;
+        ::futures::future::poll_fn(|cx| {
+            #(
+                if !#done {
+                    if let ::std::task::Poll::Ready(result) =
+                        ::std::future::Future::poll(#futures.as_mut(), cx)
+                    {
+                        #done = true;
+                        match result {
+                            ::std::result::Result::Ok(value) => {
+                                #result_vars = ::std::option::Option::Some(value);
+                                return ::std::task::Poll::Ready(::std::result::Result::Ok(()));
+                            }
+                            ::std::result::Result::Err(error) => {
+                                __dioxus_composed_error = ::std::option::Option::Some(
+                                    ::std::convert::From::from(error),
+                                );
+                            }
+                        }
+                    }
+                }
+            )*
+            if #(#done)&&* {
+                ::std::task::Poll::Ready(::std::result::Result::Err(
+                    __dioxus_composed_error
+                        .take()
+                        .expect("every composed provider failed"),
+                ))
+            } else {
+                ::std::task::Poll::Pending
+            }
+        })
+        .await?;
+    }});
+    statements
+}
+
 /// Generate compile-time validation statements for better error messages
 fn generate_validation_statements(
     compose_providers: &[syn::Ident],
@@ -1314,3 +2890,76 @@ fn generate_validation_statements(
 
     statements
 }
+
+/// Expansion snapshots of the generated items
+///
+/// These pin the documented shape of the generated API. After an intended change to the
+/// expansion, review and update them with `INSTA_UPDATE=always cargo test -p
+/// dioxus-provider-macros` (or `cargo insta review`).
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    fn pretty(tokens: TokenStream2) -> String {
+        prettyplease::unparse(&syn::parse2(tokens).expect("expansion is a valid file"))
+    }
+
+    fn expand_provider(args: TokenStream2, input_fn: ItemFn) -> String {
+        let args = syn::parse2(args).expect("valid provider arguments");
+        pretty(generate_provider(input_fn, args).expect("provider expands"))
+    }
+
+    fn expand_mutation(args: TokenStream2, input_fn: ItemFn) -> String {
+        let args = syn::parse2(args).expect("valid mutation arguments");
+        pretty(generate_mutation(input_fn, args).expect("mutation expands"))
+    }
+
+    #[test]
+    fn provider_without_parameters() {
+        insta::assert_snapshot!(expand_provider(
+            quote! {},
+            parse_quote! {
+                async fn fetch_config() -> Result<Config, String> {
+                    api::config().await
+                }
+            },
+        ));
+    }
+
+    #[test]
+    fn provider_with_one_parameter() {
+        insta::assert_snapshot!(expand_provider(
+            quote! { stale_time = "30s", cache_expiration = "5min" },
+            parse_quote! {
+                pub async fn fetch_user(id: u32) -> Result<User, String> {
+                    api::user(id).await
+                }
+            },
+        ));
+    }
+
+    #[test]
+    fn provider_with_parameters() {
+        insta::assert_snapshot!(expand_provider(
+            quote! { interval = "1m" },
+            parse_quote! {
+                async fn search(query: &str, page: u32) -> Result<Vec<Hit>, String> {
+                    api::search(query, page).await
+                }
+            },
+        ));
+    }
+
+    #[test]
+    fn mutation_with_invalidation() {
+        insta::assert_snapshot!(expand_mutation(
+            quote! { invalidates = [fetch_user] },
+            parse_quote! {
+                async fn rename_user(id: u32, name: String) -> Result<User, String> {
+                    api::rename(id, name).await
+                }
+            },
+        ));
+    }
+}
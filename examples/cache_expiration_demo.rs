@@ -283,7 +283,7 @@ fn CacheExpirationDemo() -> Element {
                 CacheCard {
                     title: "⚡ Quick Cache (5s TTL)",
                     cache_type: "quick",
-                    data: quick_data,
+                    data: quick_data.signal(),
                     render_success: |data: &QuickData| rsx! {
                         div { class: "data-content",
                             h4 { "{data.message}" }
@@ -296,7 +296,7 @@ fn CacheExpirationDemo() -> Element {
                 CacheCard {
                     title: "🔄 Medium Cache (15s TTL)",
                     cache_type: "medium",
-                    data: medium_data,
+                    data: medium_data.signal(),
                     render_success: |data: &MediumData| rsx! {
                         div { class: "data-content",
                             h4 { "{data.content}" }
@@ -314,7 +314,7 @@ fn CacheExpirationDemo() -> Element {
                 CacheCard {
                     title: "🐌 Long Cache (30s TTL)",
                     cache_type: "long",
-                    data: long_data,
+                    data: long_data.signal(),
                     render_success: |data: &LongData| rsx! {
                         div { class: "data-content",
                             h4 { "{data.title}" }
@@ -333,7 +333,7 @@ fn CacheExpirationDemo() -> Element {
                 CacheCard {
                     title: format!("👤 User Cache (10s TTL) - User {}", *selected_user_id.read()),
                     cache_type: "user",
-                    data: user_data,
+                    data: user_data.signal(),
                     render_success: |data: &UserCacheData| rsx! {
                         div { class: "data-content",
                             div { class: "user-preferences",
@@ -402,7 +402,7 @@ fn CacheCard<T: 'static + Clone + PartialEq, E: 'static + Clone + PartialEq + st
 ) -> Element {
     let cache_status = match &*data.read() {
         State::Loading { .. } => "cache-miss",
-        State::Success(_) => "cache-hit",
+        State::Success(_) | State::Reloading { .. } => "cache-hit",
         State::Error(_) => "cache-error",
     };
 
@@ -433,7 +433,7 @@ fn CacheCard<T: 'static + Clone + PartialEq, E: 'static + Clone + PartialEq + st
                             span { class: "error-message", "Error: {e}" }
                         }
                     },
-                    State::Success(value) => render_success(value),
+                    State::Success(value) | State::Reloading { previous: value, .. } => render_success(value),
                 }
             }
         }
@@ -52,7 +52,7 @@ fn App() -> Element {
                             "🔄 Loading data..."
                         }
                     },
-                    State::Success(result) => rsx! {
+                    State::Success(result) | State::Reloading { previous: result, .. } => rsx! {
                         div { style: "color: green;",
                             "✅ Success: {result}"
                         }
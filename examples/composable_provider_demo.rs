@@ -293,7 +293,7 @@ fn IndividualProvidersDemo(user_id: u32) -> Element {
                 h4 { "👤 User Data" }
                 match &*user_data.read() {
                     State::Loading { .. } => rsx! { p { class: "loading", "Loading user..." } },
-                    State::Success(user) => rsx! {
+                    State::Success(user) | State::Reloading { previous: user, .. } => rsx! {
                         div {
                             p { strong { "Name: " } {user.name.clone()} }
                             p { strong { "Email: " } {user.email.clone()} }
@@ -309,7 +309,7 @@ fn IndividualProvidersDemo(user_id: u32) -> Element {
                 h4 { "🔐 Permissions" }
                 match &*permissions_data.read() {
                     State::Loading { .. } => rsx! { p { class: "loading", "Loading permissions..." } },
-                    State::Success(perms) => rsx! {
+                    State::Success(perms) | State::Reloading { previous: perms, .. } => rsx! {
                         div {
                             p { strong { "Role: " } {perms.role.clone()} }
                             p { strong { "Permissions: " } {perms.permissions.join(", ")} }
@@ -324,7 +324,7 @@ fn IndividualProvidersDemo(user_id: u32) -> Element {
                 h4 { "⚙️ Settings" }
                 match &*settings_data.read() {
                     State::Loading { .. } => rsx! { p { class: "loading", "Loading settings..." } },
-                    State::Success(settings) => rsx! {
+                    State::Success(settings) | State::Reloading { previous: settings, .. } => rsx! {
                         div {
                             p { strong { "Theme: " } {settings.theme.clone()} }
                             p { strong { "Language: " } {settings.language.clone()} }
@@ -350,7 +350,7 @@ fn ComposableProviderDemo(user_id: u32) -> Element {
                         p { "⚡ Loading full profile in parallel..." }
                     }
                 },
-                State::Success(profile) => rsx! {
+                State::Success(profile) | State::Reloading { previous: profile, .. } => rsx! {
                     div { class: "success composition-section",
                         div { style: "display: flex; justify-content: space-between; align-items: center; margin-bottom: 15px;",
                             h3 { style: "color: #28a745; margin: 0;", "✅ Full Profile Loaded" }
@@ -409,7 +409,7 @@ fn PartialCompositionDemo(user_id: u32) -> Element {
                 State::Loading { .. } => rsx! {
                     p { class: "loading", "Loading user with permissions..." }
                 },
-                State::Success(user_perms) => rsx! {
+                State::Success(user_perms) | State::Reloading { previous: user_perms, .. } => rsx! {
                     div { class: "card",
                         h3 { style: "color: #6f42c1; margin-top: 0;", "👤🔐 User + Permissions (Partial Composition)" }
 
@@ -475,21 +475,21 @@ fn ComprehensiveCacheTest() -> Element {
 
             div { class: "features-grid",
                 // Live Metrics (Interval Refresh)
-                LiveMetricsCard { data: live_metrics }
+                LiveMetricsCard { data: live_metrics.signal() }
 
                 // User Dashboard (SWR)
                 UserDashboardCard {
-                    data: user_dashboard,
+                    data: user_dashboard.signal(),
                     user_id: *selected_user_id.read(),
                 }
 
                 // Analytics Report (Cache Expiration)
-                AnalyticsCard { data: analytics }
+                AnalyticsCard { data: analytics.signal() }
 
                 // Temporary Data (Cache Expiration)
                 if *show_temp_data.read() {
                     TempDataCard {
-                        data: temp_data,
+                        data: temp_data.signal(),
                         session_id: session_id.read().clone(),
                     }
                 }
@@ -497,7 +497,7 @@ fn ComprehensiveCacheTest() -> Element {
                 // Chat Data (SWR + Cache Expiration)
                 if *show_chat.read() {
                     ChatCard {
-                        data: chat_data,
+                        data: chat_data.signal(),
                         chat_id: *selected_chat_id.read(),
                     }
                 }
@@ -569,7 +569,7 @@ fn LiveMetricsCard(data: Signal<State<LiveMetrics, String>>) -> Element {
     let refresh_metrics = use_invalidate_provider(fetch_live_metrics(), ());
     let status_class = match &*data.read() {
         State::Loading { .. } => "loading",
-        State::Success(_) => "success",
+        State::Success(_) | State::Reloading { .. } => "success",
         State::Error(_) => "error",
     };
 
@@ -611,7 +611,7 @@ fn LiveMetricsCard(data: Signal<State<LiveMetrics, String>>) -> Element {
                             span { class: "error-message", "Error: {e}" }
                         }
                     },
-                    State::Success(metrics) => rsx! {
+                    State::Success(metrics) | State::Reloading { previous: metrics, .. } => rsx! {
                         div { class: "metrics-content",
                             div { class: "metric-item",
                                 span { class: "metric-label", "CPU Usage:" }
@@ -653,7 +653,7 @@ fn UserDashboardCard(data: Signal<State<UserDashboard, String>>, user_id: u32) -
     let refresh_dashboard = use_invalidate_provider(fetch_user_dashboard(), user_id);
     let status_class = match &*data.read() {
         State::Loading { .. } => "loading",
-        State::Success(_) => "success",
+        State::Success(_) | State::Reloading { .. } => "success",
         State::Error(_) => "error",
     };
 
@@ -695,7 +695,7 @@ fn UserDashboardCard(data: Signal<State<UserDashboard, String>>, user_id: u32) -
                             span { class: "error-message", "Error: {e}" }
                         }
                     },
-                    State::Success(dashboard) => rsx! {
+                    State::Success(dashboard) | State::Reloading { previous: dashboard, .. } => rsx! {
                         div { class: "dashboard-content",
                             div { class: "preferences",
                                 h5 { "Preferences:" }
@@ -727,7 +727,7 @@ fn AnalyticsCard(data: Signal<State<AnalyticsReport, String>>) -> Element {
     let refresh_analytics = use_invalidate_provider(fetch_analytics_report(), ());
     let status_class = match &*data.read() {
         State::Loading { .. } => "loading",
-        State::Success(_) => "success",
+        State::Success(_) | State::Reloading { .. } => "success",
         State::Error(_) => "error",
     };
 
@@ -769,7 +769,7 @@ fn AnalyticsCard(data: Signal<State<AnalyticsReport, String>>) -> Element {
                             span { class: "error-message", "Error: {e}" }
                         }
                     },
-                    State::Success(report) => rsx! {
+                    State::Success(report) | State::Reloading { previous: report, .. } => rsx! {
                         div { class: "analytics-content",
                             div { class: "analytics-summary",
                                 div { class: "summary-item",
@@ -810,7 +810,7 @@ fn TempDataCard(data: Signal<State<TempSessionData, String>>, session_id: String
     let refresh_temp = use_invalidate_provider(fetch_temporary_data(), session_id);
     let status_class = match &*data.read() {
         State::Loading { .. } => "loading",
-        State::Success(_) => "success",
+        State::Success(_) | State::Reloading { .. } => "success",
         State::Error(_) => "error",
     };
 
@@ -852,7 +852,7 @@ fn TempDataCard(data: Signal<State<TempSessionData, String>>, session_id: String
                             span { class: "error-message", "Error: {e}" }
                         }
                     },
-                    State::Success(temp) => rsx! {
+                    State::Success(temp) | State::Reloading { previous: temp, .. } => rsx! {
                         div { class: "temp-content",
                             p { class: "session-id", "Session: {temp.session_id}" }
                             p { class: "memory-usage", "Memory: {temp.memory_usage_mb} MB" }
@@ -881,7 +881,7 @@ fn ChatCard(data: Signal<State<ChatData, String>>, chat_id: u32) -> Element {
     let refresh_chat = use_invalidate_provider(fetch_chat_messages(), chat_id);
     let status_class = match &*data.read() {
         State::Loading { .. } => "loading",
-        State::Success(_) => "success",
+        State::Success(_) | State::Reloading { .. } => "success",
         State::Error(_) => "error",
     };
 
@@ -923,7 +923,7 @@ fn ChatCard(data: Signal<State<ChatData, String>>, chat_id: u32) -> Element {
                             span { class: "error-message", "Error: {e}" }
                         }
                     },
-                    State::Success(chat) => rsx! {
+                    State::Success(chat) | State::Reloading { previous: chat, .. } => rsx! {
                         div { class: "chat-content",
                             div { class: "chat-header",
                                 span { class: "online-users", "👥 {chat.online_users} online" }
@@ -47,7 +47,7 @@ fn CounterApp() -> Element {
             h2 { class: "text-lg font-semibold mt-4", "Provider State:" },
             match &*counter.read() {
                 State::Loading { .. } => rsx! { p { "Loading counter..." } },
-                State::Success(val) => rsx! { p { "Counter (from provider): {val}" } },
+                State::Success(val) | State::Reloading { previous: val, .. } => rsx! { p { "Counter (from provider): {val}" } },
                 State::Error(err) => rsx! { p { "Error: {err}" } },
             },
             h2 { class: "text-lg font-semibold mt-4", "Mutation State:" },
@@ -170,7 +170,7 @@ fn UserProfile(user_id: u32) -> Element {
                 State::Loading { .. } => rsx! {
                     div { class: "loading", "Loading user..." }
                 },
-                State::Success(user) => rsx! {
+                State::Success(user) | State::Reloading { previous: user, .. } => rsx! {
                     div { class: "user-info",
                         h2 { "{user.name}" }
                         p { "Email: {user.email}" }
@@ -186,7 +186,7 @@ fn UserProfile(user_id: u32) -> Element {
                 State::Loading { .. } => rsx! {
                     div { class: "loading", "Loading posts..." }
                 },
-                State::Success(posts) => rsx! {
+                State::Success(posts) | State::Reloading { previous: posts, .. } => rsx! {
                     div { class: "posts",
                         h3 { "Posts" }
                         for post in posts {
@@ -226,7 +226,7 @@ fn CachedUserProfile(user_id: u32) -> Element {
                 State::Loading { .. } => rsx! {
                     div { class: "loading", "Loading cached user..." }
                 },
-                State::Success(user) => rsx! {
+                State::Success(user) | State::Reloading { previous: user, .. } => rsx! {
                     div { class: "user-info",
                         h4 { "{user.name}" }
                         p { "Email: {user.email}" }
@@ -243,7 +243,7 @@ fn CachedUserProfile(user_id: u32) -> Element {
                 State::Loading { .. } => rsx! {
                     div { class: "loading", "Loading fresh posts..." }
                 },
-                State::Success(posts) => rsx! {
+                State::Success(posts) | State::Reloading { previous: posts, .. } => rsx! {
                     div { class: "posts",
                         for post in posts {
                             div { class: "post",
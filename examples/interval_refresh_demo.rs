@@ -174,7 +174,7 @@ fn LiveStatsCard() -> Element {
         MetricsCard {
             title: "Live Stats (2s interval)".to_string(),
             description: "High-frequency metrics that update every 2 seconds".to_string(),
-            data: data,
+            data: data.signal(),
             color_class: "live".to_string(),
         }
     }
@@ -191,7 +191,7 @@ fn SystemMetricsCard() -> Element {
                 div { class: match &*data.read() {
                     State::Loading { .. } => "status loading",
                     State::Error(_) => "status error",
-                    State::Success(_) => "status success",
+                    State::Success(_) | State::Reloading { .. } => "status success",
                 }}
             }
 
@@ -210,7 +210,7 @@ fn SystemMetricsCard() -> Element {
                             span { "❌ Error: {e}" }
                         }
                     },
-                    State::Success(metrics) => rsx! {
+                    State::Success(metrics) | State::Reloading { previous: metrics, .. } => rsx! {
                         div { class: "data-grid",
                             div { class: "metric",
                                 span { class: "label", "Disk Usage:" }
@@ -251,7 +251,7 @@ fn BusinessMetricsCard() -> Element {
                 div { class: match &*data.read() {
                     State::Loading { .. } => "status loading",
                     State::Error(_) => "status error",
-                    State::Success(_) => "status success",
+                    State::Success(_) | State::Reloading { .. } => "status success",
                 }}
             }
 
@@ -270,7 +270,7 @@ fn BusinessMetricsCard() -> Element {
                             span { "❌ Error: {e}" }
                         }
                     },
-                    State::Success(metrics) => rsx! {
+                    State::Success(metrics) | State::Reloading { previous: metrics, .. } => rsx! {
                         div { class: "data-grid",
                             div { class: "metric",
                                 span { class: "label", "Daily Revenue:" }
@@ -310,7 +310,7 @@ fn MetricsCard(
     let status_class = match &*data.read() {
         State::Loading { .. } => "status loading",
         State::Error(_) => "status error",
-        State::Success(_) => "status success",
+        State::Success(_) | State::Reloading { .. } => "status success",
     };
 
     rsx! {
@@ -335,7 +335,7 @@ fn MetricsCard(
                             span { "❌ Error: {e}" }
                         }
                     },
-                    State::Success(stats) => rsx! {
+                    State::Success(stats) | State::Reloading { previous: stats, .. } => rsx! {
                         div { class: "data-grid",
                             div { class: "metric",
                                 span { class: "label", "Active Users:" }
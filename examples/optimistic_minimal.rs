@@ -14,16 +14,15 @@
 //! 3. Update any item's name. It changes immediately with the multi-arg optimistic mutation.
 //! 4. Toggle "Simulate Errors" to see automatic rollback when mutations fail!
 //!
+//! Server calls go through a `FakeTransport`, which adds latency and fails on demand.
+//!
 //! The rest of this file stays intentionally small so you can focus on the macro APIs.
 
 use dioxus::prelude::*;
 use dioxus_provider::prelude::*;
+use dioxus_provider::testing::FakeTransport;
 use serde::{Deserialize, Serialize};
-use std::sync::atomic::{AtomicBool, Ordering};
-use tokio::time::{Duration, sleep};
-
-/// Global flag to simulate errors for demonstration purposes
-static SIMULATE_ERRORS: AtomicBool = AtomicBool::new(false);
+use std::time::Duration;
 
 /// Simple item to demonstrate optimistic mutations
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -62,24 +61,23 @@ pub async fn load_items() -> Result<Vec<Item>, ItemError> {
     ])
 }
 
+/// The fake server registered in `main`
+fn server() -> Result<std::sync::Arc<FakeTransport>, ItemError> {
+    inject::<FakeTransport>().map_err(|e| ItemError::Other(e.to_string()))
+}
+
 /// Delete an item - optimistic update is auto-applied!
 #[mutation(
     invalidates = [load_items],
     optimistic = |items: &mut Vec<Item>, id: &u64| items.retain(|item| item.id != *id)
 )]
 pub async fn delete_item(_id: u64, items: Vec<Item>) -> Result<Vec<Item>, ItemError> {
-    sleep(Duration::from_millis(1000)).await;
-
-    // Simulate server error for demonstration
-    if SIMULATE_ERRORS.load(Ordering::Relaxed) {
-        return Err(ItemError::Other(
-            "Simulated server error - deletion rejected!".to_string(),
-        ));
-    }
-
     // In a real app, you'd persist to a backend here
     // The optimistic update is already applied to `items`
-    Ok(items)
+    server()?
+        .request("delete_item", || items)
+        .await
+        .map_err(|e| ItemError::Other(format!("{e} - deletion rejected!")))
 }
 
 /// Update an item's name - demonstrates multi-argument optimistic mutation
@@ -96,18 +94,12 @@ pub async fn update_item(
     _new_name: String,
     items: Vec<Item>,
 ) -> Result<Vec<Item>, ItemError> {
-    sleep(Duration::from_millis(1000)).await;
-
-    // Simulate server error for demonstration
-    if SIMULATE_ERRORS.load(Ordering::Relaxed) {
-        return Err(ItemError::Other(
-            "Simulated server error - update rejected!".to_string(),
-        ));
-    }
-
     // In a real app, you'd persist to a backend here
     // The optimistic update is already applied to `items`
-    Ok(items)
+    server()?
+        .request("update_item", || items)
+        .await
+        .map_err(|e| ItemError::Other(format!("{e} - update rejected!")))
 }
 
 /// Item component with delete and update buttons demonstrating optimistic mutations
@@ -170,7 +162,7 @@ pub fn ItemsList() -> Element {
                 State::Error(err) => rsx! {
                     div { "Error: {err}" }
                 },
-                State::Success(items) => {
+                State::Success(items) | State::Reloading { previous: items, .. } => {
                     if items.is_empty() {
                         rsx! {
                             div { "No items" }
@@ -198,7 +190,9 @@ pub fn App() -> Element {
     let toggle_errors = move |_| {
         let new_value = !simulate_errors();
         simulate_errors.set(new_value);
-        SIMULATE_ERRORS.store(new_value, Ordering::Relaxed);
+        if let Ok(server) = server() {
+            server.set_failure_rate(if new_value { 1.0 } else { 0.0 });
+        }
     };
 
     rsx! {
@@ -252,5 +246,6 @@ pub fn App() -> Element {
 
 fn main() {
     let _ = dioxus_provider::init();
+    let _ = register_dependency(FakeTransport::new().with_latency(Duration::from_millis(1000)));
     dioxus::launch(App);
 }
@@ -37,7 +37,7 @@ fn UserAgeCard(user_id: u32) -> Element {
     rsx! {
         match &message {
             State::Loading { .. } => rsx!(div { "Loading age..." }),
-            State::Success(msg) => rsx!(div { "{msg}" }),
+            State::Success(msg) | State::Reloading { previous: msg, .. } => rsx!(div { "{msg}" }),
             State::Error(err) => rsx!(div { style: "color: red;", "{err}" }),
         }
     }
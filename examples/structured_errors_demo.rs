@@ -147,7 +147,7 @@ fn UserProfile(user_id: u32) -> Element {
                 State::Loading { .. } => rsx! {
                     div { class: "text-blue-500", "Loading user..." }
                 },
-                State::Success(user) => rsx! {
+                State::Success(user) | State::Reloading { previous: user, .. } => rsx! {
                     div { class: "bg-white rounded-lg shadow p-6",
                         h2 { class: "text-xl font-semibold mb-4", "User Information" }
                         p { "Name: {user.name}" }
@@ -1,5 +1,5 @@
 use dioxus::prelude::*;
-use dioxus_provider::{hooks::SuspenseSignalExt, prelude::*};
+use dioxus_provider::prelude::*;
 use std::time::Duration;
 
 // A simple provider that simulates a delayed async fetch
@@ -17,7 +17,7 @@ async fn fetch_user(id: u32) -> Result<String, String> {
 #[component]
 fn UserCard(id: u32) -> Element {
     // Use the provider and suspend rendering until data is ready
-    let user = use_provider(fetch_user(), id).suspend()?;
+    let user = use_provider_suspense(fetch_user(), id)?;
 
     match user {
         Ok(name) => rsx!(div { "Loaded: {name}" }),
@@ -177,7 +177,7 @@ fn SwrDemo() -> Element {
                         span { class: "cache-indicator", "⚡ SWR Active" }
                     }
                     SWRDataDisplay {
-                        data: user_profile,
+                        data: user_profile.signal(),
                         render_success: |profile: &UserProfile| rsx! {
                             div { class: "profile-content",
                                 div { class: "profile-info",
@@ -203,7 +203,7 @@ fn SwrDemo() -> Element {
                         span { class: "cache-indicator", "⚡ SWR Active" }
                     }
                     SWRDataDisplay {
-                        data: user_posts,
+                        data: user_posts.signal(),
                         render_success: |posts: &Vec<Post>| rsx! {
                             div { class: "posts-content",
                                 for post in posts.iter().take(2) {
@@ -255,7 +255,10 @@ fn SWRDataDisplay<
                 span { class: "error-message", "Error: {e}" }
             }
         },
-        State::Success(value) => render_success(value),
+        State::Success(value)
+        | State::Reloading {
+            previous: value, ..
+        } => render_success(value),
     }
 }
 
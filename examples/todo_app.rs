@@ -382,7 +382,7 @@ pub fn TodoList(filter: Filter) -> Element {
                 State::Error(err) => rsx! {
                     div { class: "text-center text-red-500", "Failed to load todos: {err}" }
                 },
-                State::Success(_) => rsx! {
+                State::Success(_) | State::Reloading { .. } => rsx! {
                     if let Some(filtered) = filtered_todos {
                         if filtered.is_empty() {
                             div { class: "text-center text-gray-500", "No todos found" }
@@ -446,7 +446,7 @@ pub fn TodoStatsDisplay() -> Element {
                 State::Error(err) => rsx! {
                     div { class: "text-center text-red-500", "Failed to load stats: {err}" }
                 },
-                State::Success(stats) => rsx! {
+                State::Success(stats) | State::Reloading { previous: stats, .. } => rsx! {
                     div { class: "grid grid-cols-2 md:grid-cols-4 gap-4 text-center",
                         div { class: "space-y-1",
                             div { class: "text-2xl font-bold text-blue-600", "{stats.total}" }
@@ -0,0 +1,267 @@
+//! # Binary Payloads
+//!
+//! Images, audio and other binary payloads are large, and the cache otherwise counts every
+//! entry as [`DEFAULT_ENTRY_WEIGHT`](crate::cache::DEFAULT_ENTRY_WEIGHT) bytes and clones
+//! the output for each consumer. A [`BytesProvider`] outputs [`Bytes`], an `Arc<[u8]>`
+//! that is shared instead of copied, and weighs each entry with its length, so a byte
+//! budget set with `RuntimeSettings::with_max_cache_bytes` evicts a few large images
+//! instead of many small entries.
+//!
+//! On native targets, payloads can also spill over to disk with
+//! [`BytesProvider::with_spillover`]: every fetched payload is written to a directory,
+//! and runs read it back from there instead of fetching it again, for example after the
+//! entry was evicted from memory.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use dioxus_provider::{bytes::BytesProvider, prelude::*};
+//!
+//! fn thumbnails() -> BytesProvider<String, String> {
+//!     BytesProvider::new("thumbnail", |url: String| async move {
+//!         api::download(&url).await
+//!     })
+//!     .with_spillover(cache_dir().join("thumbnails"))
+//! }
+//!
+//! #[component]
+//! fn Thumbnail(url: String) -> Element {
+//!     let image = use_provider(thumbnails(), url);
+//!     rsx! { "{image.read().data().map_or(0, |bytes| bytes.len())} bytes" }
+//! }
+//! ```
+
+use std::{fmt, future::Future, sync::Arc};
+
+#[cfg(not(target_family = "wasm"))]
+use std::{path::PathBuf, time::Duration};
+
+use crate::{
+    hooks::{FnProvider, Provider, WeightFn},
+    query_key::QueryKey,
+    types::{ProviderErrorBounds, ProviderParamBounds},
+};
+
+/// A shared binary payload
+pub type Bytes = Arc<[u8]>;
+
+/// A [`Provider`] of binary payloads, weighed by their length in the cache
+///
+/// Like [`FnProvider`], it runs a closure and is given a name its cache keys start with.
+/// The closure returns anything that converts into [`Bytes`], such as a `Vec<u8>`.
+pub struct BytesProvider<Param, E> {
+    inner: FnProvider<Param, Bytes, E>,
+    #[cfg(not(target_family = "wasm"))]
+    spillover: Option<Spillover>,
+}
+
+/// Where and for how long payloads are kept on disk
+#[cfg(not(target_family = "wasm"))]
+#[derive(Debug, Clone, PartialEq)]
+struct Spillover {
+    dir: Arc<PathBuf>,
+    max_age: Option<Duration>,
+}
+
+impl<Param, E> BytesProvider<Param, E>
+where
+    Param: ProviderParamBounds,
+    E: ProviderErrorBounds,
+{
+    /// Create a provider named `name` that downloads payloads with `fetch`
+    pub fn new<F, Fut, B>(name: impl Into<String>, fetch: F) -> Self
+    where
+        F: Fn(Param) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<B, E>> + 'static,
+        B: Into<Bytes>,
+    {
+        Self {
+            inner: FnProvider::new(name, move |param| {
+                let fetch = fetch(param);
+                async move { fetch.await.map(Into::into) }
+            }),
+            #[cfg(not(target_family = "wasm"))]
+            spillover: None,
+        }
+    }
+
+    /// The name the provider's cache keys start with
+    pub fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    /// Keep fetched payloads in `dir` and serve runs from there (native only)
+    ///
+    /// Payloads on disk are served until they are older than
+    /// [`with_spillover_max_age`](Self::with_spillover_max_age), or forever by default,
+    /// which suits content that doesn't change under its parameters, like images by URL.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn with_spillover(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.spillover = Some(Spillover {
+            dir: Arc::new(dir.into()),
+            max_age: None,
+        });
+        self
+    }
+
+    /// Fetch payloads again once their copy on disk is older than `max_age`
+    ///
+    /// Only has an effect together with [`with_spillover`](Self::with_spillover).
+    #[cfg(not(target_family = "wasm"))]
+    pub fn with_spillover_max_age(mut self, max_age: Duration) -> Self {
+        if let Some(spillover) = &mut self.spillover {
+            spillover.max_age = Some(max_age);
+        }
+        self
+    }
+
+    /// The file the payload for `param` spills over to
+    #[cfg(not(target_family = "wasm"))]
+    fn spill_path(&self, param: &Param) -> Option<PathBuf> {
+        let spillover = self.spillover.as_ref()?;
+        let key = self.inner.id(param);
+        let file = format!("{:016x}.bin", xxhash_rust::xxh3::xxh3_64(key.as_bytes()));
+        Some(spillover.dir.join(file))
+    }
+}
+
+impl<Param, E> Clone for BytesProvider<Param, E> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            #[cfg(not(target_family = "wasm"))]
+            spillover: self.spillover.clone(),
+        }
+    }
+}
+
+impl<Param, E> PartialEq for BytesProvider<Param, E> {
+    fn eq(&self, other: &Self) -> bool {
+        #[cfg(not(target_family = "wasm"))]
+        if self.spillover != other.spillover {
+            return false;
+        }
+        self.inner == other.inner
+    }
+}
+
+impl<Param, E> fmt::Debug for BytesProvider<Param, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BytesProvider")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Read a spilled payload, if there is one younger than `max_age`
+#[cfg(not(target_family = "wasm"))]
+async fn read_spilled(path: PathBuf, max_age: Option<Duration>) -> Option<Bytes> {
+    crate::platform::run_blocking(move || {
+        let age = std::fs::metadata(&path).ok()?.modified().ok()?.elapsed();
+        if max_age.is_some_and(|max_age| age.is_ok_and(|age| age > max_age)) {
+            return None;
+        }
+        std::fs::read(&path).ok().map(Bytes::from)
+    })
+    .await
+}
+
+/// Write a fetched payload to disk, logging failures
+#[cfg(not(target_family = "wasm"))]
+async fn write_spilled(path: PathBuf, bytes: Bytes) {
+    crate::platform::run_blocking(move || {
+        let written = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|_| std::fs::write(&path, &bytes));
+        if let Err(_error) = written {
+            crate::debug_log!(
+                "⚠️ [BYTES] Failed to spill payload to {}: {}",
+                path.display(),
+                _error
+            );
+        }
+    })
+    .await
+}
+
+impl<Param, E> Provider<Param> for BytesProvider<Param, E>
+where
+    Param: ProviderParamBounds,
+    E: ProviderErrorBounds,
+{
+    type Output = Bytes;
+    type Error = E;
+
+    fn run(&self, param: Param) -> impl Future<Output = Result<Self::Output, Self::Error>> {
+        let inner = self.inner.clone();
+        #[cfg(not(target_family = "wasm"))]
+        let spill = self
+            .spill_path(&param)
+            .zip(self.spillover.as_ref().map(|spillover| spillover.max_age));
+        async move {
+            #[cfg(not(target_family = "wasm"))]
+            if let Some((path, max_age)) = &spill
+                && let Some(bytes) = read_spilled(path.clone(), *max_age).await
+            {
+                crate::debug_log!("💾 [BYTES] Serving spilled payload: {}", path.display());
+                return Ok(bytes);
+            }
+            let result = inner.run(param).await;
+            #[cfg(not(target_family = "wasm"))]
+            if let (Some((path, _)), Ok(bytes)) = (spill, &result) {
+                write_spilled(path, bytes.clone()).await;
+            }
+            result
+        }
+    }
+
+    fn query_key(&self, param: &Param) -> Option<QueryKey> {
+        self.inner.query_key(param)
+    }
+
+    fn weight(&self) -> Option<WeightFn<Self::Output>> {
+        Some(|bytes| bytes.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+
+    #[test]
+    fn payloads_are_weighed_by_length() {
+        let avatar = BytesProvider::new("avatar", |size: usize| async move {
+            Ok::<_, String>(vec![0u8; size])
+        });
+        let bytes = block_on(avatar.run(4096)).unwrap();
+        assert_eq!(bytes.len(), 4096);
+        assert_eq!(avatar.weight().map(|weight| weight(&bytes)), Some(4096));
+        assert_eq!(avatar.id(&4096), "avatar/4096");
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    #[test]
+    fn spilled_payloads_are_served_from_disk() {
+        let dir = std::env::temp_dir().join(format!("provider-bytes-{}", std::process::id()));
+        let downloads = Arc::new(std::sync::atomic::AtomicU32::new(0));
+        let counted = downloads.clone();
+        let cover = BytesProvider::new("cover", move |id: u32| {
+            counted.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            async move { Ok::<_, String>(id.to_le_bytes().to_vec()) }
+        })
+        .with_spillover(&dir);
+
+        let fetched = block_on(cover.run(7)).unwrap();
+        let spilled = block_on(cover.run(7)).unwrap();
+        assert_eq!(fetched, spilled);
+        assert_eq!(downloads.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        let expired = cover.clone().with_spillover_max_age(Duration::ZERO);
+        std::thread::sleep(Duration::from_millis(5));
+        block_on(expired.run(7)).unwrap();
+        assert_eq!(downloads.load(std::sync::atomic::Ordering::SeqCst), 2);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
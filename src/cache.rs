@@ -3,7 +3,8 @@
 //! This module implements a global, type-erased cache for provider results, supporting:
 //! - **Expiration**: Entries are removed after a configurable TTL.
 //! - **Staleness (SWR)**: Entries can be marked stale and revalidated in the background.
-//! - **LRU Eviction**: Least-recently-used entries are evicted to maintain a size limit.
+//! - **Eviction**: Least-recently-used entries, or those an [`EvictionPolicy`] picks, are
+//!   evicted to maintain a size limit.
 //! - **Access/Usage Stats**: Provides statistics for cache introspection and tuning.
 //!
 //! ## Example
@@ -17,15 +18,28 @@
 
 use std::{
     any::Any,
-    collections::HashMap,
+    collections::{HashMap, HashSet, VecDeque},
+    fmt::{self, Debug},
+    future::Future,
+    pin::Pin,
     sync::{
         Arc, Mutex,
-        atomic::{AtomicU32, Ordering},
+        atomic::{AtomicBool, AtomicU32, Ordering},
     },
     time::Duration,
 };
 
-use crate::platform::{DEFAULT_MAX_CACHE_SIZE, DEFAULT_UNUSED_THRESHOLD};
+use crate::{
+    demo::{CacheSnapshot, DemoStorage},
+    entity::EntityStore,
+    errors::ProviderError,
+    eviction::{EvictionCandidate, EvictionPolicy, Lru, order_candidates},
+    history::QueryHistory,
+    platform::task::yield_now,
+    query_key::QueryKey,
+    served::ServedEntities,
+    settings::SharedSettings,
+};
 
 // Platform-specific time imports
 #[cfg(not(target_family = "wasm"))]
@@ -79,6 +93,168 @@ pub struct CacheGetResult<T> {
     pub is_stale: bool,
 }
 
+/// How invalidating a provider treats its cached entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InvalidationMode {
+    /// Keep the entry but mark it stale, so consumers keep showing the old data
+    /// (with `is_stale = true`) while it is refetched in the background
+    #[default]
+    MarkStale,
+    /// Remove the entry from the cache, so the next read is a cache miss
+    Remove,
+}
+
+/// How long a provider's errors stay cached
+///
+/// Errors past their caching window are only shown to consumers that already show them;
+/// consumers that mount later refetch instead of receiving the cached failure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorCaching {
+    /// Don't serve errors from the cache to other consumers
+    Never,
+    /// Cache errors for the given duration, then refetch on the next use
+    Short(Duration),
+    /// Cache errors exactly like successful results
+    #[default]
+    Same,
+}
+
+impl ErrorCaching {
+    /// The expiration of a cached error, or None to use the provider's usual timings
+    pub fn expiration(self) -> Option<Duration> {
+        match self {
+            ErrorCaching::Never => Some(Duration::ZERO),
+            ErrorCaching::Short(duration) => Some(duration),
+            ErrorCaching::Same => None,
+        }
+    }
+}
+
+/// Per-entry cache timings that override a provider's static `stale_time` and `cache_expiration`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CachePolicy {
+    /// Stale time for this entry, overriding the provider's `stale_time`
+    pub stale_time: Option<Duration>,
+    /// Expiration for this entry, overriding the provider's `cache_expiration`
+    pub expiration: Option<Duration>,
+}
+
+impl CachePolicy {
+    /// Create an empty policy that keeps the provider's static timings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the stale time for this entry
+    pub fn with_stale_time(mut self, stale_time: Duration) -> Self {
+        self.stale_time = Some(stale_time);
+        self
+    }
+
+    /// Set the expiration for this entry
+    pub fn with_expiration(mut self, expiration: Duration) -> Self {
+        self.expiration = Some(expiration);
+        self
+    }
+}
+
+/// Provider output wrapper that sets the cache expiration for this particular result
+///
+/// Lets a single provider cache volatile results briefly and stable ones for longer,
+/// instead of using one static `cache_expiration` for every entry. Providers generated
+/// with `#[provider]` whose output is `WithTtl<T>` apply the TTL automatically; equality
+/// only compares the data.
+///
+/// ```rust,ignore
+/// #[provider]
+/// async fn fetch_row(id: u32) -> Result<WithTtl<Row>, String> {
+///     let row = load_row(id).await?;
+///     let ttl = if row.archived { Duration::from_secs(3600) } else { Duration::from_secs(10) };
+///     Ok(WithTtl::new(row, ttl))
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct WithTtl<T> {
+    /// The provider data
+    pub data: T,
+    /// How long this result stays cached
+    pub ttl: Duration,
+    /// Optional stale time for this result
+    pub stale_time: Option<Duration>,
+}
+
+impl<T> WithTtl<T> {
+    /// Wrap data with a time-to-live
+    pub fn new(data: T, ttl: Duration) -> Self {
+        Self {
+            data,
+            ttl,
+            stale_time: None,
+        }
+    }
+
+    /// Also mark this result stale after the given duration
+    pub fn with_stale_time(mut self, stale_time: Duration) -> Self {
+        self.stale_time = Some(stale_time);
+        self
+    }
+
+    /// Cache policy for the wrapped result, usable as a provider's `cache_policy` function
+    pub fn cache_policy(output: &Self) -> Option<CachePolicy> {
+        Some(CachePolicy {
+            stale_time: output.stale_time,
+            expiration: Some(output.ttl),
+        })
+    }
+
+    /// Unwrap the provider data
+    pub fn into_inner(self) -> T {
+        self.data
+    }
+}
+
+impl<T: PartialEq> PartialEq for WithTtl<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
+    }
+}
+
+impl<T> std::ops::Deref for WithTtl<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.data
+    }
+}
+
+/// The provider and parameters that produced a cache entry
+///
+/// Recorded in debug builds so that two providers (or two parameter values) hashing to the
+/// same key fail loudly instead of serving each other's data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyOrigin {
+    /// Type name of the provider
+    pub provider: &'static str,
+    /// `Debug` representation of the parameters
+    pub param: String,
+}
+
+impl KeyOrigin {
+    /// The origin of a key computed by provider `P` for `param`
+    pub fn of<P: ?Sized, Param: Debug>(param: &Param) -> Self {
+        Self {
+            provider: std::any::type_name::<P>(),
+            param: format!("{param:?}"),
+        }
+    }
+}
+
+impl fmt::Display for KeyOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}({})", self.provider, self.param)
+    }
+}
+
 /// A type-erased cache entry for storing provider results with timestamp and access tracking
 #[derive(Clone)]
 pub struct CacheEntry {
@@ -86,8 +262,20 @@ pub struct CacheEntry {
     cached_at: Arc<Mutex<Instant>>,
     last_accessed: Arc<Mutex<Instant>>,
     access_count: Arc<AtomicU32>,
+    policy: CachePolicy,
+    marked_stale: Arc<AtomicBool>,
+    origin: Option<Arc<KeyOrigin>>,
+    weight: usize,
+    encoder: Option<EntryEncoder>,
 }
 
+/// Serializes a type-erased entry, for providers that know how to (see [`ssr`](crate::ssr))
+pub(crate) type EntryEncoder =
+    Arc<dyn Fn(&(dyn Any + Send + Sync)) -> Option<String> + Send + Sync>;
+
+/// Size in bytes assumed for entries whose provider doesn't weigh its output
+pub const DEFAULT_ENTRY_WEIGHT: usize = 1024;
+
 impl CacheEntry {
     /// Creates a new cache entry with the given data.
     ///
@@ -105,6 +293,11 @@ impl CacheEntry {
             cached_at: Arc::new(Mutex::new(now)),
             last_accessed: Arc::new(Mutex::new(now)),
             access_count: Arc::new(AtomicU32::new(0)),
+            policy: CachePolicy::default(),
+            marked_stale: Arc::new(AtomicBool::new(false)),
+            origin: None,
+            weight: DEFAULT_ENTRY_WEIGHT,
+            encoder: None,
         }
     }
 
@@ -143,6 +336,7 @@ impl CacheEntry {
         if let Ok(mut cached_at) = self.cached_at.lock() {
             *cached_at = Instant::now();
         }
+        self.marked_stale.store(false, Ordering::SeqCst);
     }
 
     /// Checks if the cache entry has expired based on the given expiration duration.
@@ -174,6 +368,9 @@ impl CacheEntry {
     ///
     /// A boolean indicating whether the entry is stale.
     pub fn is_stale(&self, stale_time: Duration) -> bool {
+        if self.is_marked_stale() {
+            return true;
+        }
         if let Ok(cached_at) = self.cached_at.lock() {
             cached_at.elapsed() > stale_time
         } else {
@@ -181,6 +378,38 @@ impl CacheEntry {
         }
     }
 
+    /// Marks the entry as stale regardless of its age.
+    ///
+    /// The flag is cleared when the entry is refreshed with new data.
+    pub fn mark_stale(&self) {
+        self.marked_stale.store(true, Ordering::SeqCst);
+    }
+
+    /// Checks whether the entry was explicitly marked stale by a soft invalidation.
+    pub fn is_marked_stale(&self) -> bool {
+        self.marked_stale.load(Ordering::SeqCst)
+    }
+
+    /// Gets the per-entry cache policy.
+    pub fn policy(&self) -> CachePolicy {
+        self.policy
+    }
+
+    /// Expiration for this entry, preferring its policy over the given provider default.
+    pub fn effective_expiration(&self, default: Option<Duration>) -> Option<Duration> {
+        self.policy.expiration.or(default)
+    }
+
+    /// Stale time for this entry, preferring its policy over the given provider default.
+    pub fn effective_stale_time(&self, default: Option<Duration>) -> Option<Duration> {
+        self.policy.stale_time.or(default)
+    }
+
+    /// Approximate size of the entry in bytes, used by byte-based eviction.
+    pub fn weight(&self) -> usize {
+        self.weight
+    }
+
     /// Gets the current access count for the cache entry.
     ///
     /// # Arguments
@@ -247,6 +476,68 @@ impl CacheEntry {
     }
 }
 
+/// Number of previously visited routes whose providers are kept warm
+const RECENT_ROUTES_KEPT: usize = 3;
+
+/// Cache keys of the providers used by the active and recently visited routes
+#[derive(Debug, Default)]
+struct RouteKeys {
+    active: HashSet<String>,
+    recent: VecDeque<HashSet<String>>,
+}
+
+impl RouteKeys {
+    /// Eviction priority of a key: active route > recent routes > everything else
+    fn priority(&self, key: &str) -> u8 {
+        if self.active.contains(key) {
+            2
+        } else if self.recent.iter().any(|keys| keys.contains(key)) {
+            1
+        } else {
+            0
+        }
+    }
+}
+
+/// Future returned by [`CacheStorage`] operations
+pub type StorageFuture<T> = Pin<Box<dyn Future<Output = Result<T, ProviderError>>>>;
+
+/// A persistence layer backing the entries of providers marked `#[provider(persist)]`
+///
+/// Entries are stored as serialized strings under their cache keys. Operations are async
+/// so that backends like IndexedDB fit; synchronous backends return ready futures. See
+/// [`storage`](crate::storage) for the built-in adapters.
+pub trait CacheStorage: Send + Sync + 'static {
+    /// Read the entry stored under `key`, if any
+    fn read(&self, key: &str) -> StorageFuture<Option<String>>;
+
+    /// Store `value` under `key`, replacing any previous entry
+    fn write(&self, key: &str, value: String) -> StorageFuture<()>;
+
+    /// Remove the entry stored under `key`
+    fn remove(&self, key: &str) -> StorageFuture<()>;
+}
+
+impl Debug for dyn CacheStorage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CacheStorage").finish_non_exhaustive()
+    }
+}
+
+/// The storage backing a cache, with the keys restored from it so far
+#[derive(Clone)]
+struct StorageBackend {
+    storage: Arc<dyn CacheStorage>,
+    /// Keys whose stored entry was read, or is being read, since startup
+    restored: Arc<Mutex<HashSet<String>>>,
+    /// Keys whose stored entry is being read
+    restoring: Arc<Mutex<HashSet<String>>>,
+    /// Whether evicted entries with a stored copy are restored again on their next use
+    tiered: bool,
+    /// Keys known to have a stored copy, tracked in tiered mode
+    on_disk: Arc<Mutex<HashSet<String>>>,
+}
+
 /// Global cache for provider results with automatic cleanup
 #[derive(Clone, Default)]
 pub struct ProviderCache {
@@ -254,6 +545,28 @@ pub struct ProviderCache {
     /// Tracks pending requests to enable request deduplication
     /// Key: cache key, Value: number of components waiting for this request
     pending_requests: Arc<Mutex<HashMap<String, u32>>>,
+    /// Normalized entities written by providers that opt into normalization
+    entities: EntityStore,
+    /// Keys of route providers, which cleanup and eviction keep around longer
+    route_keys: Arc<Mutex<RouteKeys>>,
+    /// Entries serialized on the server, decoded on their key's first use
+    server_entries: Arc<Mutex<HashMap<String, String>>>,
+    /// Hot keys that maintenance never evicts or removes as unused
+    pinned_keys: Arc<Mutex<HashSet<String>>>,
+    /// Keys holding local edits that fetched results must not overwrite
+    dirty_keys: Arc<Mutex<HashSet<String>>>,
+    /// Recently visited keys of providers that retain history, which maintenance keeps
+    history: QueryHistory,
+    /// Keys of providers that declare the server entities they serve
+    served: ServedEntities,
+    /// Live settings of the runtime owning this cache, such as the size limit
+    settings: SharedSettings,
+    /// Persistent storage for the entries of persisted providers
+    storage: Option<StorageBackend>,
+    /// Whether entries come from a demo snapshot instead of running providers
+    demo: bool,
+    /// The order entries are evicted in, least recently used first if None
+    eviction_policy: Option<Arc<dyn EvictionPolicy>>,
 }
 
 impl ProviderCache {
@@ -266,6 +579,242 @@ impl ProviderCache {
         Self::default()
     }
 
+    /// Back the entries of persisted providers with `storage`
+    pub fn with_storage(mut self, storage: Arc<dyn CacheStorage>) -> Self {
+        self.storage = Some(StorageBackend {
+            storage,
+            restored: Arc::new(Mutex::new(HashSet::new())),
+            restoring: Arc::new(Mutex::new(HashSet::new())),
+            tiered: false,
+            on_disk: Arc::new(Mutex::new(HashSet::new())),
+        });
+        self
+    }
+
+    /// Use `storage` as a second cache tier behind memory
+    ///
+    /// Like [`with_storage`](Self::with_storage), but entries of persisted providers that
+    /// maintenance evicts from memory stay in `storage` and are restored from it on their
+    /// key's next use instead of being fetched again.
+    pub fn with_tiered_storage(mut self, storage: Arc<dyn CacheStorage>) -> Self {
+        self = self.with_storage(storage);
+        if let Some(backend) = &mut self.storage {
+            backend.tiered = true;
+        }
+        self
+    }
+
+    /// Returns true if evicted entries move to the cache's storage instead of being dropped
+    pub fn is_tiered(&self) -> bool {
+        self.storage.as_ref().is_some_and(|backend| backend.tiered)
+    }
+
+    /// Record that the storage holds a copy of `key`, in tiered mode
+    pub(crate) fn mark_on_disk(&self, key: &str) {
+        if let Some(backend) = &self.storage
+            && backend.tiered
+            && let Ok(mut on_disk) = backend.on_disk.lock()
+        {
+            on_disk.insert(key.to_string());
+        }
+    }
+
+    /// Let an entry evicted from memory be restored from storage on its next use
+    fn demote(&self, key: &str) {
+        let Some(backend) = &self.storage else {
+            return;
+        };
+        if backend.tiered
+            && backend
+                .on_disk
+                .lock()
+                .is_ok_and(|on_disk| on_disk.contains(key))
+        {
+            crate::debug_log!("💽 [TIERED] Demoted evicted entry to storage: {}", key);
+            self.allow_restore_again(key);
+        }
+    }
+
+    /// Serve the entries of `snapshot` and never run providers (see [`demo`](crate::demo))
+    pub fn with_demo_snapshot(mut self, snapshot: CacheSnapshot) -> Self {
+        self = self.with_storage(Arc::new(DemoStorage::new(snapshot)));
+        self.demo = true;
+        self
+    }
+
+    /// Evict entries in the order `policy` chooses instead of least recently used first
+    pub fn with_eviction_policy(mut self, policy: Arc<dyn EvictionPolicy>) -> Self {
+        self.eviction_policy = Some(policy);
+        self
+    }
+
+    /// The policy choosing which entries are evicted first
+    pub fn eviction_policy(&self) -> &dyn EvictionPolicy {
+        self.eviction_policy.as_deref().unwrap_or(&Lru)
+    }
+
+    /// Returns true if this cache serves a demo snapshot instead of running providers
+    pub fn is_demo(&self) -> bool {
+        self.demo
+    }
+
+    /// The persistent storage backing this cache, if one was configured
+    pub fn storage(&self) -> Option<&Arc<dyn CacheStorage>> {
+        self.storage.as_ref().map(|backend| &backend.storage)
+    }
+
+    /// Claim the restoration of `key` from storage, returning true on its first use
+    ///
+    /// The caller must read the entry and then call [`finish_restore`](Self::finish_restore).
+    pub fn begin_restore(&self, key: &str) -> bool {
+        let Some(backend) = &self.storage else {
+            return false;
+        };
+        if !backend
+            .restored
+            .lock()
+            .is_ok_and(|mut restored| restored.insert(key.to_string()))
+        {
+            return false;
+        }
+        if let Ok(mut restoring) = backend.restoring.lock() {
+            restoring.insert(key.to_string());
+        }
+        true
+    }
+
+    /// Returns true while the stored entry for `key` is being restored
+    pub fn is_restoring(&self, key: &str) -> bool {
+        self.storage.as_ref().is_some_and(|backend| {
+            backend
+                .restoring
+                .lock()
+                .is_ok_and(|restoring| restoring.contains(key))
+        })
+    }
+
+    /// Mark the restoration of `key` as done
+    pub fn finish_restore(&self, key: &str) {
+        if let Some(backend) = &self.storage
+            && let Ok(mut restoring) = backend.restoring.lock()
+        {
+            restoring.remove(key);
+        }
+    }
+
+    /// Let `key` be restored again once it leaves the cache
+    ///
+    /// Used in demo mode, where the snapshot is the only source of a key's data, and for
+    /// entries evicted in tiered mode.
+    pub(crate) fn allow_restore_again(&self, key: &str) {
+        if let Some(backend) = &self.storage
+            && let Ok(mut restored) = backend.restored.lock()
+        {
+            restored.remove(key);
+        }
+    }
+
+    /// The normalized entity store shared by all providers using this cache
+    pub fn entities(&self) -> &EntityStore {
+        &self.entities
+    }
+
+    /// The live settings applied by maintenance and stale checks
+    pub fn settings(&self) -> &SharedSettings {
+        &self.settings
+    }
+
+    /// The recently visited keys of providers with a history window
+    pub fn history(&self) -> &QueryHistory {
+        &self.history
+    }
+
+    /// The keys of providers serving server entities, for `invalidate_entity`
+    pub fn served(&self) -> &ServedEntities {
+        &self.served
+    }
+
+    /// Hint which cache keys belong to the active route
+    ///
+    /// Entries of the active route are evicted last and never removed as unused. The keys
+    /// of the previously active routes are kept warm too, so navigating back is instant.
+    pub fn set_route_keys(&self, keys: &[String]) {
+        if let Ok(mut route_keys) = self.route_keys.lock() {
+            let active: HashSet<String> = keys.iter().cloned().collect();
+            if route_keys.active == active {
+                return;
+            }
+            let previous = std::mem::replace(&mut route_keys.active, active);
+            if !previous.is_empty() {
+                route_keys.recent.push_front(previous);
+                route_keys.recent.truncate(RECENT_ROUTES_KEPT);
+            }
+        }
+    }
+
+    /// Pin hot keys, such as the most visited screens' data, so maintenance keeps them
+    ///
+    /// Pinned entries are never evicted or removed as unused. Keys can be pinned before
+    /// anything is cached under them. See [`warm`](crate::warm) for pinning and prefetching
+    /// providers together.
+    pub fn pin_keys(&self, keys: &[String]) {
+        if let Ok(mut pinned_keys) = self.pinned_keys.lock() {
+            pinned_keys.extend(keys.iter().cloned());
+        }
+    }
+
+    /// Let maintenance evict the given keys again
+    pub fn unpin_keys(&self, keys: &[String]) {
+        if let Ok(mut pinned_keys) = self.pinned_keys.lock() {
+            for key in keys {
+                pinned_keys.remove(key);
+            }
+        }
+    }
+
+    /// Check if a key is pinned
+    pub fn is_pinned(&self, key: &str) -> bool {
+        self.pinned_keys
+            .lock()
+            .is_ok_and(|pinned_keys| pinned_keys.contains(key))
+    }
+
+    /// The pinned keys, sorted
+    pub fn pinned_keys(&self) -> Vec<String> {
+        let mut keys: Vec<String> = self
+            .pinned_keys
+            .lock()
+            .map(|pinned_keys| pinned_keys.iter().cloned().collect())
+            .unwrap_or_default();
+        keys.sort();
+        keys
+    }
+
+    /// Mark a key as holding local edits that haven't been committed
+    ///
+    /// Fetched results for a dirty key are discarded until the key is cleared, so a
+    /// background refetch can't overwrite a draft.
+    pub fn mark_dirty(&self, key: &str) {
+        if let Ok(mut dirty_keys) = self.dirty_keys.lock() {
+            dirty_keys.insert(key.to_string());
+        }
+    }
+
+    /// Check if a key holds uncommitted local edits
+    pub fn is_dirty(&self, key: &str) -> bool {
+        self.dirty_keys
+            .lock()
+            .map(|dirty_keys| dirty_keys.contains(key))
+            .unwrap_or(false)
+    }
+
+    /// Stop protecting a key's local edits from fetched results
+    pub fn clear_dirty(&self, key: &str) {
+        if let Ok(mut dirty_keys) = self.dirty_keys.lock() {
+            dirty_keys.remove(key);
+        }
+    }
+
     /// Check if a request is currently pending for the given cache key
     ///
     /// # Arguments
@@ -311,10 +860,10 @@ impl ProviderCache {
     ///
     /// * `key` - The cache key
     pub fn mark_request_complete(&self, key: &str) {
-        if let Ok(mut pending) = self.pending_requests.lock() {
-            if pending.remove(key).is_some() {
-                crate::debug_log!("✅ [REQUEST-DEDUP] Request completed for key: {}", key);
-            }
+        if let Ok(mut pending) = self.pending_requests.lock()
+            && pending.remove(key).is_some()
+        {
+            crate::debug_log!("✅ [REQUEST-DEDUP] Request completed for key: {}", key);
         }
     }
 
@@ -391,19 +940,19 @@ impl ProviderCache {
         let entry = cache_guard.get(key)?;
 
         // Check expiration first
-        if let Some(exp_duration) = options.expiration {
-            if entry.is_expired(exp_duration) {
-                drop(cache_guard);
-                // Remove expired entry
-                if let Ok(mut cache) = self.cache.lock() {
-                    cache.remove(key);
-                    crate::debug_log!(
-                        "🗑️ [CACHE-EXPIRATION] Removing expired cache entry for key: {}",
-                        key
-                    );
-                }
-                return None;
+        if let Some(exp_duration) = entry.effective_expiration(options.expiration)
+            && entry.is_expired(exp_duration)
+        {
+            drop(cache_guard);
+            // Remove expired entry
+            if let Ok(mut cache) = self.cache.lock() {
+                cache.remove(key);
+                crate::debug_log!(
+                    "🗑️ [CACHE-EXPIRATION] Removing expired cache entry for key: {}",
+                    key
+                );
             }
+            return None;
         }
 
         // Get the data
@@ -411,10 +960,10 @@ impl ProviderCache {
 
         // Check staleness if requested
         let is_stale = if options.check_staleness {
-            if let Some(stale_duration) = options.stale_time {
+            if let Some(stale_duration) = entry.effective_stale_time(options.stale_time) {
                 entry.is_stale(stale_duration)
             } else {
-                false
+                entry.is_marked_stale()
             }
         } else {
             false
@@ -567,6 +1116,174 @@ impl ProviderCache {
         false
     }
 
+    /// Sets the per-entry cache policy for a key.
+    ///
+    /// # Returns
+    ///
+    /// A boolean indicating whether an entry existed for the key.
+    pub fn set_policy(&self, key: &str, policy: CachePolicy) -> bool {
+        if let Ok(mut cache) = self.cache.lock()
+            && let Some(entry) = cache.get_mut(key)
+        {
+            entry.policy = policy;
+            return true;
+        }
+        false
+    }
+
+    /// Sets the approximate size in bytes of the entry for a key.
+    ///
+    /// # Returns
+    ///
+    /// A boolean indicating whether an entry existed for the key.
+    pub fn set_weight(&self, key: &str, weight: usize) -> bool {
+        if let Ok(mut cache) = self.cache.lock()
+            && let Some(entry) = cache.get_mut(key)
+        {
+            entry.weight = weight;
+            return true;
+        }
+        false
+    }
+
+    /// Records how to serialize the entry for a key, for [`serialize`](Self::serialize)
+    pub(crate) fn set_encoder(&self, key: &str, encoder: EntryEncoder) -> bool {
+        if let Ok(mut cache) = self.cache.lock()
+            && let Some(entry) = cache.get_mut(key)
+        {
+            entry.encoder = Some(encoder);
+            return true;
+        }
+        false
+    }
+
+    /// Serialize the successful entries of persisted providers, as after server-side rendering
+    ///
+    /// Other entries are skipped, since only persisted providers know how to serialize
+    /// their output. Reading the entries doesn't count as an access.
+    pub fn serialize(&self) -> CacheSnapshot {
+        let mut snapshot = CacheSnapshot::new();
+        if let Ok(cache) = self.cache.lock() {
+            for (key, entry) in cache.iter() {
+                if let Some(value) = entry
+                    .encoder
+                    .as_ref()
+                    .and_then(|encode| encode(entry.data.as_ref()))
+                {
+                    snapshot.insert(key.clone(), value);
+                }
+            }
+        }
+        snapshot
+    }
+
+    /// Serve the entries of a snapshot taken with [`serialize`](Self::serialize)
+    ///
+    /// Each entry is decoded by its provider on its key's first use, synchronously, so the
+    /// first render shows the data the server rendered instead of `Loading`. Entries already
+    /// in the cache are kept, and hydrated entries are revalidated like fetched ones.
+    pub fn hydrate(&self, snapshot: &CacheSnapshot) {
+        if let Ok(mut server_entries) = self.server_entries.lock() {
+            for key in snapshot.keys() {
+                if let Some(value) = snapshot.get(key) {
+                    server_entries.insert(key.to_string(), value.to_string());
+                }
+            }
+        }
+    }
+
+    /// Take the serialized entry the server rendered for a key, if it wasn't used yet
+    pub(crate) fn take_server_entry(&self, key: &str) -> Option<String> {
+        self.server_entries.lock().ok()?.remove(key)
+    }
+
+    /// Returns true if the entry for a key has outlived its expiration
+    ///
+    /// The entry's own policy takes precedence over `default`; entries without either never
+    /// expire.
+    pub fn is_expired(&self, key: &str, default: Option<Duration>) -> bool {
+        self.cache.lock().is_ok_and(|cache| {
+            cache.get(key).is_some_and(|entry| {
+                entry
+                    .effective_expiration(default)
+                    .is_some_and(|expiration| entry.is_expired(expiration))
+            })
+        })
+    }
+
+    /// Records which provider and parameters produced the entry for a key
+    ///
+    /// # Returns
+    ///
+    /// A boolean indicating whether an entry existed for the key.
+    pub fn set_key_origin(&self, key: &str, origin: KeyOrigin) -> bool {
+        if let Ok(mut cache) = self.cache.lock()
+            && let Some(entry) = cache.get_mut(key)
+        {
+            entry.origin = Some(Arc::new(origin));
+            return true;
+        }
+        false
+    }
+
+    /// Panics if the entry for a key was produced by a different provider or parameters
+    ///
+    /// Entries without a recorded origin are accepted.
+    pub fn assert_key_origin(&self, key: &str, origin: &KeyOrigin) {
+        let stored = self
+            .cache
+            .lock()
+            .ok()
+            .and_then(|cache| cache.get(key).and_then(|entry| entry.origin.clone()));
+        if let Some(stored) = stored
+            && *stored != *origin
+        {
+            panic!(
+                "Cache key collision on \"{key}\": the entry was stored by {stored} but read by \
+                 {origin}. Give one of the providers a distinct query key, or choose a \
+                 different key hasher."
+            );
+        }
+    }
+
+    /// Applies an in-place patch to cached data without refetching.
+    ///
+    /// The entry keeps its timestamps and policy; only the data is replaced.
+    ///
+    /// # Returns
+    ///
+    /// A boolean indicating whether the data changed. Missing entries and entries of a
+    /// different type are left untouched.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use dioxus_provider::cache::ProviderCache;
+    ///
+    /// let cache = ProviderCache::new();
+    /// cache.set("todos".to_string(), vec!["milk".to_string()]);
+    /// cache.update::<Vec<String>, _>("todos", |todos| todos.push("eggs".to_string()));
+    /// ```
+    pub fn update<T, F>(&self, key: &str, patch: F) -> bool
+    where
+        T: Clone + Send + Sync + PartialEq + 'static,
+        F: FnOnce(&mut T),
+    {
+        if let Ok(mut cache) = self.cache.lock()
+            && let Some(entry) = cache.get_mut(key)
+            && let Some(mut data) = entry.get::<T>()
+        {
+            let before = data.clone();
+            patch(&mut data);
+            if data != before {
+                entry.data = Arc::new(data);
+                crate::debug_log!("🩹 [CACHE-UPDATE] Patched cache entry for key: {}", key);
+                return true;
+            }
+        }
+        false
+    }
+
     /// Removes a cached result by key.
     ///
     /// # Arguments
@@ -607,6 +1324,90 @@ impl ProviderCache {
         );
     }
 
+    /// Invalidates every entry whose key, parsed as a [`QueryKey`], starts with `prefix`.
+    ///
+    /// # Returns
+    ///
+    /// The keys of the invalidated entries.
+    pub fn invalidate_prefix(&self, prefix: &QueryKey, mode: InvalidationMode) -> Vec<String> {
+        let keys: Vec<String> = self
+            .keys_snapshot()
+            .into_iter()
+            .filter(|key| QueryKey::parse(key).starts_with(prefix))
+            .collect();
+        for key in &keys {
+            self.invalidate_with_mode(key, mode);
+        }
+        crate::debug_log!(
+            "🗑️ [CACHE-INVALIDATE] Invalidated {} entries with prefix: {}",
+            keys.len(),
+            prefix
+        );
+        keys
+    }
+
+    /// Invalidates the entries of providers serving a server entity, optionally one id of it.
+    ///
+    /// Keys that are no longer cached stop being tracked. Returns the invalidated keys.
+    pub fn invalidate_entity(
+        &self,
+        entity: &str,
+        id: Option<&dyn std::fmt::Debug>,
+        mode: InvalidationMode,
+    ) -> Vec<String> {
+        let cached: HashSet<String> = self.keys_snapshot().into_iter().collect();
+        self.served.retain(|key| cached.contains(key));
+        let keys = self.served.keys_for(entity, id);
+        for key in &keys {
+            self.invalidate_with_mode(key, mode);
+        }
+        crate::debug_log!(
+            "🗑️ [CACHE-INVALIDATE] Invalidated {} entries serving entity: {} ({:?})",
+            keys.len(),
+            entity,
+            id
+        );
+        keys
+    }
+
+    /// Marks a cached result as stale without removing it (soft invalidation).
+    ///
+    /// # Returns
+    ///
+    /// A boolean indicating whether an entry existed for the key.
+    pub fn mark_stale(&self, key: &str) -> bool {
+        if let Ok(cache) = self.cache.lock()
+            && let Some(entry) = cache.get(key)
+        {
+            entry.mark_stale();
+            crate::debug_log!(
+                "🪦 [CACHE-INVALIDATE] Marked cache entry stale for key: {}",
+                key
+            );
+            return true;
+        }
+        false
+    }
+
+    /// Checks whether the entry for a key was marked stale by a soft invalidation.
+    pub fn is_marked_stale(&self, key: &str) -> bool {
+        self.cache
+            .lock()
+            .ok()
+            .and_then(|cache| cache.get(key).map(CacheEntry::is_marked_stale))
+            .unwrap_or(false)
+    }
+
+    /// Invalidates a cached result using the given mode.
+    pub fn invalidate_with_mode(&self, key: &str, mode: InvalidationMode) {
+        match mode {
+            InvalidationMode::MarkStale => {
+                self.mark_stale(key);
+            }
+            InvalidationMode::Remove => self.invalidate(key),
+        }
+    }
+
     /// Clears all cached results.
     ///
     /// # Arguments
@@ -645,6 +1446,9 @@ impl ProviderCache {
 
     /// Cleans up unused entries based on access time.
     ///
+    /// Entries are checked in chunks, releasing the cache lock between chunks, so other
+    /// readers aren't blocked for the whole pass on large caches.
+    ///
     /// # Arguments
     ///
     /// * `&self` - A reference to the `ProviderCache`.
@@ -658,28 +1462,37 @@ impl ProviderCache {
     ///
     /// Unused entries are removed from the cache.
     pub fn cleanup_unused_entries(&self, unused_threshold: Duration) -> usize {
-        if let Ok(mut cache) = self.cache.lock() {
-            let initial_size = cache.len();
-            cache.retain(|_key, entry| {
-                let should_keep = !entry.is_unused_for(unused_threshold);
-                #[cfg(feature = "tracing")]
-                if !should_keep {
-                    crate::debug_log!("🧹 [CACHE-CLEANUP] Removing unused entry: {}", _key);
-                }
-                should_keep
-            });
-            let removed = initial_size - cache.len();
-            if removed > 0 {
-                crate::debug_log!("🧹 [CACHE-CLEANUP] Removed {} unused entries", removed);
-            }
-            removed
-        } else {
-            0
+        let removed = self
+            .keys_snapshot()
+            .chunks(MAINTENANCE_CHUNK_SIZE)
+            .map(|keys| self.remove_unused_in(keys, unused_threshold))
+            .sum();
+        if removed > 0 {
+            crate::debug_log!("🧹 [CACHE-CLEANUP] Removed {} unused entries", removed);
+        }
+        removed
+    }
+
+    /// Like [`cleanup_unused_entries`](Self::cleanup_unused_entries), but yields to the
+    /// executor between chunks so maintenance of a large cache doesn't stall other tasks.
+    pub async fn cleanup_unused_entries_incremental(&self, unused_threshold: Duration) -> usize {
+        let mut removed = 0;
+        for keys in self.keys_snapshot().chunks(MAINTENANCE_CHUNK_SIZE) {
+            removed += self.remove_unused_in(keys, unused_threshold);
+            yield_now().await;
+        }
+        if removed > 0 {
+            crate::debug_log!("🧹 [CACHE-CLEANUP] Removed {} unused entries", removed);
         }
+        removed
     }
 
     /// Evicts least recently used entries to maintain cache size limit.
     ///
+    /// The cache's [`EvictionPolicy`] decides the order, least recently used first unless
+    /// configured otherwise. Entries are ranked and evicted in chunks, releasing the cache
+    /// lock between chunks. An entry accessed after it was ranked is kept.
+    ///
     /// # Arguments
     ///
     /// * `&self` - A reference to the `ProviderCache`.
@@ -693,36 +1506,88 @@ impl ProviderCache {
     ///
     /// Least recently used entries are removed from the cache.
     pub fn evict_lru_entries(&self, max_size: usize) -> usize {
-        if let Ok(mut cache) = self.cache.lock() {
-            if cache.len() <= max_size {
-                return 0;
-            }
+        let keys = self.keys_snapshot();
+        if keys.len() <= max_size {
+            return 0;
+        }
 
-            // Convert to vector for sorting
-            let mut entries: Vec<_> = cache.drain().collect();
+        let mut ranking = Vec::with_capacity(keys.len());
+        for chunk in keys.chunks(MAINTENANCE_CHUNK_SIZE) {
+            self.rank_in(chunk, &mut ranking);
+        }
+        let evicted = select_victims(self.eviction_policy(), ranking, max_size)
+            .chunks(MAINTENANCE_CHUNK_SIZE)
+            .map(|victims| self.evict_in(victims))
+            .sum();
+        log_lru_eviction(evicted);
+        evicted
+    }
 
-            // Sort by last access time (oldest first)
-            entries.sort_by(|(_, a), (_, b)| {
-                a.time_since_last_access().cmp(&b.time_since_last_access())
-            });
+    /// Evicts least recently used entries until the entries' weights add up to at most
+    /// `max_bytes`.
+    ///
+    /// Entries are ranked like [`evict_lru_entries`](Self::evict_lru_entries), so a few
+    /// large payloads such as images are evicted instead of many small entries.
+    ///
+    /// # Returns
+    ///
+    /// The number of entries evicted.
+    pub fn evict_lru_bytes(&self, max_bytes: usize) -> usize {
+        let keys = self.keys_snapshot();
+        let mut ranking = Vec::with_capacity(keys.len());
+        for chunk in keys.chunks(MAINTENANCE_CHUNK_SIZE) {
+            self.rank_in(chunk, &mut ranking);
+        }
+        let evicted = select_victims_by_weight(self.eviction_policy(), ranking, max_bytes)
+            .chunks(MAINTENANCE_CHUNK_SIZE)
+            .map(|victims| self.evict_in(victims))
+            .sum();
+        log_lru_eviction(evicted);
+        evicted
+    }
 
-            // Keep the most recently used entries
-            let to_keep = entries.split_off(entries.len().saturating_sub(max_size));
-            let evicted = entries.len();
+    /// Like [`evict_lru_entries`](Self::evict_lru_entries), but yields to the executor
+    /// between chunks so maintenance of a large cache doesn't stall other tasks.
+    pub async fn evict_lru_entries_incremental(&self, max_size: usize) -> usize {
+        let keys = self.keys_snapshot();
+        if keys.len() <= max_size {
+            return 0;
+        }
 
-            // Rebuild cache with kept entries
-            cache.extend(to_keep);
+        let mut ranking = Vec::with_capacity(keys.len());
+        for chunk in keys.chunks(MAINTENANCE_CHUNK_SIZE) {
+            self.rank_in(chunk, &mut ranking);
+            yield_now().await;
+        }
+        let mut evicted = 0;
+        for victims in
+            select_victims(self.eviction_policy(), ranking, max_size).chunks(MAINTENANCE_CHUNK_SIZE)
+        {
+            evicted += self.evict_in(victims);
+            yield_now().await;
+        }
+        log_lru_eviction(evicted);
+        evicted
+    }
 
-            if evicted > 0 {
-                crate::debug_log!(
-                    "🗑️ [LRU-EVICT] Evicted {} entries due to cache size limit",
-                    evicted
-                );
-            }
-            evicted
-        } else {
-            0
+    /// Like [`evict_lru_bytes`](Self::evict_lru_bytes), but yields to the executor between
+    /// chunks.
+    pub async fn evict_lru_bytes_incremental(&self, max_bytes: usize) -> usize {
+        let keys = self.keys_snapshot();
+        let mut ranking = Vec::with_capacity(keys.len());
+        for chunk in keys.chunks(MAINTENANCE_CHUNK_SIZE) {
+            self.rank_in(chunk, &mut ranking);
+            yield_now().await;
         }
+        let mut evicted = 0;
+        for victims in select_victims_by_weight(self.eviction_policy(), ranking, max_bytes)
+            .chunks(MAINTENANCE_CHUNK_SIZE)
+        {
+            evicted += self.evict_in(victims);
+            yield_now().await;
+        }
+        log_lru_eviction(evicted);
+        evicted
     }
 
     /// Performs comprehensive cache maintenance.
@@ -739,13 +1604,131 @@ impl ProviderCache {
     ///
     /// Unused entries are removed and LRU entries are evicted.
     pub fn maintain(&self) -> CacheMaintenanceStats {
+        let settings = self.settings.get();
+        let unused_removed = self.cleanup_unused_entries(settings.unused_threshold);
+        let mut lru_evicted = self.evict_lru_entries(settings.max_cache_size);
+        if let Some(max_bytes) = settings.max_cache_bytes {
+            lru_evicted += self.evict_lru_bytes(max_bytes);
+        }
         CacheMaintenanceStats {
-            unused_removed: self.cleanup_unused_entries(DEFAULT_UNUSED_THRESHOLD),
-            lru_evicted: self.evict_lru_entries(DEFAULT_MAX_CACHE_SIZE),
+            unused_removed,
+            lru_evicted,
             final_size: self.size(),
         }
     }
 
+    /// Like [`maintain`](Self::maintain), but yields to the executor between chunks.
+    pub async fn maintain_incremental(&self) -> CacheMaintenanceStats {
+        let settings = self.settings.get();
+        let unused_removed = self
+            .cleanup_unused_entries_incremental(settings.unused_threshold)
+            .await;
+        let mut lru_evicted = self
+            .evict_lru_entries_incremental(settings.max_cache_size)
+            .await;
+        if let Some(max_bytes) = settings.max_cache_bytes {
+            lru_evicted += self.evict_lru_bytes_incremental(max_bytes).await;
+        }
+        CacheMaintenanceStats {
+            unused_removed,
+            lru_evicted,
+            final_size: self.size(),
+        }
+    }
+
+    /// Keys of all cached entries, taken under a single short lock
+    pub(crate) fn keys_snapshot(&self) -> Vec<String> {
+        self.cache
+            .lock()
+            .map(|cache| cache.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Remove the unused entries among `keys`, skipping route keys, pinned keys and retained
+    /// history
+    fn remove_unused_in(&self, keys: &[String], unused_threshold: Duration) -> usize {
+        let route_keys = self.route_keys.lock();
+        let pinned_keys = self.pinned_keys.lock();
+        let Ok(mut cache) = self.cache.lock() else {
+            return 0;
+        };
+        let mut removed = 0;
+        for key in keys {
+            let is_route_key = route_keys
+                .as_ref()
+                .is_ok_and(|route_keys| route_keys.priority(key) > 0);
+            let is_pinned = pinned_keys
+                .as_ref()
+                .is_ok_and(|pinned_keys| pinned_keys.contains(key));
+            if !is_route_key
+                && !is_pinned
+                && !self.history.is_retained(key)
+                && cache
+                    .get(key)
+                    .is_some_and(|entry| entry.is_unused_for(unused_threshold))
+            {
+                crate::debug_log!("🧹 [CACHE-CLEANUP] Removing unused entry: {}", key);
+                cache.remove(key);
+                self.demote(key);
+                removed += 1;
+            }
+        }
+        removed
+    }
+
+    /// Describe the entries among `keys` to the eviction policy, with their route priority
+    fn rank_in(&self, keys: &[String], ranking: &mut Vec<EvictionCandidate>) {
+        let route_keys = self.route_keys.lock();
+        let pinned_keys = self.pinned_keys.lock();
+        let Ok(cache) = self.cache.lock() else {
+            return;
+        };
+        for key in keys {
+            if let Some(entry) = cache.get(key) {
+                let priority = if pinned_keys
+                    .as_ref()
+                    .is_ok_and(|pinned_keys| pinned_keys.contains(key))
+                {
+                    PINNED_PRIORITY
+                } else {
+                    route_keys
+                        .as_ref()
+                        .map_or(0, |route_keys| route_keys.priority(key))
+                        .max(u8::from(self.history.is_retained(key)))
+                };
+                ranking.push(EvictionCandidate {
+                    key: key.clone(),
+                    idle: entry.time_since_last_access(),
+                    age: entry.age(),
+                    access_count: entry.access_count(),
+                    weight: entry.weight(),
+                    expiration: entry.effective_expiration(None),
+                    priority,
+                });
+            }
+        }
+    }
+
+    /// Remove ranked entries that weren't accessed since they were ranked
+    fn evict_in(&self, victims: &[EvictionCandidate]) -> usize {
+        let Ok(mut cache) = self.cache.lock() else {
+            return 0;
+        };
+        let mut evicted = 0;
+        for victim in victims {
+            let key = &victim.key;
+            if cache
+                .get(key)
+                .is_some_and(|entry| entry.time_since_last_access() >= victim.idle)
+            {
+                cache.remove(key);
+                self.demote(key);
+                evicted += 1;
+            }
+        }
+        evicted
+    }
+
     /// Gets cache statistics.
     ///
     /// # Arguments
@@ -754,7 +1737,8 @@ impl ProviderCache {
     ///
     /// # Returns
     ///
-    /// A `CacheStats` containing statistics about the cache.
+    /// A `CacheStats` containing statistics about the cache. Observer counts are only known
+    /// to the runtime, so they are left empty; see `ProviderRuntime::cache_stats`.
     ///
     /// # Side Effects
     ///
@@ -763,10 +1747,12 @@ impl ProviderCache {
         if let Ok(cache) = self.cache.lock() {
             let mut total_age = Duration::ZERO;
             let mut total_accesses = 0;
+            let mut total_size_bytes = 0;
 
             for entry in cache.values() {
                 total_age += entry.age();
                 total_accesses += entry.access_count();
+                total_size_bytes += entry.weight();
             }
 
             let entry_count = cache.len();
@@ -781,7 +1767,8 @@ impl ProviderCache {
                 total_accesses,
                 total_references: 0, // No longer tracking references
                 avg_age,
-                total_size_bytes: entry_count * 1024, // Rough estimate
+                total_size_bytes,
+                ..CacheStats::default()
             }
         } else {
             CacheStats::default()
@@ -789,6 +1776,58 @@ impl ProviderCache {
     }
 }
 
+/// Entries processed per cache lock acquisition during maintenance
+const MAINTENANCE_CHUNK_SIZE: usize = 256;
+
+/// Priority of pinned entries, which are ranked last and never evicted
+const PINNED_PRIORITY: u8 = u8::MAX;
+
+/// The entries to evict to get down to `max_size`: lowest route priority first, then in
+/// the order of `policy`, never pinned entries
+fn select_victims(
+    policy: &dyn EvictionPolicy,
+    mut ranking: Vec<EvictionCandidate>,
+    max_size: usize,
+) -> Vec<EvictionCandidate> {
+    order_candidates(policy, &mut ranking);
+    ranking.truncate(ranking.len().saturating_sub(max_size));
+    ranking.retain(|candidate| candidate.priority != PINNED_PRIORITY);
+    ranking
+}
+
+/// The entries to evict to get the total weight down to `max_bytes`, in the same order as
+/// [`select_victims`]
+fn select_victims_by_weight(
+    policy: &dyn EvictionPolicy,
+    mut ranking: Vec<EvictionCandidate>,
+    max_bytes: usize,
+) -> Vec<EvictionCandidate> {
+    let mut excess = ranking
+        .iter()
+        .map(|candidate| candidate.weight)
+        .sum::<usize>()
+        .saturating_sub(max_bytes);
+    order_candidates(policy, &mut ranking);
+    ranking
+        .into_iter()
+        .take_while(|candidate| {
+            let evict = excess > 0;
+            excess = excess.saturating_sub(candidate.weight);
+            evict
+        })
+        .filter(|candidate| candidate.priority != PINNED_PRIORITY)
+        .collect()
+}
+
+fn log_lru_eviction(#[allow(unused_variables)] evicted: usize) {
+    if evicted > 0 {
+        crate::debug_log!(
+            "🗑️ [LRU-EVICT] Evicted {} entries due to cache size limit",
+            evicted
+        );
+    }
+}
+
 /// Statistics for cache maintenance operations
 #[derive(Debug, Clone, Default)]
 pub struct CacheMaintenanceStats {
@@ -805,6 +1844,12 @@ pub struct CacheStats {
     pub total_references: u32,
     pub avg_age: Duration,
     pub total_size_bytes: usize,
+    /// Entries watched by at least one component
+    pub observed_entries: usize,
+    /// Components watching entries, summed over all entries
+    pub total_observers: usize,
+    /// Number of components watching each cached key, including keys nobody watches
+    pub observers: HashMap<String, usize>,
 }
 
 impl CacheStats {
@@ -823,4 +1868,147 @@ impl CacheStats {
             0.0
         }
     }
+
+    /// Entries that are only cached, with no component watching them
+    pub fn unobserved_entries(&self) -> usize {
+        self.entry_count.saturating_sub(self.observed_entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn eviction_keeps_route_keys_and_recent_entries() {
+        let cache = ProviderCache::new();
+        for key in ["route-a", "route-b", "old", "new"] {
+            cache.set(key.to_string(), key.to_string());
+            std::thread::sleep(Duration::from_millis(2));
+        }
+
+        cache.set_route_keys(&["route-a".to_string()]);
+        cache.set_route_keys(&["route-b".to_string()]);
+
+        // Route entries outrank other entries, and the oldest other entry goes first
+        assert_eq!(cache.evict_lru_entries(3), 1);
+        assert!(cache.get::<String>("old").is_none());
+        assert_eq!(cache.evict_lru_entries(2), 1);
+        assert!(cache.get::<String>("new").is_none());
+        assert!(cache.get::<String>("route-a").is_some());
+        assert!(cache.get::<String>("route-b").is_some());
+
+        // Route entries are never removed as unused
+        assert_eq!(cache.cleanup_unused_entries(Duration::ZERO), 0);
+    }
+
+    #[test]
+    fn pinned_keys_survive_maintenance() {
+        let cache = ProviderCache::new();
+        for key in ["dashboard", "old", "new"] {
+            cache.set(key.to_string(), key.to_string());
+            std::thread::sleep(Duration::from_millis(2));
+        }
+        cache.pin_keys(&["dashboard".to_string()]);
+
+        assert_eq!(cache.evict_lru_entries(2), 1);
+        assert!(cache.get::<String>("old").is_none());
+        assert_eq!(cache.evict_lru_entries(0), 1);
+        assert_eq!(cache.cleanup_unused_entries(Duration::ZERO), 0);
+        assert!(cache.get::<String>("dashboard").is_some());
+
+        cache.unpin_keys(&["dashboard".to_string()]);
+        assert_eq!(cache.evict_lru_entries(0), 1);
+    }
+
+    #[test]
+    fn byte_eviction_removes_the_oldest_entries_over_budget() {
+        let cache = ProviderCache::new();
+        for (key, weight) in [("cover", 600_000), ("avatar", 40_000), ("banner", 500_000)] {
+            cache.set(key.to_string(), key.to_string());
+            cache.set_weight(key, weight);
+            std::thread::sleep(Duration::from_millis(2));
+        }
+        assert_eq!(cache.stats().total_size_bytes, 1_140_000);
+
+        assert_eq!(cache.evict_lru_bytes(1_000_000), 1);
+        assert!(cache.get::<String>("cover").is_none());
+        assert_eq!(cache.evict_lru_bytes(1_000_000), 0);
+        assert_eq!(cache.stats().total_size_bytes, 540_000);
+    }
+
+    #[test]
+    fn lfu_eviction_keeps_frequently_read_entries() {
+        let cache = ProviderCache::new().with_eviction_policy(Arc::new(crate::eviction::Lfu));
+        for key in ["popular", "once", "recent"] {
+            cache.set(key.to_string(), key.to_string());
+            std::thread::sleep(Duration::from_millis(2));
+        }
+        for _ in 0..3 {
+            cache.get::<String>("popular");
+        }
+        cache.get::<String>("recent");
+
+        assert_eq!(cache.evict_lru_entries(2), 1);
+        assert!(cache.get::<String>("once").is_none());
+        assert_eq!(cache.evict_lru_entries(1), 1);
+        assert!(cache.get::<String>("recent").is_none());
+        assert!(cache.get::<String>("popular").is_some());
+    }
+
+    #[test]
+    fn tiered_storage_restores_evicted_entries_again() {
+        let storage: Arc<dyn CacheStorage> = Arc::new(crate::demo::SnapshotRecorder::new());
+        for (cache, restored_again) in [
+            (
+                ProviderCache::new().with_tiered_storage(storage.clone()),
+                true,
+            ),
+            (ProviderCache::new().with_storage(storage), false),
+        ] {
+            assert!(cache.begin_restore("report"));
+            cache.set("report".to_string(), 1);
+            cache.mark_on_disk("report");
+            cache.finish_restore("report");
+            std::thread::sleep(Duration::from_millis(2));
+            cache.set("draft".to_string(), 2);
+
+            assert_eq!(cache.evict_lru_entries(1), 1);
+            assert!(cache.get::<i32>("report").is_none());
+            assert_eq!(cache.begin_restore("report"), restored_again);
+        }
+    }
+
+    #[test]
+    fn incremental_maintenance_spans_chunks() {
+        let cache = ProviderCache::new();
+        let total = MAINTENANCE_CHUNK_SIZE * 2 + 10;
+        for i in 0..total {
+            cache.set(format!("key-{i}"), i);
+        }
+
+        let evicted = futures::executor::block_on(cache.evict_lru_entries_incremental(100));
+        assert_eq!(evicted, total - 100);
+        assert_eq!(cache.size(), 100);
+
+        let removed =
+            futures::executor::block_on(cache.cleanup_unused_entries_incremental(Duration::ZERO));
+        assert_eq!(removed, 100);
+        assert_eq!(cache.size(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Cache key collision")]
+    fn mismatched_key_origin_panics() {
+        struct UserProvider;
+        struct PostProvider;
+
+        let cache = ProviderCache::new();
+        cache.set("3f2a".to_string(), 1u32);
+        assert!(cache.set_key_origin("3f2a", KeyOrigin::of::<UserProvider, _>(&1u32)));
+
+        cache.assert_key_origin("3f2a", &KeyOrigin::of::<UserProvider, _>(&1u32));
+        cache.assert_key_origin("unrecorded", &KeyOrigin::of::<PostProvider, _>(&1u32));
+        cache.assert_key_origin("3f2a", &KeyOrigin::of::<PostProvider, _>(&1u32));
+    }
 }
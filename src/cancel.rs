@@ -0,0 +1,181 @@
+//! # Cancellation
+//!
+//! A fetch is cancelled once nothing needs its result anymore: when the last component
+//! using its key unmounts or switches to other parameters. The runtime then drops the
+//! provider's future at its next `.await`, so the stale result is neither cached nor shown,
+//! and a component switching parameters never sees the result of the ones it left.
+//!
+//! Dropping the future is enough for most providers. Long-running ones that hold resources
+//! outside of it, like a download spawned on another task or a worker thread, can read the
+//! fetch's [`ProviderContext`] and stop that work when its [`CancellationToken`] fires.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use dioxus_provider::prelude::*;
+//!
+//! #[provider]
+//! async fn export_report(id: u32) -> Result<Report, String> {
+//!     let token = ProviderContext::current()
+//!         .map(|context| context.cancellation_token())
+//!         .unwrap_or_default();
+//!     let job = api::start_export(id).await?;
+//!     loop {
+//!         if token.is_cancelled() {
+//!             api::abort_export(job).await;
+//!             return Err("cancelled".to_string());
+//!         }
+//!         if let Some(report) = api::poll_export(job).await? {
+//!             return Ok(report);
+//!         }
+//!     }
+//! }
+//! ```
+
+use std::{
+    cell::RefCell,
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use futures::future::{Either, select};
+use tokio::sync::watch;
+
+/// A flag shared by clones that is set once, when an operation is cancelled
+#[derive(Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<watch::Sender<bool>>,
+}
+
+impl CancellationToken {
+    /// Create a token that isn't cancelled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel the operation, waking everything waiting in [`cancelled`](Self::cancelled)
+    pub fn cancel(&self) {
+        self.cancelled
+            .send_if_modified(|cancelled| !std::mem::replace(cancelled, true));
+    }
+
+    /// Returns true once the operation was cancelled
+    pub fn is_cancelled(&self) -> bool {
+        *self.cancelled.borrow()
+    }
+
+    /// Wait until the operation is cancelled
+    pub async fn cancelled(&self) {
+        let mut receiver = self.cancelled.subscribe();
+        let _ = receiver.wait_for(|cancelled| *cancelled).await;
+    }
+}
+
+impl fmt::Debug for CancellationToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CancellationToken")
+            .field("cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+/// The fetch a provider is running for
+#[derive(Debug, Clone)]
+pub struct ProviderContext {
+    cache_key: Arc<str>,
+    token: CancellationToken,
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<ProviderContext>> = const { RefCell::new(None) };
+}
+
+impl ProviderContext {
+    pub(crate) fn new(cache_key: &str, token: CancellationToken) -> Self {
+        Self {
+            cache_key: Arc::from(cache_key),
+            token,
+        }
+    }
+
+    /// The context of the fetch being polled, if any
+    ///
+    /// Only set while the runtime polls a provider's future, so tasks a provider spawns
+    /// should be given the context or its token up front.
+    pub fn current() -> Option<Self> {
+        CURRENT.with(|current| current.borrow().clone())
+    }
+
+    /// The cache key being fetched
+    pub fn cache_key(&self) -> &str {
+        &self.cache_key
+    }
+
+    /// The token cancelled when the fetch's result is no longer needed
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.token.clone()
+    }
+
+    /// Returns true once the fetch was cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.token.is_cancelled()
+    }
+
+    /// Run `future` with this context, dropping it if the token is cancelled first
+    ///
+    /// Returns None if the future was cancelled.
+    pub(crate) async fn run<F: Future>(self, future: F) -> Option<F::Output> {
+        let token = self.token.clone();
+        let future = std::pin::pin!(WithContext {
+            context: self,
+            future: Box::pin(future),
+        });
+        let cancelled = std::pin::pin!(token.cancelled());
+        // Checked first, so a fetch finishing after it was cancelled is still dropped
+        match select(cancelled, future).await {
+            Either::Left(_) => None,
+            Either::Right((output, _)) => Some(output),
+        }
+    }
+}
+
+/// A future polled with its provider context set
+struct WithContext<F> {
+    context: ProviderContext,
+    future: Pin<Box<F>>,
+}
+
+impl<F: Future> Future for WithContext<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let previous = CURRENT.with(|current| current.replace(Some(self.context.clone())));
+        let poll = self.future.as_mut().poll(cx);
+        CURRENT.with(|current| *current.borrow_mut() = previous);
+        poll
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+
+    #[test]
+    fn cancelled_fetches_are_dropped() {
+        let token = CancellationToken::new();
+        let context = ProviderContext::new("report/1", token.clone());
+        let key = block_on(context.clone().run(async {
+            ProviderContext::current().map(|context| context.cache_key().to_string())
+        }));
+        assert_eq!(key, Some(Some("report/1".to_string())));
+        assert!(ProviderContext::current().is_none());
+
+        token.cancel();
+        assert!(context.is_cancelled());
+        assert_eq!(block_on(context.run(std::future::pending::<()>())), None);
+    }
+}
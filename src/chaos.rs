@@ -0,0 +1,200 @@
+//! # Chaos Injection
+//!
+//! Race conditions in optimistic updates and stale-while-revalidate usually hide behind a
+//! fast, reliable network. Enabling [`ChaosSettings`] in a runtime's
+//! [`RuntimeSettings`](crate::settings::RuntimeSettings) makes provider fetches misbehave
+//! the way real networks do:
+//!
+//! - **Random delays**: every fetch waits up to `max_delay` before it starts, so responses
+//!   arrive out of order
+//! - **Dropped responses**: a fetch's result is thrown away as if the connection died, and
+//!   the entry keeps its previous value until the next refetch
+//! - **Duplicated refreshes**: a result is delivered a second time `max_delay` later,
+//!   possibly on top of newer data such as an optimistic update
+//!
+//! Chaos applies to fetches for components, invalidations, prefetches and SWR
+//! revalidations, and can be switched on and off at runtime with
+//! [`ProviderRuntime::reconfigure`](crate::runtime::ProviderRuntime::reconfigure). It only
+//! has an effect in debug builds, so a forgotten toggle can't reach production.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use dioxus_provider::{chaos::ChaosSettings, global::get_global_runtime};
+//! use std::time::Duration;
+//!
+//! let runtime = get_global_runtime().unwrap();
+//! runtime.reconfigure(
+//!     runtime.settings().with_chaos(
+//!         ChaosSettings::new()
+//!             .with_max_delay(Duration::from_millis(800))
+//!             .with_drop_rate(0.1)
+//!             .with_duplicate_rate(0.2),
+//!     ),
+//! );
+//!
+//! // Back to normal
+//! runtime.reconfigure(runtime.settings().without_chaos());
+//! ```
+
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use crate::{
+    cache::{KeyOrigin, ProviderCache},
+    hooks::Provider,
+    platform::{sleep, task},
+    refresh::RefreshRegistry,
+    runtime::cache_mgmt::store_provider_result,
+    types::ProviderParamBounds,
+};
+
+/// State of the generator deciding delays, drops and duplicates
+static CHAOS_RNG: AtomicU64 = AtomicU64::new(0x9e37_79b9_7f4a_7c15);
+
+/// How provider fetches misbehave while chaos is enabled
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ChaosSettings {
+    /// Longest random delay before a fetch starts, and the delay of duplicated deliveries
+    pub max_delay: Duration,
+    /// Probability in [0, 1] that a fetch's result is dropped
+    pub drop_rate: f64,
+    /// Probability in [0, 1] that a fetch's result is delivered twice
+    pub duplicate_rate: f64,
+}
+
+impl ChaosSettings {
+    /// Chaos that doesn't change anything yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Delay fetches by a random duration up to `max_delay`
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Drop fetch results with probability `rate`
+    pub fn with_drop_rate(mut self, rate: f64) -> Self {
+        self.drop_rate = rate.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Deliver fetch results twice with probability `rate`
+    pub fn with_duplicate_rate(mut self, rate: f64) -> Self {
+        self.duplicate_rate = rate.clamp(0.0, 1.0);
+        self
+    }
+
+    fn random_delay(&self) -> Duration {
+        self.max_delay.mul_f64(next_random())
+    }
+}
+
+/// Next value in [0, 1) of a xorshift generator shared by all runtimes
+fn next_random() -> f64 {
+    let mut state = CHAOS_RNG.load(Ordering::Relaxed);
+    state ^= state << 13;
+    state ^= state >> 7;
+    state ^= state << 17;
+    CHAOS_RNG.store(state, Ordering::Relaxed);
+    (state >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// The chaos settings in effect for a cache, None in release builds
+fn active(cache: &ProviderCache) -> Option<ChaosSettings> {
+    if !cfg!(debug_assertions) {
+        return None;
+    }
+    cache.settings().get().chaos
+}
+
+/// Run a provider, subject to the chaos settings of `cache`
+///
+/// Returns None if the response was dropped.
+pub(crate) async fn fetch<P, Param>(
+    provider: &P,
+    param: Param,
+    cache: &ProviderCache,
+    #[allow(unused_variables)] cache_key: &str,
+) -> Option<Result<P::Output, P::Error>>
+where
+    P: Provider<Param>,
+    Param: ProviderParamBounds,
+{
+    let Some(chaos) = active(cache) else {
+        return Some(crate::timeout::run(provider, param).await);
+    };
+
+    let delay = chaos.random_delay();
+    if !delay.is_zero() {
+        crate::debug_log!("🐒 [CHAOS] Delaying fetch of {} by {:?}", cache_key, delay);
+        sleep(delay).await;
+    }
+    let result = crate::timeout::run(provider, param).await;
+    if next_random() < chaos.drop_rate {
+        crate::debug_log!("🐒 [CHAOS] Dropped response for {}", cache_key);
+        return None;
+    }
+    Some(result)
+}
+
+/// Deliver a fetched result a second time after `max_delay`, if chaos says so
+///
+/// The duplicate is stored like a late response, overwriting whatever the entry holds by
+/// then, and its subscribers are refreshed if that changed the entry.
+pub(crate) fn maybe_duplicate<P, Param>(
+    provider: &P,
+    cache: &ProviderCache,
+    refresh_registry: &RefreshRegistry,
+    cache_key: &str,
+    origin: Option<KeyOrigin>,
+    result: &Result<P::Output, P::Error>,
+) where
+    P: Provider<Param> + Clone,
+    Param: ProviderParamBounds,
+{
+    let Some(chaos) = active(cache) else {
+        return;
+    };
+    if next_random() >= chaos.duplicate_rate {
+        return;
+    }
+
+    let delay = chaos.max_delay;
+    crate::debug_log!(
+        "🐒 [CHAOS] Duplicating response for {} in {:?}",
+        cache_key,
+        delay
+    );
+    let provider = provider.clone();
+    let cache = cache.clone();
+    let refresh_registry = refresh_registry.clone();
+    let cache_key = cache_key.to_string();
+    let result = result.clone();
+    task::spawn(async move {
+        sleep(delay).await;
+        if store_provider_result(&provider, &cache, &cache_key, origin, result) {
+            refresh_registry.trigger_refresh(&cache_key);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn random_values_stay_in_range() {
+        for _ in 0..1000 {
+            let value = next_random();
+            assert!((0.0..1.0).contains(&value));
+        }
+        let chaos = ChaosSettings::new().with_max_delay(Duration::from_millis(10));
+        assert!(chaos.random_delay() < Duration::from_millis(10));
+        assert_eq!(ChaosSettings::new().with_drop_rate(3.0).drop_rate, 1.0);
+    }
+}
@@ -0,0 +1,200 @@
+//! # Demo Mode
+//!
+//! Offline demos and visual regression tests need the same data on every run. Demo mode
+//! serves a captured [`CacheSnapshot`] instead of running providers:
+//!
+//! 1. Capture: back the runtime with a [`SnapshotRecorder`] and use the app as usual. Every
+//!    result of a `#[provider(persist)]` provider is recorded; [`CacheSnapshot::to_json`]
+//!    exports them.
+//! 2. Replay: start the runtime with `ProviderConfig::with_demo_snapshot`. Providers read
+//!    their entries from the snapshot and are never run: fetches, refetches, prefetches and
+//!    background refreshes are all skipped, and nothing is written back to the snapshot.
+//!    Keys missing from the snapshot stay `Loading`.
+//!
+//! Only persisted providers can be captured, since they are the ones that know how to
+//! serialize their output.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use dioxus_provider::{demo::{CacheSnapshot, SnapshotRecorder}, prelude::*};
+//!
+//! // Capture
+//! let recorder = SnapshotRecorder::new();
+//! ProviderConfig::new().with_storage(recorder.clone()).init()?;
+//! // ... use the app, then
+//! std::fs::write("demo.json", recorder.snapshot().to_json())?;
+//!
+//! // Replay
+//! let snapshot = CacheSnapshot::from_json(&std::fs::read_to_string("demo.json")?)?;
+//! ProviderConfig::new().with_demo_snapshot(snapshot).init()?;
+//! ```
+
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cache::{CacheStorage, StorageFuture},
+    errors::ProviderError,
+};
+
+/// Serialized cache entries by cache key, as exported for demo mode
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheSnapshot {
+    entries: BTreeMap<String, String>,
+}
+
+impl CacheSnapshot {
+    /// Create an empty snapshot
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store the serialized entry for `key`, as a persisted provider would write it
+    pub fn insert(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.entries.insert(key.into(), value.into());
+    }
+
+    /// The serialized entry stored for `key`
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries.get(key).map(String::as_str)
+    }
+
+    /// The cache keys in the snapshot, sorted
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(String::as_str)
+    }
+
+    /// Number of entries in the snapshot
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if the snapshot has no entries
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Export the snapshot as JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// Read a snapshot exported with [`to_json`](Self::to_json)
+    pub fn from_json(json: &str) -> Result<Self, ProviderError> {
+        Ok(serde_json::from_str(json)?)
+    }
+}
+
+/// A [`CacheStorage`] recording every persisted entry into a [`CacheSnapshot`]
+///
+/// Clones share their recording, so keep one to export the snapshot after passing another
+/// to `ProviderConfig::with_storage`.
+#[derive(Debug, Clone, Default)]
+pub struct SnapshotRecorder {
+    snapshot: Arc<Mutex<CacheSnapshot>>,
+}
+
+impl SnapshotRecorder {
+    /// Create a recorder with nothing recorded
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The entries recorded so far
+    pub fn snapshot(&self) -> CacheSnapshot {
+        self.snapshot
+            .lock()
+            .map(|snapshot| snapshot.clone())
+            .unwrap_or_default()
+    }
+}
+
+impl CacheStorage for SnapshotRecorder {
+    fn read(&self, key: &str) -> StorageFuture<Option<String>> {
+        let value = self
+            .snapshot
+            .lock()
+            .ok()
+            .and_then(|snapshot| snapshot.get(key).map(str::to_string));
+        Box::pin(std::future::ready(Ok(value)))
+    }
+
+    fn write(&self, key: &str, value: String) -> StorageFuture<()> {
+        if let Ok(mut snapshot) = self.snapshot.lock() {
+            snapshot.insert(key, value);
+        }
+        Box::pin(std::future::ready(Ok(())))
+    }
+
+    fn remove(&self, key: &str) -> StorageFuture<()> {
+        if let Ok(mut snapshot) = self.snapshot.lock() {
+            snapshot.entries.remove(key);
+        }
+        Box::pin(std::future::ready(Ok(())))
+    }
+}
+
+/// Read-only storage serving a snapshot in demo mode
+#[derive(Debug)]
+pub(crate) struct DemoStorage {
+    snapshot: CacheSnapshot,
+}
+
+impl DemoStorage {
+    pub(crate) fn new(snapshot: CacheSnapshot) -> Self {
+        Self { snapshot }
+    }
+}
+
+impl CacheStorage for DemoStorage {
+    fn read(&self, key: &str) -> StorageFuture<Option<String>> {
+        let value = self.snapshot.get(key).map(str::to_string);
+        Box::pin(std::future::ready(Ok(value)))
+    }
+
+    fn write(&self, _key: &str, _value: String) -> StorageFuture<()> {
+        Box::pin(std::future::ready(Ok(())))
+    }
+
+    fn remove(&self, _key: &str) -> StorageFuture<()> {
+        Box::pin(std::future::ready(Ok(())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+
+    #[test]
+    fn recorded_snapshots_round_trip_through_json() {
+        let recorder = SnapshotRecorder::new();
+        block_on(async {
+            recorder
+                .write("fetch_user/1", "\"Ada\"".to_string())
+                .await
+                .unwrap();
+            recorder.write("stale", "1".to_string()).await.unwrap();
+            recorder.remove("stale").await.unwrap();
+        });
+
+        let snapshot = CacheSnapshot::from_json(&recorder.snapshot().to_json()).unwrap();
+        assert_eq!(snapshot.keys().collect::<Vec<_>>(), ["fetch_user/1"]);
+
+        let demo = DemoStorage::new(snapshot);
+        block_on(async {
+            demo.write("fetch_user/1", "\"Grace\"".to_string())
+                .await
+                .unwrap();
+            assert_eq!(
+                demo.read("fetch_user/1").await.unwrap().as_deref(),
+                Some("\"Ada\"")
+            );
+        });
+    }
+}
@@ -0,0 +1,141 @@
+//! # Keyed List Diffing
+//!
+//! This module computes which items of a keyed list were added, removed or updated between
+//! two versions of a provider's data. Virtualized or animated lists can use the diff to apply
+//! minimal updates instead of re-rendering the entire collection whenever it is refetched.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use dioxus_provider::{diff::use_list_diff, prelude::*};
+//!
+//! #[component]
+//! fn TodoList() -> Element {
+//!     let todos = use_provider(fetch_todos(), ());
+//!     let diff = use_list_diff(todos, |todo: &Todo| todo.id);
+//!
+//!     use_effect(move || {
+//!         let diff = diff.read();
+//!         virtual_list.insert(&diff.added);
+//!         virtual_list.remove(&diff.removed);
+//!         virtual_list.refresh(&diff.updated);
+//!     });
+//!     // ...
+//! }
+//! ```
+
+use dioxus::prelude::*;
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use crate::state::State;
+
+/// The ids of the items that changed between two versions of a keyed list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListDiff<Id> {
+    /// Ids present in the new list but not the old one, in new-list order
+    pub added: Vec<Id>,
+    /// Ids present in the old list but not the new one, in old-list order
+    pub removed: Vec<Id>,
+    /// Ids present in both lists whose items are no longer equal, in new-list order
+    pub updated: Vec<Id>,
+}
+
+impl<Id> Default for ListDiff<Id> {
+    fn default() -> Self {
+        Self {
+            added: Vec::new(),
+            removed: Vec::new(),
+            updated: Vec::new(),
+        }
+    }
+}
+
+impl<Id: Clone + Eq + Hash> ListDiff<Id> {
+    /// Compute the diff between two versions of a list, identifying items with `id`
+    pub fn between<T: PartialEq>(old: &[T], new: &[T], id: impl Fn(&T) -> Id) -> Self {
+        let old_items: HashMap<Id, &T> = old.iter().map(|item| (id(item), item)).collect();
+        let new_ids: HashSet<Id> = new.iter().map(&id).collect();
+
+        let mut diff = Self::default();
+        for item in new {
+            let item_id = id(item);
+            match old_items.get(&item_id) {
+                None => diff.added.push(item_id),
+                Some(previous) if *previous != item => diff.updated.push(item_id),
+                Some(_) => {}
+            }
+        }
+        diff.removed = old
+            .iter()
+            .map(&id)
+            .filter(|item_id| !new_ids.contains(item_id))
+            .collect();
+        diff
+    }
+}
+
+impl<Id> ListDiff<Id> {
+    /// Returns true if nothing was added, removed or updated
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.updated.is_empty()
+    }
+}
+
+/// Hook that exposes the changes between successive successful versions of a list provider
+///
+/// The first successful value reports every item as added. Loading and error states keep
+/// the previous diff, so a refetch is diffed against the last data that was shown.
+pub fn use_list_diff<T, E, Id>(
+    state: Signal<State<Vec<T>, E>>,
+    id: fn(&T) -> Id,
+) -> Signal<ListDiff<Id>>
+where
+    T: Clone + PartialEq + 'static,
+    E: 'static,
+    Id: Clone + Eq + Hash + 'static,
+{
+    let mut previous = use_signal(|| None::<Vec<T>>);
+    let mut diff = use_signal(ListDiff::default);
+
+    let _diff_memo = use_memo(move || {
+        let State::Success(current) = &*state.read() else {
+            return;
+        };
+        if previous.peek().as_ref() == Some(current) {
+            return;
+        }
+
+        let next = ListDiff::between(previous.peek().as_deref().unwrap_or_default(), current, id);
+        crate::debug_log!(
+            "🧮 [LIST-DIFF] {} added, {} removed, {} updated",
+            next.added.len(),
+            next.removed.len(),
+            next.updated.len()
+        );
+        previous.set(Some(current.clone()));
+        diff.set(next);
+    });
+
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_added_removed_and_updated_ids() {
+        let old = [(1, "a"), (2, "b"), (3, "c")];
+        let new = [(3, "c"), (2, "B"), (4, "d")];
+
+        let diff = ListDiff::between(&old, &new, |item| item.0);
+
+        assert_eq!(diff.added, vec![4]);
+        assert_eq!(diff.removed, vec![1]);
+        assert_eq!(diff.updated, vec![2]);
+        assert!(ListDiff::between(&new, &new, |item| item.0).is_empty());
+    }
+}
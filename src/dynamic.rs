@@ -0,0 +1,356 @@
+//! # Dynamic Providers
+//!
+//! Plugin architectures don't know their data sources at compile time. Providers can be
+//! registered with the runtime under a name instead, with
+//! [`ProviderRuntime::register_provider`](crate::runtime::ProviderRuntime::register_provider),
+//! and used by that name with [`use_dynamic_provider`].
+//!
+//! A dynamic provider's cache keys are its registered name followed by the parameter, so
+//! registering a provider under a name refetches the entries of the one it replaces, and
+//! unregistering it turns them into errors. Using a name nothing is registered under, or
+//! with different parameter, output or error types than it was registered with, resolves to
+//! an error too.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use dioxus_provider::{dynamic::use_dynamic_provider, global, prelude::*};
+//!
+//! fn load_plugin(plugin: &Plugin) {
+//!     let endpoint = plugin.endpoint.clone();
+//!     global::get_global_runtime().unwrap().register_provider(
+//!         "plugin.weather",
+//!         FnProvider::new("weather", move |city: String| {
+//!             let endpoint = endpoint.clone();
+//!             async move { api::weather(&endpoint, &city).await }
+//!         }),
+//!     );
+//! }
+//!
+//! #[component]
+//! fn Weather(city: String) -> Element {
+//!     let weather = use_dynamic_provider::<Forecast, String, _>("plugin.weather", city);
+//!     rsx! { "{weather.read().data().map_or(0.0, |forecast| forecast.temperature)}" }
+//! }
+//! ```
+
+use std::{
+    any::Any,
+    collections::HashMap,
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use crate::{
+    errors::ProviderError,
+    global::hook_runtime,
+    hooks::{Provider, ProviderHandle, use_provider},
+    param_utils::IntoProviderParam,
+    query_key::QueryKey,
+    retry::RetryPolicy,
+    types::{ProviderErrorBounds, ProviderOutputBounds, ProviderParamBounds},
+};
+
+type ErasedFuture<T, E> = Pin<Box<dyn Future<Output = Result<T, E>>>>;
+
+/// The parts of a [`Provider`] a dynamic provider forwards, as an object-safe trait
+trait ErasedProvider<Param, T, E>: Send + Sync {
+    fn erased_run(&self, param: Param) -> ErasedFuture<T, E>;
+    fn erased_interval(&self) -> Option<Duration>;
+    fn erased_stale_time(&self) -> Option<Duration>;
+    fn erased_cache_expiration(&self) -> Option<Duration>;
+    fn erased_retry_policy(&self) -> Option<RetryPolicy>;
+    fn erased_refetch_on_focus(&self) -> bool;
+    fn erased_refetch_on_reconnect(&self) -> bool;
+}
+
+impl<P, Param> ErasedProvider<Param, P::Output, P::Error> for P
+where
+    P: Provider<Param> + Send + Sync,
+    Param: ProviderParamBounds,
+{
+    fn erased_run(&self, param: Param) -> ErasedFuture<P::Output, P::Error> {
+        let provider = self.clone();
+        Box::pin(async move { Provider::run(&provider, param).await })
+    }
+
+    fn erased_interval(&self) -> Option<Duration> {
+        Provider::interval(self)
+    }
+
+    fn erased_stale_time(&self) -> Option<Duration> {
+        Provider::stale_time(self)
+    }
+
+    fn erased_cache_expiration(&self) -> Option<Duration> {
+        Provider::cache_expiration(self)
+    }
+
+    fn erased_retry_policy(&self) -> Option<RetryPolicy> {
+        Provider::retry_policy(self)
+    }
+
+    fn erased_refetch_on_focus(&self) -> bool {
+        Provider::refetch_on_focus(self)
+    }
+
+    fn erased_refetch_on_reconnect(&self) -> bool {
+        Provider::refetch_on_reconnect(self)
+    }
+}
+
+type SharedProvider<Param, T, E> = Arc<dyn ErasedProvider<Param, T, E>>;
+
+/// Providers registered by name, shared by a runtime's clones
+#[derive(Clone, Default)]
+pub struct DynamicRegistry {
+    providers: Arc<Mutex<HashMap<String, Arc<dyn Any + Send + Sync>>>>,
+}
+
+impl DynamicRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `provider` under `name`, replacing any provider registered under it
+    pub fn register<P, Param>(&self, name: impl Into<String>, provider: P)
+    where
+        P: Provider<Param> + Send + Sync,
+        Param: ProviderParamBounds,
+    {
+        let provider: SharedProvider<Param, P::Output, P::Error> = Arc::new(provider);
+        if let Ok(mut providers) = self.providers.lock() {
+            providers.insert(name.into(), Arc::new(provider));
+        }
+    }
+
+    /// Remove the provider registered under `name`, returning true if there was one
+    pub fn unregister(&self, name: &str) -> bool {
+        self.providers
+            .lock()
+            .is_ok_and(|mut providers| providers.remove(name).is_some())
+    }
+
+    /// Returns true if a provider is registered under `name`
+    pub fn contains(&self, name: &str) -> bool {
+        self.providers
+            .lock()
+            .is_ok_and(|providers| providers.contains_key(name))
+    }
+
+    /// The names providers are registered under, sorted
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .providers
+            .lock()
+            .map(|providers| providers.keys().cloned().collect())
+            .unwrap_or_default();
+        names.sort();
+        names
+    }
+
+    /// A provider resolving `name` in this registry each time it runs
+    pub fn provider<Param, T, E>(&self, name: &str) -> DynamicProvider<Param, T, E> {
+        DynamicProvider {
+            name: Arc::from(name),
+            registry: self.clone(),
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    fn lookup<Param, T, E>(&self, name: &str) -> Lookup<Param, T, E>
+    where
+        Param: 'static,
+        T: 'static,
+        E: 'static,
+    {
+        let Some(entry) = self
+            .providers
+            .lock()
+            .ok()
+            .and_then(|providers| providers.get(name).cloned())
+        else {
+            return Lookup::Missing;
+        };
+        match entry.downcast_ref::<SharedProvider<Param, T, E>>() {
+            Some(provider) => Lookup::Found(provider.clone()),
+            None => Lookup::WrongTypes,
+        }
+    }
+}
+
+impl fmt::Debug for DynamicRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DynamicRegistry")
+            .field("names", &self.names())
+            .finish()
+    }
+}
+
+enum Lookup<Param, T, E> {
+    Found(SharedProvider<Param, T, E>),
+    Missing,
+    WrongTypes,
+}
+
+/// A provider registered by name, looked up in a [`DynamicRegistry`] whenever it is used
+pub struct DynamicProvider<Param, T, E> {
+    name: Arc<str>,
+    registry: DynamicRegistry,
+    _marker: std::marker::PhantomData<fn(Param) -> (T, E)>,
+}
+
+impl<Param, T, E> DynamicProvider<Param, T, E> {
+    /// The name the provider is registered under
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl<Param, T, E> Clone for DynamicProvider<Param, T, E> {
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            registry: self.registry.clone(),
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<Param, T, E> PartialEq for DynamicProvider<Param, T, E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && Arc::ptr_eq(&self.registry.providers, &other.registry.providers)
+    }
+}
+
+impl<Param, T, E> fmt::Debug for DynamicProvider<Param, T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DynamicProvider")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Param, T, E> DynamicProvider<Param, T, E>
+where
+    Param: ProviderParamBounds,
+    T: ProviderOutputBounds,
+    E: ProviderErrorBounds,
+{
+    fn registered(&self) -> Option<SharedProvider<Param, T, E>> {
+        match self.registry.lookup(&self.name) {
+            Lookup::Found(provider) => Some(provider),
+            _ => None,
+        }
+    }
+}
+
+impl<Param, T, E> Provider<Param> for DynamicProvider<Param, T, E>
+where
+    Param: ProviderParamBounds,
+    T: ProviderOutputBounds,
+    E: ProviderErrorBounds + From<ProviderError>,
+{
+    type Output = T;
+    type Error = E;
+
+    fn run(&self, param: Param) -> impl Future<Output = Result<Self::Output, Self::Error>> {
+        let result = match self.registry.lookup(&self.name) {
+            Lookup::Found(provider) => Ok(provider.erased_run(param)),
+            Lookup::Missing => Err(ProviderError::NotFound(format!(
+                "no provider registered as '{}'",
+                self.name
+            ))),
+            Lookup::WrongTypes => Err(ProviderError::Configuration(format!(
+                "provider '{}' was registered with different types",
+                self.name
+            ))),
+        };
+        async move {
+            match result {
+                Ok(future) => future.await,
+                Err(error) => Err(error.into()),
+            }
+        }
+    }
+
+    fn query_key(&self, param: &Param) -> Option<QueryKey> {
+        Some(QueryKey::named(&self.name, param))
+    }
+
+    fn interval(&self) -> Option<Duration> {
+        self.registered()?.erased_interval()
+    }
+
+    fn stale_time(&self) -> Option<Duration> {
+        self.registered()?.erased_stale_time()
+    }
+
+    fn cache_expiration(&self) -> Option<Duration> {
+        self.registered()?.erased_cache_expiration()
+    }
+
+    fn retry_policy(&self) -> Option<RetryPolicy> {
+        self.registered()?.erased_retry_policy()
+    }
+
+    fn refetch_on_focus(&self) -> bool {
+        self.registered()
+            .is_some_and(|provider| provider.erased_refetch_on_focus())
+    }
+
+    fn refetch_on_reconnect(&self) -> bool {
+        self.registered()
+            .is_some_and(|provider| provider.erased_refetch_on_reconnect())
+    }
+}
+
+/// Hook to use the provider registered under `name` with the current runtime
+///
+/// The parameter, output and error types are the ones the provider was registered with.
+/// The error type must convert from [`ProviderError`], which is what the handle holds while
+/// nothing is registered under `name`.
+pub fn use_dynamic_provider<T, E, Args>(name: &str, args: Args) -> ProviderHandle<T, E>
+where
+    T: ProviderOutputBounds,
+    E: ProviderErrorBounds + From<ProviderError>,
+    Args: IntoProviderParam,
+{
+    let provider = hook_runtime().dynamic_provider::<Args::Param, T, E>(name);
+    use_provider(provider, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hooks::FnProvider;
+    use futures::executor::block_on;
+
+    #[test]
+    fn resolves_providers_by_name_when_run() {
+        let registry = DynamicRegistry::new();
+        let weather = registry.provider::<String, u32, String>("plugin.weather");
+        assert!(block_on(weather.run("Oslo".to_string())).is_err());
+
+        registry.register(
+            "plugin.weather",
+            FnProvider::new("weather", |city: String| async move {
+                Ok::<_, String>(city.len() as u32)
+            }),
+        );
+        assert_eq!(registry.names(), ["plugin.weather"]);
+        assert_eq!(block_on(weather.run("Oslo".to_string())), Ok(4));
+        assert_eq!(weather.id(&"Oslo".to_string()), "plugin.weather/\"Oslo\"");
+
+        let mistyped = registry.provider::<u32, u32, String>("plugin.weather");
+        assert!(block_on(mistyped.run(1)).is_err());
+
+        assert!(registry.unregister("plugin.weather"));
+        assert!(!registry.contains("plugin.weather"));
+        assert!(block_on(weather.run("Oslo".to_string())).is_err());
+    }
+}
@@ -0,0 +1,336 @@
+//! # Normalized Entity Store
+//!
+//! This module provides a normalized cache of entities, keyed by type and ID. Instead of
+//! every provider holding its own copy of a `User`, providers that opt into normalization
+//! write the entities they fetch into the shared [`EntityStore`], and components can
+//! subscribe to individual entities with [`use_entity`].
+//!
+//! Entities declare their relationships through [`Entity::relations`], so fetching a `Post`
+//! also stores the `User` it references, and every component showing that user updates.
+//! Mutations marked `normalize` write their results into the same store, so an edited
+//! entity shows up everywhere without refetching the providers that contain it.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use dioxus_provider::{entity::{Entity, EntityStore, use_entity}, prelude::*};
+//!
+//! #[derive(Clone, PartialEq)]
+//! struct User { id: u32, name: String }
+//!
+//! impl Entity for User {
+//!     type Id = u32;
+//!     fn entity_id(&self) -> u32 { self.id }
+//! }
+//!
+//! #[derive(Clone, PartialEq)]
+//! struct Post { id: u32, title: String, author: User }
+//!
+//! impl Entity for Post {
+//!     type Id = u32;
+//!     fn entity_id(&self) -> u32 { self.id }
+//!
+//!     // A post references its author
+//!     fn relations(&self, store: &EntityStore) {
+//!         store.upsert(self.author.clone());
+//!     }
+//! }
+//!
+//! #[provider(normalize)]
+//! async fn fetch_posts() -> Result<Vec<Post>, String> {
+//!     api::posts().await
+//! }
+//!
+//! #[mutation(normalize)]
+//! async fn rename_user(id: u32, name: String) -> Result<User, String> {
+//!     api::rename_user(id, name).await
+//! }
+//!
+//! #[component]
+//! fn Author(id: u32) -> Element {
+//!     let user = use_entity::<User>(id);
+//!     rsx! { "{user.read().as_ref().map(|u| u.name.as_str()).unwrap_or_default()}" }
+//! }
+//! ```
+
+use dioxus::{core::ReactiveContext, prelude::*};
+use std::{
+    any::{Any, TypeId, type_name},
+    collections::HashMap,
+    fmt::Debug,
+    hash::Hash,
+    sync::{Arc, Mutex},
+};
+
+use crate::{global::hook_runtime, refresh::RefreshRegistry};
+
+/// A record with a stable identity that can be shared between providers
+pub trait Entity: Clone + PartialEq + Send + Sync + 'static {
+    /// The type identifying an entity of this kind
+    type Id: Clone + Eq + Hash + Debug + Send + Sync + 'static;
+
+    /// The identity of this entity
+    fn entity_id(&self) -> Self::Id;
+
+    /// Store the entities this one references
+    ///
+    /// Called whenever the entity is written to the store, so related entities embedded in
+    /// it (e.g. the author of a post) are hydrated alongside it.
+    fn relations(&self, _store: &EntityStore) {}
+}
+
+/// Provider output that can be split into entities
+///
+/// Implemented for every [`Entity`] and for `Vec` and `Option` of normalizable values.
+pub trait Normalize {
+    /// Write the entities contained in this value into the store
+    fn normalize(&self, store: &EntityStore);
+}
+
+impl<E: Entity> Normalize for E {
+    fn normalize(&self, store: &EntityStore) {
+        store.upsert(self.clone());
+    }
+}
+
+impl<T: Normalize> Normalize for Vec<T> {
+    fn normalize(&self, store: &EntityStore) {
+        for item in self {
+            item.normalize(store);
+        }
+    }
+}
+
+impl<T: Normalize> Normalize for Option<T> {
+    fn normalize(&self, store: &EntityStore) {
+        if let Some(item) = self {
+            item.normalize(store);
+        }
+    }
+}
+
+/// Entities of a single type, stored behind `dyn Any`
+type EntityTable<E> = HashMap<<E as Entity>::Id, E>;
+
+/// Normalized store of entities, keyed by entity type and ID
+///
+/// Cloning the store is cheap and clones share the same entities.
+#[derive(Clone, Default)]
+pub struct EntityStore {
+    tables: Arc<Mutex<HashMap<TypeId, Box<dyn Any + Send + Sync>>>>,
+    /// Notifies components subscribed to individual entities
+    refresh_registry: RefreshRegistry,
+}
+
+impl EntityStore {
+    /// Create an empty entity store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The subscription key for an entity
+    pub fn entity_key<E: Entity>(id: &E::Id) -> String {
+        format!("entity:{}:{:?}", type_name::<E>(), id)
+    }
+
+    /// Get an entity by ID
+    pub fn get<E: Entity>(&self, id: &E::Id) -> Option<E> {
+        let tables = self.tables.lock().ok()?;
+        tables
+            .get(&TypeId::of::<E>())
+            .and_then(|table| table.downcast_ref::<EntityTable<E>>())
+            .and_then(|table| table.get(id).cloned())
+    }
+
+    /// Insert or replace an entity, along with the entities it references
+    ///
+    /// Returns whether the stored entity changed. Subscribers are only notified on change.
+    pub fn upsert<E: Entity>(&self, entity: E) -> bool {
+        // Related entities take the lock themselves, so store them first
+        entity.relations(self);
+
+        let id = entity.entity_id();
+        let changed = {
+            let Ok(mut tables) = self.tables.lock() else {
+                return false;
+            };
+            let table = tables
+                .entry(TypeId::of::<E>())
+                .or_insert_with(|| Box::new(EntityTable::<E>::new()))
+                .downcast_mut::<EntityTable<E>>()
+                .expect("entity table type matches its TypeId");
+            if table.get(&id) == Some(&entity) {
+                false
+            } else {
+                table.insert(id.clone(), entity);
+                true
+            }
+        };
+
+        if changed {
+            crate::debug_log!("🧩 [ENTITY] Updated {}", Self::entity_key::<E>(&id));
+            self.refresh_registry
+                .trigger_refresh(&Self::entity_key::<E>(&id));
+        }
+        changed
+    }
+
+    /// Patch a stored entity in place
+    ///
+    /// Returns whether the entity existed and changed. Related entities are not re-hydrated.
+    pub fn update<E: Entity, F: FnOnce(&mut E)>(&self, id: &E::Id, patch: F) -> bool {
+        let changed = {
+            let Ok(mut tables) = self.tables.lock() else {
+                return false;
+            };
+            let Some(entity) = tables
+                .get_mut(&TypeId::of::<E>())
+                .and_then(|table| table.downcast_mut::<EntityTable<E>>())
+                .and_then(|table| table.get_mut(id))
+            else {
+                return false;
+            };
+            let previous = entity.clone();
+            patch(entity);
+            *entity != previous
+        };
+
+        if changed {
+            self.refresh_registry
+                .trigger_refresh(&Self::entity_key::<E>(id));
+        }
+        changed
+    }
+
+    /// Remove an entity, returning it if it was stored
+    pub fn remove<E: Entity>(&self, id: &E::Id) -> Option<E> {
+        let removed = self
+            .tables
+            .lock()
+            .ok()?
+            .get_mut(&TypeId::of::<E>())
+            .and_then(|table| table.downcast_mut::<EntityTable<E>>())
+            .and_then(|table| table.remove(id));
+
+        if removed.is_some() {
+            self.refresh_registry
+                .trigger_refresh(&Self::entity_key::<E>(id));
+        }
+        removed
+    }
+
+    /// Number of stored entities of a type
+    pub fn count<E: Entity>(&self) -> usize {
+        self.tables
+            .lock()
+            .ok()
+            .and_then(|tables| {
+                tables
+                    .get(&TypeId::of::<E>())
+                    .and_then(|table| table.downcast_ref::<EntityTable<E>>())
+                    .map(HashMap::len)
+            })
+            .unwrap_or(0)
+    }
+
+    /// Subscribe a reactive context to changes of a single entity
+    pub fn subscribe<E: Entity>(&self, id: &E::Id, reactive_context: ReactiveContext) {
+        self.refresh_registry
+            .subscribe_to_refresh(&Self::entity_key::<E>(id), reactive_context);
+    }
+}
+
+/// Hook to subscribe to a single entity from the normalized store
+///
+/// Returns `None` until a normalizing provider or mutation (or a manual `upsert`) stores the
+/// entity, and updates whenever any of them changes it, without a provider per entity.
+pub fn use_entity<E: Entity>(id: E::Id) -> Signal<Option<E>> {
+    let runtime = hook_runtime();
+    let store = runtime.cache().entities().clone();
+
+    let mut entity = use_signal(|| store.get::<E>(&id));
+    let _entity_memo = use_memo(use_reactive!(|id| {
+        if let Some(reactive_context) = ReactiveContext::current() {
+            store.subscribe::<E>(&id, reactive_context);
+        }
+        let current = store.get::<E>(&id);
+        if *entity.peek() != current {
+            entity.set(current);
+        }
+    }));
+
+    entity
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct User {
+        id: u32,
+        name: String,
+    }
+
+    impl Entity for User {
+        type Id = u32;
+
+        fn entity_id(&self) -> u32 {
+            self.id
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Post {
+        id: u32,
+        author: User,
+    }
+
+    impl Entity for Post {
+        type Id = u32;
+
+        fn entity_id(&self) -> u32 {
+            self.id
+        }
+
+        fn relations(&self, store: &EntityStore) {
+            store.upsert(self.author.clone());
+        }
+    }
+
+    #[test]
+    fn normalizing_posts_hydrates_authors() {
+        let store = EntityStore::new();
+        let ada = User {
+            id: 1,
+            name: "Ada".to_string(),
+        };
+        let posts = vec![
+            Post {
+                id: 10,
+                author: ada.clone(),
+            },
+            Post {
+                id: 11,
+                author: ada.clone(),
+            },
+        ];
+
+        posts.normalize(&store);
+
+        assert_eq!(store.count::<Post>(), 2);
+        assert_eq!(store.count::<User>(), 1);
+        assert_eq!(store.get::<User>(&1), Some(ada.clone()));
+
+        // Writing an identical entity again is not a change
+        assert!(!store.upsert(ada));
+        assert!(store.update::<User, _>(&1, |user| user.name = "Ada L.".to_string()));
+        assert_eq!(
+            store.get::<User>(&1).map(|user| user.name),
+            Some("Ada L.".to_string())
+        );
+        assert!(!store.update::<User, _>(&2, |user| user.name.clear()));
+        assert_eq!(store.remove::<Post>(&10).map(|post| post.id), Some(10));
+        assert_eq!(store.get::<Post>(&10), None);
+    }
+}
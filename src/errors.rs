@@ -22,6 +22,26 @@
 //! }
 //! ```
 //!
+//! ### Wrapping source errors:
+//! Errors from I/O, `serde_json` and (with the `reqwest` feature) `reqwest` convert into
+//! `ProviderError` with `?`, keeping the original error as the `source()`. Errors stored by
+//! the runtime are annotated with the provider and cache key that produced them.
+//! ```rust,ignore
+//! use dioxus_provider::{errors::ProviderError, prelude::*};
+//!
+//! #[provider]
+//! async fn fetch_config() -> Result<Config, ProviderError> {
+//!     let text = std::fs::read_to_string("config.json")?;
+//!     Ok(serde_json::from_str(&text)?)
+//! }
+//!
+//! // In the error state
+//! if let State::Error(error) = &*config.read() {
+//!     tracing::error!(cache_key = ?error.cache_key(), "{error}");
+//!     let root_cause = std::error::Error::source(error);
+//! }
+//! ```
+//!
 //! ### Using custom domain-specific errors:
 //! ```rust,ignore
 //! use dioxus_provider::prelude::*;
@@ -44,8 +64,53 @@
 //! }
 //! ```
 
+use std::{any::Any, error::Error as StdError, fmt, sync::Arc, time::Duration};
+
 use thiserror::Error;
 
+/// A shared, cloneable handle to an underlying error
+///
+/// Displays like the error it wraps and forwards its `source()`, so error chains read the
+/// same as without the wrapper. Two handles are equal if they display the same message.
+#[derive(Clone)]
+pub struct SharedError(Arc<dyn StdError + Send + Sync>);
+
+impl SharedError {
+    /// Wrap an error
+    pub fn new(error: impl StdError + Send + Sync + 'static) -> Self {
+        Self(Arc::new(error))
+    }
+
+    /// The wrapped error, for downcasting to its concrete type
+    pub fn inner(&self) -> &(dyn StdError + Send + Sync + 'static) {
+        self.0.as_ref()
+    }
+}
+
+impl fmt::Debug for SharedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Display for SharedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl StdError for SharedError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.0.source()
+    }
+}
+
+impl PartialEq for SharedError {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0) || self.to_string() == other.to_string()
+    }
+}
+
 /// Common error types for provider operations
 #[derive(Error, Debug, Clone, PartialEq)]
 pub enum ProviderError {
@@ -93,9 +158,81 @@ pub enum ProviderError {
     #[error("Cache error: {0}")]
     Cache(String),
 
+    /// The requested resource doesn't exist
+    #[error("Not found: {0}")]
+    NotFound(String),
+
     /// Generic provider errors for cases not covered above
     #[error("Provider error: {0}")]
     Generic(String),
+
+    /// A failure caused by another error, kept as the `source()`
+    #[error("{message}: {source}")]
+    Source {
+        message: String,
+        #[source]
+        source: SharedError,
+    },
+
+    /// An error annotated with the provider and cache key that produced it
+    ///
+    /// Stored `ProviderError`s are wrapped in it; match on
+    /// [`without_context`](ProviderError::without_context) to reach the original error.
+    #[error("{provider} ({cache_key}): {error}")]
+    Context {
+        provider: String,
+        cache_key: String,
+        #[source]
+        error: Box<ProviderError>,
+    },
+}
+
+impl ProviderError {
+    /// Wrap an underlying error, describing what failed with `message`
+    pub fn wrap(message: impl Into<String>, source: impl StdError + Send + Sync + 'static) -> Self {
+        ProviderError::Source {
+            message: message.into(),
+            source: SharedError::new(source),
+        }
+    }
+
+    /// Annotate the error with the provider and cache key that produced it
+    ///
+    /// Errors that already carry a context keep it.
+    pub fn with_context(self, provider: impl Into<String>, cache_key: impl Into<String>) -> Self {
+        match self {
+            ProviderError::Context { .. } => self,
+            error => ProviderError::Context {
+                provider: provider.into(),
+                cache_key: cache_key.into(),
+                error: Box::new(error),
+            },
+        }
+    }
+
+    /// The name of the provider that produced the error, if annotated
+    pub fn provider(&self) -> Option<&str> {
+        match self {
+            ProviderError::Context { provider, .. } => Some(provider),
+            _ => None,
+        }
+    }
+
+    /// The cache key of the entry the error was stored under, if annotated
+    pub fn cache_key(&self) -> Option<&str> {
+        match self {
+            ProviderError::Context { cache_key, .. } => Some(cache_key),
+            _ => None,
+        }
+    }
+
+    /// The error without its provider and cache key annotation
+    pub fn without_context(&self) -> &ProviderError {
+        match self {
+            ProviderError::Context { error, .. } => error,
+            error => error,
+        }
+    }
 }
 
 /// Errors specific to user operations
@@ -190,6 +327,106 @@ pub enum DatabaseError {
     Provider(#[from] ProviderError),
 }
 
+/// Errors of a blocking fetch made outside the Dioxus runtime
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum BlockingFetchError<E> {
+    /// The provider returned an error
+    #[error("Provider failed")]
+    Provider(E),
+
+    /// The provider didn't complete within the timeout
+    #[error("Fetch timed out after {0:?}")]
+    Timeout(std::time::Duration),
+
+    /// The executor for the fetch couldn't be created
+    #[error("Failed to start fetch runtime: {0}")]
+    Runtime(String),
+
+    /// The fetch was requested from async code running on a tokio runtime, which blocking
+    /// would stall
+    #[error("Blocking fetch called from within an async runtime")]
+    NestedRuntime,
+}
+
+/// Returns true if an error says the requested resource doesn't exist
+///
+/// Recognizes the not-found variants of this module's error types, including an HTTP 404
+/// `ApiError`, and is what providers use to classify errors unless they override
+/// `Provider::is_not_found`.
+pub fn is_not_found_error(error: &dyn Any) -> bool {
+    if let Some(error) = error.downcast_ref::<ProviderError>() {
+        return matches!(error.without_context(), ProviderError::NotFound(_));
+    }
+    if let Some(error) = error.downcast_ref::<UserError>() {
+        return match error {
+            UserError::NotFound { .. } => true,
+            UserError::Provider(error) => is_not_found_error(error),
+            _ => false,
+        };
+    }
+    if let Some(error) = error.downcast_ref::<ApiError>() {
+        return match error {
+            ApiError::HttpStatus { status: 404, .. } | ApiError::EndpointNotFound { .. } => true,
+            ApiError::Provider(error) => is_not_found_error(error),
+            _ => false,
+        };
+    }
+    if let Some(error) = error.downcast_ref::<DatabaseError>() {
+        return match error {
+            DatabaseError::RecordNotFound { .. } => true,
+            DatabaseError::Provider(error) => is_not_found_error(error),
+            _ => false,
+        };
+    }
+    false
+}
+
+/// Error types that can describe a provider run that timed out
+///
+/// `#[provider(timeout = ..)]` builds its timeout error with this trait unless it is given a
+/// `timeout_error`, so error types without an implementation fail to compile instead of
+/// waiting without a timeout. It is implemented for `String` and the error types of this
+/// module, which use `ProviderError::Timeout`.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't describe a provider timeout",
+    label = "`{Self}` doesn't implement `TimeoutError`",
+    note = "give the provider a `timeout_error = |timeout| ..` or implement `TimeoutError` for `{Self}`"
+)]
+pub trait TimeoutError {
+    /// The error for a run that didn't resolve within `timeout`
+    fn timed_out(timeout: Duration) -> Self;
+}
+
+impl TimeoutError for ProviderError {
+    fn timed_out(timeout: Duration) -> Self {
+        ProviderError::Timeout(format!("no result within {timeout:?}"))
+    }
+}
+
+impl TimeoutError for UserError {
+    fn timed_out(timeout: Duration) -> Self {
+        UserError::Provider(ProviderError::timed_out(timeout))
+    }
+}
+
+impl TimeoutError for ApiError {
+    fn timed_out(timeout: Duration) -> Self {
+        ApiError::Provider(ProviderError::timed_out(timeout))
+    }
+}
+
+impl TimeoutError for DatabaseError {
+    fn timed_out(timeout: Duration) -> Self {
+        DatabaseError::Provider(ProviderError::timed_out(timeout))
+    }
+}
+
+impl TimeoutError for String {
+    fn timed_out(timeout: Duration) -> Self {
+        ProviderError::timed_out(timeout).to_string()
+    }
+}
+
 /// Convenience type alias for Results with ProviderError
 pub type ProviderResult<T> = Result<T, ProviderError>;
 
@@ -214,6 +451,32 @@ impl From<&str> for ProviderError {
     }
 }
 
+impl From<std::io::Error> for ProviderError {
+    fn from(error: std::io::Error) -> Self {
+        ProviderError::wrap("I/O error", error)
+    }
+}
+
+impl From<serde_json::Error> for ProviderError {
+    fn from(error: serde_json::Error) -> Self {
+        ProviderError::wrap("Data parsing error", error)
+    }
+}
+
+#[cfg(feature = "reqwest")]
+impl From<reqwest::Error> for ProviderError {
+    fn from(error: reqwest::Error) -> Self {
+        let message = if error.is_timeout() {
+            "Operation timed out"
+        } else if error.is_decode() {
+            "Data parsing error"
+        } else {
+            "Network error"
+        };
+        ProviderError::wrap(message, error)
+    }
+}
+
 impl From<ProviderError> for String {
     fn from(error: ProviderError) -> Self {
         error.to_string()
@@ -230,6 +493,66 @@ mod tests {
         assert_eq!(error.to_string(), "Invalid input: test input");
     }
 
+    #[test]
+    fn test_provider_error_source_chain() {
+        let io_error = std::io::Error::new(std::io::ErrorKind::NotFound, "config.json");
+        let error = ProviderError::from(io_error).with_context("FetchConfig", "fetch_config()");
+        assert_eq!(
+            error.to_string(),
+            "FetchConfig (fetch_config()): I/O error: config.json"
+        );
+        assert_eq!(error.cache_key(), Some("fetch_config()"));
+        assert_eq!(error.provider(), Some("FetchConfig"));
+
+        let wrapped = error.source().unwrap();
+        assert_eq!(wrapped.to_string(), "I/O error: config.json");
+        let cause = wrapped.source().unwrap();
+        assert!(cause.downcast_ref::<SharedError>().is_some());
+        assert_eq!(error.clone(), error);
+
+        let annotated_twice = error.clone().with_context("Other", "other()");
+        assert_eq!(annotated_twice.provider(), Some("FetchConfig"));
+        assert!(matches!(
+            error.without_context(),
+            ProviderError::Source { .. }
+        ));
+    }
+
+    #[test]
+    fn test_not_found_classification() {
+        let not_found = ProviderError::NotFound("user 7".to_string());
+        assert!(is_not_found_error(&not_found));
+        assert!(is_not_found_error(
+            &not_found.clone().with_context("FetchUser", "fetch_user(7)")
+        ));
+        assert!(is_not_found_error(&UserError::Provider(not_found)));
+        assert!(is_not_found_error(&ApiError::HttpStatus {
+            status: 404,
+            message: "Not Found".to_string(),
+        }));
+        assert!(!is_not_found_error(&ProviderError::Network(
+            "offline".to_string()
+        )));
+        assert!(!is_not_found_error(&"not found".to_string()));
+    }
+
+    #[test]
+    fn test_timeout_errors_for_known_types() {
+        let timeout = Duration::from_secs(10);
+        assert_eq!(
+            ProviderError::timed_out(timeout),
+            ProviderError::Timeout("no result within 10s".to_string())
+        );
+        assert!(matches!(
+            ApiError::timed_out(timeout),
+            ApiError::Provider(ProviderError::Timeout(_))
+        ));
+        assert_eq!(
+            String::timed_out(timeout),
+            "Operation timed out: no result within 10s"
+        );
+    }
+
     #[test]
     fn test_user_error_with_provider_error() {
         let provider_error = ProviderError::Network("connection failed".to_string());
@@ -0,0 +1,163 @@
+//! # Eviction Policies
+//!
+//! When the cache grows past its size or byte limit, maintenance evicts entries in the order
+//! an [`EvictionPolicy`] chooses. Least recently used entries go first by default; the
+//! built-in alternatives are [`Lfu`], which keeps frequently read entries, and
+//! [`TtlPriority`], which evicts the entries closest to expiring. Implement the trait for
+//! anything else.
+//!
+//! Entries of active and recently visited routes and retained history are only evicted
+//! after all others, whatever the policy, and pinned hot keys (see [`warm`](crate::warm))
+//! are never evicted. Each runtime's cache, including named runtimes,
+//! has its own policy.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use dioxus_provider::{eviction::{EvictionCandidate, EvictionPolicy, Lfu}, prelude::*};
+//!
+//! ProviderConfig::new().with_eviction_policy(Lfu).init()?;
+//!
+//! /// Evicts the largest entries first
+//! #[derive(Debug)]
+//! struct LargestFirst;
+//!
+//! impl EvictionPolicy for LargestFirst {
+//!     fn order(&self, candidates: &mut [EvictionCandidate]) {
+//!         candidates.sort_by_key(|candidate| std::cmp::Reverse(candidate.weight));
+//!     }
+//! }
+//! ```
+
+use std::{cmp::Reverse, fmt::Debug, time::Duration};
+
+/// What an eviction policy knows about a cached entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvictionCandidate {
+    /// The entry's cache key
+    pub key: String,
+    /// Time since the entry was last read
+    pub idle: Duration,
+    /// Time since the entry was stored
+    pub age: Duration,
+    /// Number of times the entry was read
+    pub access_count: u32,
+    /// Approximate size of the entry in bytes
+    pub weight: usize,
+    /// The entry's own expiration, if its provider set one per entry
+    pub expiration: Option<Duration>,
+    /// Entries of routes, retained history and pinned keys rank higher and are evicted last
+    pub(crate) priority: u8,
+}
+
+impl EvictionCandidate {
+    /// Time left until the entry expires, if it has its own expiration
+    pub fn remaining_ttl(&self) -> Option<Duration> {
+        self.expiration
+            .map(|expiration| expiration.saturating_sub(self.age))
+    }
+}
+
+/// Chooses which cache entries are evicted first when the cache is over its limits
+pub trait EvictionPolicy: Debug + Send + Sync + 'static {
+    /// Sort `candidates` so that the entries to evict first come first
+    fn order(&self, candidates: &mut [EvictionCandidate]);
+}
+
+/// Evicts the least recently used entries first (the default)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Lru;
+
+impl EvictionPolicy for Lru {
+    fn order(&self, candidates: &mut [EvictionCandidate]) {
+        candidates.sort_by_key(|candidate| Reverse(candidate.idle));
+    }
+}
+
+/// Evicts the least frequently read entries first, the least recently used among equals
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Lfu;
+
+impl EvictionPolicy for Lfu {
+    fn order(&self, candidates: &mut [EvictionCandidate]) {
+        candidates.sort_by_key(|candidate| (candidate.access_count, Reverse(candidate.idle)));
+    }
+}
+
+/// Evicts the entries closest to expiring first, then entries without an expiration of
+/// their own from least recently used
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TtlPriority;
+
+impl EvictionPolicy for TtlPriority {
+    fn order(&self, candidates: &mut [EvictionCandidate]) {
+        candidates.sort_by_key(|candidate| {
+            (
+                candidate.remaining_ttl().is_none(),
+                candidate.remaining_ttl(),
+                Reverse(candidate.idle),
+            )
+        });
+    }
+}
+
+/// Order `candidates` for eviction with `policy`, keeping prioritized entries last
+pub(crate) fn order_candidates(policy: &dyn EvictionPolicy, candidates: &mut [EvictionCandidate]) {
+    policy.order(candidates);
+    // Stable, so the policy's order holds within each priority
+    candidates.sort_by_key(|candidate| candidate.priority);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(
+        key: &str,
+        idle: u64,
+        access_count: u32,
+        expiration: Option<u64>,
+    ) -> EvictionCandidate {
+        EvictionCandidate {
+            key: key.to_string(),
+            idle: Duration::from_secs(idle),
+            age: Duration::from_secs(60),
+            access_count,
+            weight: 1,
+            expiration: expiration.map(Duration::from_secs),
+            priority: 0,
+        }
+    }
+
+    fn keys(policy: &dyn EvictionPolicy, mut candidates: Vec<EvictionCandidate>) -> Vec<String> {
+        order_candidates(policy, &mut candidates);
+        candidates
+            .into_iter()
+            .map(|candidate| candidate.key)
+            .collect()
+    }
+
+    #[test]
+    fn policies_order_candidates_for_eviction() {
+        let mut candidates = vec![
+            candidate("popular", 50, 40, None),
+            candidate("expiring", 5, 3, Some(90)),
+            candidate("recent", 1, 1, None),
+            candidate("route", 100, 0, Some(61)),
+        ];
+        candidates[3].priority = 1;
+
+        assert_eq!(
+            keys(&Lru, candidates.clone()),
+            ["popular", "expiring", "recent", "route"]
+        );
+        assert_eq!(
+            keys(&Lfu, candidates.clone()),
+            ["recent", "expiring", "popular", "route"]
+        );
+        assert_eq!(
+            keys(&TtlPriority, candidates),
+            ["expiring", "popular", "recent", "route"]
+        );
+    }
+}
@@ -2,13 +2,32 @@
 //!
 //! This module provides global singletons for cache, disposal, and refresh management
 //! that operate at application scale rather than component lifecycle scale.
+//!
+//! Hooks used before [`init`] don't panic: they throw a
+//! [`ProviderError::Configuration`](crate::errors::ProviderError::Configuration) to the
+//! nearest error boundary, so the mistake shows up on screen instead of crashing the app,
+//! and `use_provider` stays `Loading` without fetching. With the `auto-init` feature, they
+//! initialize the global providers with the defaults of [`init`] instead, and
+//! [`ProviderConfig::auto_init`] registers a configuration to initialize with on first use.
 
-use std::sync::OnceLock;
+use std::{
+    collections::HashMap,
+    sync::{LazyLock, Mutex, OnceLock},
+    time::Duration,
+};
 
 use crate::{
-    cache::ProviderCache,
+    cache::{CacheStorage, InvalidationMode, ProviderCache},
+    demo::CacheSnapshot,
+    eviction::EvictionPolicy,
+    journal::MutationJournal,
+    key_hash::{KeyHasher, set_key_hasher},
+    persist::LazyHydration,
+    platform::{PlatformExecutor, SharedExecutor, set_executor},
     refresh::RefreshRegistry,
     runtime::{ProviderRuntime, ProviderRuntimeConfig, ProviderRuntimeHandles},
+    settings::RuntimeSettings,
+    startup::PrefetchPlan,
 };
 
 /// Error type for global provider operations
@@ -23,18 +42,26 @@ pub enum GlobalProviderError {
 /// Global singleton instance of the provider runtime
 static GLOBAL_RUNTIME: OnceLock<ProviderRuntime> = OnceLock::new();
 
+/// Isolated runtimes started with `init_named`, by name
+static NAMED_RUNTIMES: LazyLock<Mutex<HashMap<String, ProviderRuntime>>> =
+    LazyLock::new(Default::default);
+
+/// Configuration registered with `ProviderConfig::auto_init`, applied on first hook usage
+static AUTO_INIT_CONFIG: OnceLock<ProviderConfig> = OnceLock::new();
+
+/// Detached runtime handed to hooks used before `init()`, in which nothing is fetched
+static FALLBACK_RUNTIME: OnceLock<ProviderRuntime> = OnceLock::new();
+
+/// Reported when hooks run before the global providers were initialized
+const NOT_INITIALIZED: &str =
+    "Global providers not initialized. Call dioxus_provider::init() before using providers.";
+
 /// Configuration for initializing the global provider system
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct ProviderConfig {
     runtime_config: ProviderRuntimeConfig,
-}
-
-impl Default for ProviderConfig {
-    fn default() -> Self {
-        Self {
-            runtime_config: ProviderRuntimeConfig::new(),
-        }
-    }
+    executor: Option<SharedExecutor>,
+    key_hasher: Option<KeyHasher>,
 }
 
 impl ProviderConfig {
@@ -49,13 +76,179 @@ impl ProviderConfig {
         self
     }
 
+    /// Journal accepted mutations so they can be replayed after a crash
+    ///
+    /// Mutation hooks record the inputs of mutations marked `#[mutation(journal)]` until
+    /// they succeed. Replay what is left on startup with
+    /// [`MutationJournal::replay`](crate::journal::MutationJournal::replay).
+    pub fn with_mutation_journal(mut self, journal: MutationJournal) -> Self {
+        self.runtime_config = self.runtime_config.clone().with_mutation_journal(journal);
+        self
+    }
+
+    /// Hydrate persisted entries when a consumer first reads their key, instead of at startup
+    pub fn with_lazy_hydration(mut self, hydration: LazyHydration) -> Self {
+        self.runtime_config = self.runtime_config.clone().with_lazy_hydration(hydration);
+        self
+    }
+
+    /// Persist the entries of providers marked `#[provider(persist)]` across restarts
+    pub fn with_storage(mut self, storage: impl CacheStorage) -> Self {
+        self.runtime_config = self.runtime_config.clone().with_storage(storage);
+        self
+    }
+
+    /// Prefetch the providers of `plan` as soon as the app renders, see
+    /// [`startup`](crate::startup)
+    pub fn with_prefetch_plan(mut self, plan: PrefetchPlan) -> Self {
+        self.runtime_config = self.runtime_config.clone().with_prefetch_plan(plan);
+        self
+    }
+
+    /// Evict cache entries in the order `policy` chooses, see [`eviction`](crate::eviction)
+    pub fn with_eviction_policy(mut self, policy: impl EvictionPolicy) -> Self {
+        self.runtime_config = self.runtime_config.clone().with_eviction_policy(policy);
+        self
+    }
+
+    /// Keep entries of persisted providers evicted from memory in `storage` as a second
+    /// cache tier, restoring them on their next use
+    pub fn with_tiered_storage(mut self, storage: impl CacheStorage) -> Self {
+        self.runtime_config = self.runtime_config.clone().with_tiered_storage(storage);
+        self
+    }
+
+    /// Serve a captured cache snapshot and never run providers, for demos and visual tests
+    pub fn with_demo_snapshot(mut self, snapshot: CacheSnapshot) -> Self {
+        self.runtime_config = self.runtime_config.clone().with_demo_snapshot(snapshot);
+        self
+    }
+
+    /// Choose whether invalidation marks entries stale (default) or removes them
+    pub fn with_invalidation_mode(mut self, mode: InvalidationMode) -> Self {
+        self.runtime_config = self.runtime_config.clone().with_invalidation_mode(mode);
+        self
+    }
+
+    /// How often the runtime's background maintenance loop cleans up the cache
+    pub fn with_maintenance_interval(mut self, interval: Duration) -> Self {
+        self.runtime_config = self
+            .runtime_config
+            .clone()
+            .with_maintenance_interval(interval);
+        self
+    }
+
+    /// Disable background cache maintenance, leaving it to `ProviderRuntime::maintain_now`
+    pub fn without_background_maintenance(mut self) -> Self {
+        self.runtime_config = self.runtime_config.clone().without_background_maintenance();
+        self
+    }
+
+    /// Release revalidation locks held longer than `timeout` (default 60 seconds)
+    ///
+    /// A revalidation that panics or hangs would otherwise keep its key from ever
+    /// revalidating again.
+    pub fn with_revalidation_timeout(mut self, timeout: Duration) -> Self {
+        self.runtime_config = self
+            .runtime_config
+            .clone()
+            .with_revalidation_timeout(timeout);
+        self
+    }
+
+    /// Start the runtime with these live settings instead of the defaults
+    ///
+    /// They can be changed later with `ProviderRuntime::reconfigure`.
+    pub fn with_settings(mut self, settings: RuntimeSettings) -> Self {
+        self.runtime_config = self.runtime_config.clone().with_settings(settings);
+        self
+    }
+
+    /// Run timers, the clock and detached tasks on a custom executor
+    ///
+    /// For targets the built-in tokio/`wasmtimer` support doesn't cover, or test harnesses
+    /// that control time. The executor is installed once for the whole program.
+    pub fn with_executor(mut self, executor: impl PlatformExecutor) -> Self {
+        self.executor = Some(SharedExecutor::new(executor));
+        self
+    }
+
+    /// Choose the hasher for provider cache keys
+    ///
+    /// Use a stable hasher such as [`KeyHasher::Xxh3`] when cache keys outlive the program,
+    /// e.g. for persisted caches. The hasher is installed once for the whole program.
+    pub fn with_key_hasher(mut self, hasher: KeyHasher) -> Self {
+        self.key_hasher = Some(hasher);
+        self
+    }
+
+    /// Initialize the global provider system with this configuration when a hook first
+    /// needs it
+    ///
+    /// Nothing is set up until then, so registering the configuration is cheap. The first
+    /// registered configuration wins, and an explicit `init` takes precedence. Enable the
+    /// `auto-init` feature to initialize lazily with the defaults without any call.
+    pub fn auto_init(self) {
+        if AUTO_INIT_CONFIG.set(self).is_err() {
+            crate::debug_log!("⚠️ [INIT] A lazy configuration is already registered, keeping it");
+        }
+    }
+
     /// Initialize the global provider system with this configuration
     pub fn init(self) -> Result<(), GlobalProviderError> {
+        self.install_program_wide();
         let runtime_config = self.runtime_config.clone();
         GLOBAL_RUNTIME.get_or_init(|| ProviderRuntime::new(runtime_config));
 
         Ok(())
     }
+
+    /// Start an isolated runtime named `name` with this configuration
+    ///
+    /// See [`init_named`].
+    pub fn init_named(self, name: &str) -> Result<(), GlobalProviderError> {
+        self.install_program_wide();
+        let mut runtimes = NAMED_RUNTIMES
+            .lock()
+            .map_err(|e| GlobalProviderError::InitializationFailed(e.to_string()))?;
+        if runtimes.contains_key(name) {
+            crate::debug_log!(
+                "⚠️ [INIT] Runtime \"{}\" is already initialized, keeping it",
+                name
+            );
+        } else {
+            runtimes.insert(
+                name.to_string(),
+                ProviderRuntime::new(self.runtime_config.clone()),
+            );
+        }
+        Ok(())
+    }
+
+    /// Create a standalone runtime with this configuration
+    ///
+    /// The runtime isn't registered anywhere: hand it to a
+    /// [`ProviderScope`](crate::scope::ProviderScope) so the hooks below it use it instead
+    /// of the global runtime.
+    pub fn build(self) -> ProviderRuntime {
+        self.install_program_wide();
+        ProviderRuntime::new(self.runtime_config)
+    }
+
+    /// Install the settings shared by every runtime in the program
+    fn install_program_wide(&self) {
+        if let Some(executor) = self.executor.clone()
+            && !set_executor(executor)
+        {
+            crate::debug_log!("⚠️ [PLATFORM] An executor is already installed, keeping it");
+        }
+        if let Some(hasher) = self.key_hasher
+            && !set_key_hasher(hasher)
+        {
+            crate::debug_log!("⚠️ [CACHE] A different key hasher is already installed, keeping it");
+        }
+    }
 }
 
 /// Initialize the global provider system with all features enabled
@@ -90,6 +283,35 @@ pub fn init() -> Result<(), GlobalProviderError> {
     ProviderConfig::new().with_dependency_injection().init()
 }
 
+/// Start an isolated provider runtime named `name`
+///
+/// A named runtime has its own cache, refresh registry and maintenance loop, so parts of an
+/// app can run different policies side by side, e.g. aggressive eviction for telemetry and
+/// long retention for user data. Consume providers in it with
+/// [`use_provider_in`](crate::hooks::use_provider_in); every other hook uses the global
+/// runtime, or that of the nearest [`ProviderScope`](crate::scope::ProviderScope). The
+/// executor and key hasher are shared by the whole program, so the first
+/// configuration to set them wins. A runtime already started under `name` is kept.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use dioxus_provider::global::{ProviderConfig, init_named};
+/// use std::time::Duration;
+///
+/// fn main() {
+///     dioxus_provider::init().unwrap();
+///     init_named(
+///         "analytics",
+///         ProviderConfig::new().with_maintenance_interval(Duration::from_secs(5)),
+///     )
+///     .unwrap();
+/// }
+/// ```
+pub fn init_named(name: &str, config: ProviderConfig) -> Result<(), GlobalProviderError> {
+    config.init_named(name)
+}
+
 /// Initialize the global provider management system (without dependency injection)
 ///
 /// This should be called once at the start of your application,
@@ -161,11 +383,88 @@ pub fn get_global_runtime() -> Result<&'static ProviderRuntime, GlobalProviderEr
         .ok_or(GlobalProviderError::NotInitialized)
 }
 
+/// Access a runtime started with [`init_named`].
+pub fn get_named_runtime(name: &str) -> Result<ProviderRuntime, GlobalProviderError> {
+    NAMED_RUNTIMES
+        .lock()
+        .ok()
+        .and_then(|runtimes| runtimes.get(name).cloned())
+        .ok_or(GlobalProviderError::NotInitialized)
+}
+
 /// Clone handles to the global runtime for use in hooks and mutations.
 pub fn get_global_runtime_handles() -> Result<ProviderRuntimeHandles, GlobalProviderError> {
     get_global_runtime().map(|runtime| runtime.handles())
 }
 
+/// Get the mutation journal configured on the global runtime, if any
+pub fn get_global_mutation_journal() -> Result<Option<MutationJournal>, GlobalProviderError> {
+    get_global_runtime().map(|runtime| runtime.mutation_journal().cloned())
+}
+
+/// The runtime of the nearest [`ProviderScope`](crate::scope::ProviderScope), or else the
+/// global runtime, for use by hooks
+///
+/// A missing global runtime is initialized lazily with the configuration registered by
+/// [`ProviderConfig::auto_init`], or with the defaults of [`init`] under the `auto-init`
+/// feature. Otherwise a configuration error is thrown to the nearest error boundary and
+/// None is returned.
+pub(crate) fn try_hook_runtime() -> Option<ProviderRuntime> {
+    if let Some(runtime) = crate::scope::scoped_runtime() {
+        return Some(runtime);
+    }
+    if let Some(runtime) = GLOBAL_RUNTIME.get() {
+        return Some(runtime.clone());
+    }
+
+    let lazy_config = AUTO_INIT_CONFIG.get().cloned().or_else(|| {
+        cfg!(feature = "auto-init").then(|| ProviderConfig::new().with_dependency_injection())
+    });
+    if let Some(config) = lazy_config {
+        crate::debug_log!("⚙️ [INIT] Initializing global providers on first use");
+        if config.init().is_ok()
+            && let Some(runtime) = GLOBAL_RUNTIME.get()
+        {
+            return Some(runtime.clone());
+        }
+    }
+
+    report_not_initialized(NOT_INITIALIZED.to_string());
+    None
+}
+
+/// The runtime hooks use, or a detached fallback if it isn't initialized
+///
+/// The fallback keeps hooks working without crashing the app; nothing should be fetched
+/// with it. See [`try_hook_runtime`].
+pub(crate) fn hook_runtime() -> ProviderRuntime {
+    try_hook_runtime().unwrap_or_else(|| fallback_runtime().clone())
+}
+
+/// The runtime started with [`init_named`] under `name`, for use by hooks
+///
+/// A missing runtime is reported to the nearest error boundary, like a missing global one.
+pub(crate) fn named_hook_runtime(name: &str) -> Option<ProviderRuntime> {
+    let runtime = get_named_runtime(name).ok();
+    if runtime.is_none() {
+        report_not_initialized(format!(
+            "Provider runtime \"{name}\" not initialized. Call dioxus_provider::global::init_named(\"{name}\", config) before using it."
+        ));
+    }
+    runtime
+}
+
+/// Detached runtime that hooks fall back to when theirs isn't initialized
+pub(crate) fn fallback_runtime() -> &'static ProviderRuntime {
+    FALLBACK_RUNTIME.get_or_init(|| ProviderRuntime::new(ProviderRuntimeConfig::new()))
+}
+
+/// Throw a configuration error to the nearest error boundary
+fn report_not_initialized(message: String) {
+    crate::debug_log!("❌ [INIT] {}", message);
+    dioxus::core::throw_error(crate::errors::ProviderError::Configuration(message));
+}
+
 /// Check if global providers have been initialized
 pub fn is_initialized() -> bool {
     GLOBAL_RUNTIME.get().is_some()
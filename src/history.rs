@@ -0,0 +1,188 @@
+//! # Retained Query History
+//!
+//! In a master-detail UI, going back to an item you just looked at should be instant. A
+//! provider with a [`HistoryWindow`] keeps its last `size` parameterizations fully
+//! materialized: their entries are never expired, removed as unused or evicted, and once a
+//! consumer navigates away from one, it is exempt from staleness-driven refetches for the
+//! window's grace period. Navigating back within the grace period shows the retained data
+//! as it was, without a refetch; after it, the usual stale-while-revalidate rules apply
+//! again.
+//!
+//! Explicit invalidation still refetches retained entries.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use dioxus_provider::prelude::*;
+//!
+//! // Keep the last 5 visited articles for 2 minutes after leaving them
+//! #[provider(stale_time = "30s", keep_history = 5, history_grace = "2m")]
+//! async fn fetch_article(id: u32) -> Result<Article, String> {
+//!     api::article(id).await
+//! }
+//! ```
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+// Platform-specific time imports
+#[cfg(not(target_family = "wasm"))]
+use std::time::Instant;
+#[cfg(target_family = "wasm")]
+use web_time::Instant;
+
+/// Default time a left parameterization stays exempt from staleness-driven refetches
+pub const DEFAULT_HISTORY_GRACE: Duration = Duration::from_secs(5 * 60);
+
+/// How many recent parameterizations of a provider are retained, and for how long
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HistoryWindow {
+    /// Number of parameterizations kept, including the ones currently shown
+    pub size: usize,
+    /// How long a parameterization is exempt from staleness after it was left
+    pub grace: Duration,
+}
+
+impl HistoryWindow {
+    /// Retain the last `size` parameterizations with the default grace period
+    pub fn new(size: usize) -> Self {
+        Self {
+            size,
+            grace: DEFAULT_HISTORY_GRACE,
+        }
+    }
+
+    /// Set the grace period
+    pub fn with_grace(mut self, grace: Duration) -> Self {
+        self.grace = grace;
+        self
+    }
+}
+
+/// A retained cache key, and when a consumer last navigated away from it
+#[derive(Debug)]
+struct Retained {
+    key: String,
+    left_at: Option<Instant>,
+}
+
+/// The retained keys of one provider, most recent first
+#[derive(Debug)]
+struct Window {
+    config: HistoryWindow,
+    keys: VecDeque<Retained>,
+}
+
+/// Recently visited cache keys of the providers that retain history
+#[derive(Clone, Default)]
+pub struct QueryHistory {
+    windows: Arc<Mutex<HashMap<&'static str, Window>>>,
+}
+
+impl QueryHistory {
+    /// Record that a consumer of `provider` is showing `key`
+    ///
+    /// The key moves to the front of the provider's window, and the oldest keys beyond the
+    /// window's size stop being retained.
+    pub fn visit(&self, provider: &'static str, config: HistoryWindow, key: &str) {
+        let Ok(mut windows) = self.windows.lock() else {
+            return;
+        };
+        let window = windows.entry(provider).or_insert_with(|| Window {
+            config,
+            keys: VecDeque::new(),
+        });
+        window.config = config;
+
+        // A revisited key keeps the time it was left, so its grace period isn't renewed
+        let left_at = window
+            .keys
+            .iter()
+            .position(|retained| retained.key == key)
+            .and_then(|index| window.keys.remove(index))
+            .and_then(|retained| retained.left_at);
+        window.keys.push_front(Retained {
+            key: key.to_string(),
+            left_at,
+        });
+        while window.keys.len() > config.size {
+            if let Some(_dropped) = window.keys.pop_back() {
+                crate::debug_log!("📚 [HISTORY] Key left the history window: {}", _dropped.key);
+            }
+        }
+    }
+
+    /// Record that a consumer navigated away from `key`, starting its grace period
+    pub fn leave(&self, key: &str) {
+        let Ok(mut windows) = self.windows.lock() else {
+            return;
+        };
+        let retained = windows
+            .values_mut()
+            .flat_map(|window| window.keys.iter_mut())
+            .find(|retained| retained.key == key);
+        if let Some(retained) = retained {
+            retained.left_at = Some(Instant::now());
+        }
+    }
+
+    /// Returns true if `key` is in a provider's history window, so maintenance keeps it
+    pub fn is_retained(&self, key: &str) -> bool {
+        self.windows.lock().is_ok_and(|windows| {
+            windows
+                .values()
+                .any(|window| window.keys.iter().any(|retained| retained.key == key))
+        })
+    }
+
+    /// Returns true if `key` was left less than its window's grace period ago
+    ///
+    /// Entries in their grace period are neither refetched for being stale nor expired.
+    pub fn in_grace_period(&self, key: &str) -> bool {
+        self.windows.lock().is_ok_and(|windows| {
+            windows.values().any(|window| {
+                window.keys.iter().any(|retained| {
+                    retained.key == key
+                        && retained
+                            .left_at
+                            .is_some_and(|left_at| left_at.elapsed() < window.config.grace)
+                })
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retains_recent_keys_and_their_grace_period() {
+        let history = QueryHistory::default();
+        let window = HistoryWindow::new(2).with_grace(Duration::from_secs(60));
+
+        history.visit("detail", window, "a");
+        assert!(history.is_retained("a"));
+        assert!(!history.in_grace_period("a"));
+
+        history.leave("a");
+        history.visit("detail", window, "b");
+        assert!(history.in_grace_period("a"));
+
+        // Revisiting keeps the original grace period running
+        history.visit("detail", window, "a");
+        assert!(history.in_grace_period("a"));
+
+        history.leave("a");
+        history.visit("detail", window, "c");
+        assert!(!history.is_retained("b"));
+        assert!(history.is_retained("a"));
+
+        let expired = HistoryWindow::new(2).with_grace(Duration::ZERO);
+        history.visit("detail", expired, "c");
+        assert!(!history.in_grace_period("a"));
+    }
+}
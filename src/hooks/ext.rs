@@ -0,0 +1,312 @@
+//! Builder helpers for hand-written providers
+
+use std::time::Duration;
+
+use crate::{
+    cache::ErrorCaching, history::HistoryWindow, persist::Persistence, query_key::QueryKey,
+    retry::RetryPolicy, schedule::Schedule, types::ProviderParamBounds,
+};
+
+use super::provider::{CachePolicyFn, NextIntervalFn, NormalizeFn, Provider, SeedFn, WeightFn};
+
+/// Timing helpers for any [`Provider`]
+///
+/// `#[provider]` sets timings from its arguments; hand-written `Provider` impls get the
+/// same behavior by wrapping themselves instead of overriding the trait methods:
+///
+/// ```rust,no_run
+/// use dioxus::prelude::*;
+/// use dioxus_provider::prelude::*;
+/// use std::time::Duration;
+///
+/// #[derive(Clone, PartialEq)]
+/// struct Prices;
+///
+/// impl Provider<()> for Prices {
+///     type Output = Vec<f64>;
+///     type Error = String;
+///
+///     async fn run(&self, _: ()) -> Result<Self::Output, Self::Error> {
+///         Ok(vec![1.0, 2.5])
+///     }
+/// }
+///
+/// #[component]
+/// fn Ticker() -> Element {
+///     let prices = use_provider(
+///         Prices
+///             .with_stale_time(Duration::from_secs(30))
+///             .with_interval(Duration::from_secs(60)),
+///         (),
+///     );
+///     rsx! { "{prices.read().data().map_or(0, Vec::len)} prices" }
+/// }
+/// ```
+///
+/// The wrapped provider shares its cache entries with the unwrapped one.
+pub trait ProviderExt<Param = ()>: Provider<Param> + Sized
+where
+    Param: ProviderParamBounds,
+{
+    /// Serve cached data for `stale_time` before revalidating it in the background
+    fn with_stale_time(self, stale_time: Duration) -> ConfiguredProvider<Self> {
+        ConfiguredProvider::new(self).with_stale_time(stale_time)
+    }
+
+    /// Refresh the data every `interval`
+    fn with_interval(self, interval: Duration) -> ConfiguredProvider<Self> {
+        ConfiguredProvider::new(self).with_interval(interval)
+    }
+
+    /// Remove cached data `cache_expiration` after it was fetched
+    fn with_cache_expiration(self, cache_expiration: Duration) -> ConfiguredProvider<Self> {
+        ConfiguredProvider::new(self).with_cache_expiration(cache_expiration)
+    }
+
+    /// Revalidate the data when the app regains focus
+    fn with_refetch_on_focus(self) -> ConfiguredProvider<Self> {
+        ConfiguredProvider::new(self).with_refetch_on_focus()
+    }
+
+    /// Refetch failed and stale data when the app comes back online
+    fn with_refetch_on_reconnect(self) -> ConfiguredProvider<Self> {
+        ConfiguredProvider::new(self).with_refetch_on_reconnect()
+    }
+}
+
+impl<P, Param> ProviderExt<Param> for P
+where
+    P: Provider<Param>,
+    Param: ProviderParamBounds,
+{
+}
+
+/// A provider with timings set by [`ProviderExt`], delegating everything else
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfiguredProvider<P> {
+    inner: P,
+    stale_time: Option<Duration>,
+    interval: Option<Duration>,
+    cache_expiration: Option<Duration>,
+    refetch_on_focus: bool,
+    refetch_on_reconnect: bool,
+}
+
+impl<P> ConfiguredProvider<P> {
+    fn new(inner: P) -> Self {
+        Self {
+            inner,
+            stale_time: None,
+            interval: None,
+            cache_expiration: None,
+            refetch_on_focus: false,
+            refetch_on_reconnect: false,
+        }
+    }
+
+    /// Serve cached data for `stale_time` before revalidating it in the background
+    pub fn with_stale_time(mut self, stale_time: Duration) -> Self {
+        self.stale_time = Some(stale_time);
+        self
+    }
+
+    /// Refresh the data every `interval`
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+
+    /// Remove cached data `cache_expiration` after it was fetched
+    pub fn with_cache_expiration(mut self, cache_expiration: Duration) -> Self {
+        self.cache_expiration = Some(cache_expiration);
+        self
+    }
+
+    /// Revalidate the data when the app regains focus
+    pub fn with_refetch_on_focus(mut self) -> Self {
+        self.refetch_on_focus = true;
+        self
+    }
+
+    /// Refetch failed and stale data when the app comes back online
+    pub fn with_refetch_on_reconnect(mut self) -> Self {
+        self.refetch_on_reconnect = true;
+        self
+    }
+
+    /// The wrapped provider
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+}
+
+impl<P, Param> Provider<Param> for ConfiguredProvider<P>
+where
+    P: Provider<Param>,
+    Param: ProviderParamBounds,
+{
+    type Output = P::Output;
+    type Error = P::Error;
+
+    fn run(
+        &self,
+        param: Param,
+    ) -> impl std::future::Future<Output = Result<Self::Output, Self::Error>> {
+        self.inner.run(param)
+    }
+
+    fn id(&self, param: &Param) -> String {
+        self.inner.id(param)
+    }
+
+    fn query_key(&self, param: &Param) -> Option<QueryKey> {
+        self.inner.query_key(param)
+    }
+
+    fn interval(&self) -> Option<Duration> {
+        self.interval.or_else(|| self.inner.interval())
+    }
+
+    fn next_interval(&self) -> Option<NextIntervalFn<Self::Output>> {
+        self.inner.next_interval()
+    }
+
+    fn cache_policy(&self) -> Option<CachePolicyFn<Self::Output>> {
+        self.inner.cache_policy()
+    }
+
+    fn weight(&self) -> Option<WeightFn<Self::Output>> {
+        self.inner.weight()
+    }
+
+    fn normalize(&self) -> Option<NormalizeFn<Self::Output>> {
+        self.inner.normalize()
+    }
+
+    fn persistence(&self) -> Option<Persistence<Self::Output>> {
+        self.inner.persistence()
+    }
+
+    fn seed_details(&self) -> Option<SeedFn<Self::Output>> {
+        self.inner.seed_details()
+    }
+
+    fn schedule(&self) -> Option<Schedule> {
+        self.inner.schedule()
+    }
+
+    fn cache_expiration(&self) -> Option<Duration> {
+        self.cache_expiration
+            .or_else(|| self.inner.cache_expiration())
+    }
+
+    fn stale_time(&self) -> Option<Duration> {
+        self.stale_time.or_else(|| self.inner.stale_time())
+    }
+
+    fn min_refetch_interval(&self) -> Option<Duration> {
+        self.inner.min_refetch_interval()
+    }
+
+    fn refetch_on_focus(&self) -> bool {
+        self.refetch_on_focus || self.inner.refetch_on_focus()
+    }
+
+    fn refetch_on_reconnect(&self) -> bool {
+        self.refetch_on_reconnect || self.inner.refetch_on_reconnect()
+    }
+
+    fn history(&self) -> Option<HistoryWindow> {
+        self.inner.history()
+    }
+
+    fn cache_errors(&self) -> ErrorCaching {
+        self.inner.cache_errors()
+    }
+
+    fn cache_not_found(&self) -> Option<Duration> {
+        self.inner.cache_not_found()
+    }
+
+    fn retry_policy(&self) -> Option<RetryPolicy> {
+        self.inner.retry_policy()
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.inner.timeout()
+    }
+
+    fn timeout_error(&self, timeout: Duration) -> Option<Self::Error> {
+        self.inner.timeout_error(timeout)
+    }
+
+    fn is_not_found(&self, error: &Self::Error) -> bool {
+        self.inner.is_not_found(error)
+    }
+
+    fn serves(&self) -> &'static [&'static str] {
+        self.inner.serves()
+    }
+
+    fn validate(&self, param: &Param) -> Result<(), Self::Error> {
+        self.inner.validate(param)
+    }
+
+    fn skip(&self, param: &Param) -> Option<Self::Output> {
+        self.inner.skip(param)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, PartialEq)]
+    struct Clock;
+
+    impl Provider<()> for Clock {
+        type Output = u32;
+        type Error = ();
+
+        async fn run(&self, _: ()) -> Result<Self::Output, Self::Error> {
+            Ok(12)
+        }
+
+        fn stale_time(&self) -> Option<Duration> {
+            Some(Duration::from_secs(5))
+        }
+    }
+
+    #[test]
+    fn overrides_timings_and_keeps_the_cache_key() {
+        let configured = Clock
+            .with_interval(Duration::from_secs(1))
+            .with_cache_expiration(Duration::from_secs(60));
+
+        assert_eq!(configured.interval(), Some(Duration::from_secs(1)));
+        assert_eq!(configured.stale_time(), Some(Duration::from_secs(5)));
+        assert_eq!(configured.cache_expiration(), Some(Duration::from_secs(60)));
+        assert_eq!(
+            configured
+                .clone()
+                .with_stale_time(Duration::from_secs(30))
+                .stale_time(),
+            Some(Duration::from_secs(30))
+        );
+        assert!(!configured.refetch_on_focus());
+        assert!(
+            configured
+                .clone()
+                .with_refetch_on_focus()
+                .refetch_on_focus()
+        );
+        assert!(
+            configured
+                .clone()
+                .with_refetch_on_reconnect()
+                .refetch_on_reconnect()
+        );
+        assert_eq!(configured.id(&()), Clock.id(&()));
+        assert_eq!(futures::executor::block_on(configured.run(())), Ok(12));
+    }
+}
@@ -0,0 +1,134 @@
+//! Providers built from closures
+
+use std::{fmt, future::Future, pin::Pin, sync::Arc};
+
+use crate::{
+    query_key::QueryKey,
+    types::{ProviderErrorBounds, ProviderOutputBounds, ProviderParamBounds},
+};
+
+use super::provider::Provider;
+
+type FetchFuture<T, E> = Pin<Box<dyn Future<Output = Result<T, E>>>>;
+type FetchFn<Param, T, E> = Arc<dyn Fn(Param) -> FetchFuture<T, E> + Send + Sync>;
+
+/// A [`Provider`] running a closure, for providers built at runtime
+///
+/// Closures have no name a cache key could be derived from, so each `FnProvider` is given
+/// one. Its cache keys are that name followed by the parameter, like `#[provider]` keys,
+/// and two `FnProvider`s with the same name are treated as the same provider.
+///
+/// ```rust,no_run
+/// use dioxus::prelude::*;
+/// use dioxus_provider::prelude::*;
+///
+/// fn plugin_source(plugin: String) -> FnProvider<u32, String, String> {
+///     let name = format!("plugin_{plugin}");
+///     FnProvider::new(name, move |page: u32| {
+///         let plugin = plugin.clone();
+///         async move { Ok(format!("{plugin} page {page}")) }
+///     })
+/// }
+///
+/// #[component]
+/// fn PluginPanel(plugin: String) -> Element {
+///     let data = use_provider(plugin_source(plugin), 1);
+///     rsx! { "{data.read().data().cloned().unwrap_or_default()}" }
+/// }
+/// ```
+///
+/// Timings are set with [`ProviderExt`](super::ProviderExt).
+pub struct FnProvider<Param, T, E> {
+    name: Arc<str>,
+    fetch: FetchFn<Param, T, E>,
+}
+
+impl<Param, T, E> FnProvider<Param, T, E>
+where
+    Param: ProviderParamBounds,
+    T: ProviderOutputBounds,
+    E: ProviderErrorBounds,
+{
+    /// Create a provider named `name` that runs `fetch`
+    pub fn new<F, Fut>(name: impl Into<String>, fetch: F) -> Self
+    where
+        F: Fn(Param) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T, E>> + 'static,
+    {
+        Self {
+            name: Arc::from(name.into()),
+            fetch: Arc::new(move |param| Box::pin(fetch(param))),
+        }
+    }
+
+    /// The name the provider's cache keys start with
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl<Param, T, E> Clone for FnProvider<Param, T, E> {
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            fetch: self.fetch.clone(),
+        }
+    }
+}
+
+impl<Param, T, E> PartialEq for FnProvider<Param, T, E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl<Param, T, E> fmt::Debug for FnProvider<Param, T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FnProvider")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Param, T, E> Provider<Param> for FnProvider<Param, T, E>
+where
+    Param: ProviderParamBounds,
+    T: ProviderOutputBounds,
+    E: ProviderErrorBounds,
+{
+    type Output = T;
+    type Error = E;
+
+    fn run(&self, param: Param) -> impl Future<Output = Result<Self::Output, Self::Error>> {
+        (self.fetch)(param)
+    }
+
+    fn query_key(&self, param: &Param) -> Option<QueryKey> {
+        Some(QueryKey::named(&self.name, param))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_are_the_name_and_parameter() {
+        let double = FnProvider::new(
+            "double",
+            |value: u32| async move { Ok::<_, String>(value * 2) },
+        );
+        let triple = FnProvider::new(
+            "triple",
+            |value: u32| async move { Ok::<_, String>(value * 3) },
+        );
+        let version = FnProvider::new("version", |()| async { Ok::<_, ()>(7) });
+
+        assert_eq!(double.id(&4), "double/4");
+        assert_ne!(double.id(&4), triple.id(&4));
+        assert_ne!(double, triple);
+        assert_eq!(double, double.clone());
+        assert_eq!(version.id(&()), "version");
+        assert_eq!(futures::executor::block_on(double.run(4)), Ok(8));
+    }
+}
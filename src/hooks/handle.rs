@@ -0,0 +1,169 @@
+//! # Provider Handles
+//!
+//! [`use_provider`](super::use_provider) returns a [`ProviderHandle`]: the provider's state
+//! signal, which the handle dereferences to, together with the operations components
+//! commonly need for that provider. Refetching, invalidating, checking the status and
+//! suspending don't need separate hooks.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use dioxus::prelude::*;
+//! use dioxus_provider::prelude::*;
+//!
+//! #[provider]
+//! async fn fetch_inbox() -> Result<Vec<String>, String> {
+//!     Ok(vec!["Welcome".to_string()])
+//! }
+//!
+//! #[component]
+//! fn Inbox() -> Element {
+//!     let inbox = use_provider(fetch_inbox(), ());
+//!
+//!     rsx! {
+//!         if let State::Success(messages) = &*inbox.read() {
+//!             "{messages.len()} messages"
+//!         }
+//!         button { onclick: move |_| inbox.refetch(), "Refresh" }
+//!     }
+//! }
+//! ```
+
+use dioxus::prelude::*;
+use std::{cell::RefCell, ops::Deref, rc::Rc};
+
+use super::{RenderError, SuspenseSignalExt};
+use crate::{cache::InvalidationMode, runtime::ProviderRuntime, state::State};
+
+/// The runtime serving a provider consumer, and the cache key it currently reads
+pub(crate) struct HandleTarget {
+    pub(crate) runtime: ProviderRuntime,
+    /// Empty while the consumer doesn't read the cache, e.g. for skipped parameters
+    pub(crate) cache_key: Rc<RefCell<String>>,
+}
+
+/// A summary of a provider's state, for UI that doesn't need the data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProviderStatus {
+    /// No data or error yet
+    Loading,
+    /// The provider returned data
+    Success,
+    /// The provider is refetching, with its previous data still available
+    Reloading,
+    /// The provider failed
+    Error,
+}
+
+/// The state of a provider consumer, with the common operations on that provider
+///
+/// Dereferences to the state signal, so `.read()`, calling the handle and everything else
+/// that works on a `Signal<State<T, E>>` works on the handle. Like signals, handles are
+/// `Copy` and can be moved into event handlers freely.
+pub struct ProviderHandle<T: 'static, E: 'static> {
+    state: Signal<State<T, E>>,
+    target: CopyValue<HandleTarget>,
+}
+
+impl<T: 'static, E: 'static> ProviderHandle<T, E> {
+    pub(crate) fn new(state: Signal<State<T, E>>, target: CopyValue<HandleTarget>) -> Self {
+        Self { state, target }
+    }
+
+    /// The state signal
+    pub fn signal(&self) -> Signal<State<T, E>> {
+        self.state
+    }
+
+    /// Refetch the provider in the background, keeping the current data until it resolves
+    pub fn refetch(&self) {
+        self.invalidate_with_mode(InvalidationMode::MarkStale);
+    }
+
+    /// Invalidate the provider's cache entry and refetch it
+    ///
+    /// The entry is treated according to the runtime's `InvalidationMode`, like
+    /// `use_invalidate_provider`.
+    pub fn invalidate(&self) {
+        let mode = self.target.read().runtime.invalidation_mode();
+        self.invalidate_with_mode(mode);
+    }
+
+    fn invalidate_with_mode(&self, mode: InvalidationMode) {
+        let target = self.target.read();
+        let cache_key = target.cache_key.borrow();
+        if cache_key.is_empty() {
+            return;
+        }
+        target
+            .runtime
+            .cache()
+            .invalidate_with_mode(&cache_key, mode);
+        target
+            .runtime
+            .refresh_registry()
+            .trigger_refresh(&cache_key);
+    }
+
+    /// A summary of the current state, subscribing the caller like `.read()` does
+    pub fn status(&self) -> ProviderStatus {
+        match &*self.state.read() {
+            State::Loading { .. } => ProviderStatus::Loading,
+            State::Success(_) => ProviderStatus::Success,
+            State::Reloading { .. } => ProviderStatus::Reloading,
+            State::Error(_) => ProviderStatus::Error,
+        }
+    }
+
+    /// Returns true while the provider's persisted entry is being hydrated
+    ///
+    /// The state is `Loading` meanwhile, so UIs can show a lighter placeholder than for a
+    /// fetch. Always false without lazy hydration configured.
+    pub fn is_hydrating(&self) -> bool {
+        if !self.state.read().is_loading() {
+            return false;
+        }
+        let target = self.target.read();
+        let cache_key = target.cache_key.borrow();
+        !cache_key.is_empty() && target.runtime.is_hydrating(&cache_key)
+    }
+
+    /// Returns the result once loaded, or suspends rendering while loading
+    ///
+    /// See [`SuspenseSignalExt::suspend`].
+    pub fn suspend(&self) -> Result<Result<T, E>, RenderError>
+    where
+        T: Clone,
+        E: Clone,
+    {
+        self.state.suspend()
+    }
+}
+
+impl<T: 'static, E: 'static> Clone for ProviderHandle<T, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: 'static, E: 'static> Copy for ProviderHandle<T, E> {}
+
+impl<T: 'static, E: 'static> PartialEq for ProviderHandle<T, E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.state == other.state && self.target == other.target
+    }
+}
+
+impl<T: 'static, E: 'static> Deref for ProviderHandle<T, E> {
+    type Target = Signal<State<T, E>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.state
+    }
+}
+
+impl<T: 'static, E: 'static> From<ProviderHandle<T, E>> for Signal<State<T, E>> {
+    fn from(handle: ProviderHandle<T, E>) -> Self {
+        handle.state
+    }
+}
@@ -1,7 +1,13 @@
 //! Provider hooks and utilities for Dioxus applications
 
 // Main hooks implementation
+mod ext;
+mod fn_provider;
+mod handle;
 mod provider;
 
 // Re-export everything from provider
+pub use ext::{ConfiguredProvider, ProviderExt};
+pub use fn_provider::FnProvider;
+pub use handle::{ProviderHandle, ProviderStatus};
 pub use provider::*;
@@ -30,19 +30,56 @@ use dioxus::{
     core::{ReactiveContext, SuspendedFuture},
     prelude::*,
 };
-use std::{fmt::Debug, future::Future, time::Duration};
+use futures::StreamExt;
+use std::{cell::RefCell, fmt::Debug, future::Future, rc::Rc, time::Duration};
 
 use crate::{
-    cache::ProviderCache,
-    global::{get_global_runtime, get_global_runtime_handles},
-    runtime::{ProviderRuntime, ProviderRuntimeHandles, request::handle_cache_miss},
+    cache::{CachePolicy, ErrorCaching, ProviderCache},
+    cancel::CancellationToken,
+    entity::EntityStore,
+    global::{fallback_runtime, hook_runtime, named_hook_runtime, try_hook_runtime},
+    history::HistoryWindow,
+    key_hash::{TypeTag, key_hasher},
+    mutation::Mutation,
+    persist::{Persistence, restore},
+    query_key::QueryKey,
+    retry::RetryPolicy,
+    runtime::{
+        ProviderRuntime, ProviderRuntimeHandles,
+        cache_mgmt::{error_expiration, key_origin},
+        request::handle_cache_miss,
+        swr::check_and_handle_swr_core,
+    },
+    schedule::Schedule,
 };
 
+use super::handle::{HandleTarget, ProviderHandle};
 use crate::param_utils::IntoProviderParam;
 use crate::types::{ProviderErrorBounds, ProviderOutputBounds, ProviderParamBounds};
 
 pub use crate::state::State;
 
+/// Function computing a per-entry cache policy from a provider's latest output
+pub type CachePolicyFn<T> = fn(&T) -> Option<CachePolicy>;
+
+/// Function writing the entities contained in a provider's output into the entity store
+pub type NormalizeFn<T> = fn(&T, &EntityStore);
+
+/// Function seeding other providers' cache entries from a provider's output
+pub type SeedFn<T> = fn(&T, &ProviderCache);
+
+/// Function computing the next refresh delay from a provider's latest output
+pub type NextIntervalFn<T> = fn(&T) -> Option<Duration>;
+
+/// Function computing the approximate size in bytes of a provider's output
+pub type WeightFn<T> = fn(&T) -> usize;
+
+/// A provider's state together with the handle for editing it locally
+pub type EditableProvider<P, Param> = (
+    Signal<State<<P as Provider<Param>>::Output, <P as Provider<Param>>::Error>>,
+    EditHandle<P, Param>,
+);
+
 /// A unified trait for defining providers - async operations that return data
 ///
 /// This trait supports both simple providers (no parameters) and parameterized providers.
@@ -97,21 +134,29 @@ where
 
     /// Get a unique identifier for this provider instance with the given parameters
     ///
-    /// This ID is used for caching and invalidation. The default implementation
-    /// hashes the provider's type, parameter type, and parameter value to generate a unique ID.
+    /// This ID is used for caching and invalidation. The default implementation uses the
+    /// provider's `query_key` if it has one, and otherwise hashes the provider's type,
+    /// parameter type, and parameter value to generate a unique ID, using the hasher chosen
+    /// with `ProviderConfig::with_key_hasher`.
     /// This ensures that different parameter types with the same value produce different keys.
     fn id(&self, param: &Param) -> String {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
+        if let Some(query_key) = self.query_key(param) {
+            return query_key.to_string();
+        }
 
-        let mut hasher = DefaultHasher::new();
-        // Hash provider type
-        std::any::TypeId::of::<Self>().hash(&mut hasher);
-        // Hash parameter type to prevent collisions between different types with same value
-        std::any::TypeId::of::<Param>().hash(&mut hasher);
-        // Hash parameter value
-        param.hash(&mut hasher);
-        format!("{:x}", hasher.finish())
+        // Hash the provider and parameter types along with the value, to prevent collisions
+        // between different types with the same value
+        let hash = key_hasher().hash_param(&[TypeTag::of::<Self>(), TypeTag::of::<Param>()], param);
+        format!("{hash:x}")
+    }
+
+    /// Get a structured, readable cache key for the given parameters (None means hashed)
+    ///
+    /// Query keys can be invalidated by prefix with `use_invalidate_prefix` and their
+    /// parameters read back with `QueryKey::parse`. `#[provider]` implements this
+    /// automatically as the function name followed by the parameters.
+    fn query_key(&self, _param: &Param) -> Option<QueryKey> {
+        None
     }
 
     /// Get the interval duration for automatic refresh (None means no interval)
@@ -122,6 +167,66 @@ where
         None
     }
 
+    /// Get a function that computes the next refresh delay from fetched data (None means disabled)
+    ///
+    /// When set, the provider refreshes after the delay returned for its latest successful
+    /// value, e.g. derived from a `next_update_at` field returned by the API. The fixed
+    /// `interval`, if any, is used as the fallback delay when the function returns `None`
+    /// or no data has been fetched yet.
+    fn next_interval(&self) -> Option<NextIntervalFn<Self::Output>> {
+        None
+    }
+
+    /// Get a function that computes a per-entry cache policy from fetched data (None means disabled)
+    ///
+    /// When set, each successful result may carry its own stale time and expiration (e.g.
+    /// derived from HTTP `Cache-Control` headers) that override the static `stale_time`
+    /// and `cache_expiration` for that cache entry.
+    fn cache_policy(&self) -> Option<CachePolicyFn<Self::Output>> {
+        None
+    }
+
+    /// Get a function that computes the size in bytes of fetched data (None means the default)
+    ///
+    /// Byte-based eviction (`RuntimeSettings::with_max_cache_bytes`) counts each entry with
+    /// this size, or with `cache::DEFAULT_ENTRY_WEIGHT` if the provider doesn't set one.
+    fn weight(&self) -> Option<WeightFn<Self::Output>> {
+        None
+    }
+
+    /// Get a function that normalizes fetched data into the entity store (None means disabled)
+    ///
+    /// When set, every successful result is split into its entities, so components using
+    /// `use_entity` see updates from all providers that return the same entity.
+    fn normalize(&self) -> Option<NormalizeFn<Self::Output>> {
+        None
+    }
+
+    /// Get how results are written to the cache's persistent storage (None means disabled)
+    ///
+    /// When set and the runtime's cache is backed by a `CacheStorage`, successful results
+    /// are stored, and a key missing from the cache is restored from storage on first use.
+    fn persistence(&self) -> Option<Persistence<Self::Output>> {
+        None
+    }
+
+    /// Get a function that seeds detail providers from fetched data (None means disabled)
+    ///
+    /// When set, every successful result is passed to the function after it is cached, e.g.
+    /// to write the items of a list into the cache of their detail provider with
+    /// `seed::seed_details`.
+    fn seed_details(&self) -> Option<SeedFn<Self::Output>> {
+        None
+    }
+
+    /// Get the wall-clock schedule for automatic refresh (None means no schedule)
+    ///
+    /// When set, the provider will automatically refresh its data at the times matched
+    /// by the schedule, in addition to any fixed `interval`.
+    fn schedule(&self) -> Option<Schedule> {
+        None
+    }
+
     /// Get the cache expiration duration (None means no expiration)
     ///
     /// When set, cached data will be considered expired after this duration and
@@ -137,6 +242,114 @@ where
     fn stale_time(&self) -> Option<Duration> {
         None
     }
+
+    /// Get the minimum time between refetches (None means no limit)
+    ///
+    /// When set, invalidations and revalidations arriving sooner than this after the last
+    /// fetch don't re-run the provider right away; they coalesce into one trailing refetch
+    /// once the interval has passed.
+    fn min_refetch_interval(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Check whether the provider revalidates when the app regains focus (defaults to false)
+    ///
+    /// Entries are revalidated like stale-while-revalidate does: in the background, while
+    /// consumers keep showing the cached data. With a stale time only stale entries are
+    /// revalidated; without one, every focus revalidates.
+    fn refetch_on_focus(&self) -> bool {
+        false
+    }
+
+    /// Check whether the provider refetches when the app comes back online (defaults to false)
+    ///
+    /// Failed entries are refetched, and so are stale ones, in the background like
+    /// stale-while-revalidate.
+    fn refetch_on_reconnect(&self) -> bool {
+        false
+    }
+
+    /// Get how many recent parameterizations stay retained (None means no history)
+    ///
+    /// When set, the last `size` cache keys shown by consumers are kept out of cleanup,
+    /// expiration and eviction, and keys navigated away from aren't refetched for being
+    /// stale during the grace period, so going back shows them instantly.
+    fn history(&self) -> Option<HistoryWindow> {
+        None
+    }
+
+    /// Get how long errors stay cached (defaults to the same as successful results)
+    ///
+    /// Transient failures usually shouldn't be served to every consumer for the whole
+    /// expiration window; `Never` and `Short` make consumers that mount later refetch.
+    fn cache_errors(&self) -> ErrorCaching {
+        ErrorCaching::Same
+    }
+
+    /// Get how long not-found errors stay cached (None means like other errors)
+    ///
+    /// Negative caching keeps lookups of ids that don't exist from hitting the backend on
+    /// every use. Takes precedence over `cache_errors` for errors that `is_not_found`
+    /// accepts.
+    fn cache_not_found(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Get how failed fetches are retried before their error is stored (None means never)
+    ///
+    /// Errors that `is_not_found` accepts are not retried.
+    fn retry_policy(&self) -> Option<RetryPolicy> {
+        None
+    }
+
+    /// Get how long a run may take before it fails with the timeout error (None means no limit)
+    ///
+    /// Each attempt of a retried provider gets the full timeout.
+    fn timeout(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Build the error a run fails with when it exceeds the timeout
+    ///
+    /// Providers that set a `timeout` must return one; `#[provider]` builds it with
+    /// [`TimeoutError`](crate::errors::TimeoutError) unless given a `timeout_error`. A run
+    /// that times out without one panics rather than waiting without a limit.
+    fn timeout_error(&self, _timeout: Duration) -> Option<Self::Error> {
+        None
+    }
+
+    /// Check whether an error says the requested resource doesn't exist
+    ///
+    /// Recognizes the not-found variants of the error types in `errors` by default.
+    fn is_not_found(&self, error: &Self::Error) -> bool {
+        crate::errors::is_not_found_error(error)
+    }
+
+    /// Get the names of the server entities this provider's data comes from
+    ///
+    /// `ProviderRuntime::invalidate_entity` refetches the entries of every provider serving
+    /// the named entity, matching ids against the provider's first parameter.
+    fn serves(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Check parameters before the provider runs
+    ///
+    /// Rejected parameters put the consumer straight into `State::Error` with the returned
+    /// error; nothing is fetched or cached. Use it to guard against empty search strings,
+    /// out-of-range ids and similar inputs.
+    fn validate(&self, _param: &Param) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Resolve parameters to a value without running the provider (None means run it)
+    ///
+    /// Skipped parameters put the consumer straight into `State::Success` with the returned
+    /// value and are never cached, e.g. an empty result for an empty search box. Checked
+    /// before `validate`.
+    fn skip(&self, _param: &Param) -> Option<Self::Output> {
+        None
+    }
 }
 
 /// Extension trait to enable suspense support for provider signals
@@ -179,31 +392,39 @@ impl<T: Clone + 'static, E: Clone + 'static> SuspenseSignalExt<T, E> for Signal<
     fn suspend(&self) -> Result<Result<T, E>, RenderError> {
         match &*self.read() {
             State::Loading { task } => Err(RenderError::Suspended(SuspendedFuture::new(*task))),
-            State::Success(data) => Ok(Ok(data.clone())),
+            State::Success(data) | State::Reloading { previous: data, .. } => Ok(Ok(data.clone())),
             State::Error(error) => Ok(Err(error.clone())),
         }
     }
 }
 
-fn runtime_handles_or_panic() -> ProviderRuntimeHandles {
-    get_global_runtime_handles().unwrap_or_else(|_| {
-        panic!(
-            "Global providers not initialized. Call dioxus_provider::init() before using providers."
-        )
-    })
+/// Suspend rendering until `pending` no longer holds for `state`
+///
+/// Suspends on a task in the current scope that ends once the state changes accordingly,
+/// rather than on the task that produces the state, which may run elsewhere or have ended.
+pub(crate) fn suspend_until<S: 'static>(state: Signal<S>, pending: fn(&S) -> bool) -> RenderError {
+    let task = spawn(async move {
+        let (reactive_context, mut updates) = ReactiveContext::new();
+        while reactive_context.run_in(|| pending(&state.read())) {
+            if updates.next().await.is_none() {
+                break;
+            }
+        }
+    });
+    RenderError::Suspended(SuspendedFuture::new(task))
+}
+
+fn runtime_handles() -> ProviderRuntimeHandles {
+    hook_runtime().handles()
 }
 
-fn runtime_instance_or_panic() -> ProviderRuntime {
-    get_global_runtime()
-        .unwrap_or_else(|_| {
-            panic!("Global providers not initialized. Call dioxus_provider::init() before using providers.")
-        })
-        .clone()
+fn runtime_instance() -> ProviderRuntime {
+    hook_runtime().clone()
 }
 
 /// Get the provider cache - requires global providers to be initialized
 fn get_provider_cache() -> ProviderCache {
-    runtime_handles_or_panic().cache
+    runtime_handles().cache
 }
 
 /// Hook to access the provider cache for manual cache management
@@ -262,6 +483,11 @@ pub fn use_provider_cache() -> ProviderCache {
 /// specified provider and parameters, and trigger a refresh of all components
 /// using that provider.
 ///
+/// By default the entry is only marked stale, so components keep showing the old data
+/// while it is refetched in the background. Use
+/// `ProviderConfig::with_invalidation_mode(InvalidationMode::Remove)` to drop the entry
+/// and show a loading state instead.
+///
 /// Requires global providers to be initialized with `init_global_providers()`.
 ///
 /// ## Example
@@ -292,18 +518,288 @@ where
     P: Provider<Param>,
     Param: ProviderParamBounds,
 {
-    let runtime = runtime_instance_or_panic();
+    let runtime = runtime_instance();
+    let invalidation_mode = runtime.invalidation_mode();
     let runtime_handles = runtime.handles();
     let cache = runtime_handles.cache;
     let refresh_registry = runtime_handles.refresh_registry;
     let cache_key = provider.id(&param);
 
     move || {
-        cache.invalidate(&cache_key);
+        cache.invalidate_with_mode(&cache_key, invalidation_mode);
         refresh_registry.trigger_refresh(&cache_key);
     }
 }
 
+/// Hook to invalidate every cached provider whose query key starts with `prefix`
+///
+/// Only providers with structured query keys (all `#[provider]` functions) can match.
+/// Entries are treated according to the runtime's `InvalidationMode`, like
+/// `use_invalidate_provider`.
+///
+/// ## Example
+///
+/// ```rust,ignore
+/// use dioxus_provider::{prelude::*, query_key::QueryKey};
+///
+/// // Refetch every page of every user's posts
+/// let invalidate_posts = use_invalidate_prefix(QueryKey::of("fetch_user_posts"));
+/// // Only user 7's posts
+/// let invalidate_user_posts = use_invalidate_prefix(QueryKey::of("fetch_user_posts").with(7));
+/// ```
+pub fn use_invalidate_prefix(prefix: QueryKey) -> impl Fn() + Clone {
+    let runtime = runtime_instance();
+    let invalidation_mode = runtime.invalidation_mode();
+    let runtime_handles = runtime.handles();
+    let cache = runtime_handles.cache;
+    let refresh_registry = runtime_handles.refresh_registry;
+
+    move || {
+        for cache_key in cache.invalidate_prefix(&prefix, invalidation_mode) {
+            refresh_registry.trigger_refresh(&cache_key);
+        }
+    }
+}
+
+/// Handle returned by [`use_update_provider_cache`] for patching a provider's cached data
+#[derive(Clone)]
+pub struct ProviderCacheUpdater<P, Param> {
+    cache: ProviderCache,
+    refresh_registry: crate::refresh::RefreshRegistry,
+    cache_key: String,
+    _marker: std::marker::PhantomData<(P, Param)>,
+}
+
+impl<P, Param> ProviderCacheUpdater<P, Param>
+where
+    P: Provider<Param>,
+    Param: ProviderParamBounds,
+{
+    /// Patch the cached successful result in place and notify subscribers if it changed
+    ///
+    /// Does nothing when the provider has no cached data or its last result was an error.
+    /// Returns whether the data changed.
+    pub fn update(&self, patch: impl FnOnce(&mut P::Output)) -> bool {
+        let updated =
+            self.cache
+                .update::<Result<P::Output, P::Error>, _>(&self.cache_key, |result| {
+                    if let Ok(data) = result {
+                        patch(data);
+                    }
+                });
+        if updated {
+            self.refresh_registry.trigger_refresh(&self.cache_key);
+        }
+        updated
+    }
+}
+
+/// Hook to patch a provider's cached data without a mutation or refetch
+///
+/// Returns a [`ProviderCacheUpdater`] whose `update` method applies a closure to the
+/// cached data and re-renders every component using that provider. This is useful
+/// for applying server-pushed deltas, e.g. from a WebSocket, to a cached list.
+///
+/// ## Example
+///
+/// ```rust,ignore
+/// use dioxus::prelude::*;
+/// use dioxus_provider::prelude::*;
+///
+/// #[provider]
+/// async fn fetch_messages(room: u32) -> Result<Vec<String>, String> {
+///     Ok(vec![])
+/// }
+///
+/// #[component]
+/// fn Room(room: u32) -> Element {
+///     let messages = use_update_provider_cache(fetch_messages(), room);
+///
+///     use_future(move || {
+///         let messages = messages.clone();
+///         async move {
+///             while let Some(message) = next_socket_message().await {
+///                 messages.update(|list| list.push(message));
+///             }
+///         }
+///     });
+///
+///     rsx! { MessageList { room } }
+/// }
+/// ```
+pub fn use_update_provider_cache<P, Param>(
+    provider: P,
+    param: Param,
+) -> ProviderCacheUpdater<P, Param>
+where
+    P: Provider<Param>,
+    Param: ProviderParamBounds,
+{
+    let runtime_handles = runtime_handles();
+
+    ProviderCacheUpdater {
+        cache: runtime_handles.cache,
+        refresh_registry: runtime_handles.refresh_registry,
+        cache_key: provider.id(&param),
+        _marker: std::marker::PhantomData,
+    }
+}
+
+/// Handle returned by [`use_provider_editable`] for editing a provider's data locally
+///
+/// Edits are written straight to the cache, so every component using the provider sees
+/// the draft immediately. The key is marked dirty while it holds uncommitted edits, and
+/// fetched results for it are discarded until the edits are committed or discarded.
+#[derive(Clone)]
+pub struct EditHandle<P, Param> {
+    updater: ProviderCacheUpdater<P, Param>,
+    invalidation_mode: crate::cache::InvalidationMode,
+}
+
+impl<P, Param> EditHandle<P, Param>
+where
+    P: Provider<Param>,
+    Param: ProviderParamBounds,
+{
+    /// Apply a local edit to the cached data and mark it dirty
+    ///
+    /// Does nothing when the provider has no cached data or its last result was an error.
+    /// Returns whether the data changed.
+    pub fn edit(&self, patch: impl FnOnce(&mut P::Output)) -> bool {
+        let updated = self.updater.update(patch);
+        if updated {
+            crate::debug_log!("✏️ [EDIT] Local edit for key: {}", self.updater.cache_key);
+            self.updater.cache.mark_dirty(&self.updater.cache_key);
+        }
+        updated
+    }
+
+    /// The current draft, including local edits
+    pub fn draft(&self) -> Option<P::Output> {
+        self.updater
+            .cache
+            .get::<Result<P::Output, P::Error>>(&self.updater.cache_key)
+            .and_then(Result::ok)
+    }
+
+    /// Returns true while the cached data holds uncommitted local edits
+    pub fn is_dirty(&self) -> bool {
+        self.updater.cache.is_dirty(&self.updater.cache_key)
+    }
+
+    /// Drop the local edits and refetch the server state
+    pub fn discard(&self) {
+        crate::debug_log!(
+            "✏️ [EDIT] Discarding local edits for key: {}",
+            self.updater.cache_key
+        );
+        self.invalidate(&self.updater.cache_key);
+    }
+
+    /// Commit the local edits with a mutation
+    ///
+    /// On success the dirty flag is cleared and the provider is invalidated along with the
+    /// mutation's `invalidates` keys, so the committed server state replaces the draft. On
+    /// failure the draft is kept, so the user can retry without losing their edits.
+    pub async fn commit<M, Input>(&self, mutation: &M, input: Input) -> Result<M::Output, M::Error>
+    where
+        M: Mutation<Input>,
+        Input: Clone + PartialEq + 'static,
+    {
+        let result = mutation.mutate(input).await;
+        if let Ok(output) = &result {
+            crate::debug_log!(
+                "✏️ [EDIT] Committed local edits for key: {}",
+                self.updater.cache_key
+            );
+            if let Some(normalize) = mutation.normalize() {
+                normalize(output, self.updater.cache.entities());
+            }
+            self.invalidate(&self.updater.cache_key);
+            for cache_key in mutation.invalidates() {
+                self.invalidate(&cache_key);
+            }
+        } else {
+            crate::debug_log!(
+                "❌ [EDIT] Commit failed, keeping local edits for key: {}",
+                self.updater.cache_key
+            );
+        }
+        result
+    }
+
+    fn invalidate(&self, cache_key: &str) {
+        self.updater.cache.clear_dirty(cache_key);
+        self.updater
+            .cache
+            .invalidate_with_mode(cache_key, self.invalidation_mode);
+        self.updater.refresh_registry.trigger_refresh(cache_key);
+    }
+}
+
+/// Hook to use a provider as an editable draft, bridging server state and form state
+///
+/// Returns the provider's state together with an [`EditHandle`]. Local edits made through
+/// the handle are written to the cache immediately and protected from refetches until
+/// they are committed with a mutation or discarded.
+///
+/// ## Example
+///
+/// ```rust,ignore
+/// use dioxus::prelude::*;
+/// use dioxus_provider::prelude::*;
+///
+/// #[component]
+/// fn ProfileForm(id: u32) -> Element {
+///     let (profile, editor) = use_provider_editable(fetch_profile(), id);
+///
+///     let save = {
+///         let editor = editor.clone();
+///         move |_| {
+///             let editor = editor.clone();
+///             spawn(async move {
+///                 if let Some(draft) = editor.draft() {
+///                     let _ = editor.commit(&save_profile(), (id, draft)).await;
+///                 }
+///             });
+///         }
+///     };
+///
+///     rsx! {
+///         if let State::Success(profile) = &*profile.read() {
+///             input {
+///                 value: "{profile.name}",
+///                 oninput: move |event| {
+///                     editor.edit(|profile| profile.name = event.value());
+///                 },
+///             }
+///         }
+///         button { onclick: save, "Save" }
+///     }
+/// }
+/// ```
+pub fn use_provider_editable<P, Args>(provider: P, args: Args) -> EditableProvider<P, Args::Param>
+where
+    P: Provider<Args::Param> + Send + Clone,
+    Args: IntoProviderParam,
+{
+    let runtime = runtime_instance();
+    let runtime_handles = runtime.handles();
+    let param = args.into_param();
+
+    let handle = EditHandle {
+        updater: ProviderCacheUpdater {
+            cache: runtime_handles.cache,
+            refresh_registry: runtime_handles.refresh_registry,
+            cache_key: provider.id(&param),
+            _marker: std::marker::PhantomData,
+        },
+        invalidation_mode: runtime.invalidation_mode(),
+    };
+
+    (use_provider_core(provider, param).signal(), handle)
+}
+
 /// Hook to clear the entire provider cache
 ///
 /// Returns a function that, when called, will clear all cached provider data
@@ -330,7 +826,7 @@ where
 /// }
 /// ```
 pub fn use_clear_provider_cache() -> impl Fn() + Clone {
-    let runtime = runtime_instance_or_panic();
+    let runtime = runtime_instance();
     let runtime_handles = runtime.handles();
     let cache = runtime_handles.cache;
     let refresh_registry = runtime_handles.refresh_registry;
@@ -355,7 +851,7 @@ pub trait UseProvider<Args> {
     type Error: ProviderErrorBounds;
 
     /// Use the provider with the given arguments
-    fn use_provider(self, args: Args) -> Signal<State<Self::Output, Self::Error>>;
+    fn use_provider(self, args: Args) -> ProviderHandle<Self::Output, Self::Error>;
 }
 
 /// Unified implementation for all providers using parameter normalization
@@ -370,56 +866,179 @@ where
     type Output = P::Output;
     type Error = P::Error;
 
-    fn use_provider(self, args: Args) -> Signal<State<Self::Output, Self::Error>> {
+    fn use_provider(self, args: Args) -> ProviderHandle<Self::Output, Self::Error> {
         let param = args.into_param();
         use_provider_core(self, param)
     }
 }
 
 /// Core provider implementation that handles all the common logic
-fn use_provider_core<P, Param>(provider: P, param: Param) -> Signal<State<P::Output, P::Error>>
+pub(crate) fn use_provider_core<P, Param>(
+    provider: P,
+    param: Param,
+) -> ProviderHandle<P::Output, P::Error>
+where
+    P: Provider<Param> + Send + Clone,
+    Param: ProviderParamBounds,
+{
+    use_dependent_provider_core(try_hook_runtime(), Some((provider, param)))
+}
+
+/// Read the data a consumer can render before its first fetch, if its key is cached
+///
+/// Runs before the consumer's memo, so it only covers what the memo would show right away:
+/// unexpired successful entries, including ones rendered on the server.
+fn cached_data<P, Param>(
+    runtime: Option<&ProviderRuntime>,
+    target: Option<&(P, Param)>,
+) -> Option<P::Output>
+where
+    P: Provider<Param>,
+    Param: ProviderParamBounds,
+{
+    let (runtime, (provider, param)) = runtime.zip(target)?;
+    if provider.skip(param).is_some() || provider.validate(param).is_err() {
+        return None;
+    }
+    let cache = runtime.cache();
+    let cache_key = provider.id(param);
+    crate::ssr::hydrate_entry(provider, cache, &cache_key);
+    if runtime.is_hydrating(&cache_key)
+        || cache.is_restoring(&cache_key)
+        || cache.is_expired(&cache_key, provider.cache_expiration())
+    {
+        return None;
+    }
+    cache.get::<Result<P::Output, P::Error>>(&cache_key)?.ok()
+}
+
+/// Core provider implementation for a provider that may not be known yet
+///
+/// While `target` is None the state stays `Loading` and nothing is fetched or subscribed.
+/// Without a `runtime`, which was reported as a configuration error, nothing is fetched
+/// either.
+pub(crate) fn use_dependent_provider_core<P, Param>(
+    runtime: Option<ProviderRuntime>,
+    target: Option<(P, Param)>,
+) -> ProviderHandle<P::Output, P::Error>
 where
     P: Provider<Param> + Send + Clone,
     Param: ProviderParamBounds,
 {
-    let mut state = use_signal(|| State::Loading {
-        task: spawn(async {}),
+    // Cached data is shown from the first render, without a loading state in between
+    let mut state = use_signal(|| match cached_data(runtime.as_ref(), target.as_ref()) {
+        Some(data) => State::Success(data),
+        None => State::Loading {
+            task: spawn(async {}),
+        },
     });
-    let runtime = runtime_instance_or_panic();
+    let target = target.filter(|_| runtime.is_some());
+    let runtime = runtime.unwrap_or_else(|| fallback_runtime().clone());
     let runtime_handles = runtime.handles();
     let cache = runtime_handles.cache;
     let refresh_registry = runtime_handles.refresh_registry;
 
-    // Track previous cache key for cleanup
-    let mut prev_cache_key = use_signal(|| String::new());
+    // Track the cache key whose tasks this component holds, released on key change and unmount
+    let held_cache_key = use_hook(|| Rc::new(RefCell::new(String::new())));
+    // Detaches this component from the fetch it started once it leaves that key
+    let started_fetch = use_hook(|| Rc::new(RefCell::new(None::<CancellationToken>)));
+    let leave_fetch = {
+        let started_fetch = started_fetch.clone();
+        move || {
+            if let Some(token) = started_fetch.borrow_mut().take() {
+                token.cancel();
+            }
+        }
+    };
+    let handle_target = {
+        let runtime = runtime.clone();
+        let cache_key = held_cache_key.clone();
+        use_hook(|| CopyValue::new(HandleTarget { runtime, cache_key }))
+    };
+    {
+        let runtime = runtime.clone();
+        let held_cache_key = held_cache_key.clone();
+        use_drop(move || {
+            let key = held_cache_key.borrow();
+            if !key.is_empty() {
+                runtime.release_provider_tasks(&key);
+            }
+        });
+    }
+
+    // With the fullstack feature, each consumer carries its entry from the server's render
+    #[cfg(feature = "fullstack")]
+    let server_entry = use_hook(crate::ssr::ServerEntry::new);
 
     // Use memo with reactive dependencies to track changes automatically
     let runtime_for_memo = runtime.clone();
     let cache_for_memo = cache.clone();
     let refresh_for_memo = refresh_registry.clone();
 
-    let _execution_memo = use_memo(use_reactive!(|(provider, param)| {
+    let _execution_memo = use_memo(use_reactive!(|target| {
         let runtime = runtime_for_memo.clone();
         let cache = cache_for_memo.clone();
         let refresh_registry = refresh_for_memo.clone();
+
+        // Wait for the provider, holding no tasks in the meantime
+        let Some((provider, param)) = target else {
+            let prev_key = held_cache_key.replace(String::new());
+            if !prev_key.is_empty() {
+                leave_fetch();
+                runtime.release_provider_tasks(&prev_key);
+            }
+            if !state.read().is_loading() {
+                state.set(State::Loading {
+                    task: spawn(async {}),
+                });
+            }
+            return;
+        };
+
+        // Skipped and rejected parameters never spawn a fetch or reach the cache
+        let short_circuit = match provider.skip(&param) {
+            Some(value) => Some(State::Success(value)),
+            None => provider.validate(&param).err().map(State::Error),
+        };
+        if let Some(short_circuit) = short_circuit {
+            crate::debug_log!("🚫 [SKIP] Not fetching for parameters: {:?}", param);
+            let prev_key = held_cache_key.replace(String::new());
+            if !prev_key.is_empty() {
+                leave_fetch();
+                runtime.release_provider_tasks(&prev_key);
+            }
+            if *state.read() != short_circuit {
+                state.set(short_circuit);
+            }
+            return;
+        }
+
         let cache_key = provider.id(&param);
 
-        // Clean up previous cache key's tasks if it changed
-        let prev_key = prev_cache_key.read().clone();
+        // Move this component's hold on tasks to the new key; tasks of the previous key stop
+        // once no other consumer holds them
+        let prev_key = held_cache_key.replace(cache_key.clone());
         if prev_key != cache_key {
             if !prev_key.is_empty() {
-                runtime.stop_provider_tasks(&prev_key);
-                crate::debug_log!(
-                    "🧹 [CLEANUP] Stopped all tasks for previous cache key: {}",
-                    prev_key
-                );
+                leave_fetch();
+                runtime.release_provider_tasks(&prev_key);
+            }
+            runtime.retain_provider_tasks(&cache_key);
+            if let Some(window) = provider.history() {
+                cache
+                    .history()
+                    .visit(std::any::type_name::<P>(), window, &cache_key);
             }
-
-            // Only update tracked cache key if it actually changed to avoid unnecessary re-renders
-            prev_cache_key.set(cache_key.clone());
         }
 
         runtime.ensure_provider_tasks(&provider, &param, &cache_key);
+        if !provider.serves().is_empty() {
+            cache.served().register(
+                provider.serves(),
+                &cache_key,
+                crate::served::entity_id(&provider, &param),
+            );
+        }
 
         // Subscribe to refresh events for this cache key if we have a reactive context
         if let Some(reactive_context) = ReactiveContext::current() {
@@ -434,13 +1053,73 @@ where
         // - SWR staleness checking is handled by the periodic stale check task
         // - These periodic tasks run in the background without causing re-render loops
 
+        // In debug builds, fail loudly if another provider's data is stored under this key
+        if let Some(origin) = key_origin(&provider, &param) {
+            cache.assert_key_origin(&cache_key, &origin);
+        }
+
+        // Entries rendered on the server are decoded before the first read, so the first
+        // render matches the server's HTML
+        #[cfg(feature = "fullstack")]
+        server_entry.hydrate(&provider, &cache, &cache_key);
+        crate::ssr::hydrate_entry(&provider, &cache, &cache_key);
+
+        // Persisted entries are hydrated on their key's first use, unless the key is already
+        // cached; consumers wait for it and rerun once it's done, reading the hydrated entry
+        // or fetching if there was none
+        if let Some(hydration) = runtime.lazy_hydration()
+            && cache
+                .get::<Result<P::Output, P::Error>>(&cache_key)
+                .is_none()
+        {
+            if hydration.begin(&cache_key) {
+                let hydration = hydration.clone();
+                let cache = cache.clone();
+                let refresh_registry = refresh_registry.clone();
+                let key = cache_key.clone();
+                let task = dioxus::core::spawn_forever(async move {
+                    hydration.hydrate(&key, cache).await;
+                    refresh_registry.trigger_refresh(&key);
+                });
+                state.set(State::Loading { task });
+                return;
+            }
+            if hydration.is_hydrating(&cache_key) {
+                if !state.read().is_loading() {
+                    state.set(State::Loading {
+                        task: spawn(async {}),
+                    });
+                }
+                return;
+            }
+        }
+
+        // Errors past their caching window are refetched, except for consumers already
+        // showing them, which would otherwise refetch in a loop
+        let cached = cache
+            .get::<Result<P::Output, P::Error>>(&cache_key)
+            .filter(|cached| match cached {
+                Err(error) if error_expiration(&provider, error).is_some() => {
+                    !cache.is_expired(&cache_key, provider.cache_expiration())
+                        || matches!(*state.peek(), State::Error(ref shown) if shown == error)
+                }
+                _ => true,
+            });
+
         // Check cache for valid data
-        if let Some(cached_result) = cache.get::<Result<P::Output, P::Error>>(&cache_key) {
+        if let Some(cached_result) = cached {
             // Access tracking is automatically handled by cache.get() updating last_accessed time
             // Removed verbose cache hit logging to reduce spam
 
+            // Soft-invalidated entries keep serving their data while revalidating
+            if cache.is_marked_stale(&cache_key) {
+                check_and_handle_swr_core(&provider, &param, &cache_key, &cache, &refresh_registry);
+            }
+
             match cached_result {
                 Ok(data) => {
+                    #[cfg(feature = "fullstack")]
+                    server_entry.capture(&provider, &data);
                     // Only update state if it's different to avoid unnecessary re-renders
                     if !matches!(*state.read(), State::Success(ref d) if d == &data) {
                         state.set(State::Success(data));
@@ -456,19 +1135,47 @@ where
             return;
         }
 
+        // Persisted providers read their stored entry on the key's first use; consumers wait
+        // for it and rerun once it's done, reading the restored entry or fetching
+        if provider.persistence().is_some() {
+            if cache.begin_restore(&cache_key) {
+                let provider = provider.clone();
+                let cache = cache.clone();
+                let refresh_registry = refresh_registry.clone();
+                let key = cache_key.clone();
+                let task = dioxus::core::spawn_forever(async move {
+                    restore(&provider, &cache, &key).await;
+                    refresh_registry.trigger_refresh(&key);
+                });
+                state.set(State::Loading { task });
+                return;
+            }
+            if cache.is_restoring(&cache_key) {
+                if !state.read().is_loading() {
+                    state.set(State::Loading {
+                        task: spawn(async {}),
+                    });
+                }
+                return;
+            }
+        }
+
         // Delegate cache miss orchestration to the runtime so hooks stay lean
-        handle_cache_miss(
+        let started = handle_cache_miss(
             &runtime,
             provider.clone(),
             param.clone(),
             cache.clone(),
             refresh_registry.clone(),
             cache_key.clone(),
-            state.clone(),
+            state,
         );
+        if started.is_some() {
+            *started_fetch.borrow_mut() = started;
+        }
     }));
 
-    state
+    ProviderHandle::new(state, handle_target)
 }
 
 /// Performs SWR staleness checking and triggers background revalidation if needed
@@ -493,6 +1200,13 @@ where
 /// - **Auto-Dispose**: Automatically cleans up unused providers
 /// - **Unified API**: Single function handles all parameter formats
 ///
+/// ## Write Discipline
+///
+/// The returned signal mirrors the cache and is owned by the hook. Writing to it doesn't
+/// change the cache and is overwritten by the next update; use mutations or
+/// `use_update_provider_cache` to change data. To hand the state to code that must not
+/// write to it, use [`use_provider_readonly`].
+///
 /// ## Usage Examples
 ///
 /// ```rust,no_run
@@ -521,9 +1235,357 @@ where
 ///     }
 /// }
 /// ```
-pub fn use_provider<P, Args>(provider: P, args: Args) -> Signal<State<P::Output, P::Error>>
+pub fn use_provider<P, Args>(provider: P, args: Args) -> ProviderHandle<P::Output, P::Error>
 where
     P: UseProvider<Args>,
 {
     provider.use_provider(args)
 }
+
+/// Hook for using a provider in a runtime started with `init_named`
+///
+/// Same as [`use_provider`], but the provider is cached, refreshed and maintained by the
+/// isolated runtime named `name` instead of the global one, with that runtime's policies.
+/// A runtime that wasn't started is reported to the nearest error boundary, and the state
+/// stays `Loading`.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use dioxus::prelude::*;
+/// use dioxus_provider::prelude::*;
+///
+/// #[provider]
+/// async fn fetch_page_views() -> Result<u64, String> {
+///     Ok(42)
+/// }
+///
+/// #[component]
+/// fn PageViews() -> Element {
+///     let views = use_provider_in("analytics", fetch_page_views(), ());
+///
+///     rsx! {
+///         if let State::Success(views) = &*views.read() {
+///             "{views} views"
+///         }
+///     }
+/// }
+/// ```
+pub fn use_provider_in<P, Args>(
+    name: &str,
+    provider: P,
+    args: Args,
+) -> ProviderHandle<P::Output, P::Error>
+where
+    P: Provider<Args::Param> + Send + Clone,
+    Args: IntoProviderParam,
+{
+    use_dependent_provider_core(
+        named_hook_runtime(name),
+        Some((provider, args.into_param())),
+    )
+}
+
+/// Hook for using a provider through a read-only signal
+///
+/// Same as [`use_provider`], but the returned `ReadSignal` (formerly `ReadOnlySignal`)
+/// can't be `.set()`, so library authors can expose provider-backed state from their own
+/// hooks without consumers writing bogus states into it.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use dioxus::prelude::*;
+/// use dioxus_provider::prelude::*;
+///
+/// #[provider]
+/// async fn fetch_settings() -> Result<String, String> {
+///     Ok("dark".to_string())
+/// }
+///
+/// pub fn use_settings() -> ReadSignal<State<String, String>> {
+///     use_provider_readonly(fetch_settings(), ())
+/// }
+/// ```
+pub fn use_provider_readonly<P, Args>(
+    provider: P,
+    args: Args,
+) -> ReadSignal<State<P::Output, P::Error>>
+where
+    P: UseProvider<Args>,
+{
+    let state = provider.use_provider(args).signal();
+    use_hook(|| ReadSignal::new(state))
+}
+
+/// Hook for using a provider inside a `SuspenseBoundary`
+///
+/// Combines [`use_provider`] with suspending: rendering suspends until the first result
+/// arrives, then the hook returns it. Use `?` to propagate the suspension. Components
+/// showing data never suspend again for background revalidation, such as a stale entry
+/// refetching or an invalidation; they keep the data and update once the refetch is done.
+/// Switching to parameters without cached data suspends again.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use dioxus::prelude::*;
+/// use dioxus_provider::prelude::*;
+///
+/// #[provider]
+/// async fn fetch_user(id: u32) -> Result<String, String> {
+///     Ok(format!("User {id}"))
+/// }
+///
+/// #[component]
+/// fn UserCard(id: u32) -> Element {
+///     match use_provider_suspense(fetch_user(), id)? {
+///         Ok(name) => rsx! { "{name}" },
+///         Err(error) => rsx! { "Error: {error}" },
+///     }
+/// }
+///
+/// #[component]
+/// fn App() -> Element {
+///     rsx! {
+///         SuspenseBoundary {
+///             fallback: |_| rsx! { "Loading..." },
+///             UserCard { id: 1 }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_provider_suspense<P, Args>(
+    provider: P,
+    args: Args,
+) -> Result<Result<P::Output, P::Error>, RenderError>
+where
+    P: UseProvider<Args>,
+{
+    let state = provider.use_provider(args).signal();
+    let current = state.read().clone();
+    match current {
+        State::Loading { .. } => Err(suspend_until(state, State::is_loading)),
+        State::Success(data) | State::Reloading { previous: data, .. } => Ok(Ok(data)),
+        State::Error(error) => Ok(Err(error)),
+    }
+}
+
+/// Hook to derive a memoized view of a provider's data
+///
+/// The `map` function only runs when the provider's state changes, not on unrelated
+/// re-renders, and components reading the returned memo only re-render when the mapped
+/// output changes (compared with `PartialEq`). Use it for heavy transformations such as
+/// sorting or grouping.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use dioxus::prelude::*;
+/// use dioxus_provider::prelude::*;
+///
+/// #[provider]
+/// async fn fetch_todos() -> Result<Vec<(String, bool)>, String> {
+///     Ok(vec![("Write docs".to_string(), true)])
+/// }
+///
+/// #[component]
+/// fn TodoStats() -> Element {
+///     let done = use_provider_map(fetch_todos(), (), |todos: &Vec<(String, bool)>| {
+///         todos.iter().filter(|(_, completed)| *completed).count()
+///     });
+///
+///     rsx! {
+///         if let State::Success(count) = &*done.read() {
+///             "{count} done"
+///         }
+///     }
+/// }
+/// ```
+pub fn use_provider_map<P, Args, U>(
+    provider: P,
+    args: Args,
+    map: impl Fn(&P::Output) -> U + 'static,
+) -> Memo<State<U, P::Error>>
+where
+    P: UseProvider<Args>,
+    U: PartialEq + 'static,
+{
+    let state = provider.use_provider(args);
+    use_memo(move || match &*state.read() {
+        State::Loading { task } => State::Loading { task: *task },
+        State::Success(data) => State::Success(map(data)),
+        State::Reloading { previous, task } => State::Reloading {
+            previous: map(previous),
+            task: *task,
+        },
+        State::Error(error) => State::Error(error.clone()),
+    })
+}
+
+/// Hook to subscribe to a slice of a provider's data
+///
+/// Returns `None` while there is no data yet, then the `select`ed slice or the error.
+/// Unlike [`use_provider_map`], loading tasks and background revalidation aren't part of
+/// the value: components reading the returned memo only re-render when the selected slice
+/// (compared with `PartialEq`) or the error changes, not on every cache update of the
+/// provider. Use [`use_provider`] alongside to show revalidation.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use dioxus::prelude::*;
+/// use dioxus_provider::prelude::*;
+///
+/// #[derive(Clone, PartialEq)]
+/// pub struct Profile {
+///     pub name: String,
+///     pub last_seen: u64,
+/// }
+///
+/// #[provider]
+/// async fn fetch_profile(id: u32) -> Result<Profile, String> {
+///     Ok(Profile { name: format!("User {id}"), last_seen: 0 })
+/// }
+///
+/// #[component]
+/// fn ProfileName(id: u32) -> Element {
+///     // Doesn't re-render when only `last_seen` changes
+///     let name = use_provider_select(fetch_profile(), id, |profile: &Profile| {
+///         profile.name.clone()
+///     });
+///
+///     rsx! {
+///         if let Some(Ok(name)) = &*name.read() {
+///             "{name}"
+///         }
+///     }
+/// }
+/// ```
+pub fn use_provider_select<P, Args, U>(
+    provider: P,
+    args: Args,
+    select: impl Fn(&P::Output) -> U + 'static,
+) -> Memo<Option<Result<U, P::Error>>>
+where
+    P: UseProvider<Args>,
+    U: PartialEq + 'static,
+{
+    let state = provider.use_provider(args);
+    use_memo(move || match &*state.read() {
+        State::Loading { .. } => None,
+        State::Success(data) | State::Reloading { previous: data, .. } => Some(Ok(select(data))),
+        State::Error(error) => Some(Err(error.clone())),
+    })
+}
+
+/// Hook to observe a cache entry by key, without knowing the provider that fills it
+///
+/// Returns `None` until some provider stores a result under `key`, and updates whenever
+/// that result changes. The hook never runs a provider and doesn't count as a use of the
+/// entry, so it suits generic components such as admin panels and devtools. Keys come
+/// from [`Provider::id`] or [`provider_cache_key`](crate::mutation::provider_cache_key).
+///
+/// `T` and `E` must match the provider's output and error types; for other types the
+/// entry reads as `None`.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use dioxus::prelude::*;
+/// use dioxus_provider::prelude::*;
+///
+/// #[component]
+/// fn CacheEntryViewer(cache_key: String) -> Element {
+///     let entry = use_provider_by_key::<String, String>(cache_key.clone());
+///
+///     rsx! {
+///         match &*entry.read() {
+///             Some(Ok(value)) => rsx! { "{cache_key}: {value}" },
+///             Some(Err(error)) => rsx! { "{cache_key} failed: {error}" },
+///             None => rsx! { "{cache_key} is not cached" },
+///         }
+///     }
+/// }
+/// ```
+pub fn use_provider_by_key<T, E>(key: impl Into<String>) -> Signal<Option<Result<T, E>>>
+where
+    T: Clone + PartialEq + Send + Sync + 'static,
+    E: Clone + PartialEq + Send + Sync + 'static,
+{
+    let key = key.into();
+    let handles = runtime_handles();
+    let cache = handles.cache;
+    let refresh_registry = handles.refresh_registry;
+
+    let mut entry = use_signal(|| cache.get::<Result<T, E>>(&key));
+    let _entry_memo = use_memo(use_reactive!(|key| {
+        if let Some(reactive_context) = ReactiveContext::current() {
+            refresh_registry.subscribe_to_refresh(&key, reactive_context);
+        }
+        let current = cache.get::<Result<T, E>>(&key);
+        if *entry.peek() != current {
+            entry.set(current);
+        }
+    }));
+
+    entry
+}
+
+/// Hook for a provider whose parameters come from another provider's data
+///
+/// Runs `first`, then derives the second provider and its parameters from the first
+/// provider's output and subscribes to it. The returned state is `Loading` until both have
+/// loaded, and carries the first provider's error (converted with `Into`) if it fails. When
+/// the first provider's data changes, the second provider is re-derived.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use dioxus::prelude::*;
+/// use dioxus_provider::prelude::*;
+///
+/// #[provider]
+/// async fn fetch_current_user() -> Result<u32, String> {
+///     Ok(7)
+/// }
+///
+/// #[provider]
+/// async fn fetch_user_projects(user_id: u32) -> Result<Vec<String>, String> {
+///     Ok(vec![format!("Project of {user_id}")])
+/// }
+///
+/// #[component]
+/// fn Projects() -> Element {
+///     let projects = use_chained_provider(fetch_current_user(), (), |user_id: &u32| {
+///         (fetch_user_projects(), *user_id)
+///     });
+///
+///     rsx! {
+///         if let State::Success(projects) = &*projects.read() {
+///             "{projects.len()} projects"
+///         }
+///     }
+/// }
+/// ```
+pub fn use_chained_provider<P, Args, P2, Param2>(
+    first: P,
+    args: Args,
+    derive: impl Fn(&P::Output) -> (P2, Param2) + 'static,
+) -> Memo<State<P2::Output, P2::Error>>
+where
+    P: UseProvider<Args>,
+    P::Error: Into<P2::Error>,
+    P2: Provider<Param2> + Send + Clone,
+    Param2: ProviderParamBounds,
+{
+    let first_state = first.use_provider(args);
+    let target = use_memo(move || first_state.read().data().map(&derive));
+    let second_state = use_dependent_provider_core(try_hook_runtime(), target());
+
+    use_memo(move || match &*first_state.read() {
+        State::Loading { task } => State::Loading { task: *task },
+        State::Error(error) => State::Error(error.clone().into()),
+        State::Success(_) | State::Reloading { .. } => second_state.read().clone(),
+    })
+}
@@ -0,0 +1,227 @@
+//! # HTTP Provider Helpers
+//!
+//! This module provides helpers for providers that fetch data over HTTP. The server's
+//! caching headers (`Cache-Control: max-age`, `stale-while-revalidate` and `Expires`) are
+//! turned into a per-entry [`CachePolicy`], so each response stays fresh for exactly as long
+//! as the server allows, overriding the static `stale_time` / `cache_expiration` values.
+//!
+//! The helpers are independent of any HTTP client: pass in the header values from
+//! whichever client you use.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use dioxus_provider::{http::HttpCached, prelude::*};
+//!
+//! // Providers returning `HttpCached<T>` honor the response headers automatically
+//! #[provider(stale_time = "1m")]
+//! async fn fetch_feed() -> Result<HttpCached<Feed>, String> {
+//!     let response = reqwest::get("https://example.com/feed").await.map_err(|e| e.to_string())?;
+//!     let headers = response.headers().clone();
+//!     let feed = response.json().await.map_err(|e| e.to_string())?;
+//!     Ok(HttpCached::from_headers(
+//!         feed,
+//!         headers.get("cache-control").and_then(|v| v.to_str().ok()),
+//!         headers.get("expires").and_then(|v| v.to_str().ok()),
+//!     ))
+//! }
+//! ```
+
+use std::{ops::Deref, time::Duration};
+
+use crate::{
+    cache::CachePolicy,
+    platform::{duration_until, now_secs},
+};
+
+/// Derive a cache policy from `Cache-Control` and `Expires` response header values
+///
+/// - `no-store`, `no-cache` and `max-age=0` make the entry stale immediately
+/// - `max-age=N` sets the stale time to N seconds
+/// - `stale-while-revalidate=M` additionally sets the expiration to N + M seconds
+/// - Without `max-age`, a valid `Expires` date sets the stale time; an invalid one means
+///   the response is already stale
+///
+/// Returns `None` when the headers carry no freshness information.
+pub fn cache_policy_from_headers(
+    cache_control: Option<&str>,
+    expires: Option<&str>,
+) -> Option<CachePolicy> {
+    let mut max_age = None;
+    let mut stale_while_revalidate = None;
+    let mut no_cache = false;
+
+    for directive in cache_control.unwrap_or_default().split(',') {
+        let (name, value) = match directive.split_once('=') {
+            Some((name, value)) => (name.trim(), Some(value.trim().trim_matches('"'))),
+            None => (directive.trim(), None),
+        };
+        let seconds = value.and_then(|value| value.parse::<u64>().ok());
+
+        match name.to_ascii_lowercase().as_str() {
+            "no-store" | "no-cache" => no_cache = true,
+            "max-age" => max_age = seconds,
+            "stale-while-revalidate" => stale_while_revalidate = seconds,
+            _ => {}
+        }
+    }
+
+    let freshness = if no_cache {
+        Some(Duration::ZERO)
+    } else if let Some(max_age) = max_age {
+        Some(Duration::from_secs(max_age))
+    } else {
+        expires.map(|expires| {
+            parse_http_date(expires)
+                .map(duration_until)
+                .unwrap_or(Duration::ZERO)
+        })
+    }?;
+
+    let mut policy = CachePolicy::new().with_stale_time(freshness);
+    if let Some(extra) = stale_while_revalidate {
+        policy = policy.with_expiration(freshness + Duration::from_secs(extra));
+    }
+    Some(policy)
+}
+
+/// Parse an HTTP date in IMF-fixdate format (`Sun, 06 Nov 1994 08:49:37 GMT`)
+///
+/// Returns the Unix timestamp in seconds, or `None` for any other format.
+pub fn parse_http_date(value: &str) -> Option<u64> {
+    let (_, rest) = value.trim().split_once(", ")?;
+    let parts: Vec<&str> = rest.split_whitespace().collect();
+    let [day, month, year, time, "GMT"] = parts.as_slice() else {
+        return None;
+    };
+
+    let day: u32 = day.parse().ok()?;
+    let month = match *month {
+        "Jan" => 1,
+        "Feb" => 2,
+        "Mar" => 3,
+        "Apr" => 4,
+        "May" => 5,
+        "Jun" => 6,
+        "Jul" => 7,
+        "Aug" => 8,
+        "Sep" => 9,
+        "Oct" => 10,
+        "Nov" => 11,
+        "Dec" => 12,
+        _ => return None,
+    };
+    let year: i64 = year.parse().ok()?;
+
+    let mut clock = time.split(':').map(|part| part.parse::<u64>().ok());
+    let (hour, minute, second) = (clock.next()??, clock.next()??, clock.next()??);
+    if clock.next().is_some() || day == 0 || day > 31 || hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+
+    let days = u64::try_from(days_from_civil(year, month, day)).ok()?;
+    Some(days * 86_400 + hour * 3600 + minute * 60 + second)
+}
+
+/// Convert a civil date into days since the Unix epoch
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let month = i64::from(month);
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Provider output wrapper that carries the cache policy of the HTTP response it came from
+///
+/// Providers generated with `#[provider]` whose output is `HttpCached<T>` apply the policy
+/// automatically. Equality only compares the data, so a refetch that returns the same body
+/// with new headers doesn't re-render consumers.
+#[derive(Debug, Clone)]
+pub struct HttpCached<T> {
+    /// The response data
+    pub data: T,
+    /// Policy derived from the response headers, if they carried freshness information
+    pub cache_policy: Option<CachePolicy>,
+    /// Unix timestamp (seconds) at which the response was received
+    pub received_at: u64,
+}
+
+impl<T> HttpCached<T> {
+    /// Wrap data without any caching information
+    pub fn new(data: T) -> Self {
+        Self {
+            data,
+            cache_policy: None,
+            received_at: now_secs(),
+        }
+    }
+
+    /// Wrap data together with the `Cache-Control` and `Expires` header values of its response
+    pub fn from_headers(data: T, cache_control: Option<&str>, expires: Option<&str>) -> Self {
+        Self {
+            cache_policy: cache_policy_from_headers(cache_control, expires),
+            ..Self::new(data)
+        }
+    }
+
+    /// Cache policy of the wrapped response, usable as a provider's `cache_policy` function
+    pub fn cache_policy(output: &Self) -> Option<CachePolicy> {
+        output.cache_policy
+    }
+
+    /// Unwrap the response data
+    pub fn into_inner(self) -> T {
+        self.data
+    }
+}
+
+impl<T: PartialEq> PartialEq for HttpCached<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
+    }
+}
+
+impl<T> Deref for HttpCached<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn max_age_and_stale_while_revalidate() {
+        let policy =
+            cache_policy_from_headers(Some("public, max-age=60, stale-while-revalidate=30"), None)
+                .unwrap();
+        assert_eq!(policy.stale_time, Some(Duration::from_secs(60)));
+        assert_eq!(policy.expiration, Some(Duration::from_secs(90)));
+    }
+
+    #[test]
+    fn no_cache_is_immediately_stale() {
+        let policy = cache_policy_from_headers(Some("no-cache"), None).unwrap();
+        assert_eq!(policy.stale_time, Some(Duration::ZERO));
+        assert!(cache_policy_from_headers(Some("private"), None).is_none());
+    }
+
+    #[test]
+    fn expires_header() {
+        assert_eq!(
+            parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT"),
+            Some(784_111_777)
+        );
+        assert_eq!(parse_http_date("not a date"), None);
+
+        // Dates in the past, and invalid dates, mean the response is already stale
+        let policy = cache_policy_from_headers(None, Some("0")).unwrap();
+        assert_eq!(policy.stale_time, Some(Duration::ZERO));
+    }
+}
@@ -0,0 +1,509 @@
+//! # Infinite Providers
+//!
+//! This module provides paginated ("infinite") providers for feed-style UIs. An infinite
+//! provider is a regular [`Provider`] that takes a `(param, cursor)` pair and returns one
+//! [`Page`] of items together with the cursor of the page after it. [`use_infinite_provider`]
+//! accumulates the loaded pages in the cache and loads more on demand.
+//!
+//! Invalidating an infinite provider doesn't have to throw away the user's scroll position:
+//! [`PageInvalidation`] chooses between refetching only the first page, refetching every
+//! loaded page, or dropping the pages beyond a limit.
+//!
+//! With `#[provider(paginated)]` the function returns `(items, next_cursor)` instead of
+//! building the [`Page`] itself.
+//!
+//! The consuming component can also save [`ScrollMetadata`] next to the cached pages and read
+//! it back after navigating back, restoring the scroll position along with the data.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use dioxus_provider::{
+//!     infinite::{Page, PageInvalidation, ScrollMetadata, use_infinite_provider},
+//!     prelude::*,
+//! };
+//!
+//! #[provider]
+//! async fn fetch_feed(topic: String, cursor: Option<u32>) -> Result<Page<Post, u32>, String> {
+//!     let (posts, next) = api::feed(&topic, cursor.unwrap_or(0)).await?;
+//!     Ok(Page::new(posts, next))
+//! }
+//!
+//! #[component]
+//! fn Feed(topic: String) -> Element {
+//!     let feed = use_infinite_provider(fetch_feed(), topic);
+//!     let refresh = feed.clone();
+//!     let more = feed.clone();
+//!     let scroll = feed.clone();
+//!
+//!     // Restore the position saved before navigating away
+//!     let restored = feed.scroll_metadata().unwrap_or_default();
+//!
+//!     rsx! {
+//!         button { onclick: move |_| refresh.invalidate(PageInvalidation::FirstPage), "Refresh" }
+//!         div {
+//!             onmounted: move |e| scroll_to(e, restored.scroll_offset),
+//!             onscroll: move |e| scroll.save_scroll(ScrollMetadata {
+//!                 scroll_offset: scroll_top(&e),
+//!                 ..Default::default()
+//!             }),
+//!             if let State::Success(pages) = &*feed.state().read() {
+//!                 for post in pages.items() { PostCard { post: post.clone() } }
+//!             }
+//!         }
+//!         button { onclick: move |_| more.fetch_next_page(), "Load more" }
+//!     }
+//! }
+//! ```
+
+use dioxus::{core::ReactiveContext, prelude::*};
+
+use crate::{
+    cache::ProviderCache,
+    global::hook_runtime,
+    hooks::Provider,
+    refresh::RefreshRegistry,
+    state::State,
+    types::{ProviderOutputBounds, ProviderParamBounds},
+};
+
+/// One page of an infinite provider, with the cursor of the page after it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Page<T, C> {
+    /// The items on this page
+    pub items: Vec<T>,
+    /// Cursor for the next page, or `None` if this is the last page
+    pub next_cursor: Option<C>,
+}
+
+impl<T, C> Page<T, C> {
+    /// Create a page from its items and the cursor of the next page
+    pub fn new(items: Vec<T>, next_cursor: Option<C>) -> Self {
+        Self { items, next_cursor }
+    }
+}
+
+/// `#[provider(paginated)]` functions return `(items, next_cursor)`
+impl<T, C> From<(Vec<T>, Option<C>)> for Page<T, C> {
+    fn from((items, next_cursor): (Vec<T>, Option<C>)) -> Self {
+        Self::new(items, next_cursor)
+    }
+}
+
+/// The pages of an infinite provider loaded so far
+#[derive(Debug, Clone, PartialEq)]
+pub struct InfinitePages<T, C> {
+    pages: Vec<Page<T, C>>,
+}
+
+impl<T, C> InfinitePages<T, C> {
+    /// The loaded pages, in order
+    pub fn pages(&self) -> &[Page<T, C>] {
+        &self.pages
+    }
+
+    /// All loaded items across pages, in order
+    pub fn items(&self) -> impl Iterator<Item = &T> {
+        self.pages.iter().flat_map(|page| page.items.iter())
+    }
+
+    /// Cursor of the next page to load, if there is one
+    pub fn next_cursor(&self) -> Option<&C> {
+        self.pages.last().and_then(|page| page.next_cursor.as_ref())
+    }
+
+    /// Returns true if another page can be loaded
+    pub fn has_next_page(&self) -> bool {
+        self.next_cursor().is_some()
+    }
+}
+
+/// How to refetch an infinite provider when it is invalidated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PageInvalidation {
+    /// Refetch every loaded page in order, following the fresh cursors
+    #[default]
+    AllPages,
+    /// Refetch only the first page and keep the other loaded pages as they are
+    FirstPage,
+    /// Drop the pages beyond the first `n` and refetch the rest
+    KeepPages(usize),
+}
+
+/// Scroll position of an infinite list, stored next to its cached pages
+///
+/// Saving it doesn't re-render subscribers; read it back when the list mounts again.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScrollMetadata {
+    /// Id of the item at the top of the viewport, if the list anchors on items
+    pub anchor: Option<String>,
+    /// Scroll offset of the list in pixels
+    pub scroll_offset: f64,
+    /// Measured offsets of the loaded items, for virtualized lists
+    pub item_offsets: Vec<f64>,
+}
+
+/// Cache key under which the loaded pages of an infinite provider are stored
+pub fn infinite_cache_key<P, Param, C>(provider: &P, param: &Param) -> String
+where
+    P: Provider<(Param, Option<C>)>,
+    Param: ProviderParamBounds,
+    C: ProviderParamBounds,
+{
+    format!("{}_infinite", provider.id(&(param.clone(), None)))
+}
+
+/// Refetch pages according to an invalidation mode
+///
+/// Without `current` pages only the first page is fetched.
+async fn refetch_pages<P, Param, T, C>(
+    provider: &P,
+    param: &Param,
+    current: Option<&InfinitePages<T, C>>,
+    mode: PageInvalidation,
+) -> Result<InfinitePages<T, C>, P::Error>
+where
+    P: Provider<(Param, Option<C>), Output = Page<T, C>>,
+    Param: ProviderParamBounds,
+    T: ProviderOutputBounds,
+    C: ProviderParamBounds,
+{
+    let loaded = current.map_or(1, |current| current.pages.len().max(1));
+    let target = match mode {
+        PageInvalidation::AllPages => loaded,
+        PageInvalidation::FirstPage => 1,
+        PageInvalidation::KeepPages(limit) => loaded.min(limit.max(1)),
+    };
+
+    let mut pages = Vec::with_capacity(loaded);
+    let mut cursor = None;
+    for _ in 0..target {
+        let page = provider.run((param.clone(), cursor)).await?;
+        cursor = page.next_cursor.clone();
+        pages.push(page);
+        if cursor.is_none() {
+            break;
+        }
+    }
+
+    if mode == PageInvalidation::FirstPage
+        && let Some(current) = current
+    {
+        pages.extend(current.pages.iter().skip(1).cloned());
+    }
+
+    Ok(InfinitePages { pages })
+}
+
+/// Handle returned by [`use_infinite_provider`]
+pub struct InfiniteQuery<P, Param, T, C>
+where
+    P: Provider<(Param, Option<C>), Output = Page<T, C>>,
+    Param: ProviderParamBounds,
+    C: ProviderParamBounds,
+{
+    state: Signal<State<InfinitePages<T, C>, P::Error>>,
+    next_page_error: Signal<Option<P::Error>>,
+    fetching_next_page: Signal<bool>,
+    provider: P,
+    param: Param,
+    cache: ProviderCache,
+    refresh_registry: RefreshRegistry,
+}
+
+impl<P, Param, T, C> Clone for InfiniteQuery<P, Param, T, C>
+where
+    P: Provider<(Param, Option<C>), Output = Page<T, C>> + Clone,
+    Param: ProviderParamBounds,
+    C: ProviderParamBounds,
+{
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state,
+            next_page_error: self.next_page_error,
+            fetching_next_page: self.fetching_next_page,
+            provider: self.provider.clone(),
+            param: self.param.clone(),
+            cache: self.cache.clone(),
+            refresh_registry: self.refresh_registry.clone(),
+        }
+    }
+}
+
+impl<P, Param, T, C> InfiniteQuery<P, Param, T, C>
+where
+    P: Provider<(Param, Option<C>), Output = Page<T, C>> + Clone,
+    Param: ProviderParamBounds,
+    T: ProviderOutputBounds,
+    C: ProviderParamBounds,
+{
+    /// The loaded pages, or the loading / error state of the first page
+    pub fn state(&self) -> Signal<State<InfinitePages<T, C>, P::Error>> {
+        self.state
+    }
+
+    /// The error of the last failed `fetch_next_page`, cleared by the next attempt
+    pub fn next_page_error(&self) -> Signal<Option<P::Error>> {
+        self.next_page_error
+    }
+
+    /// Returns true if another page can be loaded
+    pub fn has_next_page(&self) -> bool {
+        matches!(&*self.state.read(), State::Success(pages) if pages.has_next_page())
+    }
+
+    /// Returns true while `fetch_next_page` is loading a page, subscribing the caller
+    ///
+    /// Unlike the first page's `State::Loading`, the loaded pages stay in `state` meanwhile.
+    pub fn is_fetching_next_page(&self) -> bool {
+        *self.fetching_next_page.read()
+    }
+
+    /// Returns true while a page is being fetched
+    pub fn is_fetching(&self) -> bool {
+        self.cache.is_request_pending(&self.cache_key())
+    }
+
+    fn cache_key(&self) -> String {
+        infinite_cache_key(&self.provider, &self.param)
+    }
+
+    fn scroll_key(&self) -> String {
+        format!("{}_scroll", self.cache_key())
+    }
+
+    /// Save the list's scroll position alongside the cached pages
+    pub fn save_scroll(&self, metadata: ScrollMetadata) {
+        self.cache.set(self.scroll_key(), metadata);
+    }
+
+    /// The scroll position saved with the cached pages, e.g. to restore after back-navigation
+    ///
+    /// Returns `None` if nothing was saved or the pages are no longer cached.
+    pub fn scroll_metadata(&self) -> Option<ScrollMetadata> {
+        let cache_key = self.cache_key();
+        self.cache
+            .get::<Result<InfinitePages<T, C>, P::Error>>(&cache_key)
+            .and_then(|_| self.cache.get::<ScrollMetadata>(&self.scroll_key()))
+    }
+
+    /// Load the page after the last loaded one, if there is one
+    pub fn fetch_next_page(&self) {
+        let cache_key = self.cache_key();
+        let Some(Ok(current)) = self
+            .cache
+            .get::<Result<InfinitePages<T, C>, P::Error>>(&cache_key)
+        else {
+            return;
+        };
+        let Some(cursor) = current.next_cursor().cloned() else {
+            return;
+        };
+        if !self.cache.mark_request_pending(&cache_key) {
+            return;
+        }
+
+        let query = self.clone();
+        let mut next_page_error = self.next_page_error;
+        let mut fetching_next_page = self.fetching_next_page;
+        next_page_error.set(None);
+        fetching_next_page.set(true);
+        spawn(async move {
+            let result = query
+                .provider
+                .run((query.param.clone(), Some(cursor)))
+                .await;
+            match result {
+                Ok(page) => {
+                    let mut pages = current;
+                    pages.pages.push(page);
+                    crate::debug_log!(
+                        "📄 [INFINITE] Loaded page {} for key: {}",
+                        pages.pages.len(),
+                        cache_key
+                    );
+                    query
+                        .cache
+                        .set::<Result<InfinitePages<T, C>, P::Error>>(cache_key.clone(), Ok(pages));
+                }
+                Err(error) => next_page_error.set(Some(error)),
+            }
+            fetching_next_page.set(false);
+            query.cache.mark_request_complete(&cache_key);
+            query.refresh_registry.trigger_refresh(&cache_key);
+        });
+    }
+
+    /// Refetch the loaded pages according to `mode`, keeping them visible meanwhile
+    ///
+    /// If the refetch fails while pages are loaded, the loaded pages are kept.
+    pub fn invalidate(&self, mode: PageInvalidation) {
+        let cache_key = self.cache_key();
+        if !self.cache.mark_request_pending(&cache_key) {
+            return;
+        }
+
+        let query = self.clone();
+        spawn(async move {
+            let current = query
+                .cache
+                .get::<Result<InfinitePages<T, C>, P::Error>>(&cache_key)
+                .and_then(Result::ok);
+            let result = refetch_pages(&query.provider, &query.param, current.as_ref(), mode).await;
+            if result.is_ok() || current.is_none() {
+                query.cache.set(cache_key.clone(), result);
+            } else {
+                crate::debug_log!(
+                    "⚠️ [INFINITE] Refetch failed, keeping loaded pages for key: {}",
+                    cache_key
+                );
+            }
+            query.cache.mark_request_complete(&cache_key);
+            query.refresh_registry.trigger_refresh(&cache_key);
+        });
+    }
+}
+
+/// Hook to load a paginated provider page by page
+///
+/// The provider receives `(param, cursor)`, starting with a `None` cursor, and returns a
+/// [`Page`]. Loaded pages are cached, so remounting the component restores every page
+/// that was loaded instead of starting over at the first one.
+pub fn use_infinite_provider<P, Param, T, C>(
+    provider: P,
+    param: Param,
+) -> InfiniteQuery<P, Param, T, C>
+where
+    P: Provider<(Param, Option<C>), Output = Page<T, C>> + Send + Clone,
+    Param: ProviderParamBounds,
+    T: ProviderOutputBounds,
+    C: ProviderParamBounds,
+{
+    let runtime_handles = hook_runtime().handles();
+    let cache = runtime_handles.cache;
+    let refresh_registry = runtime_handles.refresh_registry;
+
+    let mut state = use_signal(|| State::Loading {
+        task: spawn(async {}),
+    });
+    let next_page_error = use_signal(|| None);
+    let fetching_next_page = use_signal(|| false);
+
+    let query_provider = provider.clone();
+    let query_param = param.clone();
+    let cache_for_memo = cache.clone();
+    let refresh_for_memo = refresh_registry.clone();
+    let _pages_memo = use_memo(use_reactive!(|(provider, param)| {
+        let cache = cache_for_memo.clone();
+        let refresh_registry = refresh_for_memo.clone();
+        let cache_key = infinite_cache_key(&provider, &param);
+
+        if let Some(reactive_context) = ReactiveContext::current() {
+            refresh_registry.subscribe_to_refresh(&cache_key, reactive_context);
+        }
+        let _current_refresh_count = refresh_registry.get_refresh_count(&cache_key);
+
+        if let Some(result) = cache.get::<Result<InfinitePages<T, C>, P::Error>>(&cache_key) {
+            let next = match result {
+                Ok(pages) => State::Success(pages),
+                Err(error) => State::Error(error),
+            };
+            if *state.peek() != next {
+                state.set(next);
+            }
+            return;
+        }
+
+        if !cache.mark_request_pending(&cache_key) {
+            return;
+        }
+        let task = spawn(async move {
+            let result = refetch_pages(&provider, &param, None, PageInvalidation::AllPages).await;
+            cache.set(cache_key.clone(), result);
+            cache.mark_request_complete(&cache_key);
+            refresh_registry.trigger_refresh(&cache_key);
+        });
+        state.set(State::Loading { task });
+    }));
+
+    InfiniteQuery {
+        state,
+        next_page_error,
+        fetching_next_page,
+        provider: query_provider,
+        param: query_param,
+        cache,
+        refresh_registry,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{
+        Arc,
+        atomic::{AtomicU32, Ordering},
+    };
+
+    /// Pages of three numbers, ten pages in total; each fetch bumps the generation
+    #[derive(Clone)]
+    struct NumbersProvider {
+        generation: Arc<AtomicU32>,
+    }
+
+    impl PartialEq for NumbersProvider {
+        fn eq(&self, _other: &Self) -> bool {
+            true
+        }
+    }
+
+    impl Provider<((), Option<u32>)> for NumbersProvider {
+        type Output = Page<(u32, u32), u32>;
+        type Error = ();
+
+        async fn run(&self, (_, cursor): ((), Option<u32>)) -> Result<Self::Output, ()> {
+            let generation = self.generation.fetch_add(1, Ordering::SeqCst);
+            let page = cursor.unwrap_or(0);
+            let items = (page * 3..page * 3 + 3).map(|n| (n, generation)).collect();
+            Ok(Page::new(items, (page < 9).then_some(page + 1)))
+        }
+    }
+
+    fn refetch(
+        provider: &NumbersProvider,
+        current: Option<&InfinitePages<(u32, u32), u32>>,
+        mode: PageInvalidation,
+    ) -> InfinitePages<(u32, u32), u32> {
+        futures::executor::block_on(refetch_pages(provider, &(), current, mode)).unwrap()
+    }
+
+    #[test]
+    fn invalidation_modes_keep_scroll_position() {
+        let provider = NumbersProvider {
+            generation: Arc::new(AtomicU32::new(0)),
+        };
+        let mut pages = refetch(&provider, None, PageInvalidation::AllPages);
+        for cursor in 1..4 {
+            let page = futures::executor::block_on(provider.run(((), Some(cursor)))).unwrap();
+            pages.pages.push(page);
+        }
+        assert_eq!(pages.pages().len(), 4);
+
+        // Only the first page is refetched, the other three are kept
+        let first = refetch(&provider, Some(&pages), PageInvalidation::FirstPage);
+        assert_eq!(first.pages().len(), 4);
+        assert_eq!(first.pages()[0].items[0], (0, 4));
+        assert_eq!(first.pages()[1], pages.pages()[1]);
+
+        // Every loaded page is refetched in order
+        let all = refetch(&provider, Some(&pages), PageInvalidation::AllPages);
+        assert_eq!(all.pages().len(), 4);
+        assert!(all.items().all(|(_, generation)| *generation >= 5));
+        assert_eq!(all.next_cursor(), Some(&4));
+
+        // Pages beyond the limit are dropped
+        let kept = refetch(&provider, Some(&pages), PageInvalidation::KeepPages(2));
+        assert_eq!(kept.pages().len(), 2);
+        assert_eq!(kept.items().count(), 6);
+    }
+}
@@ -0,0 +1,621 @@
+//! # Mutation Journal
+//!
+//! This module provides an optional write-ahead journal for mutations. When a mutation is
+//! accepted, its input is recorded in a [`JournalStore`] before the request is sent and removed
+//! again once the mutation succeeds. If the application is killed mid-sync, the records that are
+//! still present on the next launch describe exactly the mutations whose outcome is unknown, so
+//! they can be replayed or reconciled.
+//!
+//! Give the runtime a journal with `ProviderConfig::with_mutation_journal`, and mutation
+//! hooks journal every mutation marked `#[mutation(journal)]`. [`Journaled`] journals a
+//! single mutation into a journal of its own instead.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use dioxus_provider::{journal::{FileJournalStore, MutationJournal}, prelude::*};
+//!
+//! #[mutation(journal, invalidates = [fetch_todos])]
+//! async fn add_todo(title: String) -> Result<Todo, ApiError> {
+//!     api::add_todo(title).await
+//! }
+//!
+//! let journal = MutationJournal::new(FileJournalStore::new("mutations.journal.json"));
+//! ProviderConfig::new().with_mutation_journal(journal.clone()).init()?;
+//!
+//! // On startup, replay anything left over from a crash
+//! let outcomes = journal.replay(&add_todo()).await;
+//!
+//! // In components, journaled mutations are used like any other
+//! let (state, mutate) = use_mutation(add_todo());
+//! ```
+
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::{
+    future::Future,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use crate::{
+    errors::ProviderError,
+    mutation::{Mutation, OptimisticUpdates},
+    platform::now_secs,
+    retry::{self, RetryPolicy},
+};
+
+/// Identifier assigned to a journal record
+pub type JournalId = u64;
+
+/// A single accepted-but-unconfirmed mutation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalRecord {
+    /// Unique identifier of the record within the journal
+    pub id: JournalId,
+    /// The `Mutation::id()` of the mutation that was started
+    pub mutation_id: String,
+    /// The serialized mutation input
+    pub input: serde_json::Value,
+    /// Unix timestamp (seconds) at which the mutation was accepted
+    pub recorded_at: u64,
+}
+
+/// Storage backend for the mutation journal
+///
+/// Implementations must make `append` and `remove` durable before returning, otherwise
+/// the journal cannot guarantee that a crash is recoverable.
+pub trait JournalStore: Send + Sync + 'static {
+    /// Load every record currently stored in the journal
+    fn load(&self) -> Result<Vec<JournalRecord>, ProviderError>;
+
+    /// Durably append a record to the journal
+    fn append(&self, record: &JournalRecord) -> Result<(), ProviderError>;
+
+    /// Remove a record from the journal once its mutation has settled
+    fn remove(&self, id: JournalId) -> Result<(), ProviderError>;
+}
+
+/// In-memory journal store
+///
+/// Records do not survive a restart, which makes this store mainly useful for tests and
+/// for platforms without a persistence layer.
+#[derive(Default)]
+pub struct MemoryJournalStore {
+    records: Mutex<Vec<JournalRecord>>,
+}
+
+impl MemoryJournalStore {
+    /// Create an empty in-memory journal store
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl JournalStore for MemoryJournalStore {
+    fn load(&self) -> Result<Vec<JournalRecord>, ProviderError> {
+        self.records
+            .lock()
+            .map(|records| records.clone())
+            .map_err(|_| ProviderError::Cache("Failed to acquire journal lock".to_string()))
+    }
+
+    fn append(&self, record: &JournalRecord) -> Result<(), ProviderError> {
+        self.records
+            .lock()
+            .map(|mut records| records.push(record.clone()))
+            .map_err(|_| ProviderError::Cache("Failed to acquire journal lock".to_string()))
+    }
+
+    fn remove(&self, id: JournalId) -> Result<(), ProviderError> {
+        self.records
+            .lock()
+            .map(|mut records| records.retain(|record| record.id != id))
+            .map_err(|_| ProviderError::Cache("Failed to acquire journal lock".to_string()))
+    }
+}
+
+/// File-backed journal store (native targets)
+///
+/// The journal is kept as a JSON array and rewritten atomically (write and sync a temporary
+/// file, then rename it) on every change.
+#[cfg(not(target_family = "wasm"))]
+pub struct FileJournalStore {
+    path: std::path::PathBuf,
+    lock: Mutex<()>,
+}
+
+#[cfg(not(target_family = "wasm"))]
+impl FileJournalStore {
+    /// Create a journal store backed by the file at `path`
+    ///
+    /// The file is created lazily on the first append.
+    pub fn new(path: impl Into<std::path::PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    fn read_records(&self) -> Result<Vec<JournalRecord>, ProviderError> {
+        match std::fs::read(&self.path) {
+            Ok(bytes) if bytes.is_empty() => Ok(Vec::new()),
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .map_err(|e| ProviderError::DataParsing(format!("Corrupt mutation journal: {e}"))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(ProviderError::Cache(format!(
+                "Failed to read mutation journal: {e}"
+            ))),
+        }
+    }
+
+    fn write_records(&self, records: &[JournalRecord]) -> Result<(), ProviderError> {
+        let bytes = serde_json::to_vec(records)
+            .map_err(|e| ProviderError::DataParsing(format!("Failed to encode journal: {e}")))?;
+        let tmp_path = self.path.with_extension("tmp");
+        Self::replace_durably(&tmp_path, &self.path, &bytes)
+            .map_err(|e| ProviderError::Cache(format!("Failed to write mutation journal: {e}")))
+    }
+
+    /// Write `bytes` to `tmp_path`, sync them, and rename the file over `path`
+    ///
+    /// The parent directory is synced too, so the rename itself survives a crash.
+    fn replace_durably(
+        tmp_path: &std::path::Path,
+        path: &std::path::Path,
+        bytes: &[u8],
+    ) -> std::io::Result<()> {
+        use std::io::Write;
+
+        let mut file = std::fs::File::create(tmp_path)?;
+        file.write_all(bytes)?;
+        file.sync_all()?;
+        std::fs::rename(tmp_path, path)?;
+        #[cfg(unix)]
+        {
+            let parent = match path.parent() {
+                Some(parent) if !parent.as_os_str().is_empty() => parent,
+                _ => std::path::Path::new("."),
+            };
+            std::fs::File::open(parent)?.sync_all()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+impl JournalStore for FileJournalStore {
+    fn load(&self) -> Result<Vec<JournalRecord>, ProviderError> {
+        let _guard = self
+            .lock
+            .lock()
+            .map_err(|_| ProviderError::Cache("Failed to acquire journal lock".to_string()))?;
+        self.read_records()
+    }
+
+    fn append(&self, record: &JournalRecord) -> Result<(), ProviderError> {
+        let _guard = self
+            .lock
+            .lock()
+            .map_err(|_| ProviderError::Cache("Failed to acquire journal lock".to_string()))?;
+        let mut records = self.read_records()?;
+        records.push(record.clone());
+        self.write_records(&records)
+    }
+
+    fn remove(&self, id: JournalId) -> Result<(), ProviderError> {
+        let _guard = self
+            .lock
+            .lock()
+            .map_err(|_| ProviderError::Cache("Failed to acquire journal lock".to_string()))?;
+        let mut records = self.read_records()?;
+        records.retain(|record| record.id != id);
+        self.write_records(&records)
+    }
+}
+
+/// How a mutation's inputs are written to a journal
+pub struct Journaling<Input> {
+    pub(crate) encode: fn(&Input) -> Option<serde_json::Value>,
+}
+
+impl<Input> Journaling<Input> {
+    /// Journal with custom encoding, where None means the input can't be recorded
+    pub fn new(encode: fn(&Input) -> Option<serde_json::Value>) -> Self {
+        Self { encode }
+    }
+
+    /// Journal as JSON, as `#[mutation(journal)]` does
+    pub fn json() -> Self
+    where
+        Input: Serialize,
+    {
+        Self::new(|input| serde_json::to_value(input).ok())
+    }
+}
+
+impl<Input> Clone for Journaling<Input> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Input> Copy for Journaling<Input> {}
+
+/// Handle to a mutation journal
+///
+/// Cloning the handle shares the underlying store.
+#[derive(Clone)]
+pub struct MutationJournal {
+    store: Arc<dyn JournalStore>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl std::fmt::Debug for MutationJournal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MutationJournal")
+            .field("next_id", &self.next_id.load(Ordering::SeqCst))
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for MutationJournal {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.store, &other.store)
+    }
+}
+
+impl MutationJournal {
+    /// Create a journal on top of the given store
+    ///
+    /// Record ids continue after the highest id already present in the store.
+    pub fn new(store: impl JournalStore) -> Self {
+        let next_id = store
+            .load()
+            .ok()
+            .and_then(|records| records.iter().map(|record| record.id).max())
+            .map(|max| max + 1)
+            .unwrap_or(1);
+
+        Self {
+            store: Arc::new(store),
+            next_id: Arc::new(AtomicU64::new(next_id)),
+        }
+    }
+
+    /// Record that a mutation has been accepted and is about to be sent
+    pub fn record<Input: Serialize>(
+        &self,
+        mutation_id: &str,
+        input: &Input,
+    ) -> Result<JournalId, ProviderError> {
+        let input = serde_json::to_value(input).map_err(|e| {
+            ProviderError::DataParsing(format!("Failed to encode mutation input: {e}"))
+        })?;
+        self.append(mutation_id, input)
+    }
+
+    /// Record an input encoded with `journaling`, logging instead of failing the mutation
+    pub(crate) fn try_record<Input>(
+        &self,
+        mutation_id: &str,
+        journaling: Journaling<Input>,
+        input: &Input,
+    ) -> Option<JournalId> {
+        (journaling.encode)(input)
+            .ok_or_else(|| {
+                ProviderError::DataParsing("Failed to encode mutation input".to_string())
+            })
+            .and_then(|input| self.append(mutation_id, input))
+            .inspect_err(|_error| {
+                crate::debug_log!(
+                    "❌ [JOURNAL] Failed to record mutation {}: {}",
+                    mutation_id,
+                    _error
+                );
+            })
+            .ok()
+    }
+
+    fn append(
+        &self,
+        mutation_id: &str,
+        input: serde_json::Value,
+    ) -> Result<JournalId, ProviderError> {
+        let record = JournalRecord {
+            id: self.next_id.fetch_add(1, Ordering::SeqCst),
+            mutation_id: mutation_id.to_string(),
+            input,
+            recorded_at: now_secs(),
+        };
+        self.store.append(&record)?;
+        crate::debug_log!(
+            "📝 [JOURNAL] Recorded mutation {} as entry {}",
+            mutation_id,
+            record.id
+        );
+        Ok(record.id)
+    }
+
+    /// Mark a journaled mutation as settled, removing it from the journal
+    pub fn acknowledge(&self, id: JournalId) -> Result<(), ProviderError> {
+        self.store.remove(id)?;
+        crate::debug_log!("📝 [JOURNAL] Acknowledged entry {}", id);
+        Ok(())
+    }
+
+    /// Acknowledge a settled mutation, logging instead of failing if the store can't remove it
+    ///
+    /// An entry that fails to be removed stays in the journal, so a later replay runs the
+    /// mutation again. Mutations that may be replayed should therefore be idempotent.
+    pub(crate) fn try_acknowledge(&self, id: JournalId) {
+        if let Err(_error) = self.acknowledge(id) {
+            crate::debug_log!(
+                "❌ [JOURNAL] Failed to acknowledge entry {}, it may be replayed: {}",
+                id,
+                _error
+            );
+        }
+    }
+
+    /// All records whose mutations have not been confirmed yet
+    pub fn pending(&self) -> Result<Vec<JournalRecord>, ProviderError> {
+        self.store.load()
+    }
+
+    /// Pending inputs recorded for the given mutation, decoded back into their input type
+    pub fn pending_inputs<M, Input>(
+        &self,
+        mutation: &M,
+    ) -> Result<Vec<(JournalId, Input)>, ProviderError>
+    where
+        M: Mutation<Input>,
+        Input: Clone + PartialEq + DeserializeOwned + 'static,
+    {
+        let mutation_id = mutation.id();
+        self.pending()?
+            .into_iter()
+            .filter(|record| record.mutation_id == mutation_id)
+            .map(|record| {
+                serde_json::from_value(record.input)
+                    .map(|input| (record.id, input))
+                    .map_err(|e| {
+                        ProviderError::DataParsing(format!(
+                            "Failed to decode journaled input for {mutation_id}: {e}"
+                        ))
+                    })
+            })
+            .collect()
+    }
+
+    /// Re-run every pending mutation of this type, acknowledging each one that succeeds
+    ///
+    /// Call this on startup, after `init()`, to recover mutations interrupted by a crash.
+    /// Records whose mutation fails again, or that fail to decode, are left in the journal
+    /// for a later replay or manual reconciliation with [`acknowledge`](Self::acknowledge).
+    /// A record whose acknowledgement fails to reach the store is left as well, so its
+    /// mutation is run again by the next replay.
+    pub async fn replay<M, Input>(&self, mutation: &M) -> Vec<Result<M::Output, M::Error>>
+    where
+        M: Mutation<Input>,
+        Input: Clone + PartialEq + DeserializeOwned + 'static,
+    {
+        let pending = match self.pending_inputs(mutation) {
+            Ok(pending) => pending,
+            Err(_error) => {
+                crate::debug_log!("❌ [JOURNAL] Failed to load pending mutations: {}", _error);
+                return Vec::new();
+            }
+        };
+
+        let mut outcomes = Vec::with_capacity(pending.len());
+        for (id, input) in pending {
+            crate::debug_log!("🔁 [JOURNAL] Replaying entry {} for {}", id, mutation.id());
+            let outcome = mutation.mutate(input).await;
+            if outcome.is_ok() {
+                self.try_acknowledge(id);
+            }
+            outcomes.push(outcome);
+        }
+        outcomes
+    }
+}
+
+/// Mutation adapter that journals every accepted input until the mutation succeeds
+///
+/// `Journaled` delegates everything to the wrapped mutation, so it can be passed to
+/// `use_mutation` in place of the original. An input whose mutation fails, after any
+/// retries, stays in the journal as unconfirmed work for [`MutationJournal::replay`], as
+/// does a successful one whose acknowledgement can't be written. The runtime's journal
+/// doesn't record it again.
+#[derive(Clone, PartialEq)]
+pub struct Journaled<M> {
+    inner: M,
+    journal: MutationJournal,
+}
+
+impl<M> Journaled<M> {
+    /// Wrap a mutation so that its inputs are recorded in `journal`
+    pub fn new(inner: M, journal: MutationJournal) -> Self {
+        Self { inner, journal }
+    }
+
+    /// Access the wrapped mutation
+    pub fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    /// Access the journal this mutation writes to
+    pub fn journal(&self) -> &MutationJournal {
+        &self.journal
+    }
+}
+
+impl<M, Input> Mutation<Input> for Journaled<M>
+where
+    M: Mutation<Input>,
+    Input: Clone + PartialEq + Serialize + 'static,
+{
+    type Output = M::Output;
+    type Error = M::Error;
+
+    fn mutate(&self, input: Input) -> impl Future<Output = Result<Self::Output, Self::Error>> {
+        self.mutate_with_current(input, None)
+    }
+
+    fn mutate_with_current(
+        &self,
+        input: Input,
+        current_data: Option<&Result<Self::Output, Self::Error>>,
+    ) -> impl Future<Output = Result<Self::Output, Self::Error>> {
+        let entry = self
+            .journal
+            .try_record(&self.inner.id(), Journaling::json(), &input);
+        async move {
+            // Retried here rather than by the caller, so an input is recorded only once
+            let result = retry::mutate(&self.inner, input, current_data).await;
+            if let Some(id) = entry
+                && result.is_ok()
+            {
+                self.journal.try_acknowledge(id);
+            }
+            result
+        }
+    }
+
+    fn id(&self) -> String {
+        self.inner.id()
+    }
+
+    fn invalidates(&self) -> Vec<String> {
+        self.inner.invalidates()
+    }
+
+    fn has_optimistic(&self) -> bool {
+        self.inner.has_optimistic()
+    }
+
+    fn retry_policy(&self) -> Option<RetryPolicy> {
+        // The wrapped mutation's policy is applied in `mutate_with_current`
+        None
+    }
+
+    fn optimistic_updates(&self, input: &Input) -> OptimisticUpdates<Self::Output, Self::Error> {
+        self.inner.optimistic_updates(input)
+    }
+
+    fn optimistic_updates_with_current(
+        &self,
+        input: &Input,
+        current_data: Option<&Result<Self::Output, Self::Error>>,
+    ) -> OptimisticUpdates<Self::Output, Self::Error> {
+        self.inner
+            .optimistic_updates_with_current(input, current_data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, PartialEq)]
+    struct AddItem;
+
+    impl Mutation<String> for AddItem {
+        type Output = usize;
+        type Error = String;
+
+        async fn mutate(&self, input: String) -> Result<usize, String> {
+            Ok(input.len())
+        }
+    }
+
+    static REJECT_ATTEMPTS: AtomicU64 = AtomicU64::new(0);
+
+    #[derive(Clone, PartialEq)]
+    struct RejectItem;
+
+    impl Mutation<String> for RejectItem {
+        type Output = usize;
+        type Error = String;
+
+        async fn mutate(&self, _input: String) -> Result<usize, String> {
+            REJECT_ATTEMPTS.fetch_add(1, Ordering::SeqCst);
+            Err("offline".to_string())
+        }
+
+        fn retry_policy(&self) -> Option<RetryPolicy> {
+            Some(RetryPolicy::new(2).with_delay(std::time::Duration::ZERO))
+        }
+    }
+
+    #[test]
+    fn records_until_acknowledged() {
+        let journal = MutationJournal::new(MemoryJournalStore::new());
+        let id = journal.record("add", &"milk".to_string()).unwrap();
+        assert_eq!(journal.pending().unwrap().len(), 1);
+
+        journal.acknowledge(id).unwrap();
+        assert!(journal.pending().unwrap().is_empty());
+    }
+
+    #[test]
+    fn replays_pending_inputs() {
+        let journal = MutationJournal::new(MemoryJournalStore::new());
+        journal.record(&AddItem.id(), &"eggs".to_string()).unwrap();
+        journal.record("other", &1u32).unwrap();
+
+        let pending = journal.pending_inputs(&AddItem).unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].1, "eggs");
+
+        let outcomes = futures::executor::block_on(journal.replay(&AddItem));
+        assert_eq!(outcomes, vec![Ok(4)]);
+        assert_eq!(journal.pending().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn failed_mutations_stay_journaled() {
+        let journal = MutationJournal::new(MemoryJournalStore::new());
+        let reject = Journaled::new(RejectItem, journal.clone());
+
+        assert_eq!(
+            reject.mutate("milk".to_string()).await,
+            Err("offline".to_string())
+        );
+        assert_eq!(
+            REJECT_ATTEMPTS.load(Ordering::SeqCst),
+            3,
+            "retried by the adapter"
+        );
+        assert_eq!(journal.pending().unwrap().len(), 1, "one record per input");
+
+        let outcomes = journal.replay(reject.inner()).await;
+        assert_eq!(outcomes, vec![Err("offline".to_string())]);
+        assert_eq!(journal.pending().unwrap().len(), 1);
+
+        let add = Journaled::new(AddItem, journal.clone());
+        assert_eq!(add.mutate("eggs".to_string()).await, Ok(4));
+        assert_eq!(journal.pending().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn file_store_survives_reopen() {
+        let path = std::env::temp_dir().join(format!(
+            "dioxus-provider-journal-{}.json",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+
+        let journal = MutationJournal::new(FileJournalStore::new(&path));
+        let first = journal.record("add", &"bread".to_string()).unwrap();
+
+        let reopened = MutationJournal::new(FileJournalStore::new(&path));
+        assert_eq!(reopened.pending().unwrap()[0].id, first);
+        let second = reopened.record("add", &"jam".to_string()).unwrap();
+        assert!(second > first);
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,146 @@
+//! # Cache Key Hashing
+//!
+//! Providers without a [`QueryKey`](crate::query_key::QueryKey) are cached under a hash of
+//! their type and parameters. The default uses the standard library's `DefaultHasher`, whose
+//! output is only guaranteed within a single build: a toolchain upgrade can change every
+//! key, which orphans anything persisted under the old ones. Choose a [`KeyHasher`] with a
+//! fixed algorithm to keep keys stable across builds.
+//!
+//! The stable hashers identify types by their names instead of `TypeId`s, which change
+//! between builds. Renaming or moving a provider or parameter type therefore changes its keys.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use dioxus_provider::{global::ProviderConfig, key_hash::KeyHasher};
+//!
+//! ProviderConfig::new()
+//!     .with_key_hasher(KeyHasher::Xxh3)
+//!     .init()
+//!     .unwrap();
+//! ```
+
+use std::{
+    any::{TypeId, type_name},
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    sync::OnceLock,
+};
+
+use siphasher::sip::SipHasher13;
+use xxhash_rust::xxh3::Xxh3;
+
+/// Fixed SipHash keys, so hashes don't depend on per-process random state
+const SIP_KEYS: (u64, u64) = (0x6469_6f78_7573_2d70, 0x726f_7669_6465_7221);
+
+/// The algorithm used to hash provider cache keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeyHasher {
+    /// `std`'s `DefaultHasher`: fast, but keys may change with the Rust version
+    #[default]
+    Std,
+    /// SipHash-1-3 with fixed keys, stable across builds
+    SipHash13,
+    /// XXH3 (64-bit), stable across builds
+    Xxh3,
+}
+
+impl KeyHasher {
+    /// Returns true if this hasher produces the same keys across builds and toolchains
+    pub fn is_stable(self) -> bool {
+        !matches!(self, KeyHasher::Std)
+    }
+
+    /// Hash a parameter together with the types that qualify it
+    pub fn hash_param<Param: Hash + ?Sized>(self, tags: &[TypeTag], param: &Param) -> u64 {
+        match self {
+            KeyHasher::Std => hash_with(DefaultHasher::new(), false, tags, param),
+            KeyHasher::SipHash13 => hash_with(
+                SipHasher13::new_with_keys(SIP_KEYS.0, SIP_KEYS.1),
+                true,
+                tags,
+                param,
+            ),
+            KeyHasher::Xxh3 => hash_with(Xxh3::new(), true, tags, param),
+        }
+    }
+}
+
+/// A type that is part of a hashed key, such as the provider or its parameter type
+#[derive(Debug, Clone, Copy)]
+pub struct TypeTag {
+    id: TypeId,
+    name: &'static str,
+}
+
+impl TypeTag {
+    /// Tag the type `T`
+    pub fn of<T: ?Sized + 'static>() -> Self {
+        Self {
+            id: TypeId::of::<T>(),
+            name: type_name::<T>(),
+        }
+    }
+}
+
+fn hash_with<H: Hasher, Param: Hash + ?Sized>(
+    mut hasher: H,
+    stable: bool,
+    tags: &[TypeTag],
+    param: &Param,
+) -> u64 {
+    for tag in tags {
+        if stable {
+            tag.name.hash(&mut hasher);
+        } else {
+            tag.id.hash(&mut hasher);
+        }
+    }
+    param.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// The hasher installed with `ProviderConfig::with_key_hasher`, if any
+static KEY_HASHER: OnceLock<KeyHasher> = OnceLock::new();
+
+/// Install the key hasher used for the rest of the program
+///
+/// Returns false if a different hasher was already installed; the first one stays in place.
+pub fn set_key_hasher(hasher: KeyHasher) -> bool {
+    *KEY_HASHER.get_or_init(|| hasher) == hasher
+}
+
+/// The installed key hasher, or [`KeyHasher::Std`] if none was installed
+pub fn key_hasher() -> KeyHasher {
+    KEY_HASHER.get().copied().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stable_hashers_produce_fixed_keys() {
+        let tags = [TypeTag::of::<u32>()];
+
+        // Changing these values breaks every persisted cache, so they are pinned
+        assert_eq!(
+            KeyHasher::SipHash13.hash_param(&tags, &7u32),
+            0x8447_f52a_6ede_e123
+        );
+        assert_eq!(
+            KeyHasher::Xxh3.hash_param(&tags, &7u32),
+            0xdecb_756a_2832_e04e
+        );
+        assert_ne!(
+            KeyHasher::Xxh3.hash_param(&tags, &7u32),
+            KeyHasher::Xxh3.hash_param(&tags, &8u32)
+        );
+        assert_ne!(
+            KeyHasher::Xxh3.hash_param(&tags, &7u32),
+            KeyHasher::Xxh3.hash_param(&[TypeTag::of::<u64>()], &7u32)
+        );
+        assert!(KeyHasher::Xxh3.is_stable());
+        assert!(!KeyHasher::Std.is_stable());
+    }
+}
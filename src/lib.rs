@@ -1,38 +1,102 @@
 #![doc = include_str!("../README.md")]
 
 // Core modules
+pub mod bytes;
 pub mod cache;
+pub mod cancel;
+pub mod chaos;
+pub mod demo;
+pub mod diff;
+pub mod dynamic;
+pub mod entity;
 pub mod errors;
+pub mod eviction;
 pub mod global;
+pub mod history;
 pub mod hooks;
+pub mod http;
+pub mod infinite;
 pub mod injection;
+pub mod journal;
+pub mod key_hash;
 mod log_utils;
 pub mod mutation;
+#[cfg(feature = "mutation-trace")]
+pub mod mutation_trace;
 pub mod param_utils;
+pub mod pause;
+pub mod persist;
 pub mod platform;
+pub mod prefetch;
+pub mod query_key;
+pub mod record;
 pub mod refresh;
+pub mod resource;
+pub mod retry;
+pub mod route;
 mod runtime;
+pub mod schedule;
+pub mod scope;
+pub mod seed;
+pub mod served;
+pub mod settings;
+pub mod ssr;
+pub mod startup;
 mod state;
+pub mod storage;
+pub mod stream;
+pub mod sync;
+pub mod testing;
+pub mod timeout;
 pub mod types;
+pub mod warm;
 
 // Re-export commonly used items at crate root for convenience
 pub use global::ProviderConfig;
 pub use global::init;
+pub use runtime::ProviderRuntime;
 
 pub mod prelude {
     //! The prelude exports all the most common types and functions for using dioxus-provider.
 
     // The main provider trait and the macro
-    pub use crate::hooks::Provider;
-    pub use dioxus_provider_macros::{mutation, provider};
+    pub use crate::hooks::{FnProvider, Provider, ProviderExt};
+    pub use dioxus_provider_macros::{mutation, provider, stream_provider};
 
     // The core hook for using providers
-    pub use crate::hooks::use_provider;
+    pub use crate::hooks::{
+        ProviderHandle, ProviderStatus, use_chained_provider, use_provider, use_provider_by_key,
+        use_provider_in, use_provider_map, use_provider_readonly, use_provider_select,
+        use_provider_suspense,
+    };
+
+    // Per-result cache expiration
+    pub use crate::cache::WithTtl;
+
+    // Normalized entities
+    pub use crate::entity::{Entity, use_entity};
 
     // Hooks for manual cache management
     pub use crate::hooks::use_clear_provider_cache;
+    pub use crate::hooks::use_invalidate_prefix;
     pub use crate::hooks::use_invalidate_provider;
     pub use crate::hooks::use_provider_cache;
+    pub use crate::hooks::use_update_provider_cache;
+
+    // Editable drafts committed with a mutation
+    pub use crate::hooks::use_provider_editable;
+
+    // Streaming providers
+    pub use crate::stream::{StreamProvider, use_stream_provider};
+
+    // Pausing background refreshes
+    pub use crate::pause::{PauseHandle, use_pause_provider};
+
+    // Prefetching
+    pub use crate::prefetch::use_prefetch_on_intent;
+
+    // Cancellation of fetches nothing needs anymore
+    pub use crate::cancel::{CancellationToken, ProviderContext};
 
     // The async state enum, needed for matching
     pub use crate::state::{AsyncState, State};
@@ -40,19 +104,29 @@ pub mod prelude {
     // Global initialization
     pub use crate::global::{ProviderConfig, init};
 
+    // Scoped runtimes
+    pub use crate::scope::ProviderScope;
+
+    // Background sync
+    pub use crate::sync::use_sync_status;
+
+    // Connectivity
+    pub use crate::platform::network::use_network_status;
+
     // Dependency Injection
     pub use crate::injection::{clear_dependencies, has_dependency, inject, register_dependency};
 
     // Mutation system - Manual Implementation Pattern
     pub use crate::mutation::{
-        Mutation, MutationContext, MutationState, provider_cache_key, provider_cache_key_simple,
-        use_mutation, use_optimistic_mutation,
+        Mutation, MutationContext, MutationState, MutationSuspenseExt, PendingBoundary,
+        provider_cache_key, provider_cache_key_simple, use_mutation, use_mutation_silent,
+        use_optimistic_mutation,
     };
 
     // Error types
     pub use crate::errors::{
         ApiError, ApiResult, DatabaseError, DatabaseResult, ProviderError, ProviderResult,
-        UserError, UserResult,
+        TimeoutError, UserError, UserResult,
     };
 
     // Parameter utilities for custom types
@@ -17,12 +17,25 @@
 //! dioxus-provider = { version = "0.1", features = ["plain-logs"] }
 //! ```
 
-/// Internal debug logging macro that respects the tracing feature flag
+/// Internal debug logging macro that respects the tracing feature flag and the runtime's
+/// logging setting
 #[macro_export]
 macro_rules! debug_log {
     ($($arg:tt)*) => {
         #[cfg(feature = "tracing")]
-        tracing::debug!($($arg)*);
+        if $crate::settings::logging_enabled() {
+            tracing::debug!($($arg)*);
+        }
+    };
+}
+
+/// Internal macro recording a mutation step when the `mutation-trace` feature is enabled
+#[doc(hidden)]
+#[macro_export]
+macro_rules! trace_mutation {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "mutation-trace")]
+        $crate::mutation_trace::record($($arg)*);
     };
 }
 
@@ -22,7 +22,10 @@ use std::{
 };
 
 use crate::{
-    global::get_global_runtime_handles, hooks::Provider, runtime::ProviderRuntimeHandles,
+    global::hook_runtime,
+    hooks::{NormalizeFn, Provider, RenderError, suspend_until},
+    journal::Journaling,
+    retry::{self, RetryPolicy},
     types::ProviderParamBounds,
 };
 
@@ -120,10 +123,7 @@ impl<'a, Data, Error> MutationContext<'a, Data, Error> {
 
     /// Returns a reference to the current successful cached data, if available.
     pub fn current_success(&self) -> Option<&Data> {
-        match self.current? {
-            Ok(data) => Some(data),
-            Err(_) => None,
-        }
+        self.current?.as_ref().ok()
     }
 
     /// Clones the current successful cached data, if available.
@@ -213,14 +213,6 @@ impl<'a, Data, Error> MutationContext<'a, Data, Error> {
     }
 }
 
-fn runtime_handles_or_panic() -> ProviderRuntimeHandles {
-    get_global_runtime_handles().unwrap_or_else(|_| {
-        panic!(
-            "Global providers not initialized. Call dioxus_provider::init() before using mutations."
-        )
-    })
-}
-
 /// Trait for defining mutations - operations that modify data
 ///
 /// Mutations are similar to providers but are designed for data modification operations.
@@ -292,14 +284,36 @@ where
         false
     }
 
+    /// Get a function that normalizes the mutation result into the entity store (None means disabled)
+    ///
+    /// When set, entities returned by a successful mutation replace their stored versions,
+    /// so components using `use_entity` update without refetching any provider.
+    fn normalize(&self) -> Option<NormalizeFn<Self::Output>> {
+        None
+    }
+
+    /// Get the retry policy for failed mutations (None means no retries)
+    ///
+    /// Failed attempts are retried before the error is reported and optimistic updates are
+    /// rolled back, so a flaky connection doesn't revert the user's action right away.
+    fn retry_policy(&self) -> Option<RetryPolicy> {
+        None
+    }
+
+    /// Get how inputs are recorded in the runtime's mutation journal (None means they aren't)
+    ///
+    /// When the runtime has a journal (see `ProviderConfig::with_mutation_journal`), mutation
+    /// hooks record every input before running it and acknowledge it once it succeeds.
+    /// `#[mutation(journal)]` records inputs as JSON.
+    fn journaling(&self) -> Option<Journaling<Input>> {
+        None
+    }
+
     /// Provide optimistic cache updates for immediate UI feedback
     /// Returns a list of (cache_key, optimistic_result) pairs to update the cache with
     /// This allows the UI to update immediately with the expected result
     /// The Result should contain the optimistic success value
-    fn optimistic_updates(
-        &self,
-        _input: &Input,
-    ) -> Vec<(String, Result<Self::Output, Self::Error>)> {
+    fn optimistic_updates(&self, _input: &Input) -> OptimisticUpdates<Self::Output, Self::Error> {
         Vec::new()
     }
 
@@ -316,12 +330,15 @@ where
         &self,
         _input: &Input,
         _current_data: Option<&Result<Self::Output, Self::Error>>,
-    ) -> Vec<(String, Result<Self::Output, Self::Error>)> {
+    ) -> OptimisticUpdates<Self::Output, Self::Error> {
         // Fallback to the simple method if not overridden
         self.optimistic_updates(_input)
     }
 }
 
+/// Optimistic cache updates as `(cache_key, optimistic_result)` pairs
+pub type OptimisticUpdates<T, E> = Vec<(String, Result<T, E>)>;
+
 /// Type alias for the return type of mutation hooks
 pub type MutationHookResult<M, Input, F> = (
     Signal<MutationState<<M as Mutation<Input>>::Output, <M as Mutation<Input>>::Error>>,
@@ -363,19 +380,23 @@ impl MutationConfig {
 fn mutation_core<M, Input>(
     mutation: M,
     config: MutationConfig,
-) -> MutationHookResult<M, Input, impl Fn(Input) + Clone>
+    state: Option<Signal<MutationState<M::Output, M::Error>>>,
+) -> impl Fn(Input) + Clone
 where
     M: Mutation<Input> + 'static,
     Input: Clone + PartialEq + 'static,
 {
-    let state = use_signal(|| MutationState::Idle);
     // Use an atomic flag to prevent concurrent mutations and race conditions
     let mutation_in_progress: Arc<AtomicBool> = Arc::new(AtomicBool::new(false));
-    let runtime_handles = runtime_handles_or_panic();
+    let runtime = hook_runtime();
+    let runtime_handles = runtime.handles();
     let cache = runtime_handles.cache;
     let refresh_registry = runtime_handles.refresh_registry;
+    let journal = mutation
+        .journaling()
+        .zip(runtime.mutation_journal().cloned());
 
-    let mutate_fn = {
+    {
         let mutation = mutation.clone();
         let cache = cache.clone();
         let refresh_registry = refresh_registry.clone();
@@ -383,44 +404,58 @@ where
         let mutation_in_progress = mutation_in_progress.clone();
 
         move |input: Input| {
-            // Prevent concurrent mutations using atomic compare-and-swap
-            // This ensures only one mutation runs at a time, preventing race conditions
-            let was_in_progress = mutation_in_progress.compare_exchange(
-                false,
-                true,
-                Ordering::Acquire,
-                Ordering::Relaxed,
-            );
-
-            if was_in_progress.is_err() {
-                // Another mutation is already in progress
-                crate::debug_log!(
-                    "⏸️ [MUTATION] Skipping mutation - already in progress for: {}",
-                    mutation.id()
+            // Silent mutations aren't tracked, so none of their calls are skipped
+            if let Some(state) = state {
+                // Prevent concurrent mutations using atomic compare-and-swap
+                // This ensures only one mutation runs at a time, preventing race conditions
+                let was_in_progress = mutation_in_progress.compare_exchange(
+                    false,
+                    true,
+                    Ordering::Acquire,
+                    Ordering::Relaxed,
                 );
-                return;
-            }
 
-            // Double-check state to prevent race conditions
-            if matches!(*state.read(), MutationState::Loading) {
-                // Reset the flag since we're not proceeding
-                mutation_in_progress.store(false, Ordering::Release);
-                crate::debug_log!(
-                    "⏸️ [MUTATION] Skipping mutation - state is Loading for: {}",
-                    mutation.id()
-                );
-                return;
+                if was_in_progress.is_err() {
+                    // Another mutation is already in progress
+                    crate::debug_log!(
+                        "⏸️ [MUTATION] Skipping mutation - already in progress for: {}",
+                        mutation.id()
+                    );
+                    return;
+                }
+
+                // Double-check state to prevent race conditions
+                if matches!(*state.read(), MutationState::Loading) {
+                    // Reset the flag since we're not proceeding
+                    mutation_in_progress.store(false, Ordering::Release);
+                    crate::debug_log!(
+                        "⏸️ [MUTATION] Skipping mutation - state is Loading for: {}",
+                        mutation.id()
+                    );
+                    return;
+                }
             }
 
             let mutation = mutation.clone();
             let cache = cache.clone();
             let refresh_registry = refresh_registry.clone();
             let input = input.clone();
-            let mut ui_state = state;
             let mutation_in_progress_for_cleanup = mutation_in_progress.clone();
 
+            // Journaled inputs are recorded as soon as they are accepted
+            let journal_entry = journal.as_ref().and_then(|(journaling, journal)| {
+                journal
+                    .try_record(&mutation.id(), *journaling, &input)
+                    .map(|id| (journal.clone(), id))
+            });
+
             // Set loading state atomically
-            ui_state.set(MutationState::Loading);
+            if let Some(mut state) = state {
+                state.set(MutationState::Loading);
+            }
+
+            #[cfg(feature = "mutation-trace")]
+            let invocation = crate::mutation_trace::next_invocation();
 
             // Collect optimistic updates if enabled
             let cache_keys_to_check: Vec<String> = mutation.invalidates();
@@ -460,24 +495,29 @@ where
                     );
                     for (cache_key, optimistic_result) in &optimistic_updates {
                         cache.set(cache_key.clone(), optimistic_result.clone());
+                        crate::trace_mutation!(
+                            invocation,
+                            &mutation.id(),
+                            cache_key,
+                            crate::mutation_trace::MutationStep::OptimisticApply
+                        );
                         refresh_registry.trigger_refresh(cache_key);
                     }
                 }
             }
 
             let optimistic_updates_for_rollback = optimistic_updates.clone();
-            let (result_tx, result_rx) = oneshot::channel::<Result<M::Output, M::Error>>();
-
-            spawn({
-                let mut state = ui_state;
-                async move {
+            let result_tx = state.map(|mut state| {
+                let (result_tx, result_rx) = oneshot::channel::<Result<M::Output, M::Error>>();
+                spawn(async move {
                     if let Ok(outcome) = result_rx.await {
                         match outcome {
                             Ok(result) => state.set(MutationState::Success(result)),
                             Err(error) => state.set(MutationState::Error(error)),
                         }
                     }
-                }
+                });
+                result_tx
             });
 
             dioxus_core::spawn_forever(async move {
@@ -503,9 +543,15 @@ where
                     .first()
                     .and_then(|first_key| cache.get::<Result<M::Output, M::Error>>(first_key));
 
-                let mutation_result = mutation
-                    .mutate_with_current(input, mutation_current_data.as_ref())
-                    .await;
+                let mutation_result =
+                    retry::mutate(&mutation, input, mutation_current_data.as_ref()).await;
+
+                // Failed inputs stay journaled, to be replayed later
+                if let Some((journal, id)) = journal_entry
+                    && mutation_result.is_ok()
+                {
+                    journal.try_acknowledge(id);
+                }
 
                 crate::debug_log!(
                     "📡 [MUTATION] Mutation completed for: {}, result: {}",
@@ -516,10 +562,26 @@ where
                     }
                 );
 
+                #[cfg(feature = "mutation-trace")]
+                for cache_key in &cache_keys_to_check {
+                    crate::mutation_trace::record(
+                        invocation,
+                        &mutation.id(),
+                        cache_key,
+                        crate::mutation_trace::MutationStep::ServerResponse {
+                            success: mutation_result.is_ok(),
+                        },
+                    );
+                }
+
                 match &mutation_result {
                     Ok(result) => {
                         crate::debug_log!("✅ [MUTATION] Mutation succeeded: {}", mutation.id());
 
+                        if let Some(normalize) = mutation.normalize() {
+                            normalize(result, cache.entities());
+                        }
+
                         if is_optimistic && !optimistic_updates_for_rollback.is_empty() {
                             // Update optimistic caches with real result
                             let optimistic_keys: HashSet<String> = optimistic_updates_for_rollback
@@ -534,6 +596,12 @@ where
 
                             for cache_key in &optimistic_keys {
                                 cache.set(cache_key.clone(), Ok::<_, M::Error>(result.clone()));
+                                crate::trace_mutation!(
+                                    invocation,
+                                    &mutation.id(),
+                                    cache_key,
+                                    crate::mutation_trace::MutationStep::Reconcile
+                                );
                                 refresh_registry.trigger_refresh(cache_key);
                             }
 
@@ -552,6 +620,12 @@ where
 
                                 for cache_key in invalidation_keys {
                                     cache.invalidate(&cache_key);
+                                    crate::trace_mutation!(
+                                        invocation,
+                                        &mutation.id(),
+                                        &cache_key,
+                                        crate::mutation_trace::MutationStep::Invalidate
+                                    );
                                     refresh_registry.trigger_refresh(&cache_key);
                                 }
                             }
@@ -569,6 +643,12 @@ where
                                     cache_key
                                 );
                                 cache.invalidate(cache_key);
+                                crate::trace_mutation!(
+                                    invocation,
+                                    &mutation.id(),
+                                    cache_key,
+                                    crate::mutation_trace::MutationStep::Invalidate
+                                );
                                 refresh_registry.trigger_refresh(cache_key);
                             }
                         }
@@ -588,13 +668,21 @@ where
                                     cache_key
                                 );
                                 cache.invalidate(cache_key);
+                                crate::trace_mutation!(
+                                    invocation,
+                                    &mutation.id(),
+                                    cache_key,
+                                    crate::mutation_trace::MutationStep::Rollback
+                                );
                                 refresh_registry.trigger_refresh(cache_key);
                             }
                         }
                     }
                 }
 
-                if result_tx.send(mutation_result).is_err() {
+                if let Some(result_tx) = result_tx
+                    && result_tx.send(mutation_result).is_err()
+                {
                     crate::debug_log!(
                         "⚠️ [MUTATION] Result receiver dropped before completion for: {}",
                         mutation.id()
@@ -605,9 +693,7 @@ where
                 mutation_in_progress_for_cleanup.store(false, Ordering::Release);
             });
         }
-    };
-
-    (state, mutate_fn)
+    }
 }
 
 /// Core mutation logic shared between use_mutation and use_optimistic_mutation (non-WASM version)
@@ -615,19 +701,23 @@ where
 fn mutation_core<M, Input>(
     mutation: M,
     config: MutationConfig,
-) -> MutationHookResult<M, Input, impl Fn(Input) + Clone>
+    state: Option<Signal<MutationState<M::Output, M::Error>>>,
+) -> impl Fn(Input) + Clone
 where
     M: Mutation<Input> + Send + Sync + 'static,
     Input: Clone + PartialEq + Send + Sync + 'static,
 {
-    let state = use_signal(|| MutationState::Idle);
     // Use an atomic flag to prevent concurrent mutations and race conditions
     let mutation_in_progress: Arc<AtomicBool> = Arc::new(AtomicBool::new(false));
-    let runtime_handles = runtime_handles_or_panic();
+    let runtime = hook_runtime();
+    let runtime_handles = runtime.handles();
     let cache = runtime_handles.cache;
     let refresh_registry = runtime_handles.refresh_registry;
+    let journal = mutation
+        .journaling()
+        .zip(runtime.mutation_journal().cloned());
 
-    let mutate_fn = {
+    {
         let mutation = mutation.clone();
         let cache = cache.clone();
         let refresh_registry = refresh_registry.clone();
@@ -635,44 +725,58 @@ where
         let mutation_in_progress = mutation_in_progress.clone();
 
         move |input: Input| {
-            // Prevent concurrent mutations using atomic compare-and-swap
-            // This ensures only one mutation runs at a time, preventing race conditions
-            let was_in_progress = mutation_in_progress.compare_exchange(
-                false,
-                true,
-                Ordering::Acquire,
-                Ordering::Relaxed,
-            );
-
-            if was_in_progress.is_err() {
-                // Another mutation is already in progress
-                crate::debug_log!(
-                    "⏸️ [MUTATION] Skipping mutation - already in progress for: {}",
-                    mutation.id()
+            // Silent mutations aren't tracked, so none of their calls are skipped
+            if let Some(state) = state {
+                // Prevent concurrent mutations using atomic compare-and-swap
+                // This ensures only one mutation runs at a time, preventing race conditions
+                let was_in_progress = mutation_in_progress.compare_exchange(
+                    false,
+                    true,
+                    Ordering::Acquire,
+                    Ordering::Relaxed,
                 );
-                return;
-            }
 
-            // Double-check state to prevent race conditions
-            if matches!(*state.read(), MutationState::Loading) {
-                // Reset the flag since we're not proceeding
-                mutation_in_progress.store(false, Ordering::Release);
-                crate::debug_log!(
-                    "⏸️ [MUTATION] Skipping mutation - state is Loading for: {}",
-                    mutation.id()
-                );
-                return;
+                if was_in_progress.is_err() {
+                    // Another mutation is already in progress
+                    crate::debug_log!(
+                        "⏸️ [MUTATION] Skipping mutation - already in progress for: {}",
+                        mutation.id()
+                    );
+                    return;
+                }
+
+                // Double-check state to prevent race conditions
+                if matches!(*state.read(), MutationState::Loading) {
+                    // Reset the flag since we're not proceeding
+                    mutation_in_progress.store(false, Ordering::Release);
+                    crate::debug_log!(
+                        "⏸️ [MUTATION] Skipping mutation - state is Loading for: {}",
+                        mutation.id()
+                    );
+                    return;
+                }
             }
 
             let mutation = mutation.clone();
             let cache = cache.clone();
             let refresh_registry = refresh_registry.clone();
             let input = input.clone();
-            let mut ui_state = state;
             let mutation_in_progress_for_cleanup = mutation_in_progress.clone();
 
+            // Journaled inputs are recorded as soon as they are accepted
+            let journal_entry = journal.as_ref().and_then(|(journaling, journal)| {
+                journal
+                    .try_record(&mutation.id(), *journaling, &input)
+                    .map(|id| (journal.clone(), id))
+            });
+
             // Set loading state atomically
-            ui_state.set(MutationState::Loading);
+            if let Some(mut state) = state {
+                state.set(MutationState::Loading);
+            }
+
+            #[cfg(feature = "mutation-trace")]
+            let invocation = crate::mutation_trace::next_invocation();
 
             // Collect optimistic updates if enabled
             let cache_keys_to_check: Vec<String> = mutation.invalidates();
@@ -712,24 +816,29 @@ where
                     );
                     for (cache_key, optimistic_result) in &optimistic_updates {
                         cache.set(cache_key.clone(), optimistic_result.clone());
+                        crate::trace_mutation!(
+                            invocation,
+                            &mutation.id(),
+                            cache_key,
+                            crate::mutation_trace::MutationStep::OptimisticApply
+                        );
                         refresh_registry.trigger_refresh(cache_key);
                     }
                 }
             }
 
             let optimistic_updates_for_rollback = optimistic_updates.clone();
-            let (result_tx, result_rx) = oneshot::channel::<Result<M::Output, M::Error>>();
-
-            spawn({
-                let mut state = ui_state;
-                async move {
+            let result_tx = state.map(|mut state| {
+                let (result_tx, result_rx) = oneshot::channel::<Result<M::Output, M::Error>>();
+                spawn(async move {
                     if let Ok(outcome) = result_rx.await {
                         match outcome {
                             Ok(result) => state.set(MutationState::Success(result)),
                             Err(error) => state.set(MutationState::Error(error)),
                         }
                     }
-                }
+                });
+                result_tx
             });
 
             dioxus_core::spawn_forever(async move {
@@ -755,9 +864,15 @@ where
                     .first()
                     .and_then(|first_key| cache.get::<Result<M::Output, M::Error>>(first_key));
 
-                let mutation_result = mutation
-                    .mutate_with_current(input, mutation_current_data.as_ref())
-                    .await;
+                let mutation_result =
+                    retry::mutate(&mutation, input, mutation_current_data.as_ref()).await;
+
+                // Failed inputs stay journaled, to be replayed later
+                if let Some((journal, id)) = journal_entry
+                    && mutation_result.is_ok()
+                {
+                    journal.try_acknowledge(id);
+                }
 
                 crate::debug_log!(
                     "📡 [MUTATION] Mutation completed for: {}, result: {}",
@@ -768,10 +883,26 @@ where
                     }
                 );
 
+                #[cfg(feature = "mutation-trace")]
+                for cache_key in &cache_keys_to_check {
+                    crate::mutation_trace::record(
+                        invocation,
+                        &mutation.id(),
+                        cache_key,
+                        crate::mutation_trace::MutationStep::ServerResponse {
+                            success: mutation_result.is_ok(),
+                        },
+                    );
+                }
+
                 match &mutation_result {
                     Ok(result) => {
                         crate::debug_log!("✅ [MUTATION] Mutation succeeded: {}", mutation.id());
 
+                        if let Some(normalize) = mutation.normalize() {
+                            normalize(result, cache.entities());
+                        }
+
                         if is_optimistic && !optimistic_updates_for_rollback.is_empty() {
                             // Update optimistic caches with real result
                             let optimistic_keys: HashSet<String> = optimistic_updates_for_rollback
@@ -786,6 +917,12 @@ where
 
                             for cache_key in &optimistic_keys {
                                 cache.set(cache_key.clone(), Ok::<_, M::Error>(result.clone()));
+                                crate::trace_mutation!(
+                                    invocation,
+                                    &mutation.id(),
+                                    cache_key,
+                                    crate::mutation_trace::MutationStep::Reconcile
+                                );
                                 refresh_registry.trigger_refresh(cache_key);
                             }
 
@@ -804,6 +941,12 @@ where
 
                                 for cache_key in invalidation_keys {
                                     cache.invalidate(&cache_key);
+                                    crate::trace_mutation!(
+                                        invocation,
+                                        &mutation.id(),
+                                        &cache_key,
+                                        crate::mutation_trace::MutationStep::Invalidate
+                                    );
                                     refresh_registry.trigger_refresh(&cache_key);
                                 }
                             }
@@ -821,6 +964,12 @@ where
                                     cache_key
                                 );
                                 cache.invalidate(cache_key);
+                                crate::trace_mutation!(
+                                    invocation,
+                                    &mutation.id(),
+                                    cache_key,
+                                    crate::mutation_trace::MutationStep::Invalidate
+                                );
                                 refresh_registry.trigger_refresh(cache_key);
                             }
                         }
@@ -840,13 +989,21 @@ where
                                     cache_key
                                 );
                                 cache.invalidate(cache_key);
+                                crate::trace_mutation!(
+                                    invocation,
+                                    &mutation.id(),
+                                    cache_key,
+                                    crate::mutation_trace::MutationStep::Rollback
+                                );
                                 refresh_registry.trigger_refresh(cache_key);
                             }
                         }
                     }
                 }
 
-                if result_tx.send(mutation_result).is_err() {
+                if let Some(result_tx) = result_tx
+                    && result_tx.send(mutation_result).is_err()
+                {
                     crate::debug_log!(
                         "⚠️ [MUTATION] Result receiver dropped before completion for: {}",
                         mutation.id()
@@ -857,9 +1014,7 @@ where
                 mutation_in_progress_for_cleanup.store(false, Ordering::Release);
             });
         }
-    };
-
-    (state, mutate_fn)
+    }
 }
 
 /// Hook to create a mutation that can be triggered manually
@@ -916,7 +1071,8 @@ where
     } else {
         MutationConfig::default()
     };
-    mutation_core(mutation, config)
+    let state = use_signal(|| MutationState::Idle);
+    (state, mutation_core(mutation, config, Some(state)))
 }
 
 /// Hook to create a mutation that can be triggered manually (non-WASM version)
@@ -931,7 +1087,8 @@ where
     } else {
         MutationConfig::default()
     };
-    mutation_core(mutation, config)
+    let state = use_signal(|| MutationState::Idle);
+    (state, mutation_core(mutation, config, Some(state)))
 }
 
 /// Hook to create a mutation with optimistic updates
@@ -997,6 +1154,133 @@ where
     use_mutation(mutation)
 }
 
+/// Hook to create a fire-and-forget mutation (WASM version)
+#[cfg(target_family = "wasm")]
+pub fn use_mutation_silent<M, Input>(mutation: M) -> impl Fn(Input) + Clone
+where
+    M: Mutation<Input> + 'static,
+    Input: Clone + PartialEq + 'static,
+{
+    let config = if mutation.has_optimistic() {
+        MutationConfig::optimistic()
+    } else {
+        MutationConfig::default()
+    };
+    mutation_core(mutation, config, None)
+}
+
+/// Hook to create a fire-and-forget mutation, returning only the function that triggers it
+///
+/// For telemetry-style mutations whose outcome the component never shows. No state signal
+/// is kept, and calls are never skipped while an earlier one is still running; invalidation,
+/// optimistic updates and retries work as with [`use_mutation`].
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use dioxus::prelude::*;
+/// use dioxus_provider::prelude::*;
+///
+/// #[mutation]
+/// async fn track_event(name: String) -> Result<(), String> {
+///     Ok(())
+/// }
+///
+/// #[component]
+/// fn BuyButton() -> Element {
+///     let track = use_mutation_silent(track_event());
+///
+///     rsx! {
+///         button {
+///             onclick: move |_| track("buy_clicked".to_string()),
+///             "Buy"
+///         }
+///     }
+/// }
+/// ```
+#[cfg(not(target_family = "wasm"))]
+pub fn use_mutation_silent<M, Input>(mutation: M) -> impl Fn(Input) + Clone
+where
+    M: Mutation<Input> + Send + Sync + 'static,
+    Input: Clone + PartialEq + Send + Sync + 'static,
+{
+    let config = if mutation.has_optimistic() {
+        MutationConfig::optimistic()
+    } else {
+        MutationConfig::default()
+    };
+    mutation_core(mutation, config, None)
+}
+
+/// Suspense integration for mutation state signals
+///
+/// Critical mutations, like submitting a checkout, can block a subtree with a fallback
+/// instead of every button tracking a disabled state. Components under a
+/// [`PendingBoundary`] (or any `SuspenseBoundary`) call `suspend_while_loading` and
+/// are replaced by the fallback until the mutation settles.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use dioxus::prelude::*;
+/// use dioxus_provider::prelude::*;
+///
+/// #[mutation]
+/// async fn submit_order(cart_id: u32) -> Result<String, String> {
+///     Ok(format!("order-{cart_id}"))
+/// }
+///
+/// #[component]
+/// fn Checkout(cart_id: u32) -> Element {
+///     let (order, submit) = use_mutation(submit_order());
+///
+///     rsx! {
+///         PendingBoundary {
+///             fallback: rsx! { "Placing your order..." },
+///             CheckoutForm { order, on_submit: move |_| submit(cart_id) }
+///         }
+///     }
+/// }
+///
+/// #[component]
+/// fn CheckoutForm(
+///     order: Signal<MutationState<String, String>>,
+///     on_submit: EventHandler<()>,
+/// ) -> Element {
+///     order.suspend_while_loading()?;
+///
+///     rsx! {
+///         button { onclick: move |_| on_submit.call(()), "Place order" }
+///     }
+/// }
+/// ```
+pub trait MutationSuspenseExt {
+    /// Returns Ok(()) unless the mutation is running, in which case rendering suspends
+    /// until it succeeds or fails
+    fn suspend_while_loading(&self) -> Result<(), RenderError>;
+}
+
+impl<T: 'static, E: 'static> MutationSuspenseExt for Signal<MutationState<T, E>> {
+    fn suspend_while_loading(&self) -> Result<(), RenderError> {
+        if !self.read().is_loading() {
+            return Ok(());
+        }
+
+        // The mutation's own task is spawned wherever `mutate` is called, so suspend on a
+        // task in this scope that ends once the state leaves Loading
+        Err(suspend_until(*self, MutationState::is_loading))
+    }
+}
+
+/// Shows `fallback` in place of `children` while a component below it is suspended,
+/// e.g. by [`MutationSuspenseExt::suspend_while_loading`]
+#[component]
+pub fn PendingBoundary(fallback: Element, children: Element) -> Element {
+    rsx! {
+        SuspenseBoundary { fallback: move |_| fallback.clone(), {children} }
+    }
+}
+
 /// Helper function to create cache keys for providers with parameters
 pub fn provider_cache_key<P, Param>(provider: P, param: Param) -> String
 where
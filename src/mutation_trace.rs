@@ -0,0 +1,255 @@
+//! # Mutation Tracing
+//!
+//! Instrumentation for testing optimistic mutations under concurrency, enabled with the
+//! `mutation-trace` feature. Every mutation run records, per cache key and in order, when
+//! its optimistic value was applied, when the server responded, and whether the entry was
+//! reconciled with the server result, invalidated or rolled back. Tests read the trace back
+//! with [`events_for`] and check it with [`check_linearizable`], which catches
+//! double-application of optimistic updates and lost updates where an older mutation
+//! overwrites the optimistic value of a newer one.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use dioxus_provider::mutation_trace;
+//!
+//! mutation_trace::clear();
+//! // ... run overlapping mutations against `todos_key` ...
+//! mutation_trace::check_linearizable(&todos_key).expect("mutations raced");
+//! ```
+
+use std::{
+    collections::HashMap,
+    sync::{
+        Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+/// A step of a mutation run, as seen by one cache key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MutationStep {
+    /// The optimistic value was written to the cache
+    OptimisticApply,
+    /// The mutation returned, successfully or not
+    ServerResponse { success: bool },
+    /// The optimistic value was replaced with the server result
+    Reconcile,
+    /// The entry was invalidated to be refetched
+    Invalidate,
+    /// The optimistic value was rolled back after a failure
+    Rollback,
+}
+
+/// A recorded mutation step
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MutationEvent {
+    /// Position in the global trace
+    pub seq: u64,
+    /// Identifies one call of a mutation; unique for the lifetime of the program
+    pub invocation: u64,
+    /// The mutation's id
+    pub mutation_id: String,
+    /// The affected cache key
+    pub key: String,
+    pub step: MutationStep,
+}
+
+/// An ordering violation found by [`check_linearizable`]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum LinearizationError {
+    #[error("invocation {invocation} applied its optimistic update to {key} more than once")]
+    DoubleApply { invocation: u64, key: String },
+    #[error("invocation {invocation} changed {key} with {step:?} before the server responded")]
+    BeforeResponse {
+        invocation: u64,
+        key: String,
+        step: MutationStep,
+    },
+    #[error("invocation {invocation} rolled back {key} after a successful response")]
+    RollbackAfterSuccess { invocation: u64, key: String },
+    #[error(
+        "invocation {invocation} overwrote {key} with {step:?} after newer invocation {newer} applied its optimistic update"
+    )]
+    LostUpdate {
+        invocation: u64,
+        newer: u64,
+        key: String,
+        step: MutationStep,
+    },
+}
+
+static NEXT_INVOCATION: AtomicU64 = AtomicU64::new(1);
+static TRACE: Mutex<Vec<MutationEvent>> = Mutex::new(Vec::new());
+
+/// Allocate the id of a new mutation run
+pub(crate) fn next_invocation() -> u64 {
+    NEXT_INVOCATION.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Append a step to the trace
+pub(crate) fn record(invocation: u64, mutation_id: &str, key: &str, step: MutationStep) {
+    if let Ok(mut trace) = TRACE.lock() {
+        let seq = trace.len() as u64;
+        trace.push(MutationEvent {
+            seq,
+            invocation,
+            mutation_id: mutation_id.to_string(),
+            key: key.to_string(),
+            step,
+        });
+    }
+}
+
+/// All recorded steps, in order
+pub fn events() -> Vec<MutationEvent> {
+    TRACE.lock().map(|trace| trace.clone()).unwrap_or_default()
+}
+
+/// The recorded steps for one cache key, in order
+pub fn events_for(key: &str) -> Vec<MutationEvent> {
+    TRACE
+        .lock()
+        .map(|trace| {
+            trace
+                .iter()
+                .filter(|event| event.key == key)
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Discard the recorded trace
+pub fn clear() {
+    if let Ok(mut trace) = TRACE.lock() {
+        trace.clear();
+    }
+}
+
+/// Check that the mutations on a key behaved as if they ran one after another
+pub fn check_linearizable(key: &str) -> Result<(), LinearizationError> {
+    check_events(&events_for(key))
+}
+
+/// Check a trace of a single key
+pub fn check_events(events: &[MutationEvent]) -> Result<(), LinearizationError> {
+    let mut applied: HashMap<u64, bool> = HashMap::new();
+    let mut responses: HashMap<u64, bool> = HashMap::new();
+    // The invocation whose optimistic value the key currently holds
+    let mut latest_apply: Option<u64> = None;
+
+    for event in events {
+        let invocation = event.invocation;
+        let key = || event.key.clone();
+        match event.step {
+            MutationStep::OptimisticApply => {
+                if applied.insert(invocation, true).is_some() {
+                    return Err(LinearizationError::DoubleApply {
+                        invocation,
+                        key: key(),
+                    });
+                }
+                latest_apply = Some(invocation);
+            }
+            MutationStep::ServerResponse { success } => {
+                responses.insert(invocation, success);
+            }
+            step @ (MutationStep::Reconcile | MutationStep::Rollback) => {
+                let Some(&success) = responses.get(&invocation) else {
+                    return Err(LinearizationError::BeforeResponse {
+                        invocation,
+                        key: key(),
+                        step,
+                    });
+                };
+                if step == MutationStep::Rollback && success {
+                    return Err(LinearizationError::RollbackAfterSuccess {
+                        invocation,
+                        key: key(),
+                    });
+                }
+                if let Some(newer) = latest_apply.filter(|newer| *newer > invocation) {
+                    return Err(LinearizationError::LostUpdate {
+                        invocation,
+                        newer,
+                        key: key(),
+                        step,
+                    });
+                }
+            }
+            MutationStep::Invalidate => {
+                if !responses.contains_key(&invocation) {
+                    return Err(LinearizationError::BeforeResponse {
+                        invocation,
+                        key: key(),
+                        step: event.step,
+                    });
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trace(steps: &[(u64, MutationStep)]) -> Vec<MutationEvent> {
+        steps
+            .iter()
+            .enumerate()
+            .map(|(seq, (invocation, step))| MutationEvent {
+                seq: seq as u64,
+                invocation: *invocation,
+                mutation_id: "update_todo".to_string(),
+                key: "todos".to_string(),
+                step: *step,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn detects_double_apply_and_lost_updates() {
+        use MutationStep::*;
+
+        let sequential = trace(&[
+            (1, OptimisticApply),
+            (1, ServerResponse { success: true }),
+            (1, Reconcile),
+            (2, OptimisticApply),
+            (2, ServerResponse { success: false }),
+            (2, Rollback),
+        ]);
+        assert_eq!(check_events(&sequential), Ok(()));
+
+        let double = trace(&[(1, OptimisticApply), (1, OptimisticApply)]);
+        assert!(matches!(
+            check_events(&double),
+            Err(LinearizationError::DoubleApply { invocation: 1, .. })
+        ));
+
+        // The first mutation's late response overwrites the second's optimistic value
+        let lost = trace(&[
+            (1, OptimisticApply),
+            (2, OptimisticApply),
+            (1, ServerResponse { success: true }),
+            (1, Reconcile),
+        ]);
+        assert!(matches!(
+            check_events(&lost),
+            Err(LinearizationError::LostUpdate {
+                invocation: 1,
+                newer: 2,
+                ..
+            })
+        ));
+
+        let early = trace(&[(1, OptimisticApply), (1, Rollback)]);
+        assert!(matches!(
+            check_events(&early),
+            Err(LinearizationError::BeforeResponse { .. })
+        ));
+    }
+}
@@ -0,0 +1,125 @@
+//! # Pausing Background Refreshes
+//!
+//! While a user edits a provider's data, e.g. in a modal, an interval refresh or a
+//! stale-while-revalidate refetch landing underneath them is unwelcome. [`use_pause_provider`]
+//! returns a [`PauseHandle`] that freezes a provider entry: interval, scheduled and stale
+//! refreshes of its key are skipped until it is resumed, and resuming revalidates the entry
+//! right away, so nothing missed in the meantime stays stale.
+//!
+//! A pause held by a component is released when it unmounts.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use dioxus_provider::prelude::*;
+//!
+//! #[component]
+//! fn EditUserModal(id: u32, onclose: EventHandler) -> Element {
+//!     let user = use_provider(fetch_user(), id);
+//!     let freeze = use_pause_provider(fetch_user(), id);
+//!     use_hook(|| freeze.pause());
+//!
+//!     rsx! {
+//!         UserForm { user }
+//!         button {
+//!             onclick: move |_| {
+//!                 freeze.resume();
+//!                 onclose.call(());
+//!             },
+//!             "Done"
+//!         }
+//!     }
+//! }
+//! ```
+
+use dioxus::prelude::*;
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    cache::ProviderCache, global::hook_runtime, hooks::Provider, param_utils::IntoProviderParam,
+    refresh::RefreshRegistry,
+};
+
+/// Controls for pausing a provider entry's background refreshes, from [`use_pause_provider`]
+#[derive(Clone, Copy, PartialEq)]
+pub struct PauseHandle {
+    pause: Callback<()>,
+    resume: Callback<()>,
+    paused: Signal<bool>,
+}
+
+impl PauseHandle {
+    /// Stop interval, scheduled and stale refreshes of the entry, if not already paused
+    pub fn pause(&self) {
+        self.pause.call(());
+    }
+
+    /// Release this handle's pause and revalidate the entry once no other pause holds it
+    pub fn resume(&self) {
+        self.resume.call(());
+    }
+
+    /// Returns true while this handle holds a pause, subscribing the caller
+    pub fn is_paused(&self) -> bool {
+        *self.paused.read()
+    }
+}
+
+/// Hook returning pause and resume controls for a provider's background refreshes
+///
+/// Pauses from several handles on the same entry nest; the entry is revalidated when the
+/// last of them is resumed.
+pub fn use_pause_provider<P, Args>(provider: P, args: Args) -> PauseHandle
+where
+    P: Provider<Args::Param>,
+    Args: IntoProviderParam,
+{
+    let handles = hook_runtime().handles();
+    let cache_key = provider.id(&args.into_param());
+    let held = use_hook(|| Rc::new(RefCell::new(None::<String>)));
+    let mut paused = use_signal(|| false);
+
+    let pause = use_callback({
+        let held = held.clone();
+        let refresh_registry = handles.refresh_registry.clone();
+        move |()| {
+            if held.borrow().is_some() {
+                return;
+            }
+            refresh_registry.pause(&cache_key);
+            *held.borrow_mut() = Some(cache_key.clone());
+            paused.set(true);
+        }
+    });
+    let resume = use_callback({
+        let held = held.clone();
+        let cache = handles.cache.clone();
+        let refresh_registry = handles.refresh_registry.clone();
+        move |()| {
+            let Some(key) = held.borrow_mut().take() else {
+                return;
+            };
+            release(&cache, &refresh_registry, &key);
+            paused.set(false);
+        }
+    });
+    use_drop(move || {
+        if let Some(key) = held.borrow_mut().take() {
+            release(&handles.cache, &handles.refresh_registry, &key);
+        }
+    });
+
+    PauseHandle {
+        pause,
+        resume,
+        paused,
+    }
+}
+
+/// Release a pause, revalidating the entry if that resumed it
+fn release(cache: &ProviderCache, refresh_registry: &RefreshRegistry, key: &str) {
+    if refresh_registry.resume(key) {
+        cache.mark_stale(key);
+        refresh_registry.trigger_refresh(key);
+    }
+}
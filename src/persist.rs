@@ -0,0 +1,538 @@
+//! # Persistence Quota Handling, Persistent Storage and Lazy Hydration
+//!
+//! Browser storage is small and shared with the rest of the origin, so a persistence backend
+//! has to cope with running out of space. This module holds the backend-independent part:
+//! a [`PersistQuota`] that plans oldest-first trimming of persisted entries to make room for
+//! a new one, and [`QuotaEvent`]s reported to listeners whenever the quota forces entries out.
+//!
+//! Only entries of providers marked for persistence are ever handed to a backend, so trimming
+//! never touches in-memory-only data.
+//!
+//! ## Persistent Storage
+//!
+//! Providers marked `#[provider(persist)]` keep their results across page reloads and app
+//! restarts when the runtime's cache is backed by a [`CacheStorage`](crate::cache::CacheStorage): successful results are
+//! written to the storage as JSON, and a key missing from the cache is read back from the
+//! storage on its first use before the provider is run. Consumers stay `Loading` meanwhile.
+//! Restored entries are treated like freshly fetched ones, so the provider's `stale_time`
+//! decides when they are revalidated.
+//! With `with_tiered_storage` instead of `with_storage`, the storage is also a second cache
+//! tier: entries that maintenance evicts from memory are restored again on their next use.
+//!
+//! ```rust,ignore
+//! use dioxus_provider::{prelude::*, storage::FileStorage};
+//!
+//! #[provider(persist, stale_time = "1m")]
+//! async fn fetch_settings() -> Result<Settings, String> {
+//!     api::settings().await
+//! }
+//!
+//! let config = ProviderConfig::new().with_storage(FileStorage::new("cache"));
+//! ```
+//!
+//! ## Lazy Hydration
+//!
+//! Loading the whole persisted cache at startup makes startup slower as the cache grows. A
+//! backend implementing [`HydrationSource`] is instead asked for an entry the first time a
+//! consumer reads its key: [`LazyHydration`] lists the persisted keys once, without reading
+//! them, and hydrates each key on demand. Consumers stay `Loading` while their key hydrates,
+//! which `ProviderHandle::is_hydrating` tells apart from fetching, and fetch as usual if the
+//! backend had nothing for it.
+//!
+//! ```rust,ignore
+//! use dioxus_provider::{
+//!     cache::ProviderCache,
+//!     errors::ProviderError,
+//!     persist::{HydrateFuture, HydrationSource, LazyHydration},
+//!     prelude::*,
+//!     query_key::QueryKey,
+//! };
+//!
+//! struct Storage;
+//!
+//! impl HydrationSource for Storage {
+//!     fn keys(&self) -> Result<Vec<String>, ProviderError> {
+//!         local_storage_keys()
+//!     }
+//!
+//!     fn hydrate(&self, key: &str, cache: ProviderCache) -> HydrateFuture {
+//!         let key = key.to_string();
+//!         Box::pin(async move {
+//!             // Query keys tell the backend which type is stored under the key
+//!             match QueryKey::parse(&key).part(0) {
+//!                 Some("fetch_user") => {
+//!                     let user: User = read_json(&key)?;
+//!                     Ok(cache.set(key, Ok::<User, String>(user)))
+//!                 }
+//!                 _ => Ok(false),
+//!             }
+//!         })
+//!     }
+//! }
+//!
+//! let config = ProviderConfig::new().with_lazy_hydration(LazyHydration::new(Storage));
+//! ```
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use dioxus_provider::persist::{PersistQuota, PersistedEntryMeta, QuotaEvent};
+//!
+//! let quota = PersistQuota::new().with_max_bytes(4 * 1024 * 1024);
+//! quota.on_event(|event| match event {
+//!     QuotaEvent::Trimmed { evicted, .. } => tracing::info!("trimmed {} entries", evicted.len()),
+//!     QuotaEvent::Exceeded { key, .. } => tracing::warn!("{key} was not persisted"),
+//! });
+//!
+//! // In the backend, before writing an entry
+//! match quota.admit(&key, bytes.len(), &stored_entries, available_bytes) {
+//!     Some(evicted) => {
+//!         for key in evicted {
+//!             storage.remove(&key);
+//!         }
+//!         storage.write(&key, &bytes);
+//!     }
+//!     None => {} // Skipped, a `QuotaEvent::Exceeded` was emitted
+//! }
+//! ```
+
+use std::{
+    collections::HashSet,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{
+    cache::ProviderCache, errors::ProviderError, hooks::Provider, platform::task,
+    types::ProviderParamBounds,
+};
+
+/// How a provider's output is written to and read from a
+/// [`CacheStorage`](crate::cache::CacheStorage)
+pub struct Persistence<T> {
+    pub(crate) encode: fn(&T) -> Option<String>,
+    pub(crate) decode: fn(&str) -> Option<T>,
+}
+
+impl<T> Persistence<T> {
+    /// Persist with custom encoding, where None means the value can't be stored or read
+    pub fn new(encode: fn(&T) -> Option<String>, decode: fn(&str) -> Option<T>) -> Self {
+        Self { encode, decode }
+    }
+
+    /// Persist as JSON, as `#[provider(persist)]` does
+    pub fn json() -> Self
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        Self::new(
+            |data| serde_json::to_string(data).ok(),
+            |value| serde_json::from_str(value).ok(),
+        )
+    }
+}
+
+impl<T> Clone for Persistence<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Persistence<T> {}
+
+/// Encode a provider's result for the cache's storage, if the provider persists and the
+/// result is a success
+pub(crate) fn encode<P, Param>(
+    provider: &P,
+    cache: &ProviderCache,
+    result: &Result<P::Output, P::Error>,
+) -> Option<String>
+where
+    P: Provider<Param>,
+    Param: ProviderParamBounds,
+{
+    let persistence = provider.persistence()?;
+    cache.storage()?;
+    (persistence.encode)(result.as_ref().ok()?)
+}
+
+/// Write an encoded entry to the cache's storage in the background
+pub(crate) fn save(cache: &ProviderCache, key: &str, value: String) {
+    let Some(storage) = cache.storage() else {
+        return;
+    };
+    let write = storage.write(key, value);
+    cache.mark_on_disk(key);
+    #[allow(unused_variables)]
+    let key = key.to_string();
+    task::spawn(async move {
+        if let Err(_error) = write.await {
+            crate::debug_log!("💾 [PERSIST] Failed to write key: {} ({})", key, _error);
+        }
+    });
+}
+
+/// Read a provider's stored entry into the cache, returning whether one was found
+///
+/// Finishes a restoration claimed with [`ProviderCache::begin_restore`]. Unreadable entries
+/// are logged and treated as missing, so the provider fetches instead.
+pub(crate) async fn restore<P, Param>(provider: &P, cache: &ProviderCache, key: &str) -> bool
+where
+    P: Provider<Param>,
+    Param: ProviderParamBounds,
+{
+    let read = match (provider.persistence(), cache.storage()) {
+        (Some(persistence), Some(storage)) => Some((persistence, storage.read(key))),
+        _ => None,
+    };
+    let restored = match read {
+        Some((persistence, read)) => match read.await {
+            Ok(Some(value)) => match (persistence.decode)(&value) {
+                Some(data) => {
+                    cache.set(key.to_string(), Ok::<P::Output, P::Error>(data));
+                    cache.mark_on_disk(key);
+                    crate::ssr::track_entry(provider, cache, key);
+                    true
+                }
+                None => {
+                    crate::debug_log!("💾 [PERSIST] Discarding unreadable entry: {}", key);
+                    false
+                }
+            },
+            Ok(None) => false,
+            Err(_error) => {
+                crate::debug_log!("💾 [PERSIST] Failed to read key: {} ({})", key, _error);
+                false
+            }
+        },
+        None => false,
+    };
+    if restored && cache.is_demo() {
+        cache.allow_restore_again(key);
+    }
+    cache.finish_restore(key);
+    crate::debug_log!("💾 [PERSIST] Restored key: {} (found: {})", key, restored);
+    restored
+}
+
+/// Size and age of an entry held by a persistence backend
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PersistedEntryMeta {
+    /// The provider cache key
+    pub key: String,
+    /// Serialized size in bytes
+    pub size: usize,
+    /// Unix timestamp (seconds) at which the entry was written
+    pub persisted_at: u64,
+}
+
+/// Reported when the storage quota forced persisted entries out
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuotaEvent {
+    /// Older entries were removed to make room for `key`
+    Trimmed {
+        key: String,
+        evicted: Vec<String>,
+        freed_bytes: usize,
+    },
+    /// `key` didn't fit even after trimming every other entry and was not persisted
+    Exceeded { key: String, required_bytes: usize },
+}
+
+type QuotaListener = Arc<dyn Fn(&QuotaEvent) + Send + Sync>;
+
+/// Storage budget of a persistence backend, with oldest-first trimming
+///
+/// Cloning the quota is cheap and clones share their event listeners.
+#[derive(Clone, Default)]
+pub struct PersistQuota {
+    max_bytes: Option<usize>,
+    listeners: Arc<Mutex<Vec<QuotaListener>>>,
+}
+
+impl PersistQuota {
+    /// Create a quota limited only by the space the backend reports as available
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cap the total size of persisted entries, below whatever the platform allows
+    pub fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// The configured size cap, if any
+    pub fn max_bytes(&self) -> Option<usize> {
+        self.max_bytes
+    }
+
+    /// Register a listener for quota events
+    pub fn on_event(&self, listener: impl Fn(&QuotaEvent) + Send + Sync + 'static) {
+        if let Ok(mut listeners) = self.listeners.lock() {
+            listeners.push(Arc::new(listener));
+        }
+    }
+
+    /// Plan the entries to remove, oldest first, so that `size` more bytes fit
+    ///
+    /// `available` is the free space the platform reports (e.g. from
+    /// `navigator.storage.estimate()`), or `None` if unknown. An entry replacing an existing
+    /// one under the same key reuses its space. Returns `None` if the entry can't fit even
+    /// after removing every other entry.
+    pub fn plan_eviction(
+        &self,
+        key: &str,
+        size: usize,
+        entries: &[PersistedEntryMeta],
+        available: Option<usize>,
+    ) -> Option<Vec<String>> {
+        let replaced = entries
+            .iter()
+            .find(|entry| entry.key == key)
+            .map_or(0, |entry| entry.size);
+        let mut others: Vec<&PersistedEntryMeta> =
+            entries.iter().filter(|entry| entry.key != key).collect();
+        let used: usize = others.iter().map(|entry| entry.size).sum();
+
+        // Bytes that must be freed to satisfy the cap and the platform's free space
+        let over_cap = self
+            .max_bytes
+            .map_or(0, |max_bytes| (used + size).saturating_sub(max_bytes));
+        let over_platform = available.map_or(0, |available| {
+            size.saturating_sub(available.saturating_add(replaced))
+        });
+        let mut to_free = over_cap.max(over_platform);
+
+        others.sort_by_key(|entry| entry.persisted_at);
+        let mut evicted = Vec::new();
+        for entry in others {
+            if to_free == 0 {
+                break;
+            }
+            to_free = to_free.saturating_sub(entry.size);
+            evicted.push(entry.key.clone());
+        }
+
+        (to_free == 0).then_some(evicted)
+    }
+
+    /// Plan the eviction for a new entry and report the outcome to listeners
+    ///
+    /// Returns the keys the backend must remove before writing the entry, or `None` if the
+    /// entry must not be persisted.
+    pub fn admit(
+        &self,
+        key: &str,
+        size: usize,
+        entries: &[PersistedEntryMeta],
+        available: Option<usize>,
+    ) -> Option<Vec<String>> {
+        let plan = self.plan_eviction(key, size, entries, available);
+        match &plan {
+            Some(evicted) if !evicted.is_empty() => {
+                let freed_bytes = entries
+                    .iter()
+                    .filter(|entry| evicted.contains(&entry.key))
+                    .map(|entry| entry.size)
+                    .sum();
+                crate::debug_log!(
+                    "💾 [PERSIST] Trimmed {} entries ({} bytes) for key: {}",
+                    evicted.len(),
+                    freed_bytes,
+                    key
+                );
+                self.emit(&QuotaEvent::Trimmed {
+                    key: key.to_string(),
+                    evicted: evicted.clone(),
+                    freed_bytes,
+                });
+            }
+            Some(_) => {}
+            None => {
+                crate::debug_log!(
+                    "💾 [PERSIST] Quota exceeded, not persisting key: {} ({} bytes)",
+                    key,
+                    size
+                );
+                self.emit(&QuotaEvent::Exceeded {
+                    key: key.to_string(),
+                    required_bytes: size,
+                });
+            }
+        }
+        plan
+    }
+
+    fn emit(&self, event: &QuotaEvent) {
+        let listeners = match self.listeners.lock() {
+            Ok(listeners) => listeners.clone(),
+            Err(_) => return,
+        };
+        for listener in listeners {
+            listener(event);
+        }
+    }
+}
+
+/// Returns true if a storage error name is the browser's quota error
+///
+/// Browsers throw a `DOMException` named `QuotaExceededError` (or
+/// `NS_ERROR_DOM_QUOTA_REACHED` in older Firefox) when a write exceeds the quota.
+pub fn is_quota_exceeded_error(name: &str) -> bool {
+    matches!(name, "QuotaExceededError" | "NS_ERROR_DOM_QUOTA_REACHED")
+}
+
+/// Future returned by [`HydrationSource::hydrate`], resolving to whether an entry was written
+pub type HydrateFuture = Pin<Box<dyn Future<Output = Result<bool, ProviderError>>>>;
+
+/// A persistence backend that restores entries one key at a time
+pub trait HydrationSource: Send + Sync + 'static {
+    /// List the persisted keys without reading their entries
+    fn keys(&self) -> Result<Vec<String>, ProviderError>;
+
+    /// Read the entry persisted under `key` and write it into `cache`
+    ///
+    /// The backend knows the type stored under each key, e.g. from its query key, and
+    /// writes `Result<Output, Error>` as the provider would. Resolves to whether an entry
+    /// was written.
+    fn hydrate(&self, key: &str, cache: ProviderCache) -> HydrateFuture;
+}
+
+/// The keys of a [`HydrationSource`] and which of them still wait for their first use
+///
+/// Cloning is cheap and clones share their progress.
+#[derive(Clone)]
+pub struct LazyHydration {
+    source: Arc<dyn HydrationSource>,
+    /// Persisted keys not hydrated yet, listed on first use
+    pending: Arc<Mutex<Option<HashSet<String>>>>,
+    /// Keys whose hydration is running
+    hydrating: Arc<Mutex<HashSet<String>>>,
+}
+
+impl LazyHydration {
+    /// Hydrate entries from `source` on first use
+    pub fn new(source: impl HydrationSource) -> Self {
+        Self {
+            source: Arc::new(source),
+            pending: Arc::new(Mutex::new(None)),
+            hydrating: Arc::new(Mutex::new(HashSet::new())),
+        }
+    }
+
+    /// Claim the hydration of `key`, returning true if it is persisted and wasn't claimed
+    ///
+    /// The persisted keys are listed on the first call. The caller must then run
+    /// [`hydrate`](Self::hydrate) for the key.
+    pub fn begin(&self, key: &str) -> bool {
+        let Ok(mut pending) = self.pending.lock() else {
+            return false;
+        };
+        let pending = pending.get_or_insert_with(|| match self.source.keys() {
+            Ok(keys) => keys.into_iter().collect(),
+            Err(_error) => {
+                crate::debug_log!("💾 [HYDRATE] Failed to list persisted keys: {}", _error);
+                HashSet::new()
+            }
+        });
+        if !pending.remove(key) {
+            return false;
+        }
+        if let Ok(mut hydrating) = self.hydrating.lock() {
+            hydrating.insert(key.to_string());
+        }
+        true
+    }
+
+    /// Returns true while the entry for `key` is being hydrated
+    pub fn is_hydrating(&self, key: &str) -> bool {
+        self.hydrating
+            .lock()
+            .is_ok_and(|hydrating| hydrating.contains(key))
+    }
+
+    /// Hydrate a key claimed with [`begin`](Self::begin), returning whether an entry was
+    /// written
+    ///
+    /// Failures are logged and treated as a missing entry, so the provider fetches instead.
+    pub async fn hydrate(&self, key: &str, cache: ProviderCache) -> bool {
+        let hydrated = self
+            .source
+            .hydrate(key, cache)
+            .await
+            .inspect_err(|_error| {
+                crate::debug_log!("💾 [HYDRATE] Failed to hydrate key: {} ({})", key, _error);
+            })
+            .unwrap_or_default();
+        if let Ok(mut hydrating) = self.hydrating.lock() {
+            hydrating.remove(key);
+        }
+        crate::debug_log!("💾 [HYDRATE] Hydrated key: {} (found: {})", key, hydrated);
+        hydrated
+    }
+}
+
+impl std::fmt::Debug for LazyHydration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LazyHydration").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(key: &str, size: usize, persisted_at: u64) -> PersistedEntryMeta {
+        PersistedEntryMeta {
+            key: key.to_string(),
+            size,
+            persisted_at,
+        }
+    }
+
+    #[test]
+    fn trims_oldest_entries_and_reports_events() {
+        let quota = PersistQuota::new().with_max_bytes(100);
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let recorded = events.clone();
+        quota.on_event(move |event| recorded.lock().unwrap().push(event.clone()));
+
+        let entries = [
+            entry("new", 30, 3),
+            entry("old", 40, 1),
+            entry("mid", 30, 2),
+        ];
+
+        // 100 used, replacing "new" reuses its 30 bytes
+        assert_eq!(quota.admit("new", 30, &entries, None), Some(vec![]));
+        assert_eq!(
+            quota.admit("next", 50, &entries, None),
+            Some(vec!["old".to_string(), "mid".to_string()])
+        );
+        assert_eq!(quota.admit("huge", 150, &entries, None), None);
+        assert_eq!(
+            quota.plan_eviction("next", 10, &entries, Some(0)),
+            Some(vec!["old".to_string()])
+        );
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                QuotaEvent::Trimmed {
+                    key: "next".to_string(),
+                    evicted: vec!["old".to_string(), "mid".to_string()],
+                    freed_bytes: 70,
+                },
+                QuotaEvent::Exceeded {
+                    key: "huge".to_string(),
+                    required_bytes: 150,
+                },
+            ]
+        );
+        assert!(is_quota_exceeded_error("QuotaExceededError"));
+    }
+}
@@ -2,8 +2,53 @@
 //!
 //! This module provides unified abstractions for cross-platform functionality,
 //! eliminating code duplication between web and desktop targets.
+//!
+//! Spawning detached tasks, timers and the wall clock go through a [`PlatformExecutor`].
+//! The [`DefaultExecutor`] uses Dioxus's scheduler with tokio timers on native targets and
+//! `wasmtimer` on the web; targets with their own runtime (embedded executors, simulated
+//! clocks in test harnesses) can install a different one with
+//! `ProviderConfig::with_executor`. Tasks tied to a component's lifetime, such as a
+//! provider's fetch, always run on the Dioxus scheduler so they are cancelled with it.
+//!
+//! Connectivity is tracked by the [`network`] module, which providers marked
+//! `refetch_on_reconnect` watch to refetch when the app comes back online.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use dioxus_provider::{platform::{DefaultExecutor, PlatformExecutor}, prelude::*};
+//!
+//! /// Runs the default executor with a clock that tests can move forward
+//! struct TestExecutor { now: Arc<AtomicU64> }
+//!
+//! impl PlatformExecutor for TestExecutor {
+//!     fn spawn(&self, future: LocalBoxFuture<'static, ()>) {
+//!         DefaultExecutor.spawn(future);
+//!     }
+//!     fn sleep(&self, duration: Duration) -> LocalBoxFuture<'static, ()> {
+//!         DefaultExecutor.sleep(duration)
+//!     }
+//!     fn now_secs(&self) -> u64 {
+//!         self.now.load(Ordering::SeqCst)
+//!     }
+//! }
+//!
+//! ProviderConfig::new()
+//!     .with_executor(TestExecutor { now: clock.clone() })
+//!     .init()
+//!     .unwrap();
+//! ```
 
-use std::time::Duration;
+use futures::{
+    StreamExt,
+    future::LocalBoxFuture,
+    stream::{self, LocalBoxStream},
+};
+use std::{
+    fmt,
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
 
 // Cross-platform time imports
 #[cfg(not(target_family = "wasm"))]
@@ -20,24 +65,101 @@ use wasmtimer::tokio::sleep as wasm_sleep;
 // Cross-platform task spawning
 use dioxus::prelude::spawn as dioxus_spawn;
 
+/// Spawning, timers and the clock used by the provider system
+pub trait PlatformExecutor: Send + Sync + 'static {
+    /// Run a detached background task
+    fn spawn(&self, future: LocalBoxFuture<'static, ()>);
+
+    /// Complete after the given duration
+    fn sleep(&self, duration: Duration) -> LocalBoxFuture<'static, ()>;
+
+    /// Current time in seconds since the Unix epoch
+    fn now_secs(&self) -> u64;
+
+    /// Yield every `period`, starting one period from now
+    fn interval(&self, period: Duration) -> LocalBoxStream<'_, ()> {
+        stream::repeat(())
+            .then(move |_| self.sleep(period))
+            .boxed_local()
+    }
+}
+
+/// The built-in executor: Dioxus's scheduler with tokio timers (native) or `wasmtimer` (web)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultExecutor;
+
+impl PlatformExecutor for DefaultExecutor {
+    fn spawn(&self, future: LocalBoxFuture<'static, ()>) {
+        dioxus_spawn(future);
+    }
+
+    fn sleep(&self, duration: Duration) -> LocalBoxFuture<'static, ()> {
+        #[cfg(not(target_family = "wasm"))]
+        return Box::pin(tokio_sleep(duration));
+        #[cfg(target_family = "wasm")]
+        return Box::pin(wasm_sleep(duration));
+    }
+
+    fn now_secs(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+}
+
+/// A shared handle to a [`PlatformExecutor`]
+#[derive(Clone)]
+pub struct SharedExecutor(Arc<dyn PlatformExecutor>);
+
+impl SharedExecutor {
+    /// Share an executor
+    pub fn new(executor: impl PlatformExecutor) -> Self {
+        Self(Arc::new(executor))
+    }
+}
+
+impl fmt::Debug for SharedExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SharedExecutor")
+    }
+}
+
+/// The executor installed with `ProviderConfig::with_executor`, if any
+static EXECUTOR: OnceLock<SharedExecutor> = OnceLock::new();
+
+/// Install the executor used for the rest of the program
+///
+/// Returns false if an executor was already installed; the first one stays in place.
+pub fn set_executor(executor: SharedExecutor) -> bool {
+    EXECUTOR.set(executor).is_ok()
+}
+
+/// The installed executor, or the [`DefaultExecutor`] if none was installed
+pub fn executor() -> &'static dyn PlatformExecutor {
+    match EXECUTOR.get() {
+        Some(executor) => executor.0.as_ref(),
+        None => &DefaultExecutor,
+    }
+}
+
 /// Cross-platform time utilities
 pub mod time {
     use super::*;
 
     /// Get current timestamp in seconds since Unix epoch
     pub fn now_secs() -> u64 {
-        SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs()
+        executor().now_secs()
+    }
+
+    /// Time remaining until a Unix timestamp (seconds), or zero if it has passed
+    pub fn duration_until(timestamp: u64) -> Duration {
+        Duration::from_secs(timestamp.saturating_sub(now_secs()))
     }
 
     /// Sleep for the specified duration
     pub async fn sleep(duration: Duration) {
-        #[cfg(not(target_family = "wasm"))]
-        tokio_sleep(duration).await;
-        #[cfg(target_family = "wasm")]
-        wasm_sleep(duration).await;
+        executor().sleep(duration).await;
     }
 
     /// Format timestamp as relative time (e.g., "5s ago", "2m ago")
@@ -64,7 +186,22 @@ pub mod task {
     where
         F: std::future::Future<Output = ()> + 'static,
     {
-        dioxus_spawn(future);
+        executor().spawn(Box::pin(future));
+    }
+
+    /// Yield to the executor once, letting other tasks run before continuing
+    pub async fn yield_now() {
+        let mut yielded = false;
+        std::future::poll_fn(|cx| {
+            if yielded {
+                std::task::Poll::Ready(())
+            } else {
+                yielded = true;
+                cx.waker().wake_by_ref();
+                std::task::Poll::Pending
+            }
+        })
+        .await;
     }
 
     /// Spawn a task with a name for debugging
@@ -72,7 +209,7 @@ pub mod task {
     where
         F: std::future::Future<Output = ()> + 'static,
     {
-        dioxus_spawn(async move {
+        spawn(async move {
             crate::debug_log!("Starting task: {}", name);
             future.await;
             crate::debug_log!("Completed task: {}", name);
@@ -80,6 +217,186 @@ pub mod task {
     }
 }
 
+/// Run a future on a background worker thread and wait for its output (native targets)
+///
+/// Used by `#[provider(worker)]` so CPU-heavy providers (parsing, image decoding) don't
+/// stall the UI thread. The worker pool is a tokio multi-threaded runtime started on first
+/// use; the output is handed back to the awaiting task on the Dioxus scheduler. A panic in
+/// the future is resumed in the awaiting task.
+#[cfg(not(target_family = "wasm"))]
+pub async fn run_on_worker<F>(future: F) -> F::Output
+where
+    F: std::future::Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    match worker_pool().spawn(future).await {
+        Ok(output) => output,
+        Err(error) => std::panic::resume_unwind(error.into_panic()),
+    }
+}
+
+/// Run a synchronous function on the blocking thread pool and wait for its output (native targets)
+///
+/// Used by `#[provider(blocking)]` for file-system or database providers, so their blocking
+/// calls don't stall the async executor. A panic in the function is resumed in the
+/// awaiting task.
+#[cfg(not(target_family = "wasm"))]
+pub async fn run_blocking<F, T>(function: F) -> T
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    match worker_pool().spawn_blocking(function).await {
+        Ok(output) => output,
+        Err(error) => std::panic::resume_unwind(error.into_panic()),
+    }
+}
+
+/// The tokio runtime backing the worker and blocking pools, started on first use
+#[cfg(not(target_family = "wasm"))]
+fn worker_pool() -> &'static tokio::runtime::Runtime {
+    static WORKERS: OnceLock<tokio::runtime::Runtime> = OnceLock::new();
+
+    WORKERS.get_or_init(|| {
+        tokio::runtime::Builder::new_multi_thread()
+            .thread_name("dioxus-provider-worker")
+            .enable_all()
+            .build()
+            .expect("failed to start the provider worker pool")
+    })
+}
+
+/// Run a future inline on the current task (WASM targets, which have no worker threads)
+#[cfg(target_family = "wasm")]
+pub async fn run_on_worker<F>(future: F) -> F::Output
+where
+    F: std::future::Future + 'static,
+{
+    future.await
+}
+
+/// Run a synchronous function inline (WASM targets, which have no blocking thread pool)
+///
+/// The function blocks the browser's event loop while it runs, which is logged as a warning.
+#[cfg(target_family = "wasm")]
+pub async fn run_blocking<F, T>(function: F) -> T
+where
+    F: FnOnce() -> T + 'static,
+{
+    #[cfg(feature = "tracing")]
+    tracing::warn!(
+        "⚠️ [PLATFORM] Blocking provider runs on the main thread on WASM and may freeze the UI"
+    );
+    function()
+}
+
+/// Online/offline status of the app
+///
+/// The status starts online. On the web it follows the browser's `online` and `offline`
+/// events once something watches it; native apps report changes with [`set_online`], e.g.
+/// from a connectivity check or an OS network monitor.
+///
+/// [`set_online`]: network::set_online
+pub mod network {
+    use dioxus::prelude::*;
+    use tokio::sync::watch;
+
+    use super::*;
+
+    /// The current status, shared by every runtime
+    fn status() -> &'static watch::Sender<bool> {
+        static STATUS: OnceLock<watch::Sender<bool>> = OnceLock::new();
+        STATUS.get_or_init(|| watch::Sender::new(true))
+    }
+
+    /// Returns true unless the app was reported offline
+    pub fn is_online() -> bool {
+        *status().borrow()
+    }
+
+    /// Report whether the app is online
+    ///
+    /// Going from offline to online refetches providers marked `refetch_on_reconnect`.
+    pub fn set_online(online: bool) {
+        let changed = status().send_if_modified(|current| {
+            let changed = *current != online;
+            *current = online;
+            changed
+        });
+        if changed {
+            crate::debug_log!(
+                "🌐 [NETWORK] {}",
+                if online { "Back online" } else { "Offline" }
+            );
+        }
+    }
+
+    /// A receiver notified whenever the status changes
+    pub fn subscribe() -> watch::Receiver<bool> {
+        #[cfg(target_family = "wasm")]
+        listen();
+        status().subscribe()
+    }
+
+    /// Follow the browser's `online` and `offline` events, once per page
+    #[cfg(target_family = "wasm")]
+    fn listen() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use wasm_bindgen::{JsCast, closure::Closure};
+
+        static LISTENING: AtomicBool = AtomicBool::new(false);
+        if LISTENING.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let Some(window) = web_sys::window() else {
+            return;
+        };
+        set_online(window.navigator().on_line());
+        for (event, online) in [("online", true), ("offline", false)] {
+            let listener = Closure::<dyn FnMut()>::new(move || set_online(online));
+            if window
+                .add_event_listener_with_callback(event, listener.as_ref().unchecked_ref())
+                .is_ok()
+            {
+                // The listener lives as long as the page
+                listener.forget();
+            }
+        }
+    }
+
+    /// Hook returning whether the app is online, updated when the status changes
+    ///
+    /// ```rust,ignore
+    /// #[component]
+    /// fn OfflineBanner() -> Element {
+    ///     let online = use_network_status();
+    ///     rsx! {
+    ///         if !online() {
+    ///             div { "You are offline. Changes will sync when you reconnect." }
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn use_network_status() -> ReadSignal<bool> {
+        let mut online = use_signal(is_online);
+        use_hook(|| {
+            let mut changes = subscribe();
+            spawn(async move {
+                loop {
+                    let status = *changes.borrow_and_update();
+                    if *online.peek() != status {
+                        online.set(status);
+                    }
+                    if changes.changed().await.is_err() {
+                        break;
+                    }
+                }
+            });
+        });
+        use_hook(|| ReadSignal::new(online))
+    }
+}
+
 /// Cross-platform configuration
 pub mod config {
     use super::*;
@@ -96,4 +413,20 @@ pub mod config {
 
 pub use config::*;
 /// Re-export commonly used platform functions
-pub use time::{format_relative_time, now_secs, sleep};
+pub use time::{duration_until, format_relative_time, now_secs, sleep};
+
+#[cfg(all(test, not(target_family = "wasm")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn worker_futures_run_off_the_calling_thread() {
+        let caller = std::thread::current().id();
+        let worker =
+            futures::executor::block_on(run_on_worker(async { std::thread::current().id() }));
+        assert_ne!(worker, caller);
+
+        let blocking = futures::executor::block_on(run_blocking(|| std::thread::current().id()));
+        assert_ne!(blocking, caller);
+    }
+}
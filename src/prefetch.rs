@@ -0,0 +1,126 @@
+//! # Prefetch on Intent
+//!
+//! Users usually hover or focus a link shortly before following it. [`use_prefetch_on_intent`]
+//! turns those signals into prefetches: it returns a [`PrefetchIntent`] whose handlers start
+//! fetching the linked data once the pointer or focus has rested on the element for a short
+//! delay, and cancel the pending prefetch when it leaves first. Brushing past a list of links
+//! therefore doesn't fetch every one of them.
+//!
+//! Prefetches run on the runtime's low-priority lane (see
+//! [`ProviderRuntime::prefetch_low_priority`](crate::runtime::ProviderRuntime::prefetch_low_priority)),
+//! so they never get ahead of work the current screen is waiting on.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use dioxus_provider::prelude::*;
+//!
+//! #[component]
+//! fn UserLink(id: u32) -> Element {
+//!     let intent = use_prefetch_on_intent(fetch_user(), id);
+//!
+//!     rsx! {
+//!         Link {
+//!             to: Route::User { id },
+//!             onmouseenter: intent.onmouseenter(),
+//!             onmouseleave: intent.onmouseleave(),
+//!             onfocus: intent.onfocus(),
+//!             onblur: intent.onblur(),
+//!             "User {id}"
+//!         }
+//!     }
+//! }
+//! ```
+
+use dioxus::{core::Task, prelude::*};
+use std::time::Duration;
+
+use crate::{global::hook_runtime, hooks::Provider, param_utils::IntoProviderParam};
+
+/// How long the pointer or focus must rest on an element before its data is prefetched
+pub const INTENT_DELAY: Duration = Duration::from_millis(80);
+
+/// Event handlers that prefetch a provider when the user shows intent to navigate to it
+///
+/// The handlers accept any event type, so they can be attached to any element.
+#[derive(Clone, Copy)]
+pub struct PrefetchIntent {
+    start: Callback<()>,
+    cancel: Callback<()>,
+}
+
+impl PrefetchIntent {
+    /// Schedule the prefetch, unless one is already scheduled
+    pub fn start(&self) {
+        self.start.call(());
+    }
+
+    /// Cancel a scheduled prefetch that hasn't started yet
+    pub fn cancel(&self) {
+        self.cancel.call(());
+    }
+
+    /// Handler for `onmouseenter` that schedules the prefetch
+    pub fn onmouseenter<E>(&self) -> impl FnMut(E) + 'static {
+        let start = self.start;
+        move |_| start.call(())
+    }
+
+    /// Handler for `onfocus` that schedules the prefetch
+    pub fn onfocus<E>(&self) -> impl FnMut(E) + 'static {
+        let start = self.start;
+        move |_| start.call(())
+    }
+
+    /// Handler for `onmouseleave` that cancels a scheduled prefetch
+    pub fn onmouseleave<E>(&self) -> impl FnMut(E) + 'static {
+        let cancel = self.cancel;
+        move |_| cancel.call(())
+    }
+
+    /// Handler for `onblur` that cancels a scheduled prefetch
+    pub fn onblur<E>(&self) -> impl FnMut(E) + 'static {
+        let cancel = self.cancel;
+        move |_| cancel.call(())
+    }
+}
+
+/// Hook that prefetches a provider when the user hovers or focuses an element
+///
+/// The prefetch starts after [`INTENT_DELAY`] without a matching leave or blur, on the
+/// low-priority lane. Data that is already cached or being fetched is not fetched again.
+pub fn use_prefetch_on_intent<P, Args>(provider: P, args: Args) -> PrefetchIntent
+where
+    P: Provider<Args::Param> + Send + Clone,
+    Args: IntoProviderParam,
+{
+    let runtime = hook_runtime();
+    let param = args.into_param();
+    let mut pending = use_signal(|| None::<Task>);
+
+    let start = use_callback(move |()| {
+        if pending.peek().is_some() {
+            return;
+        }
+        let provider = provider.clone();
+        let param = param.clone();
+        let runtime = runtime.clone();
+        let task = spawn(async move {
+            crate::platform::sleep(INTENT_DELAY).await;
+            pending.set(None);
+            crate::debug_log!(
+                "🔮 [PREFETCH] Intent held, prefetching key: {}",
+                provider.id(&param)
+            );
+            runtime.prefetch_low_priority(provider, param);
+        });
+        pending.set(Some(task));
+    });
+    let cancel = use_callback(move |()| {
+        if let Some(task) = pending.write().take() {
+            task.cancel();
+        }
+    });
+
+    PrefetchIntent { start, cancel }
+}
@@ -0,0 +1,160 @@
+//! # Structured Query Keys
+//!
+//! By default a provider's cache key is a hash of its type and parameters, which is unique
+//! but opaque. A [`QueryKey`] is a readable alternative made of parts, such as
+//! `users/42/3` for `QueryKey::of("users").with(42).with(3)`. Because the parts are kept in
+//! order, keys can be matched by prefix (invalidate every `users/...` entry at once), shown
+//! in debugging tools and persisted, and their parameters can be read back with
+//! [`QueryKey::parse`].
+//!
+//! Providers generated by `#[provider]` use query keys automatically: the key is the
+//! function name followed by its parameters, and `FetchUser::key(id)` (the struct
+//! generated for `fetch_user`) builds it without a provider instance. Hand-written providers
+//! opt in by implementing `Provider::query_key`.
+//!
+//! Parameters are written with their `Debug` representation in generated keys, so string
+//! parameters appear quoted. Two providers with the same function name in different modules
+//! share a key namespace; give them distinct names or implement `query_key` by hand.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use dioxus_provider::{prelude::*, query_key::QueryKey};
+//!
+//! #[provider]
+//! async fn fetch_user_posts(user_id: u32, page: u32) -> Result<Vec<Post>, String> {
+//!     // ...
+//! }
+//!
+//! // "fetch_user_posts/7/2"
+//! let key = FetchUserPosts::key(7, 2);
+//! assert_eq!(key.parse_part::<u32>(1), Some(7));
+//!
+//! // Invalidate every page of every user's posts
+//! let invalidate_posts = use_invalidate_prefix(QueryKey::of("fetch_user_posts"));
+//! ```
+
+use std::{any::TypeId, fmt, str::FromStr};
+
+/// Separator between the parts of a rendered key
+const SEPARATOR: char = '/';
+
+/// A cache key made of ordered, readable parts
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct QueryKey {
+    parts: Vec<String>,
+}
+
+impl QueryKey {
+    /// Start a key with its leading part, usually the name of the resource
+    pub fn of(name: impl fmt::Display) -> Self {
+        Self {
+            parts: vec![name.to_string()],
+        }
+    }
+
+    /// Append a part
+    pub fn with(mut self, part: impl fmt::Display) -> Self {
+        self.parts.push(part.to_string());
+        self
+    }
+
+    /// Append a provider parameter, written with its `Debug` representation
+    pub fn with_param(mut self, param: &impl fmt::Debug) -> Self {
+        self.parts.push(format!("{param:?}"));
+        self
+    }
+
+    /// Key of a named provider: the name, followed by the parameter unless it is `()`
+    pub(crate) fn named<Param: fmt::Debug + 'static>(name: &str, param: &Param) -> Self {
+        let key = Self::of(name);
+        if TypeId::of::<Param>() == TypeId::of::<()>() {
+            key
+        } else {
+            key.with_param(param)
+        }
+    }
+
+    /// Parse a rendered key back into its parts
+    ///
+    /// Any string parses; a key that wasn't built from parts (such as a hashed provider id)
+    /// becomes a single part.
+    pub fn parse(key: &str) -> Self {
+        Self {
+            parts: key.split(SEPARATOR).map(decode_part).collect(),
+        }
+    }
+
+    /// The parts of the key, in order
+    pub fn parts(&self) -> &[String] {
+        &self.parts
+    }
+
+    /// The part at `index`, where 0 is the leading name
+    pub fn part(&self, index: usize) -> Option<&str> {
+        self.parts.get(index).map(String::as_str)
+    }
+
+    /// Parse the part at `index` into a value, e.g. a numeric id
+    pub fn parse_part<T: FromStr>(&self, index: usize) -> Option<T> {
+        self.part(index)?.parse().ok()
+    }
+
+    /// Returns true if this key begins with all parts of `prefix`
+    pub fn starts_with(&self, prefix: &QueryKey) -> bool {
+        self.parts.starts_with(&prefix.parts)
+    }
+}
+
+impl fmt::Display for QueryKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, part) in self.parts.iter().enumerate() {
+            if index > 0 {
+                write!(f, "{SEPARATOR}")?;
+            }
+            f.write_str(&encode_part(part))?;
+        }
+        Ok(())
+    }
+}
+
+impl From<QueryKey> for String {
+    fn from(key: QueryKey) -> Self {
+        key.to_string()
+    }
+}
+
+/// Escape the separator and the escape character inside a part
+fn encode_part(part: &str) -> String {
+    part.replace('%', "%25").replace(SEPARATOR, "%2F")
+}
+
+fn decode_part(part: &str) -> String {
+    part.replace("%2F", "/").replace("%25", "%")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_parses_and_matches_prefixes() {
+        let key = QueryKey::of("users").with(42).with("a/b%c");
+        assert_eq!(key.to_string(), "users/42/a%2Fb%25c");
+
+        let parsed = QueryKey::parse(&key.to_string());
+        assert_eq!(parsed, key);
+        assert_eq!(parsed.parse_part::<u32>(1), Some(42));
+        assert_eq!(parsed.part(2), Some("a/b%c"));
+
+        assert!(parsed.starts_with(&QueryKey::of("users")));
+        assert!(parsed.starts_with(&QueryKey::of("users").with(42)));
+        assert!(!parsed.starts_with(&QueryKey::of("users").with(7)));
+        assert!(!QueryKey::parse("users42").starts_with(&QueryKey::of("users")));
+
+        assert_eq!(
+            QueryKey::of("search").with_param(&"rust").to_string(),
+            "search/\"rust\""
+        );
+    }
+}
@@ -0,0 +1,417 @@
+//! # Record and Replay
+//!
+//! Integration tests and bug reproductions shouldn't need the real backend. Wrapping a
+//! provider with a [`Recorder`] captures the parameters and results of every run onto a
+//! [`Tape`], which can be saved to a file; a recorder replaying that tape serves the
+//! recorded results instead of running the provider.
+//!
+//! - **Recording**: [`Recorder::record`] runs the wrapped providers as usual and appends
+//!   each run to the tape under its cache key. Save it with [`Recorder::save`].
+//! - **Replaying**: [`Recorder::replay`] or [`Recorder::load`] answers each run with the
+//!   next response recorded for its cache key, repeating the last one once they run out.
+//!   Keys with nothing recorded fail with a `ProviderError::NotFound`.
+//!
+//! Outputs and errors are stored as JSON, so the wrapped provider's output and error types
+//! must implement `Serialize` and `Deserialize`, and its error type must convert from
+//! [`ProviderError`]. Parameters are stored in their `Debug` form, for reading only.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use dioxus_provider::{prelude::*, record::Recorder};
+//!
+//! // Capture a session against the real backend
+//! let recorder = Recorder::record();
+//! let user = use_provider(recorder.wrap(fetch_user()), 1);
+//! // ... then
+//! recorder.save("fixtures/session.json")?;
+//!
+//! // In the test
+//! let recorder = Recorder::load("fixtures/session.json")?;
+//! let user = use_provider(recorder.wrap(fetch_user()), 1);
+//! ```
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    future::Future,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::{
+    cache::ErrorCaching,
+    errors::ProviderError,
+    history::HistoryWindow,
+    hooks::{CachePolicyFn, NextIntervalFn, NormalizeFn, Provider, SeedFn, WeightFn},
+    persist::Persistence,
+    query_key::QueryKey,
+    retry::RetryPolicy,
+    schedule::Schedule,
+    types::ProviderParamBounds,
+};
+
+/// One recorded provider run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedCall {
+    /// The parameters the provider ran with, in their `Debug` form
+    pub input: String,
+    /// The serialized output or error
+    pub output: Result<serde_json::Value, serde_json::Value>,
+}
+
+/// Recorded provider runs by cache key, in the order they happened
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Tape {
+    calls: BTreeMap<String, Vec<RecordedCall>>,
+}
+
+impl Tape {
+    /// Create an empty tape
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The runs recorded for `key`
+    pub fn calls(&self, key: &str) -> &[RecordedCall] {
+        self.calls.get(key).map_or(&[], Vec::as_slice)
+    }
+
+    /// The cache keys with recorded runs, sorted
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.calls.keys().map(String::as_str)
+    }
+
+    /// Returns true if nothing was recorded
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+
+    /// Export the tape as JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// Read a tape exported with [`to_json`](Self::to_json)
+    pub fn from_json(json: &str) -> Result<Self, ProviderError> {
+        Ok(serde_json::from_str(json)?)
+    }
+}
+
+/// Whether a [`Recorder`] captures runs or serves them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordMode {
+    /// Run providers and append their results to the tape
+    Record,
+    /// Answer runs from the tape without running providers
+    Replay,
+}
+
+/// Captures or replays the runs of the providers it wraps
+///
+/// Clones share their tape, so keep one to save the recording after wrapping providers
+/// with another.
+#[derive(Debug, Clone)]
+pub struct Recorder {
+    mode: RecordMode,
+    tape: Arc<Mutex<Tape>>,
+    /// Number of responses replayed per cache key
+    cursors: Arc<Mutex<HashMap<String, usize>>>,
+}
+
+impl Recorder {
+    /// A recorder capturing runs onto an empty tape
+    pub fn record() -> Self {
+        Self::new(RecordMode::Record, Tape::new())
+    }
+
+    /// A recorder serving the runs on `tape`
+    pub fn replay(tape: Tape) -> Self {
+        Self::new(RecordMode::Replay, tape)
+    }
+
+    /// A recorder serving the runs saved to `path` with [`save`](Self::save)
+    #[cfg(not(target_family = "wasm"))]
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<Self, ProviderError> {
+        let json = std::fs::read_to_string(path)?;
+        Ok(Self::replay(Tape::from_json(&json)?))
+    }
+
+    fn new(mode: RecordMode, tape: Tape) -> Self {
+        Self {
+            mode,
+            tape: Arc::new(Mutex::new(tape)),
+            cursors: Arc::default(),
+        }
+    }
+
+    /// Write the tape to `path` as JSON
+    #[cfg(not(target_family = "wasm"))]
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> Result<(), ProviderError> {
+        Ok(std::fs::write(path, self.tape().to_json())?)
+    }
+
+    /// Whether the recorder captures or replays
+    pub fn mode(&self) -> RecordMode {
+        self.mode
+    }
+
+    /// The runs recorded so far, or being replayed
+    pub fn tape(&self) -> Tape {
+        self.tape
+            .lock()
+            .map(|tape| tape.clone())
+            .unwrap_or_default()
+    }
+
+    /// Wrap `provider` so its runs are captured or replayed by this recorder
+    ///
+    /// The wrapped provider keeps the cache keys and settings of `provider`.
+    pub fn wrap<P>(&self, provider: P) -> Recorded<P> {
+        Recorded {
+            inner: provider,
+            recorder: self.clone(),
+        }
+    }
+
+    fn append(&self, key: &str, call: RecordedCall) {
+        if let Ok(mut tape) = self.tape.lock() {
+            tape.calls.entry(key.to_string()).or_default().push(call);
+        }
+    }
+
+    /// The next recorded response for `key`, repeating the last one once they run out
+    fn next_response(&self, key: &str) -> Option<RecordedCall> {
+        let tape = self.tape.lock().ok()?;
+        let calls = tape.calls.get(key)?;
+        let mut cursors = self.cursors.lock().ok()?;
+        let cursor = cursors.entry(key.to_string()).or_insert(0);
+        let call = calls.get(*cursor).or_else(|| calls.last())?.clone();
+        *cursor += 1;
+        Some(call)
+    }
+}
+
+/// A provider whose runs are captured or replayed by a [`Recorder`]
+#[derive(Debug, Clone)]
+pub struct Recorded<P> {
+    inner: P,
+    recorder: Recorder,
+}
+
+impl<P> Recorded<P> {
+    /// The wrapped provider
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+}
+
+impl<P: PartialEq> PartialEq for Recorded<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner && Arc::ptr_eq(&self.recorder.tape, &other.recorder.tape)
+    }
+}
+
+impl<P, Param> Provider<Param> for Recorded<P>
+where
+    P: Provider<Param>,
+    P::Output: Serialize + DeserializeOwned,
+    P::Error: Serialize + DeserializeOwned + From<ProviderError>,
+    Param: ProviderParamBounds,
+{
+    type Output = P::Output;
+    type Error = P::Error;
+
+    fn run(&self, param: Param) -> impl Future<Output = Result<Self::Output, Self::Error>> {
+        let key = self.inner.id(&param);
+        let inner = self.inner.clone();
+        let recorder = self.recorder.clone();
+        async move {
+            match recorder.mode {
+                RecordMode::Replay => {
+                    let Some(call) = recorder.next_response(&key) else {
+                        return Err(ProviderError::NotFound(format!(
+                            "no recorded response for '{key}'"
+                        ))
+                        .into());
+                    };
+                    crate::debug_log!("📼 [RECORD] Replaying response for key: {}", key);
+                    match call.output {
+                        Ok(output) => serde_json::from_value(output)
+                            .map_err(|error| ProviderError::from(error).into()),
+                        Err(error) => Err(serde_json::from_value(error)
+                            .unwrap_or_else(|error| ProviderError::from(error).into())),
+                    }
+                }
+                RecordMode::Record => {
+                    let input = format!("{param:?}");
+                    let result = inner.run(param).await;
+                    let output = match &result {
+                        Ok(output) => serde_json::to_value(output).map(Ok),
+                        Err(error) => serde_json::to_value(error).map(Err),
+                    };
+                    match output {
+                        Ok(output) => {
+                            crate::debug_log!("📼 [RECORD] Recorded response for key: {}", key);
+                            recorder.append(&key, RecordedCall { input, output });
+                        }
+                        Err(_error) => {
+                            crate::debug_log!(
+                                "⚠️ [RECORD] Failed to serialize response for key {}: {}",
+                                key,
+                                _error
+                            );
+                        }
+                    }
+                    result
+                }
+            }
+        }
+    }
+
+    fn id(&self, param: &Param) -> String {
+        self.inner.id(param)
+    }
+
+    fn query_key(&self, param: &Param) -> Option<QueryKey> {
+        self.inner.query_key(param)
+    }
+
+    fn interval(&self) -> Option<Duration> {
+        self.inner.interval()
+    }
+
+    fn next_interval(&self) -> Option<NextIntervalFn<Self::Output>> {
+        self.inner.next_interval()
+    }
+
+    fn cache_policy(&self) -> Option<CachePolicyFn<Self::Output>> {
+        self.inner.cache_policy()
+    }
+
+    fn weight(&self) -> Option<WeightFn<Self::Output>> {
+        self.inner.weight()
+    }
+
+    fn normalize(&self) -> Option<NormalizeFn<Self::Output>> {
+        self.inner.normalize()
+    }
+
+    fn persistence(&self) -> Option<Persistence<Self::Output>> {
+        self.inner.persistence()
+    }
+
+    fn seed_details(&self) -> Option<SeedFn<Self::Output>> {
+        self.inner.seed_details()
+    }
+
+    fn schedule(&self) -> Option<Schedule> {
+        self.inner.schedule()
+    }
+
+    fn cache_expiration(&self) -> Option<Duration> {
+        self.inner.cache_expiration()
+    }
+
+    fn stale_time(&self) -> Option<Duration> {
+        self.inner.stale_time()
+    }
+
+    fn min_refetch_interval(&self) -> Option<Duration> {
+        self.inner.min_refetch_interval()
+    }
+
+    fn refetch_on_focus(&self) -> bool {
+        self.inner.refetch_on_focus()
+    }
+
+    fn refetch_on_reconnect(&self) -> bool {
+        self.inner.refetch_on_reconnect()
+    }
+
+    fn history(&self) -> Option<HistoryWindow> {
+        self.inner.history()
+    }
+
+    fn cache_errors(&self) -> ErrorCaching {
+        self.inner.cache_errors()
+    }
+
+    fn cache_not_found(&self) -> Option<Duration> {
+        self.inner.cache_not_found()
+    }
+
+    fn retry_policy(&self) -> Option<RetryPolicy> {
+        self.inner.retry_policy()
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.inner.timeout()
+    }
+
+    fn timeout_error(&self, timeout: Duration) -> Option<Self::Error> {
+        self.inner.timeout_error(timeout)
+    }
+
+    fn is_not_found(&self, error: &Self::Error) -> bool {
+        self.inner.is_not_found(error)
+    }
+
+    fn serves(&self) -> &'static [&'static str] {
+        self.inner.serves()
+    }
+
+    fn validate(&self, param: &Param) -> Result<(), Self::Error> {
+        self.inner.validate(param)
+    }
+
+    fn skip(&self, param: &Param) -> Option<Self::Output> {
+        self.inner.skip(param)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hooks::FnProvider;
+    use futures::executor::block_on;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[test]
+    fn replays_recorded_responses_without_running_the_provider() {
+        let runs = Arc::new(AtomicU32::new(0));
+        let counted = runs.clone();
+        let lookup = FnProvider::new("lookup", move |id: u32| {
+            let run = counted.fetch_add(1, Ordering::SeqCst) + 1;
+            async move {
+                if id == 0 {
+                    Err("no such id".to_string())
+                } else {
+                    Ok(format!("item {id} run {run}"))
+                }
+            }
+        });
+
+        let recorder = Recorder::record();
+        let recording = recorder.wrap(lookup.clone());
+        block_on(async {
+            assert_eq!(recording.run(1).await, Ok("item 1 run 1".to_string()));
+            assert_eq!(recording.run(1).await, Ok("item 1 run 2".to_string()));
+            assert!(recording.run(0).await.is_err());
+        });
+        assert_eq!(recording.id(&1), lookup.id(&1));
+        assert_eq!(recorder.tape().calls("lookup/1")[0].input, "1");
+
+        let replayer = Recorder::replay(Tape::from_json(&recorder.tape().to_json()).unwrap());
+        let replaying = replayer.wrap(lookup);
+        block_on(async {
+            assert_eq!(replaying.run(1).await, Ok("item 1 run 1".to_string()));
+            assert_eq!(replaying.run(1).await, Ok("item 1 run 2".to_string()));
+            assert_eq!(replaying.run(1).await, Ok("item 1 run 2".to_string()));
+            assert_eq!(replaying.run(0).await, Err("no such id".to_string()));
+            assert!(replaying.run(2).await.is_err());
+        });
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+    }
+}
@@ -10,32 +10,70 @@
 //! - **Reactive Context Management**: Subscribes and notifies reactive contexts when data changes
 //! - **Interval Tasks**: Manages background tasks for auto-refreshing providers
 //! - **Revalidation Control**: Prevents duplicate revalidations and manages ongoing operations
+//! - **Task Introspection**: Lists and stops the background tasks registered for each key
+//! - **Focus Refetching**: Revalidates providers marked `refetch_on_focus` when the app
+//!   regains focus
+//! - **Reconnect Refetching**: Refetches failed and stale providers marked
+//!   `refetch_on_reconnect` when the app comes back online
+//!
+//! ## Inspecting Background Work
+//!
+//! ```rust,no_run
+//! use dioxus_provider::global::get_global_refresh_registry;
+//!
+//! let registry = get_global_refresh_registry().unwrap();
+//! for task in registry.list_tasks() {
+//!     println!("{} {:?} every {:?}", task.key, task.task_type, task.interval);
+//! }
+//! registry.stop_all_for_key("fetch_user(1)");
+//! ```
+//!
+//! ## Window Focus
+//!
+//! On the web the registry listens for the document's `visibilitychange` event once a
+//! `refetch_on_focus` provider is in use. Desktop windows report focus through the renderer,
+//! so desktop apps forward it with [`RefreshRegistry::notify_focus`]:
+//!
+//! ```rust,ignore
+//! use dioxus::desktop::{use_wry_event_handler, tao::event::{Event, WindowEvent}};
+//!
+//! let registry = get_global_refresh_registry().unwrap();
+//! use_wry_event_handler(move |event, _| {
+//!     if let Event::WindowEvent { event: WindowEvent::Focused(true), .. } = event {
+//!         registry.notify_focus();
+//!     }
+//! });
+//! ```
 //!
 //! ## Cross-Platform Compatibility
 //!
 //! This module uses cross-platform abstractions:
 //! - `dioxus::spawn` for background tasks (works on both web and desktop)
-//! - `wasmtimer` for web timing and `tokio` for desktop timing
+//! - The platform executor for timing (`tokio` on desktop and `wasmtimer` on web by default)
 //! - Automatic task cleanup when components unmount
 
-use dioxus::{core::ReactiveContext, prelude::*};
+use dioxus::core::ReactiveContext;
 use std::{
     collections::{HashMap, HashSet},
     sync::{Arc, Mutex, atomic::AtomicBool},
     time::Duration,
 };
 
+use tokio::sync::watch;
+
 #[cfg(not(target_family = "wasm"))]
-use tokio::time;
+use std::time::Instant;
 #[cfg(target_family = "wasm")]
-use wasmtimer::tokio as time;
+use web_time::Instant;
+
+use crate::{platform::sleep, schedule::Schedule};
 
 /// Type alias for reactive context storage
 type ReactiveContextSet = Arc<Mutex<HashSet<ReactiveContext>>>;
 type ReactiveContextRegistry = Arc<Mutex<HashMap<String, ReactiveContextSet>>>;
 
 /// Task type for different periodic operations
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum TaskType {
     /// Interval refresh task that re-executes providers at regular intervals
     IntervalRefresh,
@@ -45,11 +83,51 @@ pub enum TaskType {
     CacheCleanup,
     /// Cache expiration task that monitors and removes expired entries
     CacheExpiration,
+    /// Refresh task that re-executes providers at wall-clock times from a schedule
+    ScheduledRefresh,
+    /// Refresh task whose next tick is computed from the previously fetched data
+    AlignedRefresh,
+    /// Task revalidating a provider whenever the app regains focus
+    FocusRefetch,
+    /// Task refetching a provider whenever the app comes back online
+    ReconnectRefetch,
+}
+
+impl TaskType {
+    /// Every task type, in declaration order
+    pub const ALL: [TaskType; 8] = [
+        TaskType::IntervalRefresh,
+        TaskType::StaleCheck,
+        TaskType::CacheCleanup,
+        TaskType::CacheExpiration,
+        TaskType::ScheduledRefresh,
+        TaskType::AlignedRefresh,
+        TaskType::FocusRefetch,
+        TaskType::ReconnectRefetch,
+    ];
+}
+
+/// A background task registered for a provider key, from [`RefreshRegistry::list_tasks`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskInfo {
+    /// The provider cache key the task works on
+    pub key: String,
+    /// What the task does
+    pub task_type: TaskType,
+    /// The interval the task was registered with, or the delay until its first run for
+    /// scheduled and aligned refreshes, and zero for focus and reconnect refetches
+    pub interval: Duration,
 }
 
+/// How long a revalidation may hold its key's lock before it is considered stuck
+pub const DEFAULT_REVALIDATION_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// A registered task's type, duration, cancellation flag, and for scheduled and aligned
+/// refreshes the source it was started from, so a changed schedule or delay function restarts it
+type PeriodicTask = (TaskType, Duration, Arc<AtomicBool>, Option<String>);
+
 /// Registry for periodic tasks (intervals and stale checks)
-/// Stores task type, duration, and cancellation flag
-type PeriodicTaskRegistry = Arc<Mutex<HashMap<String, (TaskType, Duration, Arc<AtomicBool>)>>>;
+type PeriodicTaskRegistry = Arc<Mutex<HashMap<String, PeriodicTask>>>;
 
 /// Global registry for refresh signals that can trigger provider re-execution
 ///
@@ -57,6 +135,12 @@ type PeriodicTaskRegistry = Arc<Mutex<HashMap<String, (TaskType, Duration, Arc<A
 /// which reactive contexts are subscribed to which providers, maintains refresh counters,
 /// and manages periodic tasks for both auto-refreshing and stale-checking.
 ///
+/// ## Batching
+///
+/// Refreshes triggered within one scheduler tick are coalesced: a provider completion that
+/// updates its own key, normalized entities and seeded details marks each subscribed
+/// reactive context dirty once, at the end of the tick, however many of its keys changed.
+///
 /// ## Thread Safety
 ///
 /// All internal state is protected by mutexes to ensure thread-safe access across
@@ -69,8 +153,27 @@ pub struct RefreshRegistry {
     reactive_contexts: ReactiveContextRegistry,
     /// Registry of periodic tasks (both interval refresh and stale checking)
     periodic_tasks: PeriodicTaskRegistry,
-    /// Set of provider keys that are currently being revalidated
-    ongoing_revalidations: Arc<Mutex<HashSet<String>>>,
+    /// Provider keys that are currently being revalidated, with the time each one started
+    ongoing_revalidations: Arc<Mutex<HashMap<String, Instant>>>,
+    /// How long a revalidation lock is held before it is released as stuck
+    revalidation_timeout: Option<Duration>,
+    /// Channels publishing refresh counts to code running outside reactive contexts
+    refresh_watchers: Arc<Mutex<HashMap<String, watch::Sender<u64>>>>,
+    /// Reactive contexts to mark dirty at the end of the current tick
+    pending_contexts: ReactiveContextSet,
+    /// Start times of the latest refetch of keys whose provider sets a minimum refetch interval
+    last_refetches: Arc<Mutex<HashMap<String, Instant>>>,
+    /// Keys with a trailing refresh scheduled by the minimum refetch interval
+    trailing_refetches: Arc<Mutex<HashSet<String>>>,
+    /// Keys whose background refreshes are paused, with the number of pauses held on each
+    paused_keys: Arc<Mutex<HashMap<String, usize>>>,
+    /// Whether all background tasks are suspended, see [`RefreshRegistry::pause_background`]
+    background_paused: Arc<AtomicBool>,
+    /// Counts the times the app regained focus, waking focus refetch tasks
+    focus_events: Arc<watch::Sender<u64>>,
+    /// Whether the document's `visibilitychange` listener is installed
+    #[cfg(target_family = "wasm")]
+    focus_listener: Arc<AtomicBool>,
 }
 
 impl RefreshRegistry {
@@ -79,6 +182,19 @@ impl RefreshRegistry {
         Self::default()
     }
 
+    /// Release revalidation locks held longer than `timeout`
+    /// (default [`DEFAULT_REVALIDATION_TIMEOUT`])
+    pub fn with_revalidation_timeout(mut self, timeout: Duration) -> Self {
+        self.revalidation_timeout = Some(timeout);
+        self
+    }
+
+    /// How long a revalidation lock is held before it is released as stuck
+    pub fn revalidation_timeout(&self) -> Duration {
+        self.revalidation_timeout
+            .unwrap_or(DEFAULT_REVALIDATION_TIMEOUT)
+    }
+
     /// Get the current refresh count for a provider key
     ///
     /// Returns the number of times the provider has been refreshed, or 0 if not found.
@@ -94,14 +210,52 @@ impl RefreshRegistry {
     ///
     /// When the provider is refreshed, the reactive context will be marked as dirty,
     /// causing any components using it to re-render.
+    ///
+    /// The subscription is registered with the context itself, which removes it when the
+    /// context is dropped or reruns, so unmounted components don't linger in the registry.
     pub fn subscribe_to_refresh(&self, key: &str, reactive_context: ReactiveContext) {
-        if let Ok(mut contexts) = self.reactive_contexts.lock() {
-            let key_contexts = contexts
+        let key_contexts = match self.reactive_contexts.lock() {
+            Ok(mut contexts) => contexts
                 .entry(key.to_string())
-                .or_insert_with(|| Arc::new(Mutex::new(HashSet::new())));
-            if let Ok(mut context_set) = key_contexts.lock() {
-                context_set.insert(reactive_context);
-            }
+                .or_insert_with(|| Arc::new(Mutex::new(HashSet::new())))
+                .clone(),
+            Err(_) => return,
+        };
+        reactive_context.subscribe(key_contexts);
+    }
+
+    /// Number of live reactive contexts subscribed to refresh events for a provider key
+    pub fn subscriber_count(&self, key: &str) -> usize {
+        self.reactive_contexts
+            .lock()
+            .ok()
+            .and_then(|contexts| contexts.get(key).cloned())
+            .and_then(|key_contexts| key_contexts.lock().ok().map(|set| set.len()))
+            .unwrap_or(0)
+    }
+
+    /// Returns true if any reactive context or refresh watcher is following a provider key
+    pub fn has_subscribers(&self, key: &str) -> bool {
+        self.subscriber_count(key) > 0
+            || self.refresh_watchers.lock().is_ok_and(|watchers| {
+                watchers
+                    .get(key)
+                    .is_some_and(|sender| sender.receiver_count() > 0)
+            })
+    }
+
+    /// Watch refresh events for a provider key outside of a reactive context
+    ///
+    /// The returned receiver holds the key's refresh count and is notified on every refresh,
+    /// for code that can't subscribe a `ReactiveContext`, such as bridges to other runtimes.
+    pub fn watch_refresh(&self, key: &str) -> watch::Receiver<u64> {
+        let count = self.get_refresh_count(key);
+        match self.refresh_watchers.lock() {
+            Ok(mut watchers) => watchers
+                .entry(key.to_string())
+                .or_insert_with(|| watch::Sender::new(count))
+                .subscribe(),
+            Err(_) => watch::Sender::new(count).subscribe(),
         }
     }
 
@@ -109,22 +263,162 @@ impl RefreshRegistry {
     ///
     /// This increments the refresh counter and marks all subscribed reactive contexts
     /// as dirty, causing components to re-render and providers to re-execute.
+    ///
+    /// Inside a Dioxus runtime, contexts are marked at the end of the current tick, so
+    /// refreshing many overlapping keys at once (e.g. after a mutation) marks each context
+    /// only once. Outside a runtime they are marked immediately.
     pub fn trigger_refresh(&self, key: &str) {
         // Increment the counter
+        let mut count = 0;
         if let Ok(mut counters) = self.refresh_counters.lock() {
             let counter = counters.entry(key.to_string()).or_insert(0);
             *counter += 1;
+            count = *counter;
         }
 
-        // Mark all reactive contexts as dirty
-        if let Ok(contexts) = self.reactive_contexts.lock() {
-            if let Some(key_contexts) = contexts.get(key) {
-                if let Ok(context_set) = key_contexts.lock() {
-                    for reactive_context in context_set.iter() {
-                        reactive_context.mark_dirty();
-                    }
-                }
+        // Notify watchers, dropping channels whose receivers are all gone
+        if let Ok(mut watchers) = self.refresh_watchers.lock()
+            && let Some(sender) = watchers.get(key)
+            && sender.send(count).is_err()
+        {
+            watchers.remove(key);
+        }
+
+        // Queue all reactive contexts to be marked dirty
+        let mut schedule_flush = false;
+        if let Ok(contexts) = self.reactive_contexts.lock()
+            && let Some(key_contexts) = contexts.get(key)
+            && let Ok(context_set) = key_contexts.lock()
+            && let Ok(mut pending) = self.pending_contexts.lock()
+        {
+            schedule_flush = pending.is_empty() && !context_set.is_empty();
+            pending.extend(context_set.iter().copied());
+        }
+
+        if schedule_flush {
+            if dioxus::core::Runtime::try_current().is_some() {
+                let registry = self.clone();
+                dioxus::core::spawn_forever(async move {
+                    registry.flush_pending_refreshes();
+                });
+            } else {
+                self.flush_pending_refreshes();
+            }
+        }
+    }
+
+    /// Mark the reactive contexts queued by `trigger_refresh` dirty, once each
+    fn flush_pending_refreshes(&self) {
+        // Reactive contexts hash by pointer, so the key type is stable
+        #[allow(clippy::mutable_key_type)]
+        let pending = match self.pending_contexts.lock() {
+            Ok(mut pending) => std::mem::take(&mut *pending),
+            Err(_) => return,
+        };
+        for reactive_context in pending {
+            reactive_context.mark_dirty();
+        }
+    }
+
+    /// Pause interval, scheduled and stale-while-revalidate refreshes of a provider key
+    ///
+    /// Pauses nest: the key stays paused until each pause has been resumed. Fetches for
+    /// components that have no data yet are not affected.
+    pub fn pause(&self, key: &str) {
+        if let Ok(mut paused) = self.paused_keys.lock() {
+            *paused.entry(key.to_string()).or_insert(0) += 1;
+            crate::debug_log!("⏸️ [PAUSE] Paused background refreshes for key: {}", key);
+        }
+    }
+
+    /// Release one pause of a provider key, returning true if the key is no longer paused
+    pub fn resume(&self, key: &str) -> bool {
+        let Ok(mut paused) = self.paused_keys.lock() else {
+            return false;
+        };
+        let Some(count) = paused.get_mut(key) else {
+            return false;
+        };
+        *count -= 1;
+        if *count > 0 {
+            return false;
+        }
+        paused.remove(key);
+        crate::debug_log!("▶️ [PAUSE] Resumed background refreshes for key: {}", key);
+        true
+    }
+
+    /// Returns true while background refreshes of a provider key are paused
+    pub fn is_paused(&self, key: &str) -> bool {
+        self.paused_keys
+            .lock()
+            .is_ok_and(|paused| paused.contains_key(key))
+    }
+
+    /// Suspend every background task: interval, scheduled and stale-check refreshes, cache
+    /// expiration checks and cache maintenance
+    ///
+    /// Tasks keep their schedule but skip their runs until [`Self::resume_background`].
+    /// Fetches for components and explicit invalidations are not affected.
+    pub fn pause_background(&self) {
+        self.background_paused
+            .store(true, std::sync::atomic::Ordering::SeqCst);
+        crate::debug_log!("⏸️ [PAUSE] Paused all background tasks");
+    }
+
+    /// Let background tasks run again from their next tick
+    pub fn resume_background(&self) {
+        self.background_paused
+            .store(false, std::sync::atomic::Ordering::SeqCst);
+        crate::debug_log!("▶️ [PAUSE] Resumed all background tasks");
+    }
+
+    /// Returns true while background tasks are suspended
+    pub fn is_background_paused(&self) -> bool {
+        self.background_paused
+            .load(std::sync::atomic::Ordering::SeqCst)
+    }
+
+    /// Report that the app regained focus, revalidating providers marked `refetch_on_focus`
+    ///
+    /// Called by the `visibilitychange` listener on the web; desktop apps call it from their
+    /// window focus handler.
+    pub fn notify_focus(&self) {
+        self.focus_events.send_modify(|count| *count += 1);
+        crate::debug_log!("👀 [FOCUS] App regained focus");
+    }
+
+    /// Listen for the document becoming visible again, once per registry
+    #[cfg(target_family = "wasm")]
+    fn listen_for_visibility_changes(&self) {
+        use wasm_bindgen::{JsCast, closure::Closure};
+
+        if self
+            .focus_listener
+            .swap(true, std::sync::atomic::Ordering::SeqCst)
+        {
+            return;
+        }
+        let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+            return;
+        };
+        let registry = self.clone();
+        let listener = Closure::<dyn FnMut()>::new(move || {
+            let visible = web_sys::window()
+                .and_then(|window| window.document())
+                .is_some_and(|document| {
+                    document.visibility_state() == web_sys::VisibilityState::Visible
+                });
+            if visible {
+                registry.notify_focus();
             }
+        });
+        if document
+            .add_event_listener_with_callback("visibilitychange", listener.as_ref().unchecked_ref())
+            .is_ok()
+        {
+            // The listener lives as long as the page
+            listener.forget();
         }
     }
 
@@ -146,13 +440,14 @@ impl RefreshRegistry {
     ///
     /// Creates a background task that will call the provided function at regular
     /// intervals. Supports both interval refresh and stale checking operations.
-    /// If an existing task exists with a longer interval, it will be replaced.
-    /// Tasks with shorter intervals are preserved to avoid unnecessary re-creation.
+    /// An existing task of the same type for the key is kept if its interval is unchanged,
+    /// and cancelled and replaced if the interval differs.
     ///
     /// ## Cross-Platform Implementation
     ///
-    /// Uses `dioxus::spawn` to create tasks that work on both web and desktop platforms.
-    /// Tasks can be cancelled explicitly using stop_* methods, which set a cancellation flag.
+    /// Uses `spawn_forever` to create tasks that work on both web and desktop platforms and
+    /// outlive the component that started them. Tasks are cancelled explicitly using stop_*
+    /// methods, which set a cancellation flag, once the last consumer of the key is released.
     #[cfg(target_family = "wasm")]
     pub fn start_periodic_task<F>(
         &self,
@@ -166,69 +461,67 @@ impl RefreshRegistry {
         if let Ok(mut tasks) = self.periodic_tasks.lock() {
             let task_key = format!("{key}:{task_type:?}");
 
-            // For certain task types, don't create multiple tasks for the same provider
-            if (task_type == TaskType::StaleCheck || task_type == TaskType::CacheExpiration)
-                && tasks
-                    .iter()
-                    .any(|(k, (t, _, _))| k.starts_with(&format!("{key}:")) && *t == task_type)
-            {
-                return;
+            // Keep a task whose interval is unchanged, and replace it otherwise, so durations
+            // changed at runtime take effect instead of the first registered one winning
+            if let Some((_, current_interval, cancel_flag, _)) = tasks.get(&task_key) {
+                if *current_interval == interval {
+                    return;
+                }
+                cancel_flag.store(true, std::sync::atomic::Ordering::SeqCst);
+                crate::debug_log!(
+                    "🔁 [TASKS] Restarting {:?} task for key: {} ({:?} -> {:?})",
+                    task_type,
+                    key,
+                    current_interval,
+                    interval
+                );
+                tasks.remove(&task_key);
             }
 
-            // Cancel existing task if it exists and the new interval is shorter (for interval tasks)
-            let should_create_new_task = match tasks.get(&task_key) {
-                None => true,
-                Some((_, current_interval, cancel_flag)) => {
-                    if task_type == TaskType::IntervalRefresh && interval < *current_interval {
-                        // Signal existing task to stop
-                        cancel_flag.store(true, std::sync::atomic::Ordering::SeqCst);
-                        tasks.remove(&task_key);
-                        true
-                    } else {
-                        false // Don't replace stale check or cache expiration tasks
-                    }
-                }
+            // Adjust interval for different task types
+            let actual_interval = match task_type {
+                TaskType::StaleCheck => Duration::max(
+                    Duration::min(interval / 4, Duration::from_secs(30)),
+                    Duration::from_secs(1),
+                ),
+                TaskType::CacheExpiration => Duration::max(
+                    Duration::min(interval / 4, Duration::from_secs(30)),
+                    Duration::from_secs(1),
+                ),
+                _ => interval,
             };
 
-            if should_create_new_task {
-                // Adjust interval for different task types
-                let actual_interval = match task_type {
-                    TaskType::StaleCheck => Duration::max(
-                        Duration::min(interval / 4, Duration::from_secs(30)),
-                        Duration::from_secs(1),
-                    ),
-                    TaskType::CacheExpiration => Duration::max(
-                        Duration::min(interval / 4, Duration::from_secs(30)),
-                        Duration::from_secs(1),
-                    ),
-                    _ => interval,
-                };
-
-                // Create cancellation flag for this task
-                let cancel_flag = Arc::new(AtomicBool::new(false));
-                let cancel_flag_clone = cancel_flag.clone();
-                let task_fn = Arc::new(task_fn);
-
-                spawn(async move {
-                    loop {
-                        // Check if task should be cancelled before sleeping
-                        if cancel_flag_clone.load(std::sync::atomic::Ordering::SeqCst) {
-                            break;
-                        }
-
-                        time::sleep(actual_interval).await;
-
-                        // Check if task should be cancelled before running
-                        if cancel_flag_clone.load(std::sync::atomic::Ordering::SeqCst) {
-                            break;
-                        }
-
-                        task_fn();
+            // Create cancellation flag for this task
+            let cancel_flag = Arc::new(AtomicBool::new(false));
+            let cancel_flag_clone = cancel_flag.clone();
+            let task_fn = Arc::new(task_fn);
+            let pausable = matches!(task_type, TaskType::IntervalRefresh | TaskType::StaleCheck);
+            let registry = self.clone();
+            let key = key.to_string();
+
+            dioxus::core::spawn_forever(async move {
+                loop {
+                    // Check if task should be cancelled before sleeping
+                    if cancel_flag_clone.load(std::sync::atomic::Ordering::SeqCst) {
+                        break;
                     }
-                });
 
-                tasks.insert(task_key, (task_type, interval, cancel_flag));
-            }
+                    sleep(actual_interval).await;
+
+                    // Check if task should be cancelled before running
+                    if cancel_flag_clone.load(std::sync::atomic::Ordering::SeqCst) {
+                        break;
+                    }
+
+                    if registry.is_background_paused() || (pausable && registry.is_paused(&key)) {
+                        continue;
+                    }
+
+                    task_fn();
+                }
+            });
+
+            tasks.insert(task_key, (task_type, interval, cancel_flag, None));
         }
     }
 
@@ -236,13 +529,14 @@ impl RefreshRegistry {
     ///
     /// Creates a background task that will call the provided function at regular
     /// intervals. Supports both interval refresh and stale checking operations.
-    /// If an existing task exists with a longer interval, it will be replaced.
-    /// Tasks with shorter intervals are preserved to avoid unnecessary re-creation.
+    /// An existing task of the same type for the key is kept if its interval is unchanged,
+    /// and cancelled and replaced if the interval differs.
     ///
     /// ## Cross-Platform Implementation
     ///
-    /// Uses `dioxus::spawn` to create tasks that work on both web and desktop platforms.
-    /// Tasks can be cancelled explicitly using stop_* methods, which set a cancellation flag.
+    /// Uses `spawn_forever` to create tasks that work on both web and desktop platforms and
+    /// outlive the component that started them. Tasks are cancelled explicitly using stop_*
+    /// methods, which set a cancellation flag, once the last consumer of the key is released.
     #[cfg(not(target_family = "wasm"))]
     pub fn start_periodic_task<F>(
         &self,
@@ -256,69 +550,67 @@ impl RefreshRegistry {
         if let Ok(mut tasks) = self.periodic_tasks.lock() {
             let task_key = format!("{key}:{task_type:?}");
 
-            // For certain task types, don't create multiple tasks for the same provider
-            if (task_type == TaskType::StaleCheck || task_type == TaskType::CacheExpiration)
-                && tasks
-                    .iter()
-                    .any(|(k, (t, _, _))| k.starts_with(&format!("{key}:")) && *t == task_type)
-            {
-                return;
+            // Keep a task whose interval is unchanged, and replace it otherwise, so durations
+            // changed at runtime take effect instead of the first registered one winning
+            if let Some((_, current_interval, cancel_flag, _)) = tasks.get(&task_key) {
+                if *current_interval == interval {
+                    return;
+                }
+                cancel_flag.store(true, std::sync::atomic::Ordering::SeqCst);
+                crate::debug_log!(
+                    "🔁 [TASKS] Restarting {:?} task for key: {} ({:?} -> {:?})",
+                    task_type,
+                    key,
+                    current_interval,
+                    interval
+                );
+                tasks.remove(&task_key);
             }
 
-            // Cancel existing task if it exists and the new interval is shorter (for interval tasks)
-            let should_create_new_task = match tasks.get(&task_key) {
-                None => true,
-                Some((_, current_interval, cancel_flag)) => {
-                    if task_type == TaskType::IntervalRefresh && interval < *current_interval {
-                        // Signal existing task to stop
-                        cancel_flag.store(true, std::sync::atomic::Ordering::SeqCst);
-                        tasks.remove(&task_key);
-                        true
-                    } else {
-                        false // Don't replace stale check or cache expiration tasks
-                    }
-                }
+            // Adjust interval for different task types
+            let actual_interval = match task_type {
+                TaskType::StaleCheck => Duration::max(
+                    Duration::min(interval / 4, Duration::from_secs(30)),
+                    Duration::from_secs(1),
+                ),
+                TaskType::CacheExpiration => Duration::max(
+                    Duration::min(interval / 4, Duration::from_secs(30)),
+                    Duration::from_secs(1),
+                ),
+                _ => interval,
             };
 
-            if should_create_new_task {
-                // Adjust interval for different task types
-                let actual_interval = match task_type {
-                    TaskType::StaleCheck => Duration::max(
-                        Duration::min(interval / 4, Duration::from_secs(30)),
-                        Duration::from_secs(1),
-                    ),
-                    TaskType::CacheExpiration => Duration::max(
-                        Duration::min(interval / 4, Duration::from_secs(30)),
-                        Duration::from_secs(1),
-                    ),
-                    _ => interval,
-                };
-
-                // Create cancellation flag for this task
-                let cancel_flag = Arc::new(AtomicBool::new(false));
-                let cancel_flag_clone = cancel_flag.clone();
-                let task_fn = Arc::new(task_fn);
-
-                spawn(async move {
-                    loop {
-                        // Check if task should be cancelled before sleeping
-                        if cancel_flag_clone.load(std::sync::atomic::Ordering::SeqCst) {
-                            break;
-                        }
-
-                        time::sleep(actual_interval).await;
-
-                        // Check if task should be cancelled before running
-                        if cancel_flag_clone.load(std::sync::atomic::Ordering::SeqCst) {
-                            break;
-                        }
-
-                        task_fn();
+            // Create cancellation flag for this task
+            let cancel_flag = Arc::new(AtomicBool::new(false));
+            let cancel_flag_clone = cancel_flag.clone();
+            let task_fn = Arc::new(task_fn);
+            let pausable = matches!(task_type, TaskType::IntervalRefresh | TaskType::StaleCheck);
+            let registry = self.clone();
+            let key = key.to_string();
+
+            dioxus::core::spawn_forever(async move {
+                loop {
+                    // Check if task should be cancelled before sleeping
+                    if cancel_flag_clone.load(std::sync::atomic::Ordering::SeqCst) {
+                        break;
                     }
-                });
 
-                tasks.insert(task_key, (task_type, interval, cancel_flag));
-            }
+                    sleep(actual_interval).await;
+
+                    // Check if task should be cancelled before running
+                    if cancel_flag_clone.load(std::sync::atomic::Ordering::SeqCst) {
+                        break;
+                    }
+
+                    if registry.is_background_paused() || (pausable && registry.is_paused(&key)) {
+                        continue;
+                    }
+
+                    task_fn();
+                }
+            });
+
+            tasks.insert(task_key, (task_type, interval, cancel_flag, None));
         }
     }
 
@@ -366,6 +658,405 @@ impl RefreshRegistry {
         self.start_periodic_task(key, TaskType::StaleCheck, stale_time, stale_check_fn);
     }
 
+    /// Start a scheduled refresh task (WASM version)
+    ///
+    /// Creates a background task that sleeps until the next wall-clock time matched by
+    /// `schedule` and then calls the provided function. An existing task for the same key
+    /// is kept if its schedule is unchanged, and cancelled and replaced otherwise.
+    #[cfg(target_family = "wasm")]
+    pub fn start_scheduled_task<F>(&self, key: &str, schedule: Schedule, task_fn: F)
+    where
+        F: Fn() + 'static,
+    {
+        if let Ok(mut tasks) = self.periodic_tasks.lock() {
+            let task_key = format!("{key}:{:?}", TaskType::ScheduledRefresh);
+            let source = schedule.to_string();
+            if !Self::replace_sourced_task(&mut tasks, &task_key, &source) {
+                return;
+            }
+
+            let cancel_flag = Arc::new(AtomicBool::new(false));
+            let cancel_flag_clone = cancel_flag.clone();
+            let first_delay = schedule.duration_until_next().unwrap_or_default();
+            let registry = self.clone();
+            let key = key.to_string();
+
+            dioxus::core::spawn_forever(async move {
+                while let Some(delay) = schedule.duration_until_next() {
+                    sleep(delay).await;
+
+                    if cancel_flag_clone.load(std::sync::atomic::Ordering::SeqCst) {
+                        break;
+                    }
+
+                    if registry.is_background_paused() || registry.is_paused(&key) {
+                        continue;
+                    }
+
+                    task_fn();
+                }
+            });
+
+            tasks.insert(
+                task_key,
+                (
+                    TaskType::ScheduledRefresh,
+                    first_delay,
+                    cancel_flag,
+                    Some(source),
+                ),
+            );
+        }
+    }
+
+    /// Start a scheduled refresh task (non-WASM version)
+    ///
+    /// Creates a background task that sleeps until the next wall-clock time matched by
+    /// `schedule` and then calls the provided function. An existing task for the same key
+    /// is kept if its schedule is unchanged, and cancelled and replaced otherwise.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn start_scheduled_task<F>(&self, key: &str, schedule: Schedule, task_fn: F)
+    where
+        F: Fn() + Send + 'static,
+    {
+        if let Ok(mut tasks) = self.periodic_tasks.lock() {
+            let task_key = format!("{key}:{:?}", TaskType::ScheduledRefresh);
+            let source = schedule.to_string();
+            if !Self::replace_sourced_task(&mut tasks, &task_key, &source) {
+                return;
+            }
+
+            let cancel_flag = Arc::new(AtomicBool::new(false));
+            let cancel_flag_clone = cancel_flag.clone();
+            let first_delay = schedule.duration_until_next().unwrap_or_default();
+            let registry = self.clone();
+            let key = key.to_string();
+
+            dioxus::core::spawn_forever(async move {
+                while let Some(delay) = schedule.duration_until_next() {
+                    sleep(delay).await;
+
+                    if cancel_flag_clone.load(std::sync::atomic::Ordering::SeqCst) {
+                        break;
+                    }
+
+                    if registry.is_background_paused() || registry.is_paused(&key) {
+                        continue;
+                    }
+
+                    task_fn();
+                }
+            });
+
+            tasks.insert(
+                task_key,
+                (
+                    TaskType::ScheduledRefresh,
+                    first_delay,
+                    cancel_flag,
+                    Some(source),
+                ),
+            );
+        }
+    }
+
+    /// Start a data-aligned refresh task (WASM version)
+    ///
+    /// Creates a background task that asks `next_delay` how long to wait, sleeps, and then
+    /// awaits `task_fn` before asking again, so the delay can depend on the data the previous
+    /// run produced. The task ends when `next_delay` returns `None`.
+    ///
+    /// `source` identifies the delay function: an existing task for the same key is kept if
+    /// it was started from the same source, and cancelled and replaced otherwise.
+    #[cfg(target_family = "wasm")]
+    pub fn start_aligned_task<D, F, Fut>(&self, key: &str, source: &str, next_delay: D, task_fn: F)
+    where
+        D: Fn() -> Option<Duration> + 'static,
+        F: Fn() -> Fut + 'static,
+        Fut: std::future::Future<Output = ()> + 'static,
+    {
+        if let Ok(mut tasks) = self.periodic_tasks.lock() {
+            let task_key = format!("{key}:{:?}", TaskType::AlignedRefresh);
+            let source = source.to_string();
+            if !Self::replace_sourced_task(&mut tasks, &task_key, &source) {
+                return;
+            }
+
+            let cancel_flag = Arc::new(AtomicBool::new(false));
+            let cancel_flag_clone = cancel_flag.clone();
+            let first_delay = next_delay().unwrap_or_default();
+            let registry = self.clone();
+            let key = key.to_string();
+
+            dioxus::core::spawn_forever(async move {
+                while let Some(delay) = next_delay() {
+                    sleep(delay).await;
+
+                    if cancel_flag_clone.load(std::sync::atomic::Ordering::SeqCst) {
+                        break;
+                    }
+
+                    if registry.is_background_paused() || registry.is_paused(&key) {
+                        continue;
+                    }
+
+                    task_fn().await;
+                }
+            });
+
+            tasks.insert(
+                task_key,
+                (
+                    TaskType::AlignedRefresh,
+                    first_delay,
+                    cancel_flag,
+                    Some(source),
+                ),
+            );
+        }
+    }
+
+    /// Start a data-aligned refresh task (non-WASM version)
+    ///
+    /// Creates a background task that asks `next_delay` how long to wait, sleeps, and then
+    /// awaits `task_fn` before asking again, so the delay can depend on the data the previous
+    /// run produced. The task ends when `next_delay` returns `None`.
+    ///
+    /// `source` identifies the delay function: an existing task for the same key is kept if
+    /// it was started from the same source, and cancelled and replaced otherwise.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn start_aligned_task<D, F, Fut>(&self, key: &str, source: &str, next_delay: D, task_fn: F)
+    where
+        D: Fn() -> Option<Duration> + Send + 'static,
+        F: Fn() -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = ()> + 'static,
+    {
+        if let Ok(mut tasks) = self.periodic_tasks.lock() {
+            let task_key = format!("{key}:{:?}", TaskType::AlignedRefresh);
+            let source = source.to_string();
+            if !Self::replace_sourced_task(&mut tasks, &task_key, &source) {
+                return;
+            }
+
+            let cancel_flag = Arc::new(AtomicBool::new(false));
+            let cancel_flag_clone = cancel_flag.clone();
+            let first_delay = next_delay().unwrap_or_default();
+            let registry = self.clone();
+            let key = key.to_string();
+
+            dioxus::core::spawn_forever(async move {
+                while let Some(delay) = next_delay() {
+                    sleep(delay).await;
+
+                    if cancel_flag_clone.load(std::sync::atomic::Ordering::SeqCst) {
+                        break;
+                    }
+
+                    if registry.is_background_paused() || registry.is_paused(&key) {
+                        continue;
+                    }
+
+                    task_fn().await;
+                }
+            });
+
+            tasks.insert(
+                task_key,
+                (
+                    TaskType::AlignedRefresh,
+                    first_delay,
+                    cancel_flag,
+                    Some(source),
+                ),
+            );
+        }
+    }
+
+    /// Start a focus refetch task (WASM version)
+    ///
+    /// Creates a background task that calls the provided function every time
+    /// [`Self::notify_focus`] reports that the app regained focus. An existing task for the
+    /// same key is kept.
+    #[cfg(target_family = "wasm")]
+    pub fn start_focus_task<F>(&self, key: &str, task_fn: F)
+    where
+        F: Fn() + 'static,
+    {
+        self.listen_for_visibility_changes();
+        let focus = self.focus_events.subscribe();
+        self.start_event_task(key, TaskType::FocusRefetch, focus, |_| true, task_fn);
+    }
+
+    /// Start a reconnect refetch task (WASM version)
+    ///
+    /// Creates a background task that calls the provided function every time the app comes
+    /// back online, as reported by [`platform::network`](crate::platform::network). An
+    /// existing task for the same key is kept.
+    #[cfg(target_family = "wasm")]
+    pub fn start_reconnect_task<F>(&self, key: &str, task_fn: F)
+    where
+        F: Fn() + 'static,
+    {
+        let status = crate::platform::network::subscribe();
+        self.start_event_task(
+            key,
+            TaskType::ReconnectRefetch,
+            status,
+            |online| *online,
+            task_fn,
+        );
+    }
+
+    /// Start a task calling `task_fn` on every change of `events` that `fires` accepts
+    #[cfg(target_family = "wasm")]
+    fn start_event_task<T, F>(
+        &self,
+        key: &str,
+        task_type: TaskType,
+        mut events: watch::Receiver<T>,
+        fires: fn(&T) -> bool,
+        task_fn: F,
+    ) where
+        T: 'static,
+        F: Fn() + 'static,
+    {
+        if let Ok(mut tasks) = self.periodic_tasks.lock() {
+            let task_key = format!("{key}:{task_type:?}");
+            if tasks.contains_key(&task_key) {
+                return;
+            }
+
+            let cancel_flag = Arc::new(AtomicBool::new(false));
+            let cancel_flag_clone = cancel_flag.clone();
+            let registry = self.clone();
+            let key = key.to_string();
+
+            dioxus::core::spawn_forever(async move {
+                while events.changed().await.is_ok() {
+                    if cancel_flag_clone.load(std::sync::atomic::Ordering::SeqCst) {
+                        break;
+                    }
+
+                    if !fires(&events.borrow_and_update())
+                        || registry.is_background_paused()
+                        || registry.is_paused(&key)
+                    {
+                        continue;
+                    }
+
+                    task_fn();
+                }
+            });
+
+            tasks.insert(task_key, (task_type, Duration::ZERO, cancel_flag, None));
+        }
+    }
+
+    /// Start a focus refetch task (non-WASM version)
+    ///
+    /// Creates a background task that calls the provided function every time
+    /// [`Self::notify_focus`] reports that the app regained focus. An existing task for the
+    /// same key is kept.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn start_focus_task<F>(&self, key: &str, task_fn: F)
+    where
+        F: Fn() + Send + 'static,
+    {
+        let focus = self.focus_events.subscribe();
+        self.start_event_task(key, TaskType::FocusRefetch, focus, |_| true, task_fn);
+    }
+
+    /// Start a reconnect refetch task (non-WASM version)
+    ///
+    /// Creates a background task that calls the provided function every time the app comes
+    /// back online, as reported by [`platform::network`](crate::platform::network). An
+    /// existing task for the same key is kept.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn start_reconnect_task<F>(&self, key: &str, task_fn: F)
+    where
+        F: Fn() + Send + 'static,
+    {
+        let status = crate::platform::network::subscribe();
+        self.start_event_task(
+            key,
+            TaskType::ReconnectRefetch,
+            status,
+            |online| *online,
+            task_fn,
+        );
+    }
+
+    /// Start a task calling `task_fn` on every change of `events` that `fires` accepts
+    #[cfg(not(target_family = "wasm"))]
+    fn start_event_task<T, F>(
+        &self,
+        key: &str,
+        task_type: TaskType,
+        mut events: watch::Receiver<T>,
+        fires: fn(&T) -> bool,
+        task_fn: F,
+    ) where
+        T: 'static,
+        F: Fn() + Send + 'static,
+    {
+        if let Ok(mut tasks) = self.periodic_tasks.lock() {
+            let task_key = format!("{key}:{task_type:?}");
+            if tasks.contains_key(&task_key) {
+                return;
+            }
+
+            let cancel_flag = Arc::new(AtomicBool::new(false));
+            let cancel_flag_clone = cancel_flag.clone();
+            let registry = self.clone();
+            let key = key.to_string();
+
+            dioxus::core::spawn_forever(async move {
+                while events.changed().await.is_ok() {
+                    if cancel_flag_clone.load(std::sync::atomic::Ordering::SeqCst) {
+                        break;
+                    }
+
+                    if !fires(&events.borrow_and_update())
+                        || registry.is_background_paused()
+                        || registry.is_paused(&key)
+                    {
+                        continue;
+                    }
+
+                    task_fn();
+                }
+            });
+
+            tasks.insert(task_key, (task_type, Duration::ZERO, cancel_flag, None));
+        }
+    }
+
+    /// Cancel the task at `task_key` unless it was started from `source`
+    ///
+    /// Returns true if the caller should start a new task.
+    fn replace_sourced_task(
+        tasks: &mut HashMap<String, PeriodicTask>,
+        task_key: &str,
+        source: &str,
+    ) -> bool {
+        let Some((_task_type, _, cancel_flag, current_source)) = tasks.get(task_key) else {
+            return true;
+        };
+        if current_source.as_deref() == Some(source) {
+            return false;
+        }
+        cancel_flag.store(true, std::sync::atomic::Ordering::SeqCst);
+        crate::debug_log!(
+            "🔁 [TASKS] Restarting {:?} task: {} ({:?} -> {})",
+            _task_type,
+            task_key,
+            current_source,
+            source
+        );
+        tasks.remove(task_key);
+        true
+    }
+
     /// Stop a periodic task
     ///
     /// Signals the task to stop by setting its cancellation flag and removes it from the registry.
@@ -373,13 +1064,66 @@ impl RefreshRegistry {
     pub fn stop_periodic_task(&self, key: &str, task_type: TaskType) {
         if let Ok(mut tasks) = self.periodic_tasks.lock() {
             let task_key = format!("{key}:{task_type:?}");
-            if let Some((_, _, cancel_flag)) = tasks.remove(&task_key) {
+            if let Some((_, _, cancel_flag, _)) = tasks.remove(&task_key) {
                 // Signal the task to stop
                 cancel_flag.store(true, std::sync::atomic::Ordering::SeqCst);
             }
         }
     }
 
+    /// Returns true if a periodic task of the given type is registered for a key
+    pub fn has_periodic_task(&self, key: &str, task_type: TaskType) -> bool {
+        self.periodic_tasks
+            .lock()
+            .is_ok_and(|tasks| tasks.contains_key(&format!("{key}:{task_type:?}")))
+    }
+
+    /// The interval a periodic task was registered with, if one is running for a key
+    pub fn periodic_task_interval(&self, key: &str, task_type: TaskType) -> Option<Duration> {
+        self.periodic_tasks.lock().ok().and_then(|tasks| {
+            tasks
+                .get(&format!("{key}:{task_type:?}"))
+                .map(|(_, interval, _, _)| *interval)
+        })
+    }
+
+    /// All registered background tasks, sorted by key and task type
+    pub fn list_tasks(&self) -> Vec<TaskInfo> {
+        let Ok(tasks) = self.periodic_tasks.lock() else {
+            return Vec::new();
+        };
+        let mut infos: Vec<TaskInfo> = tasks
+            .iter()
+            .filter_map(|(task_key, (task_type, interval, _, _))| {
+                let key = task_key.strip_suffix(&format!(":{task_type:?}"))?;
+                Some(TaskInfo {
+                    key: key.to_string(),
+                    task_type: *task_type,
+                    interval: *interval,
+                })
+            })
+            .collect();
+        infos.sort_by(|a, b| (&a.key, a.task_type).cmp(&(&b.key, b.task_type)));
+        infos
+    }
+
+    /// Number of registered background tasks
+    pub fn task_count(&self) -> usize {
+        self.periodic_tasks.lock().map_or(0, |tasks| tasks.len())
+    }
+
+    /// Stop every background task of a provider key, returning how many were stopped
+    pub fn stop_all_for_key(&self, key: &str) -> usize {
+        TaskType::ALL
+            .into_iter()
+            .filter(|task_type| {
+                let running = self.has_periodic_task(key, *task_type);
+                self.stop_periodic_task(key, *task_type);
+                running
+            })
+            .count()
+    }
+
     /// Stop an interval task
     ///
     /// This is a convenience method for stopping interval refresh tasks.
@@ -396,35 +1140,137 @@ impl RefreshRegistry {
 
     /// Check if a revalidation is currently in progress for a provider key
     ///
-    /// This prevents duplicate revalidations from being started simultaneously.
+    /// A revalidation running longer than the [revalidation timeout](Self::revalidation_timeout)
+    /// no longer counts as in progress.
     pub fn is_revalidation_in_progress(&self, key: &str) -> bool {
-        if let Ok(revalidations) = self.ongoing_revalidations.lock() {
-            revalidations.contains(key)
-        } else {
-            false
-        }
+        let timeout = self.revalidation_timeout();
+        self.ongoing_revalidations
+            .lock()
+            .is_ok_and(|revalidations| {
+                revalidations
+                    .get(key)
+                    .is_some_and(|started| started.elapsed() < timeout)
+            })
     }
 
     /// Start a revalidation for a provider key
     ///
     /// Returns true if the revalidation was started, false if one was already in progress.
-    /// This prevents duplicate revalidations from running simultaneously.
+    /// This prevents duplicate revalidations from running simultaneously. Every successful
+    /// call must be followed by [`complete_revalidation`](Self::complete_revalidation) once
+    /// the revalidation finishes, whether it succeeded or not; prefer
+    /// [`begin_revalidation`](Self::begin_revalidation), which does so automatically.
+    ///
+    /// A lock held longer than the [revalidation timeout](Self::revalidation_timeout), e.g.
+    /// because the task holding it panicked or hung, is released and taken over, so a key
+    /// can't be wedged forever.
     pub fn start_revalidation(&self, key: &str) -> bool {
-        if let Ok(mut revalidations) = self.ongoing_revalidations.lock() {
-            if revalidations.contains(key) {
-                false
-            } else {
-                revalidations.insert(key.to_string());
-                true
+        self.try_lock_revalidation(key).is_some()
+    }
+
+    /// Start a revalidation for a provider key, returning a guard that completes it on drop
+    ///
+    /// Returns None if a revalidation is already in progress. The guard releases the lock
+    /// even if the revalidation panics, and leaves alone a lock that was taken over after
+    /// timing out.
+    ///
+    /// ```rust,no_run
+    /// use dioxus_provider::refresh::RefreshRegistry;
+    ///
+    /// # async fn fetch_and_store() {}
+    /// # async fn example(registry: RefreshRegistry) {
+    /// if let Some(_revalidation) = registry.begin_revalidation("users") {
+    ///     fetch_and_store().await;
+    ///     registry.trigger_refresh("users");
+    /// }
+    /// # }
+    /// ```
+    pub fn begin_revalidation(&self, key: &str) -> Option<RevalidationGuard> {
+        self.try_lock_revalidation(key)
+            .map(|started| RevalidationGuard {
+                registry: self.clone(),
+                key: key.to_string(),
+                started,
+            })
+    }
+
+    /// Take a key's revalidation lock, returning its start time
+    fn try_lock_revalidation(&self, key: &str) -> Option<Instant> {
+        let timeout = self.revalidation_timeout();
+        let Ok(mut revalidations) = self.ongoing_revalidations.lock() else {
+            return None;
+        };
+        if let Some(started) = revalidations.get(key) {
+            let held_for = started.elapsed();
+            if held_for < timeout {
+                return None;
             }
-        } else {
-            false
+            crate::debug_log!(
+                "⚠️ [SWR] Releasing revalidation lock for key: {} held for {:?}",
+                key,
+                held_for
+            );
+        }
+        let now = Instant::now();
+        revalidations.insert(key.to_string(), now);
+        Some(now)
+    }
+
+    /// Record that a fetch of a provider key started, for `try_begin_refetch`
+    pub fn record_refetch(&self, key: &str) {
+        if let Ok(mut last_refetches) = self.last_refetches.lock() {
+            last_refetches.insert(key.to_string(), Instant::now());
         }
     }
 
+    /// Begin a refetch of a provider key, unless one started less than `min_interval` ago
+    ///
+    /// Returns true if the refetch may run now, and records its start. Otherwise a single
+    /// trailing refresh of the key is scheduled for when the interval has passed, so all
+    /// refetches requested in the meantime coalesce into one.
+    pub fn try_begin_refetch(&self, key: &str, min_interval: Duration) -> bool {
+        let Ok(mut last_refetches) = self.last_refetches.lock() else {
+            return true;
+        };
+        let now = Instant::now();
+        let remaining = last_refetches
+            .get(key)
+            .map(|started| min_interval.saturating_sub(now.duration_since(*started)))
+            .filter(|remaining| !remaining.is_zero());
+        let Some(remaining) = remaining else {
+            last_refetches.insert(key.to_string(), now);
+            return true;
+        };
+        drop(last_refetches);
+
+        let is_new_trailing = self
+            .trailing_refetches
+            .lock()
+            .is_ok_and(|mut trailing| trailing.insert(key.to_string()));
+        if is_new_trailing {
+            crate::debug_log!(
+                "⏱️ [MIN-REFETCH] Deferring refetch of key: {} by {:?}",
+                key,
+                remaining
+            );
+            let registry = self.clone();
+            let key = key.to_string();
+            crate::platform::task::spawn(async move {
+                sleep(remaining).await;
+                if let Ok(mut trailing) = registry.trailing_refetches.lock() {
+                    trailing.remove(&key);
+                }
+                registry.trigger_refresh(&key);
+            });
+        }
+        false
+    }
+
     /// Complete a revalidation for a provider key
     ///
-    /// This should be called when a revalidation finishes, regardless of success or failure.
+    /// This should be called when a revalidation started with
+    /// [`start_revalidation`](Self::start_revalidation) finishes, regardless of success or
+    /// failure.
     pub fn complete_revalidation(&self, key: &str) {
         if let Ok(mut revalidations) = self.ongoing_revalidations.lock() {
             revalidations.remove(key);
@@ -482,16 +1328,46 @@ impl RefreshRegistry {
             stats.contexts_removed = initial_context_count - contexts.len();
         }
 
-        // Clean up completed revalidations (should be empty, but just in case)
+        // Release revalidation locks that timed out; running revalidations keep theirs
+        let timeout = self.revalidation_timeout();
         if let Ok(mut revalidations) = self.ongoing_revalidations.lock() {
-            stats.revalidations_cleared = revalidations.len();
-            revalidations.clear();
+            let initial_revalidation_count = revalidations.len();
+            revalidations.retain(|_, started| started.elapsed() < timeout);
+            stats.revalidations_cleared = initial_revalidation_count - revalidations.len();
         }
 
         stats
     }
 }
 
+/// Holds a provider key's revalidation lock, returned by
+/// [`RefreshRegistry::begin_revalidation`]
+///
+/// The lock is released when the guard is dropped.
+#[must_use = "the revalidation lock is released as soon as the guard is dropped"]
+pub struct RevalidationGuard {
+    registry: RefreshRegistry,
+    key: String,
+    started: Instant,
+}
+
+impl RevalidationGuard {
+    /// The provider key being revalidated
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+}
+
+impl Drop for RevalidationGuard {
+    fn drop(&mut self) {
+        if let Ok(mut revalidations) = self.registry.ongoing_revalidations.lock()
+            && revalidations.get(&self.key) == Some(&self.started)
+        {
+            revalidations.remove(&self.key);
+        }
+    }
+}
+
 /// Statistics for the refresh registry
 #[derive(Debug, Clone, Default)]
 pub struct RefreshRegistryStats {
@@ -507,3 +1383,229 @@ pub struct RefreshCleanupStats {
     pub contexts_removed: usize,
     pub revalidations_cleared: usize,
 }
+
+#[cfg(all(test, not(target_family = "wasm")))]
+mod tests {
+    use super::*;
+    use dioxus::{core::NoOpMutations, prelude::*};
+    use futures::FutureExt;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn idle() -> Element {
+        rsx!(div {})
+    }
+
+    #[test]
+    fn refreshes_in_one_tick_mark_each_context_once() {
+        let mut dom = VirtualDom::new(idle);
+        dom.rebuild_in_place();
+        let registry = RefreshRegistry::new();
+        let marks = Arc::new(AtomicU32::new(0));
+
+        dom.runtime().in_scope(ScopeId::ROOT, || {
+            let counter = marks.clone();
+            let context = ReactiveContext::new_with_callback(
+                move || {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                },
+                ScopeId::ROOT,
+                std::panic::Location::caller(),
+            );
+            registry.subscribe_to_refresh("a", context);
+            registry.subscribe_to_refresh("b", context);
+
+            registry.trigger_refresh("a");
+            registry.trigger_refresh("b");
+            registry.trigger_refresh("a");
+        });
+        assert_eq!(marks.load(Ordering::SeqCst), 0);
+        assert_eq!(registry.get_refresh_count("a"), 2);
+
+        while dom.wait_for_work().now_or_never().is_some() {
+            dom.render_immediate(&mut NoOpMutations);
+        }
+        assert_eq!(marks.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn lists_and_stops_tasks_by_key() {
+        let mut dom = VirtualDom::new(idle);
+        dom.rebuild_in_place();
+        let registry = RefreshRegistry::new();
+
+        dom.runtime().in_scope(ScopeId::ROOT, || {
+            registry.start_interval_task("feed", Duration::from_secs(5), || {});
+            registry.start_stale_check_task("feed", Duration::from_secs(60), || {});
+            registry.start_interval_task("clock", Duration::from_secs(1), || {});
+        });
+
+        assert_eq!(registry.task_count(), 3);
+        let tasks = registry.list_tasks();
+        assert_eq!(
+            tasks,
+            [
+                TaskInfo {
+                    key: "clock".to_string(),
+                    task_type: TaskType::IntervalRefresh,
+                    interval: Duration::from_secs(1),
+                },
+                TaskInfo {
+                    key: "feed".to_string(),
+                    task_type: TaskType::IntervalRefresh,
+                    interval: Duration::from_secs(5),
+                },
+                TaskInfo {
+                    key: "feed".to_string(),
+                    task_type: TaskType::StaleCheck,
+                    interval: Duration::from_secs(60),
+                },
+            ]
+        );
+
+        assert_eq!(registry.stop_all_for_key("feed"), 2);
+        assert_eq!(registry.stop_all_for_key("feed"), 0);
+        assert_eq!(registry.task_count(), 1);
+    }
+
+    #[derive(Props, Clone)]
+    struct SubscriberProps {
+        registry: RefreshRegistry,
+        show: Arc<AtomicBool>,
+    }
+
+    impl PartialEq for SubscriberProps {
+        fn eq(&self, _other: &Self) -> bool {
+            false
+        }
+    }
+
+    fn subscriber_app(props: SubscriberProps) -> Element {
+        if props.show.load(Ordering::SeqCst) {
+            rsx!(Subscriber {
+                registry: props.registry.clone(),
+                show: props.show.clone(),
+            })
+        } else {
+            rsx!(div {})
+        }
+    }
+
+    #[allow(non_snake_case)]
+    fn Subscriber(props: SubscriberProps) -> Element {
+        if let Some(context) = ReactiveContext::current() {
+            props.registry.subscribe_to_refresh("a", context);
+        }
+        rsx!(div {})
+    }
+
+    #[test]
+    fn dropped_contexts_are_unsubscribed() {
+        let registry = RefreshRegistry::new();
+        let show = Arc::new(AtomicBool::new(true));
+        let mut dom = VirtualDom::new_with_props(
+            subscriber_app,
+            SubscriberProps {
+                registry: registry.clone(),
+                show: show.clone(),
+            },
+        );
+        dom.rebuild_in_place();
+        assert_eq!(registry.subscriber_count("a"), 1);
+
+        show.store(false, Ordering::SeqCst);
+        dom.mark_dirty(ScopeId::APP);
+        dom.render_immediate(&mut NoOpMutations);
+        assert_eq!(registry.subscriber_count("a"), 0);
+    }
+
+    #[test]
+    fn refetches_within_min_interval_coalesce_into_one() {
+        let mut dom = VirtualDom::new(idle);
+        dom.rebuild_in_place();
+        let registry = RefreshRegistry::new();
+        let min_interval = Duration::from_millis(30);
+
+        registry.record_refetch("a");
+        dom.runtime().in_scope(ScopeId::ROOT, || {
+            assert!(!registry.try_begin_refetch("a", min_interval));
+            assert!(!registry.try_begin_refetch("a", min_interval));
+        });
+
+        tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("tokio runtime")
+            .block_on(async {
+                for _ in 0..5 {
+                    while dom.wait_for_work().now_or_never().is_some() {
+                        dom.render_immediate(&mut NoOpMutations);
+                    }
+                    tokio::time::sleep(Duration::from_millis(15)).await;
+                }
+            });
+        assert_eq!(registry.get_refresh_count("a"), 1);
+        assert!(registry.try_begin_refetch("a", min_interval));
+    }
+
+    #[test]
+    fn periodic_tasks_restart_when_their_interval_changes() {
+        let mut dom = VirtualDom::new(idle);
+        dom.rebuild_in_place();
+        let registry = RefreshRegistry::new();
+        let ticks = Arc::new(AtomicU32::new(0));
+
+        dom.runtime().in_scope(ScopeId::ROOT, || {
+            registry.start_interval_task("a", Duration::from_secs(3600), || {});
+            let counter = ticks.clone();
+            registry.start_interval_task("a", Duration::from_millis(10), move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+            });
+            // The same interval keeps the running task
+            registry.start_interval_task("a", Duration::from_millis(10), || {
+                unreachable!("unchanged interval restarted the task")
+            });
+        });
+        assert_eq!(
+            registry.periodic_task_interval("a", TaskType::IntervalRefresh),
+            Some(Duration::from_millis(10))
+        );
+
+        tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("tokio runtime")
+            .block_on(async {
+                for _ in 0..4 {
+                    while dom.wait_for_work().now_or_never().is_some() {
+                        dom.render_immediate(&mut NoOpMutations);
+                    }
+                    tokio::time::sleep(Duration::from_millis(15)).await;
+                }
+            });
+        assert!(ticks.load(Ordering::SeqCst) > 0);
+
+        registry.stop_interval_task("a");
+        assert!(!registry.has_periodic_task("a", TaskType::IntervalRefresh));
+    }
+
+    #[test]
+    fn stuck_revalidation_locks_time_out() {
+        let registry = RefreshRegistry::new().with_revalidation_timeout(Duration::from_millis(20));
+
+        assert!(registry.start_revalidation("a"));
+        assert!(!registry.start_revalidation("a"));
+        assert!(registry.is_revalidation_in_progress("a"));
+
+        // The lock is never completed, as if its task had panicked
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(!registry.is_revalidation_in_progress("a"));
+        let guard = registry
+            .begin_revalidation("a")
+            .expect("timed out lock is taken over");
+        assert!(registry.begin_revalidation("a").is_none());
+
+        drop(guard);
+        assert!(!registry.is_revalidation_in_progress("a"));
+        assert!(registry.start_revalidation("a"));
+    }
+}
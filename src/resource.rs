@@ -0,0 +1,171 @@
+//! # `use_resource` Interop
+//!
+//! This module helps codebases built on `use_resource` / `use_server_future` adopt provider
+//! caching one fetch at a time. [`from_resource`] wraps the async closure a resource already
+//! uses into a [`ResourceProvider`], which works with every provider hook, so the fetch is
+//! cached, deduplicated and invalidated like any `#[provider]` without being rewritten.
+//!
+//! ## Migration
+//!
+//! | Before                                | After                                                    |
+//! |---------------------------------------|----------------------------------------------------------|
+//! | `use_resource(move \|\| fetch(id))`   | `use_provider(from_resource("fetch", fetch), id)`        |
+//! | `resource.read().clone()`             | `state.read().resource_value()`                          |
+//! | `resource.restart()`                  | `use_invalidate_provider(provider, id)`                  |
+//! | `use_server_future(...)?`             | `use_provider(provider, id).suspend()?`                  |
+//!
+//! Once every caller goes through the provider, the closure can be turned into a
+//! `#[provider]` function without touching the components again.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use dioxus_provider::{prelude::*, resource::from_resource};
+//! use std::time::Duration;
+//!
+//! #[component]
+//! fn User(id: u32) -> Element {
+//!     // Before: let user = use_resource(move || api::fetch_user(id));
+//!     let user = use_provider(
+//!         from_resource("fetch_user", api::fetch_user).with_stale_time(Duration::from_secs(30)),
+//!         id,
+//!     );
+//!
+//!     match user.read().resource_value() {
+//!         Some(Ok(user)) => rsx! { "{user.name}" },
+//!         Some(Err(error)) => rsx! { "Error: {error}" },
+//!         None => rsx! { "Loading..." },
+//!     }
+//! }
+//! ```
+
+use std::{future::Future, time::Duration};
+
+use crate::{
+    hooks::Provider,
+    key_hash::{TypeTag, key_hasher},
+    types::{ProviderErrorBounds, ProviderOutputBounds, ProviderParamBounds},
+};
+
+/// A provider backed by the async closure of an existing resource
+///
+/// Two resource providers are equal when they have the same name, which is also part of
+/// their cache keys.
+#[derive(Clone)]
+pub struct ResourceProvider<F> {
+    name: &'static str,
+    fetch: F,
+    stale_time: Option<Duration>,
+    cache_expiration: Option<Duration>,
+    interval: Option<Duration>,
+}
+
+impl<F> ResourceProvider<F> {
+    /// Wrap an async fetch closure in a provider
+    pub fn new(name: &'static str, fetch: F) -> Self {
+        Self {
+            name,
+            fetch,
+            stale_time: None,
+            cache_expiration: None,
+            interval: None,
+        }
+    }
+
+    /// The name identifying this provider
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Serve cached data older than this while revalidating in the background
+    pub fn with_stale_time(mut self, stale_time: Duration) -> Self {
+        self.stale_time = Some(stale_time);
+        self
+    }
+
+    /// Remove cached data older than this
+    pub fn with_cache_expiration(mut self, cache_expiration: Duration) -> Self {
+        self.cache_expiration = Some(cache_expiration);
+        self
+    }
+
+    /// Refetch the data at a fixed interval
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+}
+
+impl<F> PartialEq for ResourceProvider<F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl<F, Fut, Param, T, E> Provider<Param> for ResourceProvider<F>
+where
+    F: Fn(Param) -> Fut + Clone + 'static,
+    Fut: Future<Output = Result<T, E>>,
+    Param: ProviderParamBounds,
+    T: ProviderOutputBounds,
+    E: ProviderErrorBounds,
+{
+    type Output = T;
+    type Error = E;
+
+    fn run(&self, param: Param) -> impl Future<Output = Result<T, E>> {
+        (self.fetch)(param)
+    }
+
+    fn id(&self, param: &Param) -> String {
+        let hash = key_hasher().hash_param(&[TypeTag::of::<Param>()], param);
+        format!("resource:{}:{:x}", self.name, hash)
+    }
+
+    fn interval(&self) -> Option<Duration> {
+        self.interval
+    }
+
+    fn cache_expiration(&self) -> Option<Duration> {
+        self.cache_expiration
+    }
+
+    fn stale_time(&self) -> Option<Duration> {
+        self.stale_time
+    }
+}
+
+/// Adapt the async closure of a `use_resource` call into a cached provider
+///
+/// The closure receives the provider's parameters instead of capturing them, so the
+/// cache keeps one entry per parameter value. Use `()` for resources without inputs.
+pub fn from_resource<F, Fut, Param, T, E>(name: &'static str, fetch: F) -> ResourceProvider<F>
+where
+    F: Fn(Param) -> Fut + Clone + 'static,
+    Fut: Future<Output = Result<T, E>>,
+{
+    ResourceProvider::new(name, fetch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+
+    async fn double(value: u32) -> Result<u32, String> {
+        Ok(value * 2)
+    }
+
+    #[test]
+    fn resource_closures_run_as_providers() {
+        let provider = from_resource("double", double).with_stale_time(Duration::from_secs(5));
+
+        assert_eq!(block_on(provider.run(21)), Ok(42));
+        assert_eq!(
+            Provider::<u32>::stale_time(&provider),
+            Some(Duration::from_secs(5))
+        );
+        assert_ne!(provider.id(&1), provider.id(&2));
+        assert!(provider == from_resource("double", double));
+    }
+}
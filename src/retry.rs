@@ -0,0 +1,186 @@
+//! # Retrying Failed Fetches
+//!
+//! Transient network errors shouldn't reach the UI as `State::Error` on the first failure.
+//! A provider's [`RetryPolicy`] re-runs it after a delay, up to a number of retries, before
+//! its error is stored. Errors the provider classifies as not-found are permanent and never
+//! retried.
+//!
+//! Retries apply to fetches for components, invalidations, prefetches and
+//! stale-while-revalidate refetches. Consumers keep loading, or keep showing their previous
+//! data, until the last attempt settles.
+//!
+//! Mutations take the same policy with `#[mutation(retry = 3)]`: the mutation stays loading
+//! and its optimistic updates stay applied until the last attempt fails.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use dioxus_provider::prelude::*;
+//!
+//! // Up to 3 retries, waiting 500ms, 1s and 2s
+//! #[provider(retry = 3, retry_delay = "500ms", retry_backoff = "exponential")]
+//! async fn fetch_feed() -> Result<Vec<Post>, String> {
+//!     api::feed().await
+//! }
+//! ```
+
+use std::time::Duration;
+
+use crate::{
+    cache::ProviderCache, chaos, hooks::Provider, mutation::Mutation, platform::sleep,
+    types::ProviderParamBounds,
+};
+
+/// Longest delay between attempts with exponential backoff
+pub const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// How the delay between attempts grows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RetryBackoff {
+    /// The same delay before every retry
+    #[default]
+    Fixed,
+    /// The delay doubles after every retry, up to [`MAX_RETRY_DELAY`]
+    Exponential,
+}
+
+/// How often and how patiently a failing provider is retried
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Number of retries after the first attempt
+    pub retries: u32,
+    /// Delay before the first retry
+    pub delay: Duration,
+    /// How the delay grows for later retries
+    pub backoff: RetryBackoff,
+}
+
+impl RetryPolicy {
+    /// Retry up to `retries` times, one second apart
+    pub fn new(retries: u32) -> Self {
+        Self {
+            retries,
+            delay: Duration::from_secs(1),
+            backoff: RetryBackoff::Fixed,
+        }
+    }
+
+    /// Wait `delay` before the first retry
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Double the delay after every retry, up to [`MAX_RETRY_DELAY`]
+    pub fn exponential(mut self) -> Self {
+        self.backoff = RetryBackoff::Exponential;
+        self
+    }
+
+    /// Delay before retry number `retry`, counting from 1
+    pub fn delay_for(&self, retry: u32) -> Duration {
+        match self.backoff {
+            RetryBackoff::Fixed => self.delay,
+            RetryBackoff::Exponential => {
+                let exponent = retry.saturating_sub(1).min(31);
+                self.delay
+                    .saturating_mul(1u32 << exponent)
+                    .min(MAX_RETRY_DELAY.max(self.delay))
+            }
+        }
+    }
+}
+
+/// Run a provider, retrying errors according to its retry policy
+///
+/// Returns None if chaos dropped a response, or in demo mode, where providers never run.
+pub(crate) async fn fetch<P, Param>(
+    provider: &P,
+    param: Param,
+    cache: &ProviderCache,
+    cache_key: &str,
+) -> Option<Result<P::Output, P::Error>>
+where
+    P: Provider<Param>,
+    Param: ProviderParamBounds,
+{
+    if cache.is_demo() {
+        crate::debug_log!("🎬 [DEMO] Skipping fetch of {}", cache_key);
+        return None;
+    }
+    let Some(policy) = provider.retry_policy() else {
+        return chaos::fetch(provider, param, cache, cache_key).await;
+    };
+
+    let mut retry = 0;
+    loop {
+        let result = chaos::fetch(provider, param.clone(), cache, cache_key).await?;
+        match &result {
+            Err(error) if retry < policy.retries && !provider.is_not_found(error) => {
+                retry += 1;
+                let delay = policy.delay_for(retry);
+                crate::debug_log!(
+                    "🔁 [RETRY] Fetch of {} failed, retry {}/{} in {:?}",
+                    cache_key,
+                    retry,
+                    policy.retries,
+                    delay
+                );
+                sleep(delay).await;
+            }
+            _ => return Some(result),
+        }
+    }
+}
+
+/// Run a mutation, retrying errors according to its retry policy
+pub(crate) async fn mutate<M, Input>(
+    mutation: &M,
+    input: Input,
+    current_data: Option<&Result<M::Output, M::Error>>,
+) -> Result<M::Output, M::Error>
+where
+    M: Mutation<Input>,
+    Input: Clone + PartialEq + 'static,
+{
+    let Some(policy) = mutation.retry_policy() else {
+        return mutation.mutate_with_current(input, current_data).await;
+    };
+
+    let mut retry = 0;
+    loop {
+        let result = mutation
+            .mutate_with_current(input.clone(), current_data)
+            .await;
+        if result.is_err() && retry < policy.retries {
+            retry += 1;
+            let delay = policy.delay_for(retry);
+            crate::debug_log!(
+                "🔁 [RETRY] Mutation {} failed, retry {}/{} in {:?}",
+                mutation.id(),
+                retry,
+                policy.retries,
+                delay
+            );
+            sleep(delay).await;
+        } else {
+            return result;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exponential_delays_double_up_to_the_cap() {
+        let fixed = RetryPolicy::new(3).with_delay(Duration::from_millis(500));
+        assert_eq!(fixed.delay_for(3), Duration::from_millis(500));
+
+        let exponential = fixed.exponential();
+        assert_eq!(exponential.delay_for(1), Duration::from_millis(500));
+        assert_eq!(exponential.delay_for(3), Duration::from_secs(2));
+        assert_eq!(exponential.delay_for(20), MAX_RETRY_DELAY);
+    }
+}
@@ -0,0 +1,146 @@
+//! # Route-Level Data Loading
+//!
+//! This module connects providers to routing for render-as-you-fetch. A [`RouteLoader`]
+//! lists the providers a route needs; [`use_route_loader`], called in a layout component
+//! with the current route, prefetches them as soon as the route is matched, before the
+//! routed component renders. The routed component then reads the data with
+//! [`use_route_data`], joining the requests that are already in flight.
+//!
+//! The helpers are router-agnostic: pass whatever value identifies the current route, such
+//! as the `Route` enum from `dioxus-router`'s `use_route()`.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use dioxus_provider::{prelude::*, route::{RouteLoader, use_route_data, use_route_loader}};
+//!
+//! fn loader(route: &Route) -> RouteLoader {
+//!     match route {
+//!         Route::User { id } => RouteLoader::new()
+//!             .provider(fetch_user(), *id)
+//!             .provider(fetch_user_posts(), *id),
+//!         _ => RouteLoader::new(),
+//!     }
+//! }
+//!
+//! #[component]
+//! fn Layout() -> Element {
+//!     use_route_loader(use_route::<Route>(), loader);
+//!     rsx! { Outlet::<Route> {} }
+//! }
+//!
+//! #[component]
+//! fn User(id: u32) -> Element {
+//!     let user = use_route_data(fetch_user(), id);
+//!     // ...
+//! }
+//! ```
+
+use dioxus::prelude::*;
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    global::hook_runtime,
+    hooks::{Provider, ProviderHandle, use_provider_core},
+    param_utils::IntoProviderParam,
+    runtime::ProviderRuntime,
+};
+
+type PrefetchFn = Rc<dyn Fn(&ProviderRuntime)>;
+
+/// The set of providers a route needs, prefetched when the route is matched
+#[derive(Clone, Default)]
+pub struct RouteLoader {
+    keys: Vec<String>,
+    prefetches: Vec<PrefetchFn>,
+}
+
+impl RouteLoader {
+    /// Create a loader without any providers
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a provider to prefetch when the route is matched
+    pub fn provider<P, Args>(mut self, provider: P, args: Args) -> Self
+    where
+        P: Provider<Args::Param> + Send + Clone,
+        Args: IntoProviderParam,
+    {
+        let param = args.into_param();
+        self.keys.push(provider.id(&param));
+        self.prefetches
+            .push(Rc::new(move |runtime: &ProviderRuntime| {
+                runtime.prefetch(provider.clone(), param.clone());
+            }));
+        self
+    }
+
+    /// Cache keys of the providers this loader prefetches
+    pub fn keys(&self) -> &[String] {
+        &self.keys
+    }
+
+    /// Start fetching every provider that isn't cached or already loading
+    pub fn load(&self, runtime: &ProviderRuntime) {
+        crate::debug_log!(
+            "🧭 [ROUTE] Loading {} providers for matched route",
+            self.keys.len()
+        );
+        for prefetch in &self.prefetches {
+            prefetch(runtime);
+        }
+    }
+}
+
+/// The loader of the currently matched route, shared with routed components
+#[derive(Clone, Default)]
+struct ActiveRouteLoader(Rc<RefCell<RouteLoader>>);
+
+/// Hook that prefetches the providers of the current route whenever it changes
+///
+/// Call it in a layout component above the router outlet. The loader runs during the
+/// layout's render, so the fetches start before the routed component renders. The route's
+/// providers are also hinted to the cache, which evicts them last and keeps the providers
+/// of recently visited routes warm for back navigation.
+pub fn use_route_loader<R>(route: R, loader: fn(&R) -> RouteLoader)
+where
+    R: Clone + PartialEq + 'static,
+{
+    let runtime = hook_runtime();
+    let active = use_context_provider(ActiveRouteLoader::default);
+    let last_route = use_hook(|| Rc::new(RefCell::new(None::<R>)));
+
+    if last_route.borrow().as_ref() != Some(&route) {
+        let route_loader = loader(&route);
+        runtime.cache().set_route_keys(route_loader.keys());
+        route_loader.load(&runtime);
+        *active.0.borrow_mut() = route_loader;
+        *last_route.borrow_mut() = Some(route);
+    }
+}
+
+/// Hook to read a provider loaded by the current route's loader
+///
+/// Behaves like `use_provider`, but joins the request started when the route was matched.
+/// In debug logs, providers that aren't part of the route's loader are reported, since
+/// they only start fetching once the component renders.
+pub fn use_route_data<P, Args>(provider: P, args: Args) -> ProviderHandle<P::Output, P::Error>
+where
+    P: Provider<Args::Param> + Send + Clone,
+    Args: IntoProviderParam,
+{
+    let param = args.into_param();
+
+    if let Some(active) = try_consume_context::<ActiveRouteLoader>() {
+        let cache_key = provider.id(&param);
+        if !active.0.borrow().keys().contains(&cache_key) {
+            crate::debug_log!(
+                "🧭 [ROUTE] Provider {} is not part of the route loader and was not prefetched",
+                cache_key
+            );
+        }
+    }
+
+    use_provider_core(provider, param)
+}
@@ -7,21 +7,49 @@ pub mod request;
 pub mod swr;
 pub mod tasks;
 
+use std::{fmt::Debug, time::Duration};
+
 use crate::{
-    cache::ProviderCache,
+    cache::{CacheMaintenanceStats, CacheStats, CacheStorage, InvalidationMode, ProviderCache},
+    cancel::{CancellationToken, ProviderContext},
+    demo::CacheSnapshot,
+    dynamic::{DynamicProvider, DynamicRegistry},
+    errors::BlockingFetchError,
+    eviction::EvictionPolicy,
     hooks::Provider,
-    refresh::{RefreshRegistry, TaskType},
+    journal::MutationJournal,
+    persist::LazyHydration,
+    platform::DEFAULT_CLEANUP_INTERVAL,
+    query_key::QueryKey,
+    refresh::RefreshRegistry,
+    settings::RuntimeSettings,
+    startup::{PrefetchPlan, PrefetchProgress, StartupPrefetch},
+    state::State,
+    sync::SyncScheduler,
     types::ProviderParamBounds,
 };
-use cache_mgmt::setup_intelligent_cache_management;
+use cache_mgmt::{key_origin, start_maintenance_task};
 use tasks::{
-    setup_cache_expiration_task_core, setup_interval_task_core, setup_stale_check_task_core,
+    setup_aligned_interval_task_core, setup_cache_expiration_task_core,
+    setup_focus_refetch_task_core, setup_interval_task_core, setup_reconnect_refetch_task_core,
+    setup_schedule_task_core, setup_stale_check_task_core,
 };
 
 /// Configuration for the provider runtime.
 #[derive(Debug, Clone)]
 pub struct ProviderRuntimeConfig {
     enable_dependency_injection: bool,
+    mutation_journal: Option<MutationJournal>,
+    lazy_hydration: Option<LazyHydration>,
+    storage: Option<Arc<dyn CacheStorage>>,
+    tiered_storage: bool,
+    demo_snapshot: Option<CacheSnapshot>,
+    invalidation_mode: InvalidationMode,
+    maintenance_interval: Option<Duration>,
+    revalidation_timeout: Option<Duration>,
+    settings: Option<RuntimeSettings>,
+    eviction_policy: Option<Arc<dyn EvictionPolicy>>,
+    prefetch_plan: Option<PrefetchPlan>,
 }
 
 impl ProviderRuntimeConfig {
@@ -29,6 +57,17 @@ impl ProviderRuntimeConfig {
     pub fn new() -> Self {
         Self {
             enable_dependency_injection: false,
+            mutation_journal: None,
+            lazy_hydration: None,
+            storage: None,
+            tiered_storage: false,
+            demo_snapshot: None,
+            invalidation_mode: InvalidationMode::default(),
+            maintenance_interval: Some(DEFAULT_CLEANUP_INTERVAL),
+            revalidation_timeout: None,
+            settings: None,
+            eviction_policy: None,
+            prefetch_plan: None,
         }
     }
 
@@ -38,6 +77,86 @@ impl ProviderRuntimeConfig {
         self
     }
 
+    /// Attach a mutation journal used for crash recovery of in-flight mutations.
+    pub fn with_mutation_journal(mut self, journal: MutationJournal) -> Self {
+        self.mutation_journal = Some(journal);
+        self
+    }
+
+    /// Hydrate persisted entries on their key's first use instead of at startup.
+    pub fn with_lazy_hydration(mut self, hydration: LazyHydration) -> Self {
+        self.lazy_hydration = Some(hydration);
+        self
+    }
+
+    /// Persist the entries of providers marked `#[provider(persist)]` in `storage`.
+    pub fn with_storage(mut self, storage: impl CacheStorage) -> Self {
+        self.storage = Some(Arc::new(storage));
+        self
+    }
+
+    /// Use `storage` as a disk tier behind the in-memory cache.
+    ///
+    /// Entries of providers marked `#[provider(persist)]` are persisted as with
+    /// `with_storage`, and once maintenance evicts them from memory they are restored from
+    /// `storage` on their next use instead of being fetched again.
+    pub fn with_tiered_storage(mut self, storage: impl CacheStorage) -> Self {
+        self.storage = Some(Arc::new(storage));
+        self.tiered_storage = true;
+        self
+    }
+
+    /// Serve the entries of a captured snapshot and never run providers.
+    ///
+    /// Replaces any storage set with `with_storage`; see [`demo`](crate::demo).
+    pub fn with_demo_snapshot(mut self, snapshot: CacheSnapshot) -> Self {
+        self.demo_snapshot = Some(snapshot);
+        self
+    }
+
+    /// Evict cache entries in the order `policy` chooses instead of least recently used first.
+    pub fn with_eviction_policy(mut self, policy: impl EvictionPolicy) -> Self {
+        self.eviction_policy = Some(Arc::new(policy));
+        self
+    }
+
+    /// Prefetch the providers of `plan` once the app first renders (see
+    /// [`startup`](crate::startup))
+    pub fn with_prefetch_plan(mut self, plan: PrefetchPlan) -> Self {
+        self.prefetch_plan = Some(plan);
+        self
+    }
+
+    /// Choose how `use_invalidate_provider` treats cached entries.
+    pub fn with_invalidation_mode(mut self, mode: InvalidationMode) -> Self {
+        self.invalidation_mode = mode;
+        self
+    }
+
+    /// How often the background maintenance loop cleans up the cache.
+    pub fn with_maintenance_interval(mut self, interval: Duration) -> Self {
+        self.maintenance_interval = Some(interval);
+        self
+    }
+
+    /// Don't run background maintenance; call `ProviderRuntime::maintain_now` instead.
+    pub fn without_background_maintenance(mut self) -> Self {
+        self.maintenance_interval = None;
+        self
+    }
+
+    /// Release revalidation locks held longer than this, e.g. by a task that panicked.
+    pub fn with_revalidation_timeout(mut self, timeout: Duration) -> Self {
+        self.revalidation_timeout = Some(timeout);
+        self
+    }
+
+    /// Start with these live settings instead of the defaults.
+    pub fn with_settings(mut self, settings: RuntimeSettings) -> Self {
+        self.settings = Some(settings);
+        self
+    }
+
     pub(crate) fn dependency_injection_enabled(&self) -> bool {
         self.enable_dependency_injection
     }
@@ -55,6 +174,16 @@ pub struct ProviderRuntime {
     cache: ProviderCache,
     refresh_registry: RefreshRegistry,
     pending_requests: Arc<Mutex<HashMap<String, u32>>>,
+    in_flight: Arc<Mutex<HashMap<String, CancellationToken>>>,
+    mutation_journal: Option<MutationJournal>,
+    lazy_hydration: Option<LazyHydration>,
+    sync_scheduler: SyncScheduler,
+    invalidation_mode: InvalidationMode,
+    maintenance_interval: Option<Duration>,
+    maintenance_started: Arc<AtomicBool>,
+    task_subscribers: Arc<Mutex<HashMap<String, usize>>>,
+    dynamic_providers: DynamicRegistry,
+    startup: StartupPrefetch,
 }
 
 /// Lightweight clones of the runtime handles for consumer code.
@@ -71,10 +200,40 @@ impl ProviderRuntime {
             crate::injection::ensure_dependency_injection_initialized();
         }
 
+        let mut refresh_registry = RefreshRegistry::new();
+        if let Some(timeout) = config.revalidation_timeout {
+            refresh_registry = refresh_registry.with_revalidation_timeout(timeout);
+        }
+        let mut cache = ProviderCache::new();
+        if let Some(snapshot) = config.demo_snapshot {
+            cache = cache.with_demo_snapshot(snapshot);
+        } else if let Some(storage) = config.storage {
+            cache = if config.tiered_storage {
+                cache.with_tiered_storage(storage)
+            } else {
+                cache.with_storage(storage)
+            };
+        }
+        if let Some(settings) = config.settings {
+            cache.settings().set(settings);
+        }
+        if let Some(policy) = config.eviction_policy {
+            cache = cache.with_eviction_policy(policy);
+        }
         Self {
-            cache: ProviderCache::new(),
-            refresh_registry: RefreshRegistry::new(),
+            sync_scheduler: SyncScheduler::for_runtime(refresh_registry.clone(), cache.clone()),
+            cache,
+            refresh_registry,
             pending_requests: Arc::new(Mutex::new(HashMap::new())),
+            in_flight: Arc::new(Mutex::new(HashMap::new())),
+            mutation_journal: config.mutation_journal,
+            lazy_hydration: config.lazy_hydration,
+            invalidation_mode: config.invalidation_mode,
+            maintenance_interval: config.maintenance_interval,
+            maintenance_started: Arc::new(AtomicBool::new(false)),
+            task_subscribers: Arc::new(Mutex::new(HashMap::new())),
+            dynamic_providers: DynamicRegistry::new(),
+            startup: StartupPrefetch::new(config.prefetch_plan),
         }
     }
 
@@ -88,6 +247,102 @@ impl ProviderRuntime {
         &self.refresh_registry
     }
 
+    /// Access the mutation journal, if one was configured.
+    pub fn mutation_journal(&self) -> Option<&MutationJournal> {
+        self.mutation_journal.as_ref()
+    }
+
+    /// Access the lazy hydration of persisted entries, if one was configured.
+    pub fn lazy_hydration(&self) -> Option<&LazyHydration> {
+        self.lazy_hydration.as_ref()
+    }
+
+    /// Returns true while the persisted entry for a cache key is being hydrated.
+    pub fn is_hydrating(&self, cache_key: &str) -> bool {
+        self.lazy_hydration
+            .as_ref()
+            .is_some_and(|hydration| hydration.is_hydrating(cache_key))
+    }
+
+    /// How invalidation treats cached entries.
+    pub fn invalidation_mode(&self) -> InvalidationMode {
+        self.invalidation_mode
+    }
+
+    /// Access the background sync scheduler.
+    pub fn sync_scheduler(&self) -> &SyncScheduler {
+        &self.sync_scheduler
+    }
+
+    /// The live settings of this runtime.
+    pub fn settings(&self) -> RuntimeSettings {
+        self.cache.settings().get()
+    }
+
+    /// Replace the live settings of this runtime.
+    ///
+    /// Maintenance passes and stale checks pick up the new values the next time they run,
+    /// and consumers of every active provider re-register their tasks, so a new default
+    /// stale time applies right away. The logging toggle applies to the whole program.
+    pub fn reconfigure(&self, settings: RuntimeSettings) {
+        crate::debug_log!("⚙️ [SETTINGS] Reconfiguring runtime: {:?}", settings);
+        self.cache.settings().set(settings);
+        let active_keys: Vec<String> = self
+            .task_subscribers
+            .lock()
+            .map(|subscribers| subscribers.keys().cloned().collect())
+            .unwrap_or_default();
+        for key in active_keys {
+            self.refresh_registry.trigger_refresh(&key);
+        }
+    }
+
+    /// Register a provider under `name`, for `use_dynamic_provider`.
+    ///
+    /// Replaces any provider registered under the name and refetches its cached entries.
+    pub fn register_provider<P, Param>(&self, name: &str, provider: P)
+    where
+        P: Provider<Param> + Send + Sync,
+        Param: ProviderParamBounds,
+    {
+        crate::debug_log!("🔌 [DYNAMIC] Registering provider: {}", name);
+        self.dynamic_providers.register(name, provider);
+        self.invalidate_dynamic(name);
+    }
+
+    /// Remove the provider registered under `name`, returning true if there was one.
+    ///
+    /// Its consumers are refetched and resolve to an error until a provider is registered
+    /// under the name again.
+    pub fn unregister_provider(&self, name: &str) -> bool {
+        let removed = self.dynamic_providers.unregister(name);
+        if removed {
+            crate::debug_log!("🔌 [DYNAMIC] Unregistered provider: {}", name);
+            self.invalidate_dynamic(name);
+        }
+        removed
+    }
+
+    /// The providers registered by name.
+    pub fn dynamic_providers(&self) -> &DynamicRegistry {
+        &self.dynamic_providers
+    }
+
+    /// The provider registered under `name`, looked up each time it runs.
+    pub fn dynamic_provider<Param, T, E>(&self, name: &str) -> DynamicProvider<Param, T, E> {
+        self.dynamic_providers.provider(name)
+    }
+
+    fn invalidate_dynamic(&self, name: &str) {
+        let prefix = QueryKey::of(name);
+        for cache_key in self
+            .cache
+            .invalidate_prefix(&prefix, self.invalidation_mode)
+        {
+            self.refresh_registry.trigger_refresh(&cache_key);
+        }
+    }
+
     /// Get cloned handles for cache and refresh registry.
     pub fn handles(&self) -> ProviderRuntimeHandles {
         ProviderRuntimeHandles {
@@ -96,17 +351,351 @@ impl ProviderRuntime {
         }
     }
 
-    /// Stop all scheduled tasks for a cache key.
+    /// Register a consumer of a cache key's scheduled tasks.
+    ///
+    /// Pair every call with [`release_provider_tasks`](Self::release_provider_tasks); the
+    /// tasks keep running while at least one consumer holds the key. Returns the number of
+    /// consumers after registering.
+    pub fn retain_provider_tasks(&self, cache_key: &str) -> usize {
+        let Ok(mut subscribers) = self.task_subscribers.lock() else {
+            return 0;
+        };
+        let count = subscribers.entry(cache_key.to_string()).or_insert(0);
+        *count += 1;
+        *count
+    }
+
+    /// Drop a consumer of a cache key's scheduled tasks.
+    ///
+    /// The tasks are stopped when the last consumer is released, and started again by
+    /// `ensure_provider_tasks` once the key is retained anew. Releasing the last consumer
+    /// also starts the key's history grace period, if its provider retains history.
+    pub fn release_provider_tasks(&self, cache_key: &str) {
+        let last_consumer = self.task_subscribers.lock().is_ok_and(|mut subscribers| {
+            match subscribers.get_mut(cache_key) {
+                Some(count) if *count > 1 => {
+                    *count -= 1;
+                    false
+                }
+                Some(_) => {
+                    subscribers.remove(cache_key);
+                    true
+                }
+                None => false,
+            }
+        });
+        if last_consumer {
+            self.cancel_request(cache_key);
+            self.stop_provider_tasks(cache_key);
+            self.cache.history().leave(cache_key);
+            crate::debug_log!(
+                "🧹 [CLEANUP] Last consumer released, stopped tasks for key: {}",
+                cache_key
+            );
+        }
+    }
+
+    /// Number of consumers currently holding a cache key's scheduled tasks.
+    pub fn provider_task_subscribers(&self, cache_key: &str) -> usize {
+        self.task_subscribers
+            .lock()
+            .map_or(0, |subscribers| *subscribers.get(cache_key).unwrap_or(&0))
+    }
+
+    /// Stop all scheduled tasks for a cache key, regardless of its consumers.
     pub fn stop_provider_tasks(&self, cache_key: &str) {
-        self.refresh_registry.stop_interval_task(cache_key);
-        self.refresh_registry
-            .stop_periodic_task(cache_key, TaskType::CacheExpiration);
-        self.refresh_registry
-            .stop_periodic_task(cache_key, TaskType::StaleCheck);
+        self.refresh_registry.stop_all_for_key(cache_key);
+    }
+
+    /// Run a full cache maintenance pass right away.
+    ///
+    /// Removes unused entries and evicts least recently used ones beyond the size limit,
+    /// the same as the background maintenance loop does on each tick.
+    pub fn maintain_now(&self) -> CacheMaintenanceStats {
+        let stats = self.cache.maintain();
+        crate::debug_log!(
+            "🧹 [MAINTENANCE] Manual pass removed {} unused and evicted {} entries",
+            stats.unused_removed,
+            stats.lru_evicted
+        );
+        stats
+    }
+
+    /// Cache statistics, including how many components observe each entry.
+    ///
+    /// Entries nobody observes are only cached: maintenance removes them once they go
+    /// unused for the `unused_threshold` setting. Comparing observed and unobserved
+    /// entries shows whether that threshold and the cache size limit fit the app.
+    pub fn cache_stats(&self) -> CacheStats {
+        let mut stats = self.cache.stats();
+        for key in self.cache.keys_snapshot() {
+            let observers = self.refresh_registry.subscriber_count(&key);
+            stats.total_observers += observers;
+            stats.observed_entries += usize::from(observers > 0);
+            stats.observers.insert(key, observers);
+        }
+        stats
+    }
+
+    /// Suspend all background activity of this runtime.
+    ///
+    /// Interval, scheduled and stale-check refreshes, cache expiration checks and cache
+    /// maintenance skip their runs until [`Self::resume_background`], e.g. in a low power
+    /// mode, during a long export, or while a desktop window is minimized. Components
+    /// still fetch data they don't have, and invalidations still refetch.
+    pub fn pause_background(&self) {
+        self.refresh_registry.pause_background();
+    }
+
+    /// Resume background activity paused by [`Self::pause_background`].
+    ///
+    /// Each task runs again at its next tick; stale entries are revalidated by the next
+    /// stale check.
+    pub fn resume_background(&self) {
+        self.refresh_registry.resume_background();
+    }
+
+    /// Whether background activity is paused.
+    pub fn is_background_paused(&self) -> bool {
+        self.refresh_registry.is_background_paused()
+    }
+
+    /// Refetch the entries of every provider serving a server entity.
+    ///
+    /// Providers declare the entities they serve with `#[provider(serves = [...])]`. With
+    /// an id, only entries whose first parameter matches it, and entries of providers
+    /// without parameters, are invalidated; entries are treated according to the
+    /// runtime's invalidation mode. Returns the invalidated cache keys.
+    pub fn invalidate_entity(&self, entity: &str, id: Option<&dyn Debug>) -> Vec<String> {
+        let keys = self
+            .cache
+            .invalidate_entity(entity, id, self.invalidation_mode);
+        for key in &keys {
+            self.refresh_registry.trigger_refresh(key);
+        }
+        keys
+    }
+
+    /// Start the background maintenance loop unless it is running or disabled.
+    fn ensure_maintenance_task(&self) {
+        if let Some(interval) = self.maintenance_interval
+            && !self.maintenance_started.swap(true, Ordering::SeqCst)
+        {
+            start_maintenance_task(&self.cache, &self.refresh_registry, interval);
+        }
+    }
+
+    /// Start fetching a provider into the cache ahead of any component using it.
+    ///
+    /// Returns whether a fetch was started; cached or already pending entries are skipped.
+    pub fn prefetch<P, Param>(&self, provider: P, param: Param) -> bool
+    where
+        P: Provider<Param> + Send + Clone,
+        Param: ProviderParamBounds,
+    {
+        request::prefetch_provider(self, provider, param)
+    }
+
+    /// Prefetch a provider on the low-priority lane.
+    ///
+    /// The fetch starts only after the executor has run the work already queued, such as
+    /// pending renders and the fetches they started, so speculative prefetches don't delay
+    /// data the current screen needs. Must be called while the Dioxus runtime is running.
+    pub fn prefetch_low_priority<P, Param>(&self, provider: P, param: Param)
+    where
+        P: Provider<Param> + Send + Clone,
+        Param: ProviderParamBounds,
+    {
+        let runtime = self.clone();
+        dioxus::prelude::spawn(async move {
+            crate::platform::task::yield_now().await;
+            runtime.prefetch(provider, param);
+        });
+    }
+
+    /// Run a prefetch plan now, reporting its progress instead of the startup plan's.
+    ///
+    /// Must be called while the Dioxus runtime is running.
+    pub fn run_prefetch_plan(&self, plan: PrefetchPlan) {
+        self.startup.run(self, plan);
+    }
+
+    /// Start the plan given to `with_prefetch_plan`, unless it already started.
+    pub(crate) fn start_prefetch_plan(&self) {
+        self.startup.start(self);
+    }
+
+    /// Progress of the latest prefetch plan.
+    pub fn prefetch_progress(&self) -> PrefetchProgress {
+        self.startup.progress()
+    }
+
+    /// A receiver notified whenever the latest prefetch plan progresses.
+    pub fn subscribe_prefetch_progress(&self) -> tokio::sync::watch::Receiver<PrefetchProgress> {
+        self.startup.subscribe()
+    }
+
+    /// Mirror a provider's state into a tokio watch channel (native targets).
+    ///
+    /// For subsystems outside Dioxus, such as an audio engine or a sync daemon, that need
+    /// the same cached data as the UI. The bridge fetches the provider if it isn't cached,
+    /// refetches it after invalidation and publishes every change; it stops once all
+    /// receivers are dropped. Must be called while the Dioxus runtime is running.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn bridge_to_watch<P, Param>(
+        &self,
+        provider: P,
+        param: Param,
+    ) -> tokio::sync::watch::Receiver<State<P::Output, P::Error>>
+    where
+        P: Provider<Param> + Send + Clone,
+        Param: ProviderParamBounds,
+    {
+        let cache_key = provider.id(&param);
+        let cached_state = |cache: &ProviderCache, cache_key: &str| match cache
+            .get::<Result<P::Output, P::Error>>(cache_key)
+        {
+            Some(Ok(data)) => Some(State::Success(data)),
+            Some(Err(error)) => Some(State::Error(error)),
+            None => None,
+        };
+
+        let initial = cached_state(&self.cache, &cache_key).unwrap_or_else(|| State::Loading {
+            task: dioxus::core::spawn_forever(async {}),
+        });
+        let sender = tokio::sync::watch::Sender::new(initial);
+        let receiver = sender.subscribe();
+        let mut refreshes = self.refresh_registry.watch_refresh(&cache_key);
+        self.retain_provider_tasks(&cache_key);
+        self.ensure_provider_tasks(&provider, &param, &cache_key);
+
+        crate::debug_log!("🌉 [BRIDGE] Bridging key to watch channel: {}", cache_key);
+        let runtime = self.clone();
+        dioxus::core::spawn_forever(async move {
+            loop {
+                match cached_state(&runtime.cache, &cache_key) {
+                    Some(state) => {
+                        sender.send_if_modified(|current| {
+                            let changed = *current != state;
+                            if changed {
+                                *current = state;
+                            }
+                            changed
+                        });
+                    }
+                    // Missing or invalidated: keep publishing the last state while refetching
+                    None => {
+                        runtime.prefetch(provider.clone(), param.clone());
+                    }
+                }
+
+                tokio::select! {
+                    changed = refreshes.changed() => {
+                        if changed.is_err() {
+                            break;
+                        }
+                    }
+                    _ = sender.closed() => break,
+                }
+            }
+            crate::debug_log!("🌉 [BRIDGE] All receivers dropped for key: {}", cache_key);
+            runtime.release_provider_tasks(&cache_key);
+        });
+
+        receiver
+    }
+
+    /// Fetch a provider synchronously, blocking the current thread (native targets).
+    ///
+    /// For startup code, CLIs and FFI boundaries that need a value before the UI event loop
+    /// exists. Cached results are returned without fetching, and if the key is already being
+    /// fetched elsewhere the call waits for that request to store its result instead of
+    /// starting another one. The result is cached, so components mounting later render it
+    /// immediately. Parameters skipped by `Provider::skip` or rejected by `Provider::validate`
+    /// return the skip value or the validation error without fetching.
+    ///
+    /// Blocking inside async code would stall the executor driving it, so calls made from
+    /// within a tokio runtime, including the Dioxus desktop event loop, fail with
+    /// `BlockingFetchError::NestedRuntime` instead; `.await` the provider there.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn block_on_fetch<P, Param>(
+        &self,
+        provider: P,
+        param: Param,
+        timeout: std::time::Duration,
+    ) -> Result<P::Output, BlockingFetchError<P::Error>>
+    where
+        P: Provider<Param>,
+        Param: ProviderParamBounds,
+    {
+        if let Some(value) = provider.skip(&param) {
+            return Ok(value);
+        }
+        provider
+            .validate(&param)
+            .map_err(BlockingFetchError::Provider)?;
+        let cache_key = provider.id(&param);
+        let cached = |cache: &ProviderCache| {
+            cache
+                .get::<Result<P::Output, P::Error>>(&cache_key)
+                .map(|result| result.map_err(BlockingFetchError::Provider))
+        };
+        if let Some(result) = cached(&self.cache) {
+            return result;
+        }
 
-        let cleanup_key = format!("{cache_key}_cleanup");
-        self.refresh_registry
-            .stop_periodic_task(&cleanup_key, TaskType::CacheCleanup);
+        if tokio::runtime::Handle::try_current().is_ok() {
+            return Err(BlockingFetchError::NestedRuntime);
+        }
+        let executor = blocking_executor().map_err(BlockingFetchError::Runtime)?;
+
+        if !self.mark_request_pending(&cache_key) {
+            crate::debug_log!(
+                "⏳ [BLOCKING] Waiting for pending request for key: {}",
+                cache_key
+            );
+            // Subscribed before checking the cache again, so a result stored in between
+            // still wakes the wait below
+            let mut refreshes = self.refresh_registry.watch_refresh(&cache_key);
+            let stored = executor.block_on(async {
+                tokio::time::timeout(timeout, async {
+                    loop {
+                        if let Some(result) = cached(&self.cache) {
+                            return Some(result);
+                        }
+                        refreshes.changed().await.ok()?;
+                    }
+                })
+                .await
+            });
+            return match stored {
+                Ok(Some(result)) => result,
+                _ => Err(BlockingFetchError::Timeout(timeout)),
+            };
+        }
+
+        crate::debug_log!("⏳ [BLOCKING] Fetching key: {}", cache_key);
+        let origin = key_origin(&provider, &param);
+        // The timer must be created inside the executor
+        let result =
+            executor.block_on(async { tokio::time::timeout(timeout, provider.run(param)).await });
+
+        let result = match result {
+            Ok(result) => {
+                cache_mgmt::store_provider_result(
+                    &provider,
+                    &self.cache,
+                    &cache_key,
+                    origin,
+                    result.clone(),
+                );
+                self.refresh_registry.trigger_refresh(&cache_key);
+                result.map_err(BlockingFetchError::Provider)
+            }
+            Err(_) => Err(BlockingFetchError::Timeout(timeout)),
+        };
+        self.mark_request_complete(&cache_key);
+        result
     }
 
     /// Track whether a request for a cache key is already pending.
@@ -120,15 +709,46 @@ impl ProviderRuntime {
         }
     }
 
+    /// Register the fetch of a pending request, returning the context it runs with.
+    pub(crate) fn begin_request(&self, cache_key: &str) -> ProviderContext {
+        let token = CancellationToken::new();
+        if let Ok(mut in_flight) = self.in_flight.lock() {
+            in_flight.insert(cache_key.to_string(), token.clone());
+        }
+        ProviderContext::new(cache_key, token)
+    }
+
+    /// Cancel the fetch of a pending request, returning false if none was running.
+    ///
+    /// The provider's future is dropped without storing its result, and the key is free to
+    /// be fetched again. Called when the last consumer of a key is released.
+    pub fn cancel_request(&self, cache_key: &str) -> bool {
+        let Some(token) = self
+            .in_flight
+            .lock()
+            .ok()
+            .and_then(|mut in_flight| in_flight.remove(cache_key))
+        else {
+            return false;
+        };
+        token.cancel();
+        self.mark_request_complete(cache_key);
+        crate::debug_log!("🛑 [CANCEL] Cancelled fetch for key: {}", cache_key);
+        true
+    }
+
     /// Complete a pending request and return the number of waiters that were affected.
     pub fn mark_request_complete(&self, cache_key: &str) {
-        if let Ok(mut pending) = self.pending_requests.lock() {
-            if pending.remove(cache_key).is_some() {
-                crate::debug_log!(
-                    "✅ [REQUEST-DEDUP] Request completed for key: {}",
-                    cache_key
-                );
-            }
+        if let Ok(mut in_flight) = self.in_flight.lock() {
+            in_flight.remove(cache_key);
+        }
+        if let Ok(mut pending) = self.pending_requests.lock()
+            && pending.remove(cache_key).is_some()
+        {
+            crate::debug_log!(
+                "✅ [REQUEST-DEDUP] Request completed for key: {}",
+                cache_key
+            );
         }
     }
 
@@ -142,33 +762,64 @@ impl ProviderRuntime {
     }
 
     /// Ensure scheduled tasks are registered for a provider key (native targets).
+    ///
+    /// Running tasks whose duration is unchanged are kept; tasks whose provider now reports
+    /// a different interval, stale time or expiration are restarted with it, and tasks for
+    /// settings the provider no longer has are stopped.
     #[cfg(not(target_family = "wasm"))]
     pub fn ensure_provider_tasks<P, Param>(&self, provider: &P, param: &Param, cache_key: &str)
     where
         P: Provider<Param> + Clone + Send,
         Param: ProviderParamBounds,
     {
-        setup_intelligent_cache_management(
+        self.ensure_maintenance_task();
+        self.start_prefetch_plan();
+        if self.cache.is_demo() {
+            return;
+        }
+        setup_cache_expiration_task_core(
             provider,
+            param,
             cache_key,
             &self.cache,
             &self.refresh_registry,
         );
-        setup_cache_expiration_task_core(
+        setup_interval_task_core(
             provider,
             param,
             cache_key,
             &self.cache,
             &self.refresh_registry,
         );
-        setup_interval_task_core(
+        setup_stale_check_task_core(
             provider,
             param,
             cache_key,
             &self.cache,
             &self.refresh_registry,
         );
-        setup_stale_check_task_core(
+        setup_schedule_task_core(
+            provider,
+            param,
+            cache_key,
+            &self.cache,
+            &self.refresh_registry,
+        );
+        setup_aligned_interval_task_core(
+            provider,
+            param,
+            cache_key,
+            &self.cache,
+            &self.refresh_registry,
+        );
+        setup_focus_refetch_task_core(
+            provider,
+            param,
+            cache_key,
+            &self.cache,
+            &self.refresh_registry,
+        );
+        setup_reconnect_refetch_task_core(
             provider,
             param,
             cache_key,
@@ -178,33 +829,64 @@ impl ProviderRuntime {
     }
 
     /// Ensure scheduled tasks are registered for a provider key (WASM targets).
+    ///
+    /// Running tasks whose duration is unchanged are kept; tasks whose provider now reports
+    /// a different interval, stale time or expiration are restarted with it, and tasks for
+    /// settings the provider no longer has are stopped.
     #[cfg(target_family = "wasm")]
     pub fn ensure_provider_tasks<P, Param>(&self, provider: &P, param: &Param, cache_key: &str)
     where
         P: Provider<Param> + Clone,
         Param: ProviderParamBounds,
     {
-        setup_intelligent_cache_management(
+        self.ensure_maintenance_task();
+        self.start_prefetch_plan();
+        if self.cache.is_demo() {
+            return;
+        }
+        setup_cache_expiration_task_core(
             provider,
+            param,
             cache_key,
             &self.cache,
             &self.refresh_registry,
         );
-        setup_cache_expiration_task_core(
+        setup_interval_task_core(
             provider,
             param,
             cache_key,
             &self.cache,
             &self.refresh_registry,
         );
-        setup_interval_task_core(
+        setup_stale_check_task_core(
             provider,
             param,
             cache_key,
             &self.cache,
             &self.refresh_registry,
         );
-        setup_stale_check_task_core(
+        setup_schedule_task_core(
+            provider,
+            param,
+            cache_key,
+            &self.cache,
+            &self.refresh_registry,
+        );
+        setup_aligned_interval_task_core(
+            provider,
+            param,
+            cache_key,
+            &self.cache,
+            &self.refresh_registry,
+        );
+        setup_focus_refetch_task_core(
+            provider,
+            param,
+            cache_key,
+            &self.cache,
+            &self.refresh_registry,
+        );
+        setup_reconnect_refetch_task_core(
             provider,
             param,
             cache_key,
@@ -213,7 +895,36 @@ impl ProviderRuntime {
         );
     }
 }
+
+/// The executor shared by every `block_on_fetch` call, created on first use
+///
+/// Threads blocking on it concurrently take turns driving its timers.
+#[cfg(not(target_family = "wasm"))]
+fn blocking_executor() -> Result<&'static tokio::runtime::Runtime, String> {
+    static EXECUTOR: std::sync::OnceLock<Result<tokio::runtime::Runtime, String>> =
+        std::sync::OnceLock::new();
+    EXECUTOR
+        .get_or_init(|| {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .map_err(|error| error.to_string())
+        })
+        .as_ref()
+        .map_err(Clone::clone)
+}
+
+/// Runtimes are equal when they are clones of the same runtime, sharing its cache
+impl PartialEq for ProviderRuntime {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.pending_requests, &other.pending_requests)
+    }
+}
+
 use std::{
     collections::HashMap,
-    sync::{Arc, Mutex},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
 };
@@ -1,66 +1,308 @@
 //! Cache management utilities owned by the runtime.
 
-use std::time::Duration;
+use std::{any::Any, fmt::Debug, time::Duration};
 
 use crate::{
-    cache::ProviderCache,
+    cache::{CachePolicy, KeyOrigin, ProviderCache},
+    errors::ProviderError,
     hooks::Provider,
-    refresh::{RefreshRegistry, TaskType},
+    persist,
+    refresh::RefreshRegistry,
     types::ProviderParamBounds,
 };
 
-/// Sets up intelligent cache management for a provider.
+/// The origin of a provider's cache key, recorded in debug builds only
+pub fn key_origin<P, Param: Debug>(_provider: &P, param: &Param) -> Option<KeyOrigin> {
+    cfg!(debug_assertions).then(|| KeyOrigin::of::<P, Param>(param))
+}
+
+/// The name of a provider type without its module path or generic parameters
+pub fn provider_name<P>() -> &'static str {
+    let name = std::any::type_name::<P>();
+    let name = name.split('<').next().unwrap_or(name);
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// Annotate a `ProviderError` result with the provider and cache key that produced it
+///
+/// Other error types are returned unchanged.
+pub fn with_error_context<P, Param>(
+    cache_key: &str,
+    result: Result<P::Output, P::Error>,
+) -> Result<P::Output, P::Error>
+where
+    P: Provider<Param>,
+    Param: ProviderParamBounds,
+{
+    result.map_err(|mut error| {
+        if let Some(provider_error) = (&mut error as &mut dyn Any).downcast_mut::<ProviderError>() {
+            let annotated =
+                std::mem::replace(provider_error, ProviderError::Generic(String::new()))
+                    .with_context(provider_name::<P>(), cache_key);
+            *provider_error = annotated;
+        }
+        error
+    })
+}
+
+/// How long a provider's error stays cached, or None to cache it like successful results
 ///
-/// This replaces the old component-unmount auto-dispose with a better system:
-/// 1. Access-time tracking for LRU management
-/// 2. Periodic cleanup of unused entries based on cache_expiration
-/// 3. Cache size limits with LRU eviction
-/// 4. Automatic background cleanup tasks
-pub fn setup_intelligent_cache_management<P, Param>(
+/// Not-found errors use the provider's negative caching duration, if set, and other errors
+/// its error caching.
+pub fn error_expiration<P, Param>(provider: &P, error: &P::Error) -> Option<Duration>
+where
+    P: Provider<Param>,
+    Param: ProviderParamBounds,
+{
+    match provider.cache_not_found() {
+        Some(duration) if provider.is_not_found(error) => Some(duration),
+        _ => provider.cache_errors().expiration(),
+    }
+}
+
+/// Store a provider result, apply the provider's per-entry cache policy (or its error
+/// caching for errors) and weight, and normalize its entities, if enabled. Results for keys holding
+/// uncommitted local edits are discarded.
+///
+/// In debug builds, `origin` is checked against the provider that stored the current entry,
+/// so that key collisions panic instead of mixing up data. `ProviderError`s are stored with
+/// the provider and cache key that produced them.
+///
+/// Returns whether the cached value changed.
+pub fn store_provider_result<P, Param>(
     provider: &P,
+    cache: &ProviderCache,
     cache_key: &str,
+    origin: Option<KeyOrigin>,
+    result: Result<P::Output, P::Error>,
+) -> bool
+where
+    P: Provider<Param>,
+    Param: ProviderParamBounds,
+{
+    store_result(provider, cache, cache_key, origin, result, false).0
+}
+
+/// Store a provider result like [`store_provider_result`], also returning the result as
+/// stored, with `ProviderError`s annotated, for the consumer that fetched it
+pub fn store_and_return_provider_result<P, Param>(
+    provider: &P,
     cache: &ProviderCache,
-    refresh_registry: &RefreshRegistry,
-) where
-    P: Provider<Param> + Clone,
+    cache_key: &str,
+    origin: Option<KeyOrigin>,
+    result: Result<P::Output, P::Error>,
+) -> (bool, Result<P::Output, P::Error>)
+where
+    P: Provider<Param>,
     Param: ProviderParamBounds,
 {
-    if let Some(cache_expiration) = provider.cache_expiration() {
-        let cleanup_interval = std::cmp::max(cache_expiration / 4, Duration::from_secs(30));
-
-        let cache_clone = cache.clone();
-        let unused_threshold = cache_expiration * 2;
-        let cleanup_key = format!("{cache_key}_cleanup");
-
-        refresh_registry.start_periodic_task(
-            &cleanup_key,
-            TaskType::CacheCleanup,
-            cleanup_interval,
-            move || {
-                let removed = cache_clone.cleanup_unused_entries(unused_threshold);
-                if removed > 0 {
-                    crate::debug_log!(
-                        "🧹 [SMART-CLEANUP] Removed {} unused cache entries",
-                        removed
-                    );
-                }
-
-                const MAX_CACHE_SIZE: usize = 1000;
-                let evicted = cache_clone.evict_lru_entries(MAX_CACHE_SIZE);
-                if evicted > 0 {
-                    crate::debug_log!(
-                        "🗑️ [LRU-EVICT] Evicted {} entries due to cache size limit",
-                        evicted
-                    );
-                }
-            },
+    let (updated, stored) = store_result(provider, cache, cache_key, origin, result, true);
+    (
+        updated,
+        stored.expect("the stored result is kept when asked for"),
+    )
+}
+
+/// Whether the cached value changed, and the result as stored if it was kept
+type Stored<T, E> = (bool, Option<Result<T, E>>);
+
+/// Store a provider result, keeping a copy of it as stored if `keep` is set
+fn store_result<P, Param>(
+    provider: &P,
+    cache: &ProviderCache,
+    cache_key: &str,
+    origin: Option<KeyOrigin>,
+    result: Result<P::Output, P::Error>,
+    keep: bool,
+) -> Stored<P::Output, P::Error>
+where
+    P: Provider<Param>,
+    Param: ProviderParamBounds,
+{
+    if cache.is_dirty(cache_key) {
+        crate::debug_log!(
+            "✏️ [EDIT] Discarding fetched result for key with local edits: {}",
+            cache_key
         );
+        let kept = keep.then(|| with_error_context::<P, Param>(cache_key, result));
+        return (false, kept);
+    }
 
-        #[cfg(feature = "tracing")]
+    // An unchanged value keeps its entry, so always overwrite the previous policy
+    let weight = match (&result, provider.weight()) {
+        (Ok(data), Some(weight_fn)) => Some(weight_fn(data)),
+        _ => None,
+    };
+    let policy = match (&result, provider.cache_policy()) {
+        (Ok(data), Some(policy_fn)) => Some(policy_fn(data).unwrap_or_default()),
+        (Err(error), _) => error_expiration(provider, error)
+            .map(|expiration| CachePolicy::new().with_expiration(expiration)),
+        _ => None,
+    };
+    if let (Ok(data), Some(normalize)) = (&result, provider.normalize()) {
+        normalize(data, cache.entities());
+    }
+    if let (Ok(data), Some(seed)) = (&result, provider.seed_details()) {
+        seed(data, cache);
+    }
+    if let Some(origin) = &origin {
+        cache.assert_key_origin(cache_key, origin);
+    }
+    let result = with_error_context::<P, Param>(cache_key, result);
+    let persisted = persist::encode(provider, cache, &result);
+    let kept = keep.then(|| result.clone());
+    let updated = cache.set(cache_key.to_string(), result);
+    if updated && let Some(value) = persisted {
+        persist::save(cache, cache_key, value);
+    }
+    if updated {
+        crate::ssr::track_entry(provider, cache, cache_key);
+    }
+    if let Some(origin) = origin {
+        cache.set_key_origin(cache_key, origin);
+    }
+    if let Some(weight) = weight {
+        cache.set_weight(cache_key, weight);
+    }
+    if let Some(policy) = policy {
+        cache.set_policy(cache_key, policy);
         crate::debug_log!(
-            "📊 [SMART-CACHE] Intelligent cache management enabled for: {} (cleanup every {:?})",
+            "📜 [CACHE-POLICY] Applied per-entry policy for key: {} ({:?})",
             cache_key,
-            cleanup_interval
+            policy
+        );
+    }
+    (updated, kept)
+}
+
+/// Starts the runtime-owned cache maintenance loop.
+///
+/// One loop serves the whole cache, removing entries unused for longer than the cache's
+/// `unused_threshold` setting and evicting least recently used entries beyond its
+/// `max_cache_size`, both read anew on every pass. Each pass runs in
+/// chunks and yields between them, so a large cache doesn't stall the executor. The loop
+/// is spawned at the root scope and lives as long as the Dioxus runtime. Passes are
+/// skipped while the refresh registry's background tasks are paused.
+pub fn start_maintenance_task(
+    cache: &ProviderCache,
+    refresh_registry: &RefreshRegistry,
+    interval: Duration,
+) {
+    let cache = cache.clone();
+    let refresh_registry = refresh_registry.clone();
+    dioxus::core::spawn_forever(async move {
+        loop {
+            crate::platform::sleep(interval).await;
+            if refresh_registry.is_background_paused() {
+                continue;
+            }
+            let stats = cache.maintain_incremental().await;
+            if stats.unused_removed > 0 || stats.lru_evicted > 0 {
+                crate::debug_log!(
+                    "🧹 [MAINTENANCE] Removed {} unused and evicted {} entries, {} left",
+                    stats.unused_removed,
+                    stats.lru_evicted,
+                    stats.final_size
+                );
+            }
+        }
+    });
+    crate::debug_log!(
+        "📊 [MAINTENANCE] Background cache maintenance every {:?}",
+        interval
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::WithTtl;
+
+    #[derive(Clone, PartialEq)]
+    struct RowProvider;
+
+    impl Provider<u32> for RowProvider {
+        type Output = WithTtl<u32>;
+        type Error = ();
+
+        async fn run(&self, id: u32) -> Result<Self::Output, Self::Error> {
+            Ok(WithTtl::new(id, Duration::from_secs(u64::from(id))))
+        }
+
+        fn cache_policy(&self) -> Option<crate::hooks::CachePolicyFn<Self::Output>> {
+            Some(WithTtl::cache_policy)
+        }
+    }
+
+    #[test]
+    fn stores_per_entry_ttl() {
+        let cache = ProviderCache::new();
+        let provider = RowProvider;
+
+        for id in [5, 60] {
+            let result = futures::executor::block_on(provider.run(id));
+            assert!(store_provider_result(
+                &provider,
+                &cache,
+                &id.to_string(),
+                key_origin(&provider, &id),
+                result
+            ));
+        }
+
+        let ttl = |key: &str| {
+            cache
+                .cache
+                .lock()
+                .unwrap()
+                .get(key)
+                .and_then(|entry| entry.effective_expiration(None))
+        };
+        assert_eq!(ttl("5"), Some(Duration::from_secs(5)));
+        assert_eq!(ttl("60"), Some(Duration::from_secs(60)));
+    }
+
+    #[derive(Clone, PartialEq)]
+    struct ConfigProvider;
+
+    impl Provider<()> for ConfigProvider {
+        type Output = String;
+        type Error = ProviderError;
+
+        async fn run(&self, _param: ()) -> Result<Self::Output, Self::Error> {
+            Err(ProviderError::Network("offline".to_string()))
+        }
+    }
+
+    #[test]
+    fn stores_errors_with_provider_and_key() {
+        let cache = ProviderCache::new();
+        let result = futures::executor::block_on(ConfigProvider.run(()));
+        store_provider_result(&ConfigProvider, &cache, "config", None, result);
+
+        let error = cache
+            .get::<Result<String, ProviderError>>("config")
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(error.provider(), Some("ConfigProvider"));
+        assert_eq!(error.cache_key(), Some("config"));
+        assert_eq!(
+            error.without_context(),
+            &ProviderError::Network("offline".to_string())
+        );
+    }
+
+    #[test]
+    fn returns_errors_as_stored() {
+        let cache = ProviderCache::new();
+        let result = futures::executor::block_on(ConfigProvider.run(()));
+        let (updated, returned) =
+            store_and_return_provider_result(&ConfigProvider, &cache, "config", None, result);
+
+        assert!(updated);
+        assert_eq!(
+            Some(returned),
+            cache.get::<Result<String, ProviderError>>("config")
         );
     }
 }
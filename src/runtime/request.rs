@@ -1,16 +1,29 @@
 //! Request orchestration helpers for use_provider.
 
-use dioxus::prelude::*;
+use dioxus::{core::Task, prelude::*};
 
 use crate::{
-    cache::ProviderCache, hooks::Provider, refresh::RefreshRegistry, runtime::ProviderRuntime,
-    state::State, types::ProviderParamBounds,
+    cache::ProviderCache,
+    cancel::CancellationToken,
+    chaos,
+    hooks::Provider,
+    refresh::RefreshRegistry,
+    retry,
+    runtime::{
+        ProviderRuntime,
+        cache_mgmt::{key_origin, store_and_return_provider_result, store_provider_result},
+    },
+    state::State,
+    types::ProviderParamBounds,
 };
 
 /// State handle abstraction so runtime logic can be tested without real Dioxus signals.
 pub trait RuntimeStateHandle<T, E>: Clone {
     fn set_state(&mut self, new_state: State<T, E>);
     fn is_loading(&self) -> bool;
+    /// Switch a state showing data to `Reloading` with that data, returning false if there
+    /// is no data to keep showing
+    fn set_reloading(&mut self, task: Task) -> bool;
 }
 
 impl<T: Clone + 'static, E: Clone + 'static> RuntimeStateHandle<T, E> for Signal<State<T, E>> {
@@ -21,10 +34,24 @@ impl<T: Clone + 'static, E: Clone + 'static> RuntimeStateHandle<T, E> for Signal
     fn is_loading(&self) -> bool {
         self.read().is_loading()
     }
+
+    fn set_reloading(&mut self, task: Task) -> bool {
+        let previous = match &*self.peek() {
+            State::Reloading { .. } => return true,
+            State::Success(data) => data.clone(),
+            _ => return false,
+        };
+        self.set(State::Reloading { previous, task });
+        true
+    }
 }
 
 /// Cache miss orchestration that handles pending-request dedupe, invalidation SWR,
 /// and the primary async execution.
+///
+/// Fetches run until their key's last consumer is released, which cancels them. When this
+/// call started the fetch, it returns a token that detaches `state` from it: once cancelled,
+/// the fetch still completes for other consumers but no longer writes to `state`.
 pub fn handle_cache_miss<P, Param, Handle>(
     runtime: &ProviderRuntime,
     provider: P,
@@ -33,11 +60,26 @@ pub fn handle_cache_miss<P, Param, Handle>(
     refresh_registry: RefreshRegistry,
     cache_key: String,
     state: Handle,
-) where
+) -> Option<CancellationToken>
+where
     P: Provider<Param> + Send + Clone,
     Param: ProviderParamBounds,
     Handle: RuntimeStateHandle<P::Output, P::Error> + 'static,
 {
+    let is_invalidation_refresh = refresh_registry.get_refresh_count(&cache_key) > 0;
+
+    // Invalidations sooner than the provider's minimum refetch interval wait for the
+    // trailing refetch; the component keeps its current state meanwhile
+    if let Some(min_interval) = provider.min_refetch_interval()
+        && runtime.pending_request_count(&cache_key) == 0
+    {
+        if !is_invalidation_refresh {
+            refresh_registry.record_refetch(&cache_key);
+        } else if !refresh_registry.try_begin_refetch(&cache_key, min_interval) {
+            return None;
+        }
+    }
+
     let is_new_request = runtime.mark_request_pending(&cache_key);
 
     if !is_new_request {
@@ -52,7 +94,7 @@ pub fn handle_cache_miss<P, Param, Handle>(
                 || pending_count == 100
                 || pending_count == 200
                 || pending_count == 500
-                || pending_count % 1000 == 0
+                || pending_count.is_multiple_of(1000)
             {
                 crate::debug_log!(
                     "🔄 [REQUEST-DEDUP] Request already pending for key: {} ({} components waiting)",
@@ -64,11 +106,12 @@ pub fn handle_cache_miss<P, Param, Handle>(
 
         if !state.is_loading() {
             let mut loading_handle = state.clone();
-            loading_handle.set_state(State::Loading {
-                task: dioxus::prelude::spawn(async {}),
-            });
+            let task = dioxus::prelude::spawn(async {});
+            if !loading_handle.set_reloading(task) {
+                loading_handle.set_state(State::Loading { task });
+            }
         }
-        return;
+        return None;
     }
 
     crate::debug_log!(
@@ -76,9 +119,8 @@ pub fn handle_cache_miss<P, Param, Handle>(
         cache_key
     );
 
-    let is_invalidation_refresh = refresh_registry.get_refresh_count(&cache_key) > 0;
-
-    if is_invalidation_refresh {
+    // Components still loading, e.g. because their cached error expired, wait for the result
+    if is_invalidation_refresh && !state.is_loading() {
         crate::debug_log!(
             "🔄 [INVALIDATION] Cache miss due to invalidation for: {}, using SWR behavior",
             cache_key
@@ -90,21 +132,48 @@ pub fn handle_cache_miss<P, Param, Handle>(
         let param = param.clone();
         let refresh_registry_clone = refresh_registry.clone();
         let runtime_clone = runtime.clone();
-
-        dioxus::prelude::spawn(async move {
-            let result = provider.run(param).await;
-            let updated = cache_clone.set(cache_key_clone.clone(), result.clone());
+        let context = runtime.begin_request(&cache_key);
+
+        let task = dioxus::prelude::spawn(async move {
+            let origin = key_origin(&provider, &param);
+            let fetch = retry::fetch(&provider, param, &cache_clone, &cache_key_clone);
+            let Some(result) = context.run(fetch).await else {
+                // Cancelled, which already completed the request
+                return;
+            };
+            let Some(result) = result else {
+                runtime_clone.mark_request_complete(&cache_key_clone);
+                return;
+            };
+            chaos::maybe_duplicate(
+                &provider,
+                &cache_clone,
+                &refresh_registry_clone,
+                &cache_key_clone,
+                origin.clone(),
+                &result,
+            );
+            let updated = store_provider_result(
+                &provider,
+                &cache_clone,
+                &cache_key_clone,
+                origin,
+                result.clone(),
+            );
             if updated {
-                refresh_registry_clone.trigger_refresh(&cache_key_clone);
                 crate::debug_log!(
                     "✅ [INVALIDATION] Background revalidation completed for: {}",
                     cache_key_clone
                 );
             }
+            // Refreshed even if unchanged, so reloading consumers settle on the result
+            refresh_registry_clone.trigger_refresh(&cache_key_clone);
             runtime_clone.mark_request_complete(&cache_key_clone);
         });
+        let mut reloading_handle = state;
+        reloading_handle.set_reloading(task);
 
-        return;
+        return None;
     }
 
     let cache_clone = cache.clone();
@@ -114,16 +183,49 @@ pub fn handle_cache_miss<P, Param, Handle>(
     let refresh_registry_clone = refresh_registry.clone();
     let runtime_clone = runtime.clone();
     let mut state_for_async = state.clone();
+    let context = runtime.begin_request(&cache_key);
+    let detached = CancellationToken::new();
+    let detached_for_async = detached.clone();
 
     let task = dioxus::prelude::spawn(async move {
-        let result = provider_clone.run(param_clone).await;
-        let updated = cache_clone.set(cache_key_clone.clone(), result.clone());
+        let origin = key_origin(&provider_clone, &param_clone);
+        let fetch = retry::fetch(&provider_clone, param_clone, &cache_clone, &cache_key_clone);
+        let Some(result) = context.run(fetch).await else {
+            crate::debug_log!(
+                "🛑 [CANCEL] Dropped fetch without consumers for: {}",
+                cache_key_clone
+            );
+            return;
+        };
+        let Some(result) = result else {
+            // The component keeps loading until the next refetch, as with a lost connection
+            runtime_clone.mark_request_complete(&cache_key_clone);
+            return;
+        };
+        chaos::maybe_duplicate(
+            &provider_clone,
+            &cache_clone,
+            &refresh_registry_clone,
+            &cache_key_clone,
+            origin.clone(),
+            &result,
+        );
+        let (updated, result) = store_and_return_provider_result(
+            &provider_clone,
+            &cache_clone,
+            &cache_key_clone,
+            origin,
+            result,
+        );
         crate::debug_log!(
             "📊 [CACHE-STORE] Attempted to store new data for: {} (updated: {})",
             cache_key_clone,
             updated
         );
-        if updated {
+        // An unchanged result still ends this component's loading, e.g. a refetched error
+        // that isn't cached and equals the one stored before. Components that moved on to
+        // other parameters meanwhile keep their state.
+        if !detached_for_async.is_cancelled() && (updated || state_for_async.is_loading()) {
             match result {
                 Ok(data) => {
                     state_for_async.set_state(State::Success(data));
@@ -138,13 +240,92 @@ pub fn handle_cache_miss<P, Param, Handle>(
     });
     let mut state_for_loading = state;
     state_for_loading.set_state(State::Loading { task });
+    Some(detached)
+}
+
+/// Start fetching a provider into the cache without a subscribing component.
+///
+/// Does nothing if the parameters are skipped or fail validation, the entry is already
+/// cached or a request for it is pending. Components that mount while the prefetch is
+/// running join it through request deduplication.
+/// Returns whether a fetch was started.
+pub fn prefetch_provider<P, Param>(runtime: &ProviderRuntime, provider: P, param: Param) -> bool
+where
+    P: Provider<Param> + Send + Clone,
+    Param: ProviderParamBounds,
+{
+    let Some(fetch) = begin_prefetch(runtime, provider, param) else {
+        return false;
+    };
+    dioxus::prelude::spawn(fetch);
+    true
+}
+
+/// Claim a prefetch like [`prefetch_provider`], returning the fetch to run instead of
+/// spawning it
+pub(crate) fn begin_prefetch<P, Param>(
+    runtime: &ProviderRuntime,
+    provider: P,
+    param: Param,
+) -> Option<impl Future<Output = ()> + 'static>
+where
+    P: Provider<Param> + Send + Clone,
+    Param: ProviderParamBounds,
+{
+    if provider.skip(&param).is_some() || provider.validate(&param).is_err() {
+        return None;
+    }
+    let cache_key = provider.id(&param);
+    let cache = runtime.cache().clone();
+    let origin = key_origin(&provider, &param);
+    if let Some(origin) = &origin {
+        cache.assert_key_origin(&cache_key, origin);
+    }
+    if cache
+        .get::<Result<P::Output, P::Error>>(&cache_key)
+        .is_some()
+        || !runtime.mark_request_pending(&cache_key)
+    {
+        return None;
+    }
+
+    crate::debug_log!("🔮 [PREFETCH] Prefetching key: {}", cache_key);
+    let runtime = runtime.clone();
+    Some(async move {
+        let Some(result) = retry::fetch(&provider, param, &cache, &cache_key).await else {
+            runtime.mark_request_complete(&cache_key);
+            return;
+        };
+        chaos::maybe_duplicate(
+            &provider,
+            &cache,
+            runtime.refresh_registry(),
+            &cache_key,
+            origin.clone(),
+            &result,
+        );
+        store_provider_result(&provider, &cache, &cache_key, origin, result);
+        runtime.mark_request_complete(&cache_key);
+        runtime.refresh_registry().trigger_refresh(&cache_key);
+    })
 }
 
 #[cfg(all(test, not(target_family = "wasm")))]
 mod tests {
     use super::*;
-    use crate::runtime::{ProviderRuntime, ProviderRuntimeConfig};
-    use dioxus::prelude::{Element, ScopeId, VirtualDom, rsx};
+    use crate::{
+        errors::BlockingFetchError,
+        refresh::TaskType,
+        runtime::{ProviderRuntime, ProviderRuntimeConfig},
+    };
+    use crate::{
+        hooks::{NextIntervalFn, use_provider},
+        schedule::Schedule,
+        scope::ProviderScope,
+    };
+    use dioxus::prelude::{
+        Element, Props, ScopeId, Signal, VirtualDom, WritableExt, rsx, use_signal,
+    };
     use dioxus_core::NoOpMutations;
     use futures::FutureExt;
     use std::{
@@ -219,7 +400,7 @@ mod tests {
                     }
                     self.is_loading.store(true, Ordering::SeqCst);
                 }
-                State::Success(_) => {
+                State::Success(_) | State::Reloading { .. } => {
                     self.saw_success.store(true, Ordering::SeqCst);
                     self.is_loading.store(false, Ordering::SeqCst);
                 }
@@ -232,6 +413,10 @@ mod tests {
         fn is_loading(&self) -> bool {
             self.is_loading.load(Ordering::SeqCst)
         }
+
+        fn set_reloading(&mut self, _task: Task) -> bool {
+            self.saw_success.load(Ordering::SeqCst)
+        }
     }
 
     struct DioxusRuntimeHarness {
@@ -320,4 +505,496 @@ mod tests {
             );
         });
     }
+
+    #[test]
+    fn releasing_the_last_consumer_cancels_the_fetch() {
+        block_on(async {
+            let mut harness = DioxusRuntimeHarness::new();
+            let runtime = ProviderRuntime::new(ProviderRuntimeConfig::new());
+            let handles = runtime.handles();
+            let (provider, calls) = CountingProvider::new();
+            let cache_key = provider.id(&());
+
+            runtime.retain_provider_tasks(&cache_key);
+            let handle = TestStateHandle::default();
+            let detached = harness.run(|| {
+                handle_cache_miss(
+                    &runtime,
+                    provider.clone(),
+                    (),
+                    handles.cache.clone(),
+                    handles.refresh_registry.clone(),
+                    cache_key.clone(),
+                    handle.clone(),
+                )
+            });
+            assert!(detached.is_some());
+            harness.pump();
+            assert_eq!(calls.load(Ordering::SeqCst), 1);
+            assert_eq!(runtime.pending_request_count(&cache_key), 1);
+
+            runtime.release_provider_tasks(&cache_key);
+            assert_eq!(runtime.pending_request_count(&cache_key), 0);
+            sleep(Duration::from_millis(30)).await;
+            harness.pump();
+            assert!(handle.is_loading.load(Ordering::SeqCst));
+            assert_eq!(handles.cache.get::<Result<u32, ()>>(&cache_key), None);
+            assert!(!runtime.cancel_request(&cache_key));
+        });
+    }
+
+    #[test]
+    fn bridge_publishes_fetches_and_refetches() {
+        block_on(async {
+            let mut harness = DioxusRuntimeHarness::new();
+            let runtime = ProviderRuntime::new(ProviderRuntimeConfig::new());
+            let (provider, calls) = CountingProvider::new();
+            let cache_key = provider.id(&());
+
+            let receiver = harness.run(|| runtime.bridge_to_watch(provider.clone(), ()));
+            assert!(receiver.borrow().is_loading());
+
+            for _ in 0..3 {
+                harness.pump();
+                sleep(Duration::from_millis(20)).await;
+            }
+            harness.pump();
+            assert_eq!(receiver.borrow().data(), Some(&1));
+
+            runtime.cache().invalidate(&cache_key);
+            runtime.refresh_registry().trigger_refresh(&cache_key);
+            for _ in 0..3 {
+                harness.pump();
+                sleep(Duration::from_millis(20)).await;
+            }
+            harness.pump();
+            assert_eq!(calls.load(Ordering::SeqCst), 2);
+            assert_eq!(receiver.borrow().data(), Some(&2));
+        });
+    }
+
+    #[test]
+    fn blocking_fetch_caches_result_and_times_out() {
+        let runtime = ProviderRuntime::new(ProviderRuntimeConfig::new());
+        let (provider, calls) = CountingProvider::new();
+
+        assert_eq!(
+            runtime.block_on_fetch(provider.clone(), (), Duration::from_secs(1)),
+            Ok(1)
+        );
+        assert_eq!(
+            runtime.block_on_fetch(provider, (), Duration::from_secs(1)),
+            Ok(1)
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let (slow_provider, _) = CountingProvider::new();
+        let fresh_runtime = ProviderRuntime::new(ProviderRuntimeConfig::new());
+        assert_eq!(
+            fresh_runtime.block_on_fetch(slow_provider, (), Duration::from_millis(1)),
+            Err(BlockingFetchError::Timeout(Duration::from_millis(1)))
+        );
+    }
+
+    #[test]
+    fn blocking_fetch_joins_pending_requests_and_rejects_async_callers() {
+        let runtime = ProviderRuntime::new(ProviderRuntimeConfig::new());
+        let (provider, calls) = CountingProvider::new();
+        let cache_key = provider.id(&());
+
+        // Another fetch of the key is in flight and stores its result later
+        assert!(runtime.mark_request_pending(&cache_key));
+        let cache = runtime.cache().clone();
+        let refresh_registry = runtime.refresh_registry().clone();
+        let stored_key = cache_key.clone();
+        let fetch = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(20));
+            cache.set(stored_key.clone(), Ok::<u32, ()>(7));
+            refresh_registry.trigger_refresh(&stored_key);
+        });
+        assert_eq!(
+            runtime.block_on_fetch(provider.clone(), (), Duration::from_secs(5)),
+            Ok(7)
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+        fetch.join().expect("fetch thread");
+        runtime.mark_request_complete(&cache_key);
+
+        let fresh_runtime = ProviderRuntime::new(ProviderRuntimeConfig::new());
+        block_on(async {
+            assert_eq!(
+                fresh_runtime.block_on_fetch(provider, (), Duration::from_secs(1)),
+                Err(BlockingFetchError::NestedRuntime)
+            );
+        });
+    }
+
+    #[derive(Clone, PartialEq)]
+    struct UserProvider;
+
+    impl Provider<u32> for UserProvider {
+        type Output = String;
+        type Error = String;
+
+        async fn run(&self, id: u32) -> Result<Self::Output, Self::Error> {
+            Ok(format!("user {id}"))
+        }
+
+        fn validate(&self, id: &u32) -> Result<(), Self::Error> {
+            if *id == 0 {
+                return Err("id must be positive".to_string());
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn rejected_params_never_fetch() {
+        let runtime = ProviderRuntime::new(ProviderRuntimeConfig::new());
+
+        assert!(!prefetch_provider(&runtime, UserProvider, 0));
+        assert_eq!(
+            runtime.block_on_fetch(UserProvider, 0, Duration::from_secs(1)),
+            Err(BlockingFetchError::Provider(
+                "id must be positive".to_string()
+            ))
+        );
+        assert_eq!(runtime.cache().size(), 0);
+        assert_eq!(
+            runtime.block_on_fetch(UserProvider, 7, Duration::from_secs(1)),
+            Ok("user 7".to_string())
+        );
+    }
+
+    #[derive(Clone, PartialEq)]
+    struct GuestProvider;
+
+    impl Provider<u32> for GuestProvider {
+        type Output = String;
+        type Error = String;
+
+        async fn run(&self, id: u32) -> Result<Self::Output, Self::Error> {
+            Ok(format!("user {id}"))
+        }
+
+        fn skip(&self, id: &u32) -> Option<Self::Output> {
+            (*id == 0).then(|| "guest".to_string())
+        }
+    }
+
+    #[test]
+    fn skipped_params_resolve_without_fetching() {
+        let runtime = ProviderRuntime::new(ProviderRuntimeConfig::new());
+
+        assert!(!prefetch_provider(&runtime, GuestProvider, 0));
+        assert_eq!(
+            runtime.block_on_fetch(GuestProvider, 0, Duration::from_secs(1)),
+            Ok("guest".to_string())
+        );
+        assert_eq!(runtime.cache().size(), 0);
+        assert_eq!(
+            runtime.block_on_fetch(GuestProvider, 7, Duration::from_secs(1)),
+            Ok("user 7".to_string())
+        );
+    }
+
+    #[test]
+    fn runtime_maintenance_runs_in_background_and_on_demand() {
+        block_on(async {
+            let mut harness = DioxusRuntimeHarness::new();
+            let runtime = ProviderRuntime::new(
+                ProviderRuntimeConfig::new().with_maintenance_interval(Duration::from_millis(5)),
+            );
+            for i in 0..crate::platform::DEFAULT_MAX_CACHE_SIZE + 10 {
+                runtime.cache().set(format!("entry-{i}"), i);
+            }
+            harness.run(|| {
+                runtime.ensure_maintenance_task();
+                runtime.ensure_maintenance_task();
+            });
+            for _ in 0..5 {
+                harness.pump();
+                sleep(Duration::from_millis(10)).await;
+            }
+            harness.pump();
+            assert_eq!(
+                runtime.cache().size(),
+                crate::platform::DEFAULT_MAX_CACHE_SIZE
+            );
+
+            let manual =
+                ProviderRuntime::new(ProviderRuntimeConfig::new().without_background_maintenance());
+            for i in 0..crate::platform::DEFAULT_MAX_CACHE_SIZE + 3 {
+                manual.cache().set(format!("entry-{i}"), i);
+            }
+            assert_eq!(manual.maintain_now().lru_evicted, 3);
+        });
+    }
+
+    static POLLS: AtomicU32 = AtomicU32::new(0);
+
+    #[derive(Clone, PartialEq)]
+    struct PollingProvider;
+
+    impl Provider<()> for PollingProvider {
+        type Output = u32;
+        type Error = ();
+
+        async fn run(&self, _param: ()) -> Result<Self::Output, Self::Error> {
+            Ok(POLLS.fetch_add(1, Ordering::SeqCst))
+        }
+
+        fn interval(&self) -> Option<Duration> {
+            Some(Duration::from_millis(10))
+        }
+    }
+
+    type PollersSlot = std::rc::Rc<std::cell::RefCell<Option<Signal<[bool; 2]>>>>;
+
+    #[derive(Props, Clone, PartialEq)]
+    struct PollersProps {
+        runtime: ProviderRuntime,
+        shown: PollersSlot,
+    }
+
+    #[allow(non_snake_case)]
+    fn Pollers(props: PollersProps) -> Element {
+        let shown = use_signal(|| [true, true]);
+        *props.shown.borrow_mut() = Some(shown);
+        rsx! {
+            ProviderScope { runtime: props.runtime,
+                if shown()[0] {
+                    Poller {}
+                }
+                if shown()[1] {
+                    Poller {}
+                }
+            }
+        }
+    }
+
+    #[allow(non_snake_case)]
+    fn Poller() -> Element {
+        let _polls = use_provider(PollingProvider, ());
+        rsx!(div {})
+    }
+
+    #[test]
+    fn provider_tasks_stop_with_last_consumer() {
+        block_on(async {
+            let runtime = ProviderRuntime::new(ProviderRuntimeConfig::new());
+            let slot = PollersSlot::default();
+            let mut dom = VirtualDom::new_with_props(
+                Pollers,
+                PollersProps {
+                    runtime: runtime.clone(),
+                    shown: slot.clone(),
+                },
+            );
+            dom.rebuild_in_place();
+            let settle = async |dom: &mut VirtualDom| {
+                for _ in 0..5 {
+                    crate::testing::pump(dom);
+                    sleep(Duration::from_millis(15)).await;
+                }
+            };
+            let cache_key = PollingProvider.id(&());
+            settle(&mut dom).await;
+            assert_eq!(runtime.provider_task_subscribers(&cache_key), 2);
+
+            // The task outlives the consumer that started it
+            let mut shown = slot.borrow().expect("pollers should have rendered");
+            dom.in_runtime(|| shown.set([false, true]));
+            settle(&mut dom).await;
+            assert_eq!(runtime.provider_task_subscribers(&cache_key), 1);
+            let polls = POLLS.load(Ordering::SeqCst);
+            settle(&mut dom).await;
+            assert!(
+                POLLS.load(Ordering::SeqCst) > polls,
+                "the remaining consumer keeps polling"
+            );
+
+            dom.in_runtime(|| shown.set([false, false]));
+            settle(&mut dom).await;
+            assert_eq!(runtime.provider_task_subscribers(&cache_key), 0);
+            let polls = POLLS.load(Ordering::SeqCst);
+            settle(&mut dom).await;
+            assert_eq!(
+                POLLS.load(Ordering::SeqCst),
+                polls,
+                "polling stops with the last consumer"
+            );
+            assert!(
+                !runtime
+                    .refresh_registry()
+                    .has_periodic_task(&cache_key, TaskType::IntervalRefresh)
+            );
+        });
+    }
+
+    #[derive(Clone)]
+    struct ReportProvider {
+        schedule: Option<&'static str>,
+        next_interval: Option<NextIntervalFn<u32>>,
+    }
+
+    impl PartialEq for ReportProvider {
+        fn eq(&self, _other: &Self) -> bool {
+            true
+        }
+    }
+
+    impl Provider<()> for ReportProvider {
+        type Output = u32;
+        type Error = ();
+
+        async fn run(&self, _param: ()) -> Result<Self::Output, Self::Error> {
+            Ok(0)
+        }
+
+        fn schedule(&self) -> Option<Schedule> {
+            self.schedule
+                .map(|schedule| Schedule::parse(schedule).expect("valid schedule"))
+        }
+
+        fn next_interval(&self) -> Option<NextIntervalFn<Self::Output>> {
+            self.next_interval
+        }
+    }
+
+    #[test]
+    fn scheduled_tasks_follow_schedule_changes() {
+        let mut dom = VirtualDom::new(|| rsx!(div {}));
+        dom.rebuild_in_place();
+        let runtime = ProviderRuntime::new(ProviderRuntimeConfig::new());
+        let registry = runtime.refresh_registry();
+        let cache_key = ReportProvider {
+            schedule: None,
+            next_interval: None,
+        }
+        .id(&());
+        // Aligned delays are computed from the cached data
+        runtime.cache().set(cache_key.clone(), Ok::<u32, ()>(0));
+        let ensure = |provider: ReportProvider| {
+            dom.runtime().in_scope(ScopeId::ROOT, || {
+                runtime.ensure_provider_tasks(&provider, &(), &cache_key)
+            })
+        };
+        let first_delay = |task_type| {
+            registry
+                .periodic_task_interval(&cache_key, task_type)
+                .expect("task is running")
+        };
+
+        ensure(ReportProvider {
+            schedule: Some("0 0 0 1 1 *"),
+            next_interval: None,
+        });
+        assert!(first_delay(TaskType::ScheduledRefresh) > Duration::from_secs(1));
+
+        // A new schedule replaces the running task instead of being ignored
+        ensure(ReportProvider {
+            schedule: Some("* * * * * *"),
+            next_interval: None,
+        });
+        assert!(first_delay(TaskType::ScheduledRefresh) <= Duration::from_secs(1));
+
+        ensure(ReportProvider {
+            schedule: None,
+            next_interval: Some(|_| Some(Duration::from_secs(3600))),
+        });
+        assert!(!registry.has_periodic_task(&cache_key, TaskType::ScheduledRefresh));
+        assert_eq!(
+            first_delay(TaskType::AlignedRefresh),
+            Duration::from_secs(3600)
+        );
+
+        ensure(ReportProvider {
+            schedule: None,
+            next_interval: Some(|_| Some(Duration::from_secs(7200))),
+        });
+        assert_eq!(
+            first_delay(TaskType::AlignedRefresh),
+            Duration::from_secs(7200)
+        );
+
+        ensure(ReportProvider {
+            schedule: None,
+            next_interval: None,
+        });
+        assert!(!registry.has_periodic_task(&cache_key, TaskType::AlignedRefresh));
+    }
+
+    #[derive(Clone, Default)]
+    struct StaleProvider {
+        fetched: Arc<std::sync::Mutex<Vec<u32>>>,
+    }
+
+    impl PartialEq for StaleProvider {
+        fn eq(&self, _other: &Self) -> bool {
+            true
+        }
+    }
+
+    impl Provider<u32> for StaleProvider {
+        type Output = u32;
+        type Error = ();
+
+        async fn run(&self, id: u32) -> Result<Self::Output, Self::Error> {
+            self.fetched.lock().unwrap().push(id);
+            Ok(id)
+        }
+
+        fn stale_time(&self) -> Option<Duration> {
+            Some(Duration::from_millis(20))
+        }
+    }
+
+    #[test]
+    fn stale_checks_skip_keys_without_subscribers() {
+        block_on(async {
+            let mut harness = DioxusRuntimeHarness::new();
+            let runtime = ProviderRuntime::new(ProviderRuntimeConfig::new());
+            let provider = StaleProvider::default();
+            let watched_key = provider.id(&1);
+            let unwatched_key = provider.id(&2);
+            let _watcher = runtime.refresh_registry().watch_refresh(&watched_key);
+            for (id, cache_key) in [(1, &watched_key), (2, &unwatched_key)] {
+                runtime.cache().set(cache_key.clone(), Ok::<u32, ()>(0));
+                harness.run(|| runtime.ensure_provider_tasks(&provider, &id, cache_key));
+            }
+
+            // Stale checks run at most once per second
+            for _ in 0..12 {
+                harness.pump();
+                sleep(Duration::from_millis(100)).await;
+            }
+            harness.pump();
+            assert_eq!(*provider.fetched.lock().unwrap(), [1]);
+        });
+    }
+
+    #[test]
+    fn low_priority_prefetch_waits_for_queued_work() {
+        block_on(async {
+            let mut harness = DioxusRuntimeHarness::new();
+            let runtime = ProviderRuntime::new(ProviderRuntimeConfig::new());
+            let (provider, calls) = CountingProvider::new();
+            let cache_key = provider.id(&());
+
+            harness.run(|| runtime.prefetch_low_priority(provider.clone(), ()));
+            assert_eq!(runtime.pending_request_count(&cache_key), 0);
+
+            for _ in 0..3 {
+                harness.pump();
+                sleep(Duration::from_millis(20)).await;
+            }
+            harness.pump();
+            assert_eq!(calls.load(Ordering::SeqCst), 1);
+            assert_eq!(
+                runtime.cache().get::<Result<u32, ()>>(&cache_key),
+                Some(Ok(1))
+            );
+        });
+    }
 }
@@ -1,7 +1,13 @@
 //! Stale-while-revalidate (SWR) helpers owned by the runtime.
 
 use crate::{
-    cache::ProviderCache, hooks::Provider, refresh::RefreshRegistry, types::ProviderParamBounds,
+    cache::ProviderCache,
+    chaos,
+    hooks::Provider,
+    refresh::RefreshRegistry,
+    retry,
+    runtime::cache_mgmt::{key_origin, store_provider_result},
+    types::ProviderParamBounds,
 };
 
 /// Check and handle stale-while-revalidate logic.
@@ -15,19 +21,34 @@ pub fn check_and_handle_swr_core<P, Param>(
     P: Provider<Param> + Clone,
     Param: ProviderParamBounds,
 {
-    let stale_time = provider.stale_time();
-    let cache_expiration = provider.cache_expiration();
-
-    if let Some(stale_duration) = stale_time
-        && let Ok(cache_lock) = cache.cache.lock()
+    if refresh_registry.is_paused(cache_key) {
+        return;
+    }
+    if let Ok(cache_lock) = cache.cache.lock()
         && let Some(entry) = cache_lock.get(cache_key)
     {
-        let is_stale = entry.is_stale(stale_duration);
-        let is_expired = cache_expiration
+        // Retained history in its grace period only refetches when explicitly invalidated
+        let is_stale = entry.is_marked_stale()
+            || (!cache.history().in_grace_period(cache_key)
+                && entry
+                    .effective_stale_time(
+                        provider
+                            .stale_time()
+                            .or(cache.settings().get().default_stale_time),
+                    )
+                    .is_some_and(|stale_duration| entry.is_stale(stale_duration)));
+        let is_expired = entry
+            .effective_expiration(provider.cache_expiration())
             .map(|expires_in| entry.is_expired(expires_in))
             .unwrap_or(false);
 
-        if is_stale && !is_expired && refresh_registry.start_revalidation(cache_key) {
+        if is_stale
+            && !is_expired
+            && provider.min_refetch_interval().is_none_or(|min_interval| {
+                refresh_registry.try_begin_refetch(cache_key, min_interval)
+            })
+            && let Some(revalidation) = refresh_registry.begin_revalidation(cache_key)
+        {
             crate::debug_log!(
                 "🔄 [SWR] Data is stale for key: {} - triggering background revalidation",
                 cache_key
@@ -40,9 +61,22 @@ pub fn check_and_handle_swr_core<P, Param>(
             let refresh_registry_clone = refresh_registry.clone();
 
             crate::platform::task::spawn(async move {
-                let result = provider.run(param).await;
-                let updated = cache.set(cache_key_clone.clone(), result);
-                refresh_registry_clone.complete_revalidation(&cache_key_clone);
+                let origin = key_origin(&provider, &param);
+                let Some(result) = retry::fetch(&provider, param, &cache, &cache_key_clone).await
+                else {
+                    return;
+                };
+                chaos::maybe_duplicate(
+                    &provider,
+                    &cache,
+                    &refresh_registry_clone,
+                    &cache_key_clone,
+                    origin.clone(),
+                    &result,
+                );
+                let updated =
+                    store_provider_result(&provider, &cache, &cache_key_clone, origin, result);
+                drop(revalidation);
                 if updated {
                     refresh_registry_clone.trigger_refresh(&cache_key_clone);
                     crate::debug_log!(
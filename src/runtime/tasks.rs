@@ -4,16 +4,28 @@ use dioxus::prelude::*;
 use std::time::Duration;
 
 use crate::{
-    cache::ProviderCache,
+    cache::{ErrorCaching, ProviderCache},
     hooks::Provider,
     refresh::{RefreshRegistry, TaskType},
-    runtime::swr::check_and_handle_swr_core,
+    runtime::{
+        cache_mgmt::{key_origin, store_provider_result},
+        swr::check_and_handle_swr_core,
+    },
     types::ProviderParamBounds,
 };
 
 /// Minimum interval for periodic tasks to prevent busy spinning.
 const MIN_TASK_INTERVAL: Duration = Duration::from_millis(1);
 
+/// How often entries with per-entry cache policies are checked for staleness and expiration.
+const DYNAMIC_POLICY_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Minimum delay between data-aligned refreshes, guarding against timestamps in the past.
+const MIN_ALIGNED_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Delay used by data-aligned refreshes when there is no data and no fixed interval.
+const ALIGNED_FALLBACK_INTERVAL: Duration = Duration::from_secs(30);
+
 #[cfg(not(target_family = "wasm"))]
 pub fn setup_interval_task_core<P, Param>(
     provider: &P,
@@ -25,7 +37,9 @@ pub fn setup_interval_task_core<P, Param>(
     P: Provider<Param> + Clone + Send,
     Param: ProviderParamBounds,
 {
-    if let Some(interval) = provider.interval() {
+    if let Some(interval) = provider.interval()
+        && provider.next_interval().is_none()
+    {
         let cache_clone = cache.clone();
         let provider_clone = provider.clone();
         let param_clone = param.clone();
@@ -40,13 +54,23 @@ pub fn setup_interval_task_core<P, Param>(
             let refresh_registry_for_task = refresh_registry_clone.clone();
 
             spawn(async move {
-                let result = provider_for_task.run(param_for_task).await;
-                let updated = cache_for_task.set(cache_key_for_task.clone(), result);
+                let origin = key_origin(&provider_for_task, &param_for_task);
+                let result = crate::timeout::run(&provider_for_task, param_for_task).await;
+                let updated = store_provider_result(
+                    &provider_for_task,
+                    &cache_for_task,
+                    &cache_key_for_task,
+                    origin,
+                    result,
+                );
                 if updated {
                     refresh_registry_for_task.trigger_refresh(&cache_key_for_task);
                 }
             });
         });
+    } else {
+        // The setting was removed since the task started
+        refresh_registry.stop_periodic_task(cache_key, TaskType::IntervalRefresh);
     }
 }
 
@@ -61,7 +85,9 @@ pub fn setup_interval_task_core<P, Param>(
     P: Provider<Param> + Clone,
     Param: ProviderParamBounds,
 {
-    if let Some(interval) = provider.interval() {
+    if let Some(interval) = provider.interval()
+        && provider.next_interval().is_none()
+    {
         let cache_clone = cache.clone();
         let provider_clone = provider.clone();
         let param_clone = param.clone();
@@ -76,13 +102,137 @@ pub fn setup_interval_task_core<P, Param>(
             let refresh_registry_for_task = refresh_registry_clone.clone();
 
             spawn(async move {
-                let result = provider_for_task.run(param_for_task).await;
-                let updated = cache_for_task.set(cache_key_for_task.clone(), result);
+                let origin = key_origin(&provider_for_task, &param_for_task);
+                let result = crate::timeout::run(&provider_for_task, param_for_task).await;
+                let updated = store_provider_result(
+                    &provider_for_task,
+                    &cache_for_task,
+                    &cache_key_for_task,
+                    origin,
+                    result,
+                );
                 if updated {
                     refresh_registry_for_task.trigger_refresh(&cache_key_for_task);
                 }
             });
         });
+    } else {
+        // The setting was removed since the task started
+        refresh_registry.stop_periodic_task(cache_key, TaskType::IntervalRefresh);
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+pub fn setup_schedule_task_core<P, Param>(
+    provider: &P,
+    param: &Param,
+    cache_key: &str,
+    cache: &ProviderCache,
+    refresh_registry: &RefreshRegistry,
+) where
+    P: Provider<Param> + Clone + Send,
+    Param: ProviderParamBounds,
+{
+    if let Some(schedule) = provider.schedule() {
+        let cache_clone = cache.clone();
+        let provider_clone = provider.clone();
+        let param_clone = param.clone();
+        let cache_key_clone = cache_key.to_string();
+        let refresh_registry_clone = refresh_registry.clone();
+
+        refresh_registry.start_scheduled_task(cache_key, schedule, move || {
+            let cache_for_task = cache_clone.clone();
+            let provider_for_task = provider_clone.clone();
+            let param_for_task = param_clone.clone();
+            let cache_key_for_task = cache_key_clone.clone();
+            let refresh_registry_for_task = refresh_registry_clone.clone();
+
+            spawn(async move {
+                crate::debug_log!(
+                    "⏰ [SCHEDULE] Scheduled refresh for key: {}",
+                    cache_key_for_task
+                );
+                let origin = key_origin(&provider_for_task, &param_for_task);
+                let result = crate::timeout::run(&provider_for_task, param_for_task).await;
+                let updated = store_provider_result(
+                    &provider_for_task,
+                    &cache_for_task,
+                    &cache_key_for_task,
+                    origin,
+                    result,
+                );
+                if updated {
+                    refresh_registry_for_task.trigger_refresh(&cache_key_for_task);
+                }
+            });
+        });
+    } else {
+        // The setting was removed since the task started
+        refresh_registry.stop_periodic_task(cache_key, TaskType::ScheduledRefresh);
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+pub fn setup_aligned_interval_task_core<P, Param>(
+    provider: &P,
+    param: &Param,
+    cache_key: &str,
+    cache: &ProviderCache,
+    refresh_registry: &RefreshRegistry,
+) where
+    P: Provider<Param> + Clone + Send,
+    Param: ProviderParamBounds,
+{
+    if let Some(next_interval) = provider.next_interval() {
+        let fallback = provider.interval().unwrap_or(ALIGNED_FALLBACK_INTERVAL);
+        // The delay function and its fallback identify the task, so changing either restarts it
+        let source = format!("{:p}/{fallback:?}", next_interval as *const ());
+        let cache_for_delay = cache.clone();
+        let cache_key_for_delay = cache_key.to_string();
+        let cache_clone = cache.clone();
+        let provider_clone = provider.clone();
+        let param_clone = param.clone();
+        let cache_key_clone = cache_key.to_string();
+        let refresh_registry_clone = refresh_registry.clone();
+
+        refresh_registry.start_aligned_task(
+            cache_key,
+            &source,
+            move || {
+                let delay = match cache_for_delay
+                    .get::<Result<P::Output, P::Error>>(&cache_key_for_delay)
+                {
+                    Some(Ok(data)) => next_interval(&data).unwrap_or(fallback),
+                    _ => fallback,
+                };
+                Some(std::cmp::max(delay, MIN_ALIGNED_INTERVAL))
+            },
+            move || {
+                let cache_for_task = cache_clone.clone();
+                let provider_for_task = provider_clone.clone();
+                let param_for_task = param_clone.clone();
+                let cache_key_for_task = cache_key_clone.clone();
+                let refresh_registry_for_task = refresh_registry_clone.clone();
+
+                async move {
+                    let origin = key_origin(&provider_for_task, &param_for_task);
+                    let result = crate::timeout::run(&provider_for_task, param_for_task).await;
+                    let updated = store_provider_result(
+                        &provider_for_task,
+                        &cache_for_task,
+                        &cache_key_for_task,
+                        origin,
+                        result,
+                    );
+                    if updated {
+                        refresh_registry_for_task.trigger_refresh(&cache_key_for_task);
+                    }
+                }
+            },
+        );
+    } else {
+        // The setting was removed since the task started
+        refresh_registry.stop_periodic_task(cache_key, TaskType::AlignedRefresh);
     }
 }
 
@@ -97,33 +247,161 @@ pub fn setup_cache_expiration_task_core<P, Param>(
     P: Provider<Param> + Clone + Send,
     Param: ProviderParamBounds,
 {
-    if let Some(expiration) = provider.cache_expiration() {
+    let static_expiration = provider.cache_expiration();
+    if static_expiration.is_some()
+        || provider.cache_policy().is_some()
+        || matches!(provider.cache_errors(), ErrorCaching::Short(_))
+        || provider.cache_not_found().is_some()
+    {
         let cache_clone = cache.clone();
         let cache_key_clone = cache_key.to_string();
         let refresh_registry_clone = refresh_registry.clone();
 
-        let check_interval = std::cmp::max(expiration / 4, MIN_TASK_INTERVAL);
+        let check_interval = static_expiration
+            .map(|expiration| std::cmp::max(expiration / 4, MIN_TASK_INTERVAL))
+            .unwrap_or(DYNAMIC_POLICY_CHECK_INTERVAL);
 
         refresh_registry.start_periodic_task(
             cache_key,
             TaskType::CacheExpiration,
             check_interval,
             move || {
-                if let Ok(mut cache_lock) = cache_clone.cache.lock() {
-                    if let Some(entry) = cache_lock.get(&cache_key_clone) {
-                        if entry.is_expired(expiration) {
-                            crate::debug_log!(
-                                "🗑️ [AUTO-EXPIRATION] Cache expired for key: {} - triggering reactive refresh",
-                                cache_key_clone
-                            );
-                            cache_lock.remove(&cache_key_clone);
-                            drop(cache_lock);
-                            refresh_registry_clone.trigger_refresh(&cache_key_clone);
-                        }
+                if let Ok(mut cache_lock) = cache_clone.cache.lock()
+                    && let Some(entry) = cache_lock.get(&cache_key_clone)
+                    && let Some(expiration) = entry.effective_expiration(static_expiration)
+                    // Errors that are never cached wait for their next use instead of
+                    // refetching for consumers that already show them
+                    && !expiration.is_zero()
+                    && entry.is_expired(expiration)
+                    && !cache_clone.history().in_grace_period(&cache_key_clone)
+                {
+                    crate::debug_log!(
+                        "🗑️ [AUTO-EXPIRATION] Cache expired for key: {} - triggering reactive refresh",
+                        cache_key_clone
+                    );
+                    cache_lock.remove(&cache_key_clone);
+                    drop(cache_lock);
+                    refresh_registry_clone.trigger_refresh(&cache_key_clone);
+                }
+            },
+        );
+    } else {
+        // The setting was removed since the task started
+        refresh_registry.stop_periodic_task(cache_key, TaskType::CacheExpiration);
+    }
+}
+
+#[cfg(target_family = "wasm")]
+pub fn setup_schedule_task_core<P, Param>(
+    provider: &P,
+    param: &Param,
+    cache_key: &str,
+    cache: &ProviderCache,
+    refresh_registry: &RefreshRegistry,
+) where
+    P: Provider<Param> + Clone,
+    Param: ProviderParamBounds,
+{
+    if let Some(schedule) = provider.schedule() {
+        let cache_clone = cache.clone();
+        let provider_clone = provider.clone();
+        let param_clone = param.clone();
+        let cache_key_clone = cache_key.to_string();
+        let refresh_registry_clone = refresh_registry.clone();
+
+        refresh_registry.start_scheduled_task(cache_key, schedule, move || {
+            let cache_for_task = cache_clone.clone();
+            let provider_for_task = provider_clone.clone();
+            let param_for_task = param_clone.clone();
+            let cache_key_for_task = cache_key_clone.clone();
+            let refresh_registry_for_task = refresh_registry_clone.clone();
+
+            spawn(async move {
+                crate::debug_log!(
+                    "⏰ [SCHEDULE] Scheduled refresh for key: {}",
+                    cache_key_for_task
+                );
+                let origin = key_origin(&provider_for_task, &param_for_task);
+                let result = crate::timeout::run(&provider_for_task, param_for_task).await;
+                let updated = store_provider_result(
+                    &provider_for_task,
+                    &cache_for_task,
+                    &cache_key_for_task,
+                    origin,
+                    result,
+                );
+                if updated {
+                    refresh_registry_for_task.trigger_refresh(&cache_key_for_task);
+                }
+            });
+        });
+    } else {
+        // The setting was removed since the task started
+        refresh_registry.stop_periodic_task(cache_key, TaskType::ScheduledRefresh);
+    }
+}
+
+#[cfg(target_family = "wasm")]
+pub fn setup_aligned_interval_task_core<P, Param>(
+    provider: &P,
+    param: &Param,
+    cache_key: &str,
+    cache: &ProviderCache,
+    refresh_registry: &RefreshRegistry,
+) where
+    P: Provider<Param> + Clone,
+    Param: ProviderParamBounds,
+{
+    if let Some(next_interval) = provider.next_interval() {
+        let fallback = provider.interval().unwrap_or(ALIGNED_FALLBACK_INTERVAL);
+        // The delay function and its fallback identify the task, so changing either restarts it
+        let source = format!("{:p}/{fallback:?}", next_interval as *const ());
+        let cache_for_delay = cache.clone();
+        let cache_key_for_delay = cache_key.to_string();
+        let cache_clone = cache.clone();
+        let provider_clone = provider.clone();
+        let param_clone = param.clone();
+        let cache_key_clone = cache_key.to_string();
+        let refresh_registry_clone = refresh_registry.clone();
+
+        refresh_registry.start_aligned_task(
+            cache_key,
+            &source,
+            move || {
+                let delay = match cache_for_delay
+                    .get::<Result<P::Output, P::Error>>(&cache_key_for_delay)
+                {
+                    Some(Ok(data)) => next_interval(&data).unwrap_or(fallback),
+                    _ => fallback,
+                };
+                Some(std::cmp::max(delay, MIN_ALIGNED_INTERVAL))
+            },
+            move || {
+                let cache_for_task = cache_clone.clone();
+                let provider_for_task = provider_clone.clone();
+                let param_for_task = param_clone.clone();
+                let cache_key_for_task = cache_key_clone.clone();
+                let refresh_registry_for_task = refresh_registry_clone.clone();
+
+                async move {
+                    let origin = key_origin(&provider_for_task, &param_for_task);
+                    let result = crate::timeout::run(&provider_for_task, param_for_task).await;
+                    let updated = store_provider_result(
+                        &provider_for_task,
+                        &cache_for_task,
+                        &cache_key_for_task,
+                        origin,
+                        result,
+                    );
+                    if updated {
+                        refresh_registry_for_task.trigger_refresh(&cache_key_for_task);
                     }
                 }
             },
         );
+    } else {
+        // The setting was removed since the task started
+        refresh_registry.stop_periodic_task(cache_key, TaskType::AlignedRefresh);
     }
 }
 
@@ -138,33 +416,47 @@ pub fn setup_cache_expiration_task_core<P, Param>(
     P: Provider<Param> + Clone,
     Param: ProviderParamBounds,
 {
-    if let Some(expiration) = provider.cache_expiration() {
+    let static_expiration = provider.cache_expiration();
+    if static_expiration.is_some()
+        || provider.cache_policy().is_some()
+        || matches!(provider.cache_errors(), ErrorCaching::Short(_))
+        || provider.cache_not_found().is_some()
+    {
         let cache_clone = cache.clone();
         let cache_key_clone = cache_key.to_string();
         let refresh_registry_clone = refresh_registry.clone();
 
-        let check_interval = std::cmp::max(expiration / 4, MIN_TASK_INTERVAL);
+        let check_interval = static_expiration
+            .map(|expiration| std::cmp::max(expiration / 4, MIN_TASK_INTERVAL))
+            .unwrap_or(DYNAMIC_POLICY_CHECK_INTERVAL);
 
         refresh_registry.start_periodic_task(
             cache_key,
             TaskType::CacheExpiration,
             check_interval,
             move || {
-                if let Ok(mut cache_lock) = cache_clone.cache.lock() {
-                    if let Some(entry) = cache_lock.get(&cache_key_clone) {
-                        if entry.is_expired(expiration) {
-                            crate::debug_log!(
-                                "🗑️ [AUTO-EXPIRATION] Cache expired for key: {} - triggering reactive refresh",
-                                cache_key_clone
-                            );
-                            cache_lock.remove(&cache_key_clone);
-                            drop(cache_lock);
-                            refresh_registry_clone.trigger_refresh(&cache_key_clone);
-                        }
-                    }
+                if let Ok(mut cache_lock) = cache_clone.cache.lock()
+                    && let Some(entry) = cache_lock.get(&cache_key_clone)
+                    && let Some(expiration) = entry.effective_expiration(static_expiration)
+                    // Errors that are never cached wait for their next use instead of
+                    // refetching for consumers that already show them
+                    && !expiration.is_zero()
+                    && entry.is_expired(expiration)
+                    && !cache_clone.history().in_grace_period(&cache_key_clone)
+                {
+                    crate::debug_log!(
+                        "🗑️ [AUTO-EXPIRATION] Cache expired for key: {} - triggering reactive refresh",
+                        cache_key_clone
+                    );
+                    cache_lock.remove(&cache_key_clone);
+                    drop(cache_lock);
+                    refresh_registry_clone.trigger_refresh(&cache_key_clone);
                 }
             },
         );
+    } else {
+        // The setting was removed since the task started
+        refresh_registry.stop_periodic_task(cache_key, TaskType::CacheExpiration);
     }
 }
 
@@ -179,7 +471,17 @@ pub fn setup_stale_check_task_core<P, Param>(
     P: Provider<Param> + Clone + Send,
     Param: ProviderParamBounds,
 {
-    if let Some(stale_time) = provider.stale_time() {
+    // Per-entry policies need checking even without a static stale time; the registry
+    // checks every quarter of the given duration.
+    let stale_time = provider
+        .stale_time()
+        .or(cache.settings().get().default_stale_time)
+        .or_else(|| {
+            provider
+                .cache_policy()
+                .map(|_| DYNAMIC_POLICY_CHECK_INTERVAL * 4)
+        });
+    if let Some(stale_time) = stale_time {
         let cache_clone = cache.clone();
         let provider_clone = provider.clone();
         let param_clone = param.clone();
@@ -187,6 +489,10 @@ pub fn setup_stale_check_task_core<P, Param>(
         let refresh_registry_clone = refresh_registry.clone();
 
         refresh_registry.start_stale_check_task(cache_key, stale_time, move || {
+            // Nobody displays the entry; its next use revalidates it instead
+            if !refresh_registry_clone.has_subscribers(&cache_key_clone) {
+                return;
+            }
             check_and_handle_swr_core(
                 &provider_clone,
                 &param_clone,
@@ -195,6 +501,9 @@ pub fn setup_stale_check_task_core<P, Param>(
                 &refresh_registry_clone,
             );
         });
+    } else {
+        // The setting was removed since the task started
+        refresh_registry.stop_periodic_task(cache_key, TaskType::StaleCheck);
     }
 }
 
@@ -209,7 +518,17 @@ pub fn setup_stale_check_task_core<P, Param>(
     P: Provider<Param> + Clone,
     Param: ProviderParamBounds,
 {
-    if let Some(stale_time) = provider.stale_time() {
+    // Per-entry policies need checking even without a static stale time; the registry
+    // checks every quarter of the given duration.
+    let stale_time = provider
+        .stale_time()
+        .or(cache.settings().get().default_stale_time)
+        .or_else(|| {
+            provider
+                .cache_policy()
+                .map(|_| DYNAMIC_POLICY_CHECK_INTERVAL * 4)
+        });
+    if let Some(stale_time) = stale_time {
         let cache_clone = cache.clone();
         let provider_clone = provider.clone();
         let param_clone = param.clone();
@@ -217,6 +536,104 @@ pub fn setup_stale_check_task_core<P, Param>(
         let refresh_registry_clone = refresh_registry.clone();
 
         refresh_registry.start_stale_check_task(cache_key, stale_time, move || {
+            // Nobody displays the entry; its next use revalidates it instead
+            if !refresh_registry_clone.has_subscribers(&cache_key_clone) {
+                return;
+            }
+            check_and_handle_swr_core(
+                &provider_clone,
+                &param_clone,
+                &cache_key_clone,
+                &cache_clone,
+                &refresh_registry_clone,
+            );
+        });
+    } else {
+        // The setting was removed since the task started
+        refresh_registry.stop_periodic_task(cache_key, TaskType::StaleCheck);
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+pub fn setup_focus_refetch_task_core<P, Param>(
+    provider: &P,
+    param: &Param,
+    cache_key: &str,
+    cache: &ProviderCache,
+    refresh_registry: &RefreshRegistry,
+) where
+    P: Provider<Param> + Clone + Send,
+    Param: ProviderParamBounds,
+{
+    if provider.refetch_on_focus() {
+        let cache_clone = cache.clone();
+        let provider_clone = provider.clone();
+        let param_clone = param.clone();
+        let cache_key_clone = cache_key.to_string();
+        let refresh_registry_clone = refresh_registry.clone();
+
+        refresh_registry.start_focus_task(cache_key, move || {
+            // Nobody displays the entry; its next use revalidates it instead
+            if !refresh_registry_clone.has_subscribers(&cache_key_clone) {
+                return;
+            }
+            // Without a stale time, data is stale as soon as the user looks away
+            if provider_clone
+                .stale_time()
+                .or(cache_clone.settings().get().default_stale_time)
+                .is_none()
+                && provider_clone.cache_policy().is_none()
+            {
+                cache_clone.mark_stale(&cache_key_clone);
+            }
+            crate::debug_log!("👀 [FOCUS] Checking key after focus: {}", cache_key_clone);
+            check_and_handle_swr_core(
+                &provider_clone,
+                &param_clone,
+                &cache_key_clone,
+                &cache_clone,
+                &refresh_registry_clone,
+            );
+        });
+    } else {
+        // The setting was removed since the task started
+        refresh_registry.stop_periodic_task(cache_key, TaskType::FocusRefetch);
+    }
+}
+
+#[cfg(target_family = "wasm")]
+pub fn setup_focus_refetch_task_core<P, Param>(
+    provider: &P,
+    param: &Param,
+    cache_key: &str,
+    cache: &ProviderCache,
+    refresh_registry: &RefreshRegistry,
+) where
+    P: Provider<Param> + Clone,
+    Param: ProviderParamBounds,
+{
+    if provider.refetch_on_focus() {
+        let cache_clone = cache.clone();
+        let provider_clone = provider.clone();
+        let param_clone = param.clone();
+        let cache_key_clone = cache_key.to_string();
+        let refresh_registry_clone = refresh_registry.clone();
+
+        refresh_registry.start_focus_task(cache_key, move || {
+            // Nobody displays the entry; its next use revalidates it instead
+            if !refresh_registry_clone.has_subscribers(&cache_key_clone) {
+                return;
+            }
+            // Without a stale time, data is stale as soon as the user looks away
+            if provider_clone
+                .stale_time()
+                .or(cache_clone.settings().get().default_stale_time)
+                .is_none()
+                && provider_clone.cache_policy().is_none()
+            {
+                cache_clone.mark_stale(&cache_key_clone);
+            }
+            crate::debug_log!("👀 [FOCUS] Checking key after focus: {}", cache_key_clone);
             check_and_handle_swr_core(
                 &provider_clone,
                 &param_clone,
@@ -225,6 +642,109 @@ pub fn setup_stale_check_task_core<P, Param>(
                 &refresh_registry_clone,
             );
         });
+    } else {
+        // The setting was removed since the task started
+        refresh_registry.stop_periodic_task(cache_key, TaskType::FocusRefetch);
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+pub fn setup_reconnect_refetch_task_core<P, Param>(
+    provider: &P,
+    param: &Param,
+    cache_key: &str,
+    cache: &ProviderCache,
+    refresh_registry: &RefreshRegistry,
+) where
+    P: Provider<Param> + Clone + Send,
+    Param: ProviderParamBounds,
+{
+    if provider.refetch_on_reconnect() {
+        let cache_clone = cache.clone();
+        let provider_clone = provider.clone();
+        let param_clone = param.clone();
+        let cache_key_clone = cache_key.to_string();
+        let refresh_registry_clone = refresh_registry.clone();
+
+        refresh_registry.start_reconnect_task(cache_key, move || {
+            // Nobody displays the entry; its next use refetches it instead
+            if !refresh_registry_clone.has_subscribers(&cache_key_clone) {
+                return;
+            }
+            crate::debug_log!(
+                "🌐 [NETWORK] Checking key after reconnecting: {}",
+                cache_key_clone
+            );
+            match cache_clone.get::<Result<P::Output, P::Error>>(&cache_key_clone) {
+                // Uncached errors are only held by their consumers, which refetch on refresh
+                None => refresh_registry_clone.trigger_refresh(&cache_key_clone),
+                Some(result) => {
+                    if result.is_err() {
+                        cache_clone.mark_stale(&cache_key_clone);
+                    }
+                    check_and_handle_swr_core(
+                        &provider_clone,
+                        &param_clone,
+                        &cache_key_clone,
+                        &cache_clone,
+                        &refresh_registry_clone,
+                    );
+                }
+            }
+        });
+    } else {
+        // The setting was removed since the task started
+        refresh_registry.stop_periodic_task(cache_key, TaskType::ReconnectRefetch);
+    }
+}
+
+#[cfg(target_family = "wasm")]
+pub fn setup_reconnect_refetch_task_core<P, Param>(
+    provider: &P,
+    param: &Param,
+    cache_key: &str,
+    cache: &ProviderCache,
+    refresh_registry: &RefreshRegistry,
+) where
+    P: Provider<Param> + Clone,
+    Param: ProviderParamBounds,
+{
+    if provider.refetch_on_reconnect() {
+        let cache_clone = cache.clone();
+        let provider_clone = provider.clone();
+        let param_clone = param.clone();
+        let cache_key_clone = cache_key.to_string();
+        let refresh_registry_clone = refresh_registry.clone();
+
+        refresh_registry.start_reconnect_task(cache_key, move || {
+            // Nobody displays the entry; its next use refetches it instead
+            if !refresh_registry_clone.has_subscribers(&cache_key_clone) {
+                return;
+            }
+            crate::debug_log!(
+                "🌐 [NETWORK] Checking key after reconnecting: {}",
+                cache_key_clone
+            );
+            match cache_clone.get::<Result<P::Output, P::Error>>(&cache_key_clone) {
+                // Uncached errors are only held by their consumers, which refetch on refresh
+                None => refresh_registry_clone.trigger_refresh(&cache_key_clone),
+                Some(result) => {
+                    if result.is_err() {
+                        cache_clone.mark_stale(&cache_key_clone);
+                    }
+                    check_and_handle_swr_core(
+                        &provider_clone,
+                        &param_clone,
+                        &cache_key_clone,
+                        &cache_clone,
+                        &refresh_registry_clone,
+                    );
+                }
+            }
+        });
+    } else {
+        // The setting was removed since the task started
+        refresh_registry.stop_periodic_task(cache_key, TaskType::ReconnectRefetch);
     }
 }
 
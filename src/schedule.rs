@@ -0,0 +1,315 @@
+//! # Wall-Clock Schedules
+//!
+//! This module provides [`Schedule`], a wall-clock alternative to a fixed refresh `interval`.
+//! Providers with a schedule refresh at specific times of day, such as when a daily report is
+//! published or a market opens, instead of every N seconds after they were first used.
+//!
+//! Two formats are supported:
+//!
+//! - Cron expressions with six fields (`sec min hour day-of-month month day-of-week`),
+//!   e.g. `"0 */15 * * * *"` for every quarter hour. Five-field expressions without the
+//!   seconds column are accepted as well.
+//! - `"daily at HH:MM"` (or `"daily at HH:MM:SS"`), e.g. `"daily at 09:30"`.
+//!
+//! All times are evaluated in UTC.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use dioxus_provider::prelude::*;
+//!
+//! #[provider(schedule = "daily at 06:00")]
+//! async fn daily_report() -> Result<Report, String> {
+//!     fetch_report().await
+//! }
+//! ```
+
+use std::{fmt, time::Duration};
+
+use crate::{errors::ProviderError, platform::now_secs};
+
+const SECS_PER_DAY: u64 = 86_400;
+
+/// How far ahead to search for the next matching time before giving up
+const MAX_SEARCH_DAYS: u64 = 366 * 5;
+
+/// A set of allowed values for a single cron field, stored as a bitmask
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FieldSet {
+    bits: u64,
+    restricted: bool,
+}
+
+impl FieldSet {
+    fn only(value: u32) -> Self {
+        Self {
+            bits: 1 << value,
+            restricted: true,
+        }
+    }
+
+    fn any(min: u32, max: u32) -> Self {
+        Self {
+            bits: (min..=max).fold(0, |bits, value| bits | (1 << value)),
+            restricted: false,
+        }
+    }
+
+    fn contains(&self, value: u32) -> bool {
+        self.bits & (1 << value) != 0
+    }
+
+    fn parse(field: &str, min: u32, max: u32, name: &str) -> Result<Self, ProviderError> {
+        if field == "*" {
+            return Ok(Self::any(min, max));
+        }
+
+        let invalid =
+            || ProviderError::Configuration(format!("Invalid {name} field in schedule: {field}"));
+        let parse_value = |value: &str| -> Result<u32, ProviderError> {
+            value
+                .parse::<u32>()
+                .ok()
+                .filter(|value| (min..=max).contains(value))
+                .ok_or_else(invalid)
+        };
+
+        let mut bits = 0u64;
+        for part in field.split(',') {
+            let (range, step) = match part.split_once('/') {
+                Some((range, step)) => (range, step.parse::<u32>().map_err(|_| invalid())?),
+                None => (part, 1),
+            };
+            if step == 0 {
+                return Err(invalid());
+            }
+
+            let (start, end) = if range == "*" {
+                (min, max)
+            } else if let Some((start, end)) = range.split_once('-') {
+                (parse_value(start)?, parse_value(end)?)
+            } else {
+                let start = parse_value(range)?;
+                // `5/10` means "starting at 5, every 10"
+                (start, if step > 1 { max } else { start })
+            };
+            if start > end {
+                return Err(invalid());
+            }
+
+            for value in (start..=end).step_by(step as usize) {
+                bits |= 1 << value;
+            }
+        }
+
+        Ok(Self {
+            bits,
+            restricted: true,
+        })
+    }
+}
+
+/// A wall-clock refresh schedule, evaluated in UTC
+#[derive(Clone, PartialEq, Eq)]
+pub struct Schedule {
+    source: String,
+    seconds: FieldSet,
+    minutes: FieldSet,
+    hours: FieldSet,
+    days_of_month: FieldSet,
+    months: FieldSet,
+    days_of_week: FieldSet,
+}
+
+impl fmt::Debug for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Schedule").field(&self.source).finish()
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl Schedule {
+    /// Parse a cron expression or a `"daily at HH:MM"` description
+    pub fn parse(source: &str) -> Result<Self, ProviderError> {
+        let trimmed = source.trim();
+        if let Some(time) = trimmed.strip_prefix("daily at ") {
+            return Self::parse_daily(source, time.trim());
+        }
+
+        let fields: Vec<&str> = trimmed.split_whitespace().collect();
+        let fields = match fields.len() {
+            6 => fields,
+            5 => std::iter::once("0").chain(fields).collect(),
+            _ => {
+                return Err(ProviderError::Configuration(format!(
+                    "Invalid schedule '{source}': expected a cron expression or 'daily at HH:MM'"
+                )));
+            }
+        };
+
+        let mut days_of_week = FieldSet::parse(fields[5], 0, 7, "day-of-week")?;
+        // Both 0 and 7 mean Sunday
+        if days_of_week.contains(7) {
+            days_of_week.bits = (days_of_week.bits | 1) & !(1 << 7);
+        }
+
+        Ok(Self {
+            source: source.to_string(),
+            seconds: FieldSet::parse(fields[0], 0, 59, "second")?,
+            minutes: FieldSet::parse(fields[1], 0, 59, "minute")?,
+            hours: FieldSet::parse(fields[2], 0, 23, "hour")?,
+            days_of_month: FieldSet::parse(fields[3], 1, 31, "day-of-month")?,
+            months: FieldSet::parse(fields[4], 1, 12, "month")?,
+            days_of_week,
+        })
+    }
+
+    fn parse_daily(source: &str, time: &str) -> Result<Self, ProviderError> {
+        let invalid = || {
+            ProviderError::Configuration(format!(
+                "Invalid schedule '{source}': expected 'daily at HH:MM'"
+            ))
+        };
+        let parts: Vec<u32> = time
+            .split(':')
+            .map(|part| part.parse::<u32>().map_err(|_| invalid()))
+            .collect::<Result<_, _>>()?;
+        let (hour, minute, second) = match parts.as_slice() {
+            [hour, minute] => (*hour, *minute, 0),
+            [hour, minute, second] => (*hour, *minute, *second),
+            _ => return Err(invalid()),
+        };
+        if hour > 23 || minute > 59 || second > 59 {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            source: source.to_string(),
+            seconds: FieldSet::only(second),
+            minutes: FieldSet::only(minute),
+            hours: FieldSet::only(hour),
+            days_of_month: FieldSet::any(1, 31),
+            months: FieldSet::any(1, 12),
+            days_of_week: FieldSet::any(0, 6),
+        })
+    }
+
+    /// The first matching Unix timestamp strictly after `after`
+    ///
+    /// Returns `None` if nothing matches within the next five years (e.g. `"0 0 0 31 2 *"`).
+    pub fn next_after(&self, after: u64) -> Option<u64> {
+        let start = after + 1;
+        let start_day = start / SECS_PER_DAY;
+
+        for day in start_day..start_day + MAX_SEARCH_DAYS {
+            if !self.matches_day(day) {
+                continue;
+            }
+            let day_start = day * SECS_PER_DAY;
+            let from = if day == start_day {
+                start - day_start
+            } else {
+                0
+            };
+            if let Some(offset) = self.first_time_of_day(from) {
+                return Some(day_start + offset);
+            }
+        }
+
+        None
+    }
+
+    /// Time remaining until the next matching wall-clock time
+    pub fn duration_until_next(&self) -> Option<Duration> {
+        let now = now_secs();
+        self.next_after(now)
+            .map(|next| Duration::from_secs(next.saturating_sub(now)))
+    }
+
+    fn matches_day(&self, day: u64) -> bool {
+        let (_, month, day_of_month) = civil_from_days(day);
+        if !self.months.contains(month) {
+            return false;
+        }
+        let weekday = ((day + 4) % 7) as u32; // 1970-01-01 was a Thursday
+        let dom = self.days_of_month.contains(day_of_month);
+        let dow = self.days_of_week.contains(weekday);
+
+        // Like cron, when both day fields are restricted either one may match
+        if self.days_of_month.restricted && self.days_of_week.restricted {
+            dom || dow
+        } else {
+            dom && dow
+        }
+    }
+
+    fn first_time_of_day(&self, from: u64) -> Option<u64> {
+        (from..SECS_PER_DAY).find(|&offset| {
+            let hour = (offset / 3600) as u32;
+            let minute = (offset / 60 % 60) as u32;
+            let second = (offset % 60) as u32;
+            self.hours.contains(hour)
+                && self.minutes.contains(minute)
+                && self.seconds.contains(second)
+        })
+    }
+}
+
+/// Convert days since the Unix epoch into a `(year, month, day)` civil date
+fn civil_from_days(days: u64) -> (i64, u32, u32) {
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2024-03-15 10:07:30 UTC, a Friday
+    const FRIDAY_MORNING: u64 = 1_710_497_250;
+
+    #[test]
+    fn cron_every_quarter_hour() {
+        let schedule = Schedule::parse("0 */15 * * * *").unwrap();
+        // Next quarter hour is 10:15:00
+        assert_eq!(schedule.next_after(FRIDAY_MORNING), Some(1_710_497_700));
+    }
+
+    #[test]
+    fn daily_at_rolls_over_to_next_day() {
+        let schedule = Schedule::parse("daily at 09:30").unwrap();
+        // 09:30 has already passed, so the next run is 2024-03-16 09:30:00
+        assert_eq!(schedule.next_after(FRIDAY_MORNING), Some(1_710_581_400));
+    }
+
+    #[test]
+    fn weekday_field_skips_weekend() {
+        // Weekdays at 14:30, market-open style
+        let schedule = Schedule::parse("30 14 * * 1-5").unwrap();
+        let friday_afternoon = schedule.next_after(FRIDAY_MORNING).unwrap();
+        assert_eq!(friday_afternoon, 1_710_513_000);
+        // After Friday's run the next one is Monday 2024-03-18 14:30:00
+        assert_eq!(schedule.next_after(friday_afternoon), Some(1_710_772_200));
+    }
+
+    #[test]
+    fn rejects_invalid_expressions() {
+        assert!(Schedule::parse("every tuesday").is_err());
+        assert!(Schedule::parse("0 61 * * * *").is_err());
+        assert!(Schedule::parse("daily at 25:00").is_err());
+        assert!(Schedule::parse("0 */0 * * * *").is_err());
+    }
+}
@@ -0,0 +1,44 @@
+//! # Scoped Runtimes
+//!
+//! Every hook uses the global runtime started by [`init`](crate::global::init) unless a
+//! [`ProviderScope`] above it provides another one. A scoped runtime is created with
+//! [`ProviderConfig::build`](crate::global::ProviderConfig::build) and has its own cache,
+//! refresh registry and background tasks, so tests and the windows of a multi-window
+//! desktop app don't share data. Scopes nest: the nearest one wins.
+//!
+//! The `global::get_global_*` functions keep returning the global runtime; hold on to the
+//! scoped runtime to reach its cache from outside components.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use dioxus_provider::prelude::*;
+//!
+//! #[component]
+//! fn Window() -> Element {
+//!     let runtime = use_hook(|| ProviderConfig::new().build());
+//!     rsx! {
+//!         ProviderScope { runtime, Inbox {} }
+//!     }
+//! }
+//! ```
+
+use dioxus::prelude::*;
+
+use crate::runtime::ProviderRuntime;
+
+/// Makes the hooks of `children` use `runtime` instead of the global runtime
+///
+/// The runtime given on the first render is kept for the lifetime of the scope.
+#[component]
+pub fn ProviderScope(runtime: ProviderRuntime, children: Element) -> Element {
+    use_context_provider(|| runtime);
+    rsx! { {children} }
+}
+
+/// The runtime of the nearest [`ProviderScope`], if called inside one
+pub(crate) fn scoped_runtime() -> Option<ProviderRuntime> {
+    // Hooks can run where no component is rendering, where there is no context to read
+    dioxus::core::Runtime::try_current()?.try_current_scope_id()?;
+    try_consume_context::<ProviderRuntime>()
+}
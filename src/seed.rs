@@ -0,0 +1,136 @@
+//! # Seeding Detail Providers from Lists
+//!
+//! In a collection + detail UI, the list usually already holds everything the detail screen
+//! shows first. A list provider marked `#[provider(seed_details = fetch_item)]` writes each
+//! of its items into the cache of the detail provider `fetch_item`, keyed by the item's
+//! [`Entity::entity_id`]. Opening a detail screen then renders instantly from the list data,
+//! and because seeded entries are marked stale, the detail provider revalidates in the
+//! background on first use.
+//!
+//! Items that are already cached by the detail provider are left alone, so seeding never
+//! replaces fresher detail data with a list summary.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use dioxus_provider::{entity::Entity, prelude::*};
+//!
+//! #[derive(Clone, PartialEq)]
+//! struct Item { id: u32, name: String }
+//!
+//! impl Entity for Item {
+//!     type Id = u32;
+//!     fn entity_id(&self) -> u32 { self.id }
+//! }
+//!
+//! #[provider(seed_details = fetch_item)]
+//! async fn fetch_items() -> Result<Vec<Item>, String> {
+//!     api::items().await
+//! }
+//!
+//! #[provider]
+//! async fn fetch_item(id: u32) -> Result<Item, String> {
+//!     api::item(id).await
+//! }
+//! ```
+
+use crate::{
+    cache::ProviderCache, entity::Entity, hooks::Provider, runtime::cache_mgmt::key_origin,
+};
+
+/// Write each item of a list into the cache of a detail provider
+///
+/// Each item is stored under the detail provider's key for the item's entity id, converted
+/// into the detail output with `Into` (which is the identity when the types match). Returns
+/// the number of entries seeded.
+pub fn seed_details<'a, P, L, Item>(detail: &P, list: &'a L, cache: &ProviderCache) -> usize
+where
+    &'a L: IntoIterator<Item = &'a Item>,
+    Item: Entity + Into<P::Output>,
+    P: Provider<Item::Id>,
+{
+    let mut seeded = 0;
+    for item in list {
+        let param = item.entity_id();
+        let cache_key = detail.id(&param);
+        if cache
+            .get::<Result<P::Output, P::Error>>(&cache_key)
+            .is_some()
+        {
+            continue;
+        }
+
+        let data: P::Output = item.clone().into();
+        cache.set(cache_key.clone(), Ok::<_, P::Error>(data));
+        if let Some(origin) = key_origin(detail, &param) {
+            cache.set_key_origin(&cache_key, origin);
+        }
+        // Seeds come from a list, so refresh them from the detail provider on first use
+        cache.mark_stale(&cache_key);
+        seeded += 1;
+    }
+
+    if seeded > 0 {
+        crate::debug_log!("🌱 [SEED] Seeded {} detail entries", seeded);
+    }
+    seeded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, PartialEq, Debug)]
+    struct Item {
+        id: u32,
+        name: String,
+    }
+
+    impl Entity for Item {
+        type Id = u32;
+
+        fn entity_id(&self) -> u32 {
+            self.id
+        }
+    }
+
+    #[derive(Clone, PartialEq)]
+    struct ItemProvider;
+
+    impl Provider<u32> for ItemProvider {
+        type Output = Item;
+        type Error = String;
+
+        async fn run(&self, id: u32) -> Result<Self::Output, Self::Error> {
+            Ok(Item {
+                id,
+                name: format!("item {id}"),
+            })
+        }
+    }
+
+    #[test]
+    fn seeds_missing_details_as_stale() {
+        let cache = ProviderCache::new();
+        let item = |id: u32, name: &str| Item {
+            id,
+            name: name.to_string(),
+        };
+        let fresh_key = ItemProvider.id(&2);
+        cache.set(fresh_key.clone(), Ok::<_, String>(item(2, "fresh")));
+
+        let list = vec![item(1, "one"), item(2, "summary")];
+        assert_eq!(seed_details(&ItemProvider, &list, &cache), 1);
+
+        let seeded_key = ItemProvider.id(&1);
+        assert_eq!(
+            cache.get::<Result<Item, String>>(&seeded_key),
+            Some(Ok(item(1, "one")))
+        );
+        assert!(cache.is_marked_stale(&seeded_key));
+        assert_eq!(
+            cache.get::<Result<Item, String>>(&fresh_key),
+            Some(Ok(item(2, "fresh")))
+        );
+    }
+}
@@ -0,0 +1,134 @@
+//! # Server Entity Invalidation
+//!
+//! Servers that push change notifications usually name what changed in their own terms:
+//! "user 42 was updated", not a cache key. Providers declare the server entities they serve
+//! with `#[provider(serves = ["user", "org"])]`, and
+//! [`ProviderRuntime::invalidate_entity`](crate::runtime::ProviderRuntime::invalidate_entity)
+//! refetches every cached entry of those providers that matches, so app code doesn't map
+//! entity names to providers and keys by hand.
+//!
+//! An entry matches an id when its provider's first parameter equals it, compared by `Debug`
+//! representation. Entries of providers without parameters, such as lists, match every id
+//! of the entities they serve.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use dioxus_provider::{global::get_global_runtime, prelude::*};
+//!
+//! #[provider(serves = ["user"])]
+//! async fn fetch_user(id: u32) -> Result<User, String> {
+//!     api::user(id).await
+//! }
+//!
+//! #[provider(serves = ["user", "org"])]
+//! async fn fetch_members() -> Result<Vec<User>, String> {
+//!     api::members().await
+//! }
+//!
+//! // On a server push for user 42: refetches fetch_user(42) and fetch_members()
+//! let runtime = get_global_runtime().unwrap();
+//! runtime.invalidate_entity("user", Some(&42));
+//! ```
+
+use std::{
+    any::TypeId,
+    collections::HashMap,
+    fmt::Debug,
+    sync::{Arc, Mutex},
+};
+
+use crate::{hooks::Provider, types::ProviderParamBounds};
+
+/// The server entity id of a provider's entry: its first parameter, or None for providers
+/// without parameters, whose entries serve every id
+pub fn entity_id<P, Param>(provider: &P, param: &Param) -> Option<String>
+where
+    P: Provider<Param>,
+    Param: ProviderParamBounds,
+{
+    if TypeId::of::<Param>() == TypeId::of::<()>() {
+        return None;
+    }
+    let first_part = provider
+        .query_key(param)
+        .and_then(|key| key.part(1).map(str::to_string));
+    Some(first_part.unwrap_or_else(|| format!("{param:?}")))
+}
+
+/// Entity ids by cache key, where None serves every id
+type ServedKeys = HashMap<String, Option<String>>;
+
+/// Cache keys of the providers serving each server entity, with the entity id of each key
+#[derive(Clone, Default)]
+pub struct ServedEntities {
+    entities: Arc<Mutex<HashMap<&'static str, ServedKeys>>>,
+}
+
+impl ServedEntities {
+    /// Record that `cache_key` holds data of the given entities, for one id or all of them
+    pub fn register(&self, entities: &[&'static str], cache_key: &str, id: Option<String>) {
+        let Ok(mut served) = self.entities.lock() else {
+            return;
+        };
+        for entity in entities {
+            served
+                .entry(entity)
+                .or_default()
+                .insert(cache_key.to_string(), id.clone());
+        }
+    }
+
+    /// The cache keys serving `entity`, limited to those matching `id` if given
+    ///
+    /// `id` is compared with the `Debug` representation the keys were registered with.
+    pub fn keys_for(&self, entity: &str, id: Option<&dyn Debug>) -> Vec<String> {
+        let id = id.map(|id| format!("{id:?}"));
+        let Ok(served) = self.entities.lock() else {
+            return Vec::new();
+        };
+        let Some(keys) = served.get(entity) else {
+            return Vec::new();
+        };
+        keys.iter()
+            .filter(|(_, key_id)| match (&id, key_id) {
+                (Some(id), Some(key_id)) => id == key_id,
+                _ => true,
+            })
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+
+    /// Stop tracking keys for which `keep` returns false, e.g. keys no longer cached
+    pub fn retain(&self, mut keep: impl FnMut(&str) -> bool) {
+        if let Ok(mut served) = self.entities.lock() {
+            for keys in served.values_mut() {
+                keys.retain(|key, _| keep(key));
+            }
+            served.retain(|_, keys| !keys.is_empty());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_keys_by_entity_and_id() {
+        let served = ServedEntities::default();
+        served.register(&["user"], "fetch_user/42", Some("42".to_string()));
+        served.register(&["user"], "fetch_user/7", Some("7".to_string()));
+        served.register(&["user", "org"], "fetch_members", None);
+
+        let mut keys = served.keys_for("user", Some(&42));
+        keys.sort();
+        assert_eq!(keys, ["fetch_members", "fetch_user/42"]);
+        assert_eq!(served.keys_for("user", None).len(), 3);
+        assert_eq!(served.keys_for("org", Some(&"acme")), ["fetch_members"]);
+        assert!(served.keys_for("invoice", None).is_empty());
+
+        served.retain(|key| key != "fetch_members");
+        assert!(served.keys_for("org", None).is_empty());
+    }
+}
@@ -0,0 +1,171 @@
+//! # Live Runtime Settings
+//!
+//! The defaults a runtime applies to every provider, such as the cache size limit, can be
+//! changed while the app runs with [`ProviderRuntime::reconfigure`](crate::runtime::ProviderRuntime::reconfigure).
+//! Maintenance passes and stale checks read the settings each time they run, so ops toggles
+//! take effect without restarting the app.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use dioxus_provider::global::get_global_runtime;
+//! use std::time::Duration;
+//!
+//! let runtime = get_global_runtime().unwrap();
+//! runtime.reconfigure(
+//!     runtime
+//!         .settings()
+//!         .with_max_cache_size(200)
+//!         .with_default_stale_time(Duration::from_secs(30)),
+//! );
+//! ```
+
+use std::{
+    sync::{
+        Arc, RwLock,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+
+use crate::{
+    chaos::ChaosSettings,
+    platform::{DEFAULT_MAX_CACHE_SIZE, DEFAULT_UNUSED_THRESHOLD},
+};
+
+/// Whether the library's debug logs are emitted, shared by every runtime in the program
+static LOGGING_ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Returns true if the library's debug logs are emitted
+pub fn logging_enabled() -> bool {
+    LOGGING_ENABLED.load(Ordering::Relaxed)
+}
+
+/// Defaults a runtime applies to all providers
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuntimeSettings {
+    /// Maximum number of cache entries kept by maintenance
+    pub max_cache_size: usize,
+    /// Maximum total size in bytes of the cache entries kept by maintenance (None means
+    /// no limit)
+    pub max_cache_bytes: Option<usize>,
+    /// How long an entry may go unused before maintenance removes it
+    pub unused_threshold: Duration,
+    /// Stale time for providers that don't set their own (None means no SWR)
+    pub default_stale_time: Option<Duration>,
+    /// Whether the library emits debug logs (with the `tracing` feature)
+    pub logging: bool,
+    /// Simulated network trouble for provider fetches, in debug builds only
+    pub chaos: Option<ChaosSettings>,
+}
+
+impl Default for RuntimeSettings {
+    fn default() -> Self {
+        Self {
+            max_cache_size: DEFAULT_MAX_CACHE_SIZE,
+            max_cache_bytes: None,
+            unused_threshold: DEFAULT_UNUSED_THRESHOLD,
+            default_stale_time: None,
+            logging: true,
+            chaos: None,
+        }
+    }
+}
+
+impl RuntimeSettings {
+    /// Create settings with the library defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum number of cache entries kept by maintenance
+    pub fn with_max_cache_size(mut self, max_cache_size: usize) -> Self {
+        self.max_cache_size = max_cache_size;
+        self
+    }
+
+    /// Set the maximum total size in bytes of the cache entries kept by maintenance
+    ///
+    /// Entries weigh [`DEFAULT_ENTRY_WEIGHT`](crate::cache::DEFAULT_ENTRY_WEIGHT) unless
+    /// their provider weighs its output, like [`BytesProvider`](crate::bytes::BytesProvider).
+    pub fn with_max_cache_bytes(mut self, max_cache_bytes: usize) -> Self {
+        self.max_cache_bytes = Some(max_cache_bytes);
+        self
+    }
+
+    /// Set how long an entry may go unused before maintenance removes it
+    pub fn with_unused_threshold(mut self, unused_threshold: Duration) -> Self {
+        self.unused_threshold = unused_threshold;
+        self
+    }
+
+    /// Set the stale time for providers that don't set their own
+    pub fn with_default_stale_time(mut self, stale_time: Duration) -> Self {
+        self.default_stale_time = Some(stale_time);
+        self
+    }
+
+    /// Turn the library's debug logs on or off
+    pub fn with_logging(mut self, logging: bool) -> Self {
+        self.logging = logging;
+        self
+    }
+
+    /// Inject delays, dropped responses and duplicated refreshes into provider fetches
+    pub fn with_chaos(mut self, chaos: ChaosSettings) -> Self {
+        self.chaos = Some(chaos);
+        self
+    }
+
+    /// Stop injecting chaos into provider fetches
+    pub fn without_chaos(mut self) -> Self {
+        self.chaos = None;
+        self
+    }
+}
+
+/// Settings shared between a runtime and its cache, read whenever they are needed
+#[derive(Clone, Default)]
+pub struct SharedSettings {
+    settings: Arc<RwLock<RuntimeSettings>>,
+}
+
+impl SharedSettings {
+    /// A snapshot of the current settings
+    pub fn get(&self) -> RuntimeSettings {
+        self.settings
+            .read()
+            .map(|settings| settings.clone())
+            .unwrap_or_default()
+    }
+
+    /// Replace the settings
+    ///
+    /// The logging toggle applies to the whole program.
+    pub fn set(&self, settings: RuntimeSettings) {
+        LOGGING_ENABLED.store(settings.logging, Ordering::Relaxed);
+        if let Ok(mut current) = self.settings.write() {
+            *current = settings;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cache::ProviderCache;
+
+    #[test]
+    fn maintenance_uses_reconfigured_cache_size() {
+        let cache = ProviderCache::new();
+        for id in 0..5 {
+            cache.set(id.to_string(), id);
+        }
+        assert_eq!(cache.maintain().lru_evicted, 0);
+
+        cache
+            .settings()
+            .set(cache.settings().get().with_max_cache_size(2));
+        assert_eq!(cache.maintain().final_size, 2);
+        assert_eq!(cache.size(), 2);
+    }
+}
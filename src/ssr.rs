@@ -0,0 +1,150 @@
+//! # Server-Side Rendering
+//!
+//! Providers resolved while rendering on the server shouldn't be fetched again by the
+//! client that hydrates the HTML. [`ProviderCache::serialize`] captures the successful
+//! entries of persisted providers into a [`CacheSnapshot`], and
+//! [`ProviderCache::hydrate`] hands such a snapshot to the client's cache: each entry is
+//! decoded on its key's first use, synchronously, so the first render on the client matches
+//! the server's HTML and nothing is refetched until the entry goes stale.
+//!
+//! Only providers marked `#[provider(persist)]` take part, since they know how to
+//! serialize their output; others fetch on the client as usual.
+//!
+//! With the `fullstack` feature, every consumer carries its entry through the Dioxus
+//! fullstack hydration data by itself: consumers that resolved on the server, such as ones
+//! using `use_provider_suspense`, embed their entry in the HTML, and the matching consumer
+//! on the client reads it back while hydrating. No code is needed beyond enabling the
+//! feature.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use dioxus_provider::{demo::CacheSnapshot, global, prelude::*};
+//!
+//! // Server, after rendering
+//! let snapshot = global::get_global_runtime()?.cache().serialize();
+//! let script = format!("<script id=\"provider-cache\">{}</script>", snapshot.to_json());
+//!
+//! // Client, before launching
+//! let snapshot = CacheSnapshot::from_json(&read_embedded_script("provider-cache"))?;
+//! global::get_global_runtime()?.cache().hydrate(&snapshot);
+//! ```
+//!
+//! [`CacheSnapshot`]: crate::demo::CacheSnapshot
+
+use std::sync::Arc;
+
+use crate::{
+    cache::{EntryEncoder, ProviderCache},
+    hooks::Provider,
+    types::ProviderParamBounds,
+};
+
+/// Serializes the provider's successful entries, if the provider persists its output
+fn encoder<P, Param>(provider: &P) -> Option<EntryEncoder>
+where
+    P: Provider<Param>,
+    Param: ProviderParamBounds,
+{
+    let persistence = provider.persistence()?;
+    Some(Arc::new(move |data| {
+        let result = data.downcast_ref::<Result<P::Output, P::Error>>()?;
+        (persistence.encode)(result.as_ref().ok()?)
+    }))
+}
+
+/// Let [`ProviderCache::serialize`] include the entry the provider stored under `key`
+pub(crate) fn track_entry<P, Param>(provider: &P, cache: &ProviderCache, key: &str)
+where
+    P: Provider<Param>,
+    Param: ProviderParamBounds,
+{
+    if let Some(encoder) = encoder(provider) {
+        cache.set_encoder(key, encoder);
+    }
+}
+
+/// Decode the entry the server rendered for `key` into the cache, returning whether one was
+/// written
+pub(crate) fn hydrate_entry<P, Param>(provider: &P, cache: &ProviderCache, key: &str) -> bool
+where
+    P: Provider<Param>,
+    Param: ProviderParamBounds,
+{
+    let Some(persistence) = provider.persistence() else {
+        return false;
+    };
+    let Some(value) = cache.take_server_entry(key) else {
+        return false;
+    };
+    if cache.get::<Result<P::Output, P::Error>>(key).is_some() {
+        return false;
+    }
+    let Some(data) = (persistence.decode)(&value) else {
+        crate::debug_log!("🌐 [SSR] Discarding unreadable server entry: {}", key);
+        return false;
+    };
+    crate::debug_log!("🌐 [SSR] Hydrated server entry: {}", key);
+    cache.set(key.to_string(), Ok::<P::Output, P::Error>(data));
+    track_entry(provider, cache, key);
+    true
+}
+
+/// A consumer's slot in the Dioxus fullstack hydration data
+///
+/// Slots are created in hook order, which is the same on the server and the client. The
+/// server fills a slot once its consumer has data; the client reads it on first use.
+#[cfg(feature = "fullstack")]
+#[derive(Clone)]
+pub(crate) struct ServerEntry {
+    slot: dioxus_fullstack_core::SerializeContextEntry<String>,
+    settled: std::rc::Rc<std::cell::Cell<bool>>,
+}
+
+#[cfg(feature = "fullstack")]
+impl ServerEntry {
+    /// Claim the next slot of the current hydration context
+    pub(crate) fn new() -> Self {
+        Self {
+            slot: dioxus_fullstack_core::serialize_context().create_entry(),
+            settled: Default::default(),
+        }
+    }
+
+    /// Hydrate the entry the server embedded for this consumer, the first time it's called
+    pub(crate) fn hydrate<P, Param>(&self, provider: &P, cache: &ProviderCache, key: &str)
+    where
+        P: Provider<Param>,
+        Param: ProviderParamBounds,
+    {
+        if self.settled.get() || provider.persistence().is_none() {
+            return;
+        }
+        if let Ok(value) = self.slot.get() {
+            self.settled.set(true);
+            let mut snapshot = crate::demo::CacheSnapshot::new();
+            snapshot.insert(key, value);
+            cache.hydrate(&snapshot);
+            hydrate_entry(provider, cache, key);
+        }
+    }
+
+    /// Embed the consumer's entry in the hydration data, the first time it has one
+    pub(crate) fn capture<P, Param>(&self, provider: &P, data: &P::Output)
+    where
+        P: Provider<Param>,
+        Param: ProviderParamBounds,
+    {
+        if self.settled.replace(true) {
+            return;
+        }
+        if let Some(value) = provider
+            .persistence()
+            .and_then(|persistence| (persistence.encode)(data))
+        {
+            self.slot
+                .clone()
+                .insert(&value, std::panic::Location::caller());
+        }
+    }
+}
@@ -0,0 +1,331 @@
+//! # Startup Prefetching
+//!
+//! The first screens of an app usually need the same data on every launch. A
+//! [`PrefetchPlan`] lists it, in order and with priorities, together with a budget: at most
+//! [`max_concurrency`](PrefetchPlan::with_max_concurrency) fetches run at once, and no
+//! fetch is started once the [time budget](PrefetchPlan::with_time_budget) is spent. A plan
+//! given to `ProviderConfig::with_prefetch_plan` starts as soon as the app renders after
+//! `init()`, and [`use_prefetch_progress`] follows it, e.g. to show a splash screen until
+//! the plan is [`finished`](PrefetchProgress::finished).
+//!
+//! Plan fetches are regular prefetches: components mounting meanwhile join them, and data
+//! that is already cached or being fetched is not fetched again.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use dioxus_provider::{prelude::*, startup::{PrefetchPlan, PrefetchPriority, use_prefetch_progress}};
+//!
+//! ProviderConfig::new()
+//!     .with_prefetch_plan(
+//!         PrefetchPlan::new()
+//!             .provider_with_priority(PrefetchPriority::High, fetch_session(), ())
+//!             .provider(fetch_inbox(), ())
+//!             .provider_with_priority(PrefetchPriority::Low, fetch_contacts(), ())
+//!             .with_max_concurrency(2)
+//!             .with_time_budget(Duration::from_secs(2)),
+//!     )
+//!     .init()?;
+//!
+//! #[component]
+//! fn App() -> Element {
+//!     let progress = use_prefetch_progress();
+//!     if !progress().finished {
+//!         return rsx! { Splash { fraction: progress().fraction() } };
+//!     }
+//!     rsx! { Router::<Route> {} }
+//! }
+//! ```
+
+use dioxus::prelude::*;
+use futures::{
+    FutureExt, StreamExt,
+    channel::oneshot,
+    future::{Either, LocalBoxFuture, select},
+    stream,
+};
+use std::{
+    cell::Cell,
+    fmt,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::sync::watch;
+
+use crate::{
+    global::hook_runtime,
+    hooks::Provider,
+    param_utils::IntoProviderParam,
+    runtime::{ProviderRuntime, request::begin_prefetch},
+};
+
+/// Fetches run at once by a plan unless configured otherwise
+pub const DEFAULT_PLAN_CONCURRENCY: usize = 4;
+
+/// How early a plan's provider is fetched, relative to the others
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PrefetchPriority {
+    /// Fetched before everything else
+    High,
+    /// Fetched in the order providers were added
+    #[default]
+    Normal,
+    /// Fetched after everything else, first to be skipped when the budget runs out
+    Low,
+}
+
+type PlanFetch = Arc<dyn Fn(&ProviderRuntime) -> Option<LocalBoxFuture<'static, ()>> + Send + Sync>;
+
+/// A step of a plan
+#[derive(Clone)]
+struct PlanStep {
+    key: String,
+    priority: PrefetchPriority,
+    fetch: PlanFetch,
+}
+
+/// Providers to prefetch at startup, in order, within a budget
+#[derive(Clone)]
+pub struct PrefetchPlan {
+    steps: Vec<PlanStep>,
+    max_concurrency: usize,
+    time_budget: Option<Duration>,
+}
+
+impl PrefetchPlan {
+    /// Create a plan without any providers
+    pub fn new() -> Self {
+        Self {
+            steps: Vec::new(),
+            max_concurrency: DEFAULT_PLAN_CONCURRENCY,
+            time_budget: None,
+        }
+    }
+
+    /// Add a provider to prefetch with [`PrefetchPriority::Normal`]
+    pub fn provider<P, Args>(self, provider: P, args: Args) -> Self
+    where
+        P: Provider<Args::Param> + Send + Sync + Clone,
+        Args: IntoProviderParam,
+    {
+        self.provider_with_priority(PrefetchPriority::Normal, provider, args)
+    }
+
+    /// Add a provider to prefetch with the given priority
+    ///
+    /// Providers of the same priority are fetched in the order they were added.
+    pub fn provider_with_priority<P, Args>(
+        mut self,
+        priority: PrefetchPriority,
+        provider: P,
+        args: Args,
+    ) -> Self
+    where
+        P: Provider<Args::Param> + Send + Sync + Clone,
+        Args: IntoProviderParam,
+    {
+        let param = args.into_param();
+        self.steps.push(PlanStep {
+            key: provider.id(&param),
+            priority,
+            fetch: Arc::new(move |runtime: &ProviderRuntime| {
+                begin_prefetch(runtime, provider.clone(), param.clone()).map(FutureExt::boxed_local)
+            }),
+        });
+        self
+    }
+
+    /// Run at most `max_concurrency` fetches at once (at least one)
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = max_concurrency.max(1);
+        self
+    }
+
+    /// Stop starting fetches once `budget` has passed since the plan started
+    ///
+    /// Fetches already running finish in the background; the rest are skipped and fetched
+    /// by their components as usual.
+    pub fn with_time_budget(mut self, budget: Duration) -> Self {
+        self.time_budget = Some(budget);
+        self
+    }
+
+    /// Cache keys of the plan's providers, in the order they are fetched
+    pub fn keys(&self) -> Vec<String> {
+        self.ordered_steps()
+            .into_iter()
+            .map(|step| step.key)
+            .collect()
+    }
+
+    /// Number of providers in the plan
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Returns true if the plan has no providers
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    fn ordered_steps(&self) -> Vec<PlanStep> {
+        let mut steps = self.steps.clone();
+        // Stable, so providers of the same priority keep their order
+        steps.sort_by_key(|step| step.priority);
+        steps
+    }
+
+    /// Run the plan on `runtime`, reporting to `progress`
+    async fn run(self, runtime: ProviderRuntime, progress: Arc<watch::Sender<PrefetchProgress>>) {
+        crate::debug_log!(
+            "🚀 [STARTUP] Running prefetch plan of {} providers",
+            self.steps.len()
+        );
+        let started = Cell::new(0);
+        let fetches = stream::iter(self.ordered_steps())
+            .map(|step| {
+                started.set(started.get() + 1);
+                let fetch = (step.fetch)(&runtime);
+                let progress = progress.clone();
+                // Spawned, so fetches outlive the plan when its budget runs out
+                let (done, finished) = oneshot::channel();
+                spawn(async move {
+                    if let Some(fetch) = fetch {
+                        fetch.await;
+                    }
+                    progress.send_modify(|progress| progress.completed += 1);
+                    let _ = done.send(());
+                });
+                finished
+            })
+            .buffer_unordered(self.max_concurrency)
+            .for_each(|_| async {});
+        let budget = match self.time_budget {
+            Some(budget) => crate::platform::sleep(budget).boxed_local(),
+            None => std::future::pending().boxed_local(),
+        };
+
+        if let Either::Right(_) = select(std::pin::pin!(fetches), budget).await {
+            crate::debug_log!("🚀 [STARTUP] Prefetch plan ran out of time");
+        }
+        progress.send_modify(|progress| {
+            // Fetches still running complete later, the rest never start
+            progress.skipped = progress.total - started.get();
+            progress.finished = true;
+        });
+    }
+}
+
+impl Default for PrefetchPlan {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for PrefetchPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PrefetchPlan")
+            .field("keys", &self.keys())
+            .field("max_concurrency", &self.max_concurrency)
+            .field("time_budget", &self.time_budget)
+            .finish()
+    }
+}
+
+/// How far a runtime's prefetch plan got
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PrefetchProgress {
+    /// Number of providers in the plan
+    pub total: usize,
+    /// Providers fetched, or found cached or already loading
+    pub completed: usize,
+    /// Providers not started because the time budget ran out
+    pub skipped: usize,
+    /// Whether the plan is done, by completing or running out of time
+    pub finished: bool,
+}
+
+impl PrefetchProgress {
+    /// Completed share of the plan, from 0.0 to 1.0, for progress bars
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            return if self.finished { 1.0 } else { 0.0 };
+        }
+        self.completed as f32 / self.total as f32
+    }
+}
+
+/// A runtime's prefetch plan waiting to start, and the progress of the one that started
+#[derive(Clone, Default)]
+pub(crate) struct StartupPrefetch {
+    pending: Arc<Mutex<Option<PrefetchPlan>>>,
+    progress: Arc<watch::Sender<PrefetchProgress>>,
+}
+
+impl StartupPrefetch {
+    /// Hold `plan` until [`start`](Self::start) is first called
+    pub(crate) fn new(plan: Option<PrefetchPlan>) -> Self {
+        Self {
+            pending: Arc::new(Mutex::new(plan)),
+            progress: Arc::new(watch::Sender::new(PrefetchProgress {
+                finished: true,
+                ..PrefetchProgress::default()
+            })),
+        }
+    }
+
+    /// Run the pending plan, if any; must be called while the Dioxus runtime is running
+    pub(crate) fn start(&self, runtime: &ProviderRuntime) {
+        let Some(plan) = self
+            .pending
+            .lock()
+            .ok()
+            .and_then(|mut pending| pending.take())
+        else {
+            return;
+        };
+        self.run(runtime, plan);
+    }
+
+    /// Run `plan` now, reporting its progress instead of an earlier plan's
+    pub(crate) fn run(&self, runtime: &ProviderRuntime, plan: PrefetchPlan) {
+        self.progress.send_replace(PrefetchProgress {
+            total: plan.len(),
+            ..PrefetchProgress::default()
+        });
+        dioxus::core::spawn_forever(plan.run(runtime.clone(), self.progress.clone()));
+    }
+
+    pub(crate) fn progress(&self) -> PrefetchProgress {
+        *self.progress.borrow()
+    }
+
+    pub(crate) fn subscribe(&self) -> watch::Receiver<PrefetchProgress> {
+        self.progress.subscribe()
+    }
+}
+
+/// Hook following the progress of the runtime's startup prefetch plan
+///
+/// Starts the plan if nothing else did yet. Without a plan, the progress is finished from
+/// the start.
+pub fn use_prefetch_progress() -> ReadSignal<PrefetchProgress> {
+    let runtime = hook_runtime();
+    runtime.start_prefetch_plan();
+    let mut progress = use_signal(|| runtime.prefetch_progress());
+    use_hook(|| {
+        let mut changes = runtime.subscribe_prefetch_progress();
+        spawn(async move {
+            loop {
+                let current = *changes.borrow_and_update();
+                if *progress.peek() != current {
+                    progress.set(current);
+                }
+                if changes.changed().await.is_err() {
+                    break;
+                }
+            }
+        });
+    });
+    use_hook(|| ReadSignal::new(progress))
+}
@@ -36,6 +36,9 @@ pub trait AsyncState {
 pub enum State<T, E> {
     /// The operation is currently loading
     Loading { task: Task },
+    /// The operation is being refetched after an invalidation, with the previous data still
+    /// available to show meanwhile
+    Reloading { previous: T, task: Task },
     /// The operation completed successfully with data
     Success(T),
     /// The operation failed with an error
@@ -60,7 +63,7 @@ impl<T, E> AsyncState for State<T, E> {
 
     fn data(&self) -> Option<&T> {
         match self {
-            State::Success(data) => Some(data),
+            State::Success(data) | State::Reloading { previous: data, .. } => Some(data),
             _ => None,
         }
     }
@@ -89,7 +92,12 @@ impl<T, E> State<T, E> {
         <Self as AsyncState>::is_error(self)
     }
 
-    /// Returns the data if successful, None otherwise
+    /// Returns true if the state is being refetched while keeping its previous data
+    pub fn is_reloading(&self) -> bool {
+        matches!(self, State::Reloading { .. })
+    }
+
+    /// Returns the data if successful or the previous data while reloading, None otherwise
     pub fn data(&self) -> Option<&T> {
         <Self as AsyncState>::data(self)
     }
@@ -99,6 +107,21 @@ impl<T, E> State<T, E> {
         <Self as AsyncState>::error(self)
     }
 
+    /// Returns the value in the shape `use_resource` exposes: None while loading, otherwise the result
+    pub fn resource_value(&self) -> Option<Result<T, E>>
+    where
+        T: Clone,
+        E: Clone,
+    {
+        match self {
+            State::Success(data) | State::Reloading { previous: data, .. } => {
+                Some(Ok(data.clone()))
+            }
+            State::Error(error) => Some(Err(error.clone())),
+            State::Loading { .. } => None,
+        }
+    }
+
     /// Maps a State<T, E> to State<U, E> by applying a function to the contained data if successful.
     pub fn map<U, F>(self, op: F) -> State<U, E>
     where
@@ -108,6 +131,10 @@ impl<T, E> State<T, E> {
             State::Success(data) => State::Success(op(data)),
             State::Error(e) => State::Error(e),
             State::Loading { task } => State::Loading { task },
+            State::Reloading { previous, task } => State::Reloading {
+                previous: op(previous),
+                task,
+            },
         }
     }
 
@@ -120,10 +147,14 @@ impl<T, E> State<T, E> {
             State::Success(data) => State::Success(data),
             State::Error(e) => State::Error(op(e)),
             State::Loading { task } => State::Loading { task },
+            State::Reloading { previous, task } => State::Reloading { previous, task },
         }
     }
 
     /// Chains a State<T, E> to State<U, E> by applying a function to the contained data if successful.
+    ///
+    /// While reloading, the function is applied to the previous data and a successful result
+    /// stays reloading.
     pub fn and_then<U, F>(self, op: F) -> State<U, E>
     where
         F: FnOnce(T) -> State<U, E>,
@@ -132,6 +163,10 @@ impl<T, E> State<T, E> {
             State::Success(data) => op(data),
             State::Error(e) => State::Error(e),
             State::Loading { task } => State::Loading { task },
+            State::Reloading { previous, task } => match op(previous) {
+                State::Success(previous) => State::Reloading { previous, task },
+                other => other,
+            },
         }
     }
 }
@@ -0,0 +1,278 @@
+//! # Built-in Cache Storage Adapters
+//!
+//! Implementations of [`CacheStorage`] for the platforms dioxus-provider runs on:
+//!
+//! - [`FileStorage`] (native): one file per entry in a directory
+//! - [`LocalStorage`] (WASM): the origin's `localStorage`, small but synchronous
+//! - [`IndexedDbStorage`] (WASM): an IndexedDB object store, for larger caches
+//!
+//! Pass one to `ProviderConfig::with_storage` to persist providers marked
+//! `#[provider(persist)]`; see [`persist`](crate::persist) for how entries are restored.
+
+use crate::cache::{CacheStorage, StorageFuture};
+#[cfg(not(target_family = "wasm"))]
+use std::path::PathBuf;
+
+/// Entries stored as files in a directory, one per cache key (native)
+///
+/// The directory is created on the first write. File names are the hex-encoded cache
+/// keys, so any key is a valid file name.
+#[cfg(not(target_family = "wasm"))]
+#[derive(Debug, Clone)]
+pub struct FileStorage {
+    dir: PathBuf,
+}
+
+#[cfg(not(target_family = "wasm"))]
+impl FileStorage {
+    /// Store entries in `dir`
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        let name: String = key.bytes().map(|byte| format!("{byte:02x}")).collect();
+        self.dir.join(format!("{name}.json"))
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+impl CacheStorage for FileStorage {
+    fn read(&self, key: &str) -> StorageFuture<Option<String>> {
+        let result = match std::fs::read_to_string(self.path(key)) {
+            Ok(value) => Ok(Some(value)),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error.into()),
+        };
+        Box::pin(std::future::ready(result))
+    }
+
+    fn write(&self, key: &str, value: String) -> StorageFuture<()> {
+        let result = std::fs::create_dir_all(&self.dir)
+            .and_then(|()| std::fs::write(self.path(key), value))
+            .map_err(Into::into);
+        Box::pin(std::future::ready(result))
+    }
+
+    fn remove(&self, key: &str) -> StorageFuture<()> {
+        let result = match std::fs::remove_file(self.path(key)) {
+            Err(error) if error.kind() != std::io::ErrorKind::NotFound => Err(error.into()),
+            _ => Ok(()),
+        };
+        Box::pin(std::future::ready(result))
+    }
+}
+
+#[cfg(target_family = "wasm")]
+pub use web::{IndexedDbStorage, LocalStorage};
+
+#[cfg(target_family = "wasm")]
+mod web {
+    use wasm_bindgen::{JsCast, JsValue, closure::Closure};
+    use wasm_bindgen_futures::JsFuture;
+    use web_sys::{IdbDatabase, IdbRequest, IdbTransactionMode};
+
+    use crate::{
+        cache::{CacheStorage, StorageFuture},
+        errors::ProviderError,
+    };
+
+    /// Name of the object store holding the entries in an [`IndexedDbStorage`] database
+    const STORE: &str = "entries";
+
+    fn storage_error(context: &str, error: JsValue) -> ProviderError {
+        ProviderError::Cache(format!("{context}: {error:?}"))
+    }
+
+    /// Entries stored in the origin's `localStorage` under a key prefix (WASM)
+    ///
+    /// `localStorage` is limited to a few megabytes per origin; use [`IndexedDbStorage`]
+    /// for larger caches.
+    #[derive(Debug, Clone)]
+    pub struct LocalStorage {
+        prefix: String,
+    }
+
+    impl LocalStorage {
+        /// Store entries under keys starting with `prefix`
+        pub fn new(prefix: impl Into<String>) -> Self {
+            Self {
+                prefix: prefix.into(),
+            }
+        }
+
+        fn with_storage<T>(
+            &self,
+            operation: impl FnOnce(&web_sys::Storage) -> Result<T, JsValue>,
+        ) -> Result<T, ProviderError> {
+            let storage = web_sys::window()
+                .ok_or_else(|| ProviderError::Cache("No window".to_string()))?
+                .local_storage()
+                .map_err(|error| storage_error("localStorage unavailable", error))?
+                .ok_or_else(|| ProviderError::Cache("localStorage unavailable".to_string()))?;
+            operation(&storage).map_err(|error| storage_error("localStorage", error))
+        }
+    }
+
+    impl CacheStorage for LocalStorage {
+        fn read(&self, key: &str) -> StorageFuture<Option<String>> {
+            let key = format!("{}{key}", self.prefix);
+            Box::pin(std::future::ready(
+                self.with_storage(|storage| storage.get_item(&key)),
+            ))
+        }
+
+        fn write(&self, key: &str, value: String) -> StorageFuture<()> {
+            let key = format!("{}{key}", self.prefix);
+            Box::pin(std::future::ready(
+                self.with_storage(|storage| storage.set_item(&key, &value)),
+            ))
+        }
+
+        fn remove(&self, key: &str) -> StorageFuture<()> {
+            let key = format!("{}{key}", self.prefix);
+            Box::pin(std::future::ready(
+                self.with_storage(|storage| storage.remove_item(&key)),
+            ))
+        }
+    }
+
+    /// Entries stored in an IndexedDB database (WASM)
+    ///
+    /// The database is opened for each operation and holds a single `entries` object store,
+    /// created on first use.
+    #[derive(Debug, Clone)]
+    pub struct IndexedDbStorage {
+        name: String,
+    }
+
+    impl IndexedDbStorage {
+        /// Store entries in the database named `name`
+        pub fn new(name: impl Into<String>) -> Self {
+            Self { name: name.into() }
+        }
+
+        async fn open(name: String) -> Result<IdbDatabase, JsValue> {
+            let factory = web_sys::window()
+                .ok_or_else(|| JsValue::from_str("No window"))?
+                .indexed_db()?
+                .ok_or_else(|| JsValue::from_str("IndexedDB unavailable"))?;
+            let request = factory.open_with_u32(&name, 1)?;
+            let upgrade_request = request.clone();
+            let on_upgrade = Closure::once_into_js(move || {
+                if let Ok(database) = upgrade_request
+                    .result()
+                    .and_then(|database| database.dyn_into::<IdbDatabase>())
+                    && !database.object_store_names().contains(STORE)
+                {
+                    let _ = database.create_object_store(STORE);
+                }
+            });
+            request.set_onupgradeneeded(Some(on_upgrade.unchecked_ref()));
+            request_result(&request).await?.dyn_into::<IdbDatabase>()
+        }
+
+        async fn run(
+            name: String,
+            mode: IdbTransactionMode,
+            operation: impl FnOnce(&web_sys::IdbObjectStore) -> Result<IdbRequest, JsValue>,
+        ) -> Result<JsValue, ProviderError> {
+            let result = async {
+                let database = Self::open(name).await?;
+                let store = database
+                    .transaction_with_str_and_mode(STORE, mode)?
+                    .object_store(STORE)?;
+                let result = request_result(&operation(&store)?).await;
+                database.close();
+                result
+            };
+            result
+                .await
+                .map_err(|error| storage_error("IndexedDB", error))
+        }
+    }
+
+    impl CacheStorage for IndexedDbStorage {
+        fn read(&self, key: &str) -> StorageFuture<Option<String>> {
+            let key = JsValue::from_str(key);
+            let read = Self::run(
+                self.name.clone(),
+                IdbTransactionMode::Readonly,
+                move |store| store.get(&key),
+            );
+            Box::pin(async move { read.await.map(|value| value.as_string()) })
+        }
+
+        fn write(&self, key: &str, value: String) -> StorageFuture<()> {
+            let key = JsValue::from_str(key);
+            let value = JsValue::from_str(&value);
+            let write = Self::run(
+                self.name.clone(),
+                IdbTransactionMode::Readwrite,
+                move |store| store.put_with_key(&value, &key),
+            );
+            Box::pin(async move { write.await.map(|_| ()) })
+        }
+
+        fn remove(&self, key: &str) -> StorageFuture<()> {
+            let key = JsValue::from_str(key);
+            let remove = Self::run(
+                self.name.clone(),
+                IdbTransactionMode::Readwrite,
+                move |store| store.delete(&key),
+            );
+            Box::pin(async move { remove.await.map(|_| ()) })
+        }
+    }
+
+    /// Resolve an IndexedDB request's `onsuccess` and `onerror` callbacks into a future
+    async fn request_result(request: &IdbRequest) -> Result<JsValue, JsValue> {
+        let promise = js_sys::Promise::new(&mut |resolve, reject| {
+            let success_request = request.clone();
+            let on_success = Closure::once_into_js(move || {
+                let result = success_request.result().unwrap_or(JsValue::UNDEFINED);
+                let _ = resolve.call1(&JsValue::NULL, &result);
+            });
+            let error_request = request.clone();
+            let on_error = Closure::once_into_js(move || {
+                let error = error_request
+                    .error()
+                    .ok()
+                    .flatten()
+                    .map_or(JsValue::NULL, JsValue::from);
+                let _ = reject.call1(&JsValue::NULL, &error);
+            });
+            request.set_onsuccess(Some(on_success.unchecked_ref()));
+            request.set_onerror(Some(on_error.unchecked_ref()));
+        });
+        JsFuture::from(promise).await
+    }
+}
+
+#[cfg(all(test, not(target_family = "wasm")))]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+
+    #[test]
+    fn file_storage_round_trips_entries() {
+        let dir = std::env::temp_dir().join(format!("provider-storage-{}", std::process::id()));
+        let storage = FileStorage::new(&dir);
+
+        block_on(async {
+            assert_eq!(storage.read("fetch_user/1").await.unwrap(), None);
+            storage
+                .write("fetch_user/1", "\"Ada\"".to_string())
+                .await
+                .unwrap();
+            assert_eq!(
+                storage.read("fetch_user/1").await.unwrap().as_deref(),
+                Some("\"Ada\"")
+            );
+            storage.remove("fetch_user/1").await.unwrap();
+            storage.remove("fetch_user/1").await.unwrap();
+            assert_eq!(storage.read("fetch_user/1").await.unwrap(), None);
+        });
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,144 @@
+//! # Streaming Providers
+//!
+//! A [`Provider`](crate::hooks::Provider) runs once per fetch. Live data, such as a
+//! WebSocket feed, server-sent events or a database live query, keeps producing values
+//! instead. A [`StreamProvider`] returns a stream, and [`use_stream_provider`] updates its
+//! state on every item the stream yields: `Success` for `Ok` items and `Error` for `Err`
+//! items, until the stream ends or the component unmounts.
+//!
+//! The latest item is also written to the cache under the stream provider's key, so
+//! components observing that key with `use_provider_by_key` see it too.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use dioxus::prelude::*;
+//! use dioxus_provider::{prelude::*, stream::Stream};
+//!
+//! #[stream_provider]
+//! async fn live_prices(symbol: String) -> impl Stream<Item = Result<f64, String>> {
+//!     let socket = api::subscribe(&symbol).await;
+//!     socket.prices()
+//! }
+//!
+//! #[component]
+//! fn Price(symbol: String) -> Element {
+//!     let price = use_stream_provider(live_prices(), symbol);
+//!     match &*price.read() {
+//!         State::Success(price) => rsx! { "{price}" },
+//!         State::Error(error) => rsx! { "Disconnected: {error}" },
+//!         _ => rsx! { "Connecting..." },
+//!     }
+//! }
+//! ```
+
+use dioxus::{core::Task, prelude::*};
+use futures::StreamExt;
+use std::{cell::RefCell, future::Future, pin::Pin, rc::Rc};
+
+pub use futures::stream::Stream;
+
+use crate::{
+    global::hook_runtime,
+    key_hash::{TypeTag, key_hasher},
+    param_utils::IntoProviderParam,
+    query_key::QueryKey,
+    state::State,
+    types::{ProviderErrorBounds, ProviderOutputBounds, ProviderParamBounds},
+};
+
+/// The stream returned by [`StreamProvider::stream`]
+pub type ProviderStream<T, E> = Pin<Box<dyn Stream<Item = Result<T, E>>>>;
+
+/// A source of values that keeps yielding, consumed with [`use_stream_provider`]
+///
+/// Implemented by `#[stream_provider]` for functions returning
+/// `impl Stream<Item = Result<T, E>>`.
+pub trait StreamProvider<Param = ()>: Clone + PartialEq + 'static
+where
+    Param: ProviderParamBounds,
+{
+    /// The type of the stream's successful items
+    type Output: ProviderOutputBounds;
+    /// The type of the stream's errors
+    type Error: ProviderErrorBounds;
+
+    /// Open the stream for the given parameters
+    fn stream(&self, param: Param) -> ProviderStream<Self::Output, Self::Error>;
+
+    /// Get a unique identifier for this stream provider with the given parameters
+    ///
+    /// Same as `Provider::id`: the `query_key` if there is one, and otherwise a hash of the
+    /// provider type, parameter type and parameter value.
+    fn id(&self, param: &Param) -> String {
+        if let Some(query_key) = self.query_key(param) {
+            return query_key.to_string();
+        }
+        let hash = key_hasher().hash_param(&[TypeTag::of::<Self>(), TypeTag::of::<Param>()], param);
+        format!("{hash:x}")
+    }
+
+    /// Get a structured, readable cache key for the given parameters (None means hashed)
+    fn query_key(&self, _param: &Param) -> Option<QueryKey> {
+        None
+    }
+}
+
+/// Turn a future resolving to a stream, like an `async fn` returning one, into a stream
+pub fn from_future<F, S, T, E>(future: F) -> ProviderStream<T, E>
+where
+    F: Future<Output = S> + 'static,
+    S: Stream<Item = Result<T, E>> + 'static,
+{
+    Box::pin(futures::stream::once(future).flatten())
+}
+
+/// Hook subscribing to a stream provider, updated on every item it yields
+///
+/// The state is `Loading` until the first item arrives. New parameters close the current
+/// stream and open one for them; unmounting closes it. After the stream ends, the state
+/// keeps its last item.
+pub fn use_stream_provider<P, Args>(
+    provider: P,
+    args: Args,
+) -> ReadSignal<State<P::Output, P::Error>>
+where
+    P: StreamProvider<Args::Param>,
+    Args: IntoProviderParam,
+{
+    let runtime = hook_runtime();
+    let mut state = use_signal(|| State::Loading {
+        task: spawn(async {}),
+    });
+    let subscription = use_hook(|| Rc::new(RefCell::new(None::<Task>)));
+    let target = (provider, args.into_param());
+
+    let _subscription_memo = use_memo(use_reactive!(|target| {
+        let (provider, param) = target;
+        if let Some(previous) = subscription.borrow_mut().take() {
+            previous.cancel();
+        }
+
+        let cache_key = provider.id(&param);
+        let cache = runtime.cache().clone();
+        let refresh_registry = runtime.refresh_registry().clone();
+        let mut stream = provider.stream(param);
+        crate::debug_log!("📡 [STREAM] Opening stream for key: {}", cache_key);
+        let task = spawn(async move {
+            while let Some(item) = stream.next().await {
+                if cache.set(cache_key.clone(), item.clone()) {
+                    refresh_registry.trigger_refresh(&cache_key);
+                }
+                state.set(match item {
+                    Ok(data) => State::Success(data),
+                    Err(error) => State::Error(error),
+                });
+            }
+            crate::debug_log!("📡 [STREAM] Stream ended for key: {}", cache_key);
+        });
+        *subscription.borrow_mut() = Some(task);
+        state.set(State::Loading { task });
+    }));
+
+    use_hook(|| ReadSignal::new(state))
+}
@@ -0,0 +1,670 @@
+//! # Background Sync
+//!
+//! This module provides a small scheduler for background sync jobs, such as refreshing key
+//! providers or flushing journaled mutations. Jobs run on their own interval, whenever the
+//! application reports that connectivity was restored, or on demand. A failing job backs off
+//! exponentially, on its own, before its interval or a reconnect runs it again.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use dioxus::prelude::*;
+//! use dioxus_provider::{prelude::*, sync::{SyncJob, get_global_sync_scheduler}};
+//!
+//! fn main() {
+//!     init().unwrap();
+//!
+//!     let scheduler = get_global_sync_scheduler().unwrap();
+//!     scheduler.register(
+//!         SyncJob::refresh_provider("inbox", fetch_inbox(), ())
+//!             .with_interval(Duration::from_secs(300)),
+//!     );
+//!
+//!     launch(App);
+//! }
+//!
+//! #[component]
+//! fn App() -> Element {
+//!     use_hook(|| get_global_sync_scheduler().unwrap().start());
+//!     let status = use_sync_status();
+//!     rsx! { "Pending: {status.read().pending_work}" }
+//! }
+//! ```
+
+use dioxus::{core::ReactiveContext, prelude::*};
+use serde::de::DeserializeOwned;
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    future::Future,
+    pin::Pin,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+
+#[cfg(not(target_family = "wasm"))]
+use std::time::Instant;
+#[cfg(target_family = "wasm")]
+use web_time::Instant;
+
+use crate::{
+    cache::ProviderCache,
+    errors::ProviderError,
+    global::{GlobalProviderError, get_global_runtime, hook_runtime},
+    hooks::Provider,
+    journal::MutationJournal,
+    mutation::Mutation,
+    platform::{network, now_secs, sleep},
+    refresh::RefreshRegistry,
+    types::ProviderParamBounds,
+};
+
+/// Refresh registry key used to notify subscribers about sync status changes
+const SYNC_STATUS_KEY: &str = "__dioxus_provider_sync_status";
+
+/// Boxed future returned by a sync job
+pub type SyncFuture = Pin<Box<dyn Future<Output = Result<(), ProviderError>>>>;
+
+type SyncJobFn = Arc<dyn Fn(&SyncScheduler) -> SyncFuture + Send + Sync>;
+type PendingFn = Arc<dyn Fn() -> usize + Send + Sync>;
+
+/// A unit of background work run by the [`SyncScheduler`]
+#[derive(Clone)]
+pub struct SyncJob {
+    name: String,
+    interval: Option<Duration>,
+    run: SyncJobFn,
+    pending: Option<PendingFn>,
+}
+
+impl Debug for SyncJob {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SyncJob")
+            .field("name", &self.name)
+            .field("interval", &self.interval)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SyncJob {
+    /// Create a job from an async closure
+    pub fn new<F, Fut>(name: impl Into<String>, run: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), ProviderError>> + 'static,
+    {
+        Self {
+            name: name.into(),
+            interval: None,
+            run: Arc::new(move |_| Box::pin(run())),
+            pending: None,
+        }
+    }
+
+    /// Job that invalidates a provider's cached value and refreshes its subscribers
+    ///
+    /// The job acts on the runtime that owns the scheduler it runs on, so jobs registered
+    /// with a named or scoped runtime's scheduler leave the global cache alone.
+    pub fn refresh_provider<P, Param>(name: impl Into<String>, provider: P, param: Param) -> Self
+    where
+        P: Provider<Param> + Send + Sync,
+        Param: ProviderParamBounds,
+    {
+        let cache_key = provider.id(&param);
+        let run: SyncJobFn = Arc::new(move |scheduler| {
+            let cache_key = cache_key.clone();
+            let cache = scheduler.inner.cache.clone();
+            let refresh_registry = scheduler.inner.refresh_registry.clone();
+            Box::pin(async move {
+                let cache = cache.ok_or_else(|| {
+                    ProviderError::Configuration(
+                        "sync scheduler is not attached to a provider runtime".to_string(),
+                    )
+                })?;
+                cache.invalidate(&cache_key);
+                refresh_registry.trigger_refresh(&cache_key);
+                Ok(())
+            })
+        });
+        Self {
+            name: name.into(),
+            interval: None,
+            run,
+            pending: None,
+        }
+    }
+
+    /// Job that replays every journaled input of `mutation`
+    ///
+    /// The job reports the number of journaled records as pending work.
+    pub fn replay_journal<M, Input>(
+        name: impl Into<String>,
+        journal: MutationJournal,
+        mutation: M,
+    ) -> Self
+    where
+        M: Mutation<Input> + Send + Sync,
+        Input: Clone + PartialEq + DeserializeOwned + 'static,
+    {
+        let pending_journal = journal.clone();
+        Self::new(name, move || {
+            let journal = journal.clone();
+            let mutation = mutation.clone();
+            async move {
+                let failed = journal
+                    .replay(&mutation)
+                    .await
+                    .iter()
+                    .filter(|outcome| outcome.is_err())
+                    .count();
+                if failed > 0 {
+                    return Err(ProviderError::Generic(format!(
+                        "{failed} journaled mutations failed to replay"
+                    )));
+                }
+                Ok(())
+            }
+        })
+        .with_pending(move || {
+            pending_journal
+                .pending()
+                .map(|records| records.len())
+                .unwrap_or(0)
+        })
+    }
+
+    /// Run the job periodically at the given interval once the scheduler is started
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+
+    /// Report outstanding work for this job in [`SyncStatus::pending_work`]
+    pub fn with_pending<F>(mut self, pending: F) -> Self
+    where
+        F: Fn() -> usize + Send + Sync + 'static,
+    {
+        self.pending = Some(Arc::new(pending));
+        self
+    }
+
+    /// Name of the job
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn pending_work(&self) -> usize {
+        self.pending.as_ref().map(|pending| pending()).unwrap_or(0)
+    }
+}
+
+/// Exponential backoff applied to failing sync jobs
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SyncBackoff {
+    /// Delay before the first retry
+    pub initial: Duration,
+    /// Upper bound for the retry delay
+    pub max: Duration,
+}
+
+impl Default for SyncBackoff {
+    fn default() -> Self {
+        Self {
+            initial: Duration::from_secs(1),
+            max: Duration::from_secs(300),
+        }
+    }
+}
+
+impl SyncBackoff {
+    /// Delay before the next attempt after `failures` consecutive failures
+    pub fn delay(&self, failures: u32) -> Duration {
+        let exponent = failures.saturating_sub(1).min(31);
+        self.initial.saturating_mul(1u32 << exponent).min(self.max)
+    }
+}
+
+/// Snapshot of the background sync state
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SyncStatus {
+    /// Unix timestamp (seconds) of the last sync run where every job it ran succeeded
+    pub last_sync: Option<u64>,
+    /// Whether a sync run is currently in progress
+    pub syncing: bool,
+    /// Outstanding work reported by the registered jobs
+    pub pending_work: usize,
+    /// Consecutive failures of the job that has been failing the longest, 0 if none is
+    pub consecutive_failures: u32,
+    /// Error from the most recent failing job, while any job is failing
+    pub last_error: Option<String>,
+}
+
+/// Consecutive failures of one job, and when its backoff lets it run again
+#[derive(Debug, Clone, Copy)]
+struct JobFailures {
+    count: u32,
+    retry_at: Instant,
+}
+
+struct SyncSchedulerInner {
+    jobs: Mutex<Vec<SyncJob>>,
+    status: Mutex<SyncStatus>,
+    backoff: Mutex<SyncBackoff>,
+    /// Failing jobs by name
+    failures: Mutex<HashMap<String, JobFailures>>,
+    started: AtomicBool,
+    refresh_registry: RefreshRegistry,
+    cache: Option<ProviderCache>,
+}
+
+/// Scheduler that runs registered [`SyncJob`]s in the background
+///
+/// Cloning the scheduler shares its jobs and status.
+#[derive(Clone)]
+pub struct SyncScheduler {
+    inner: Arc<SyncSchedulerInner>,
+}
+
+impl Debug for SyncScheduler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SyncScheduler")
+            .field("status", &self.status())
+            .finish_non_exhaustive()
+    }
+}
+
+impl SyncScheduler {
+    /// Create a scheduler that publishes status changes through `refresh_registry`
+    ///
+    /// The scheduler isn't attached to a cache, so [`SyncJob::refresh_provider`] jobs fail on it.
+    pub fn new(refresh_registry: RefreshRegistry) -> Self {
+        Self::with_cache(refresh_registry, None)
+    }
+
+    /// Create the scheduler owned by a runtime, whose refresh jobs act on `cache`
+    pub(crate) fn for_runtime(refresh_registry: RefreshRegistry, cache: ProviderCache) -> Self {
+        Self::with_cache(refresh_registry, Some(cache))
+    }
+
+    fn with_cache(refresh_registry: RefreshRegistry, cache: Option<ProviderCache>) -> Self {
+        Self {
+            inner: Arc::new(SyncSchedulerInner {
+                jobs: Mutex::new(Vec::new()),
+                status: Mutex::new(SyncStatus::default()),
+                backoff: Mutex::new(SyncBackoff::default()),
+                failures: Mutex::new(HashMap::new()),
+                started: AtomicBool::new(false),
+                refresh_registry,
+                cache,
+            }),
+        }
+    }
+
+    /// Replace the backoff policy used for failing jobs
+    pub fn set_backoff(&self, backoff: SyncBackoff) {
+        if let Ok(mut current) = self.inner.backoff.lock() {
+            *current = backoff;
+        }
+    }
+
+    /// Register a job
+    ///
+    /// Jobs with an interval that are registered after [`SyncScheduler::start`] are
+    /// scheduled immediately. Failures, and the backoff they cause, are tracked per job name.
+    pub fn register(&self, job: SyncJob) {
+        if let Ok(mut jobs) = self.inner.jobs.lock() {
+            jobs.push(job.clone());
+        }
+        if self.inner.started.load(Ordering::SeqCst) {
+            self.schedule(job);
+        }
+        self.publish(|_| {});
+    }
+
+    /// Current sync status
+    pub fn status(&self) -> SyncStatus {
+        let mut status = self
+            .inner
+            .status
+            .lock()
+            .map(|status| status.clone())
+            .unwrap_or_default();
+        status.pending_work = self.pending_work();
+        status
+    }
+
+    /// Start the periodic loops for every job that has an interval
+    ///
+    /// Once started, the scheduler also runs every job whenever
+    /// [`platform::network`](crate::platform::network) reports that the app is back online.
+    ///
+    /// Must be called from within a Dioxus runtime. Calling it again is a no-op.
+    pub fn start(&self) {
+        if self.inner.started.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        for job in self.jobs() {
+            self.schedule(job);
+        }
+        self.follow_network_status();
+    }
+
+    /// Notify the scheduler that connectivity was restored, triggering a sync run
+    ///
+    /// The run skips jobs that are still backing off from a failure. Started schedulers are
+    /// notified by [`platform::network`](crate::platform::network); call this when reconnects
+    /// are detected elsewhere. Must be called from within a Dioxus runtime.
+    pub fn notify_reconnect(&self) {
+        crate::debug_log!("🔌 [SYNC] Reconnected, running sync jobs");
+        let scheduler = self.clone();
+        dioxus_core::spawn_forever(async move {
+            let _ = scheduler.sync_due().await;
+        });
+    }
+
+    /// Run every registered job once, in registration order
+    ///
+    /// Jobs run even if they are backing off from a failure. Returns the error of the last
+    /// failing job, if any.
+    pub async fn sync_now(&self) -> Result<(), ProviderError> {
+        self.run_jobs(&self.jobs()).await
+    }
+
+    /// Run every registered job that isn't backing off from a failure
+    async fn sync_due(&self) -> Result<(), ProviderError> {
+        let due: Vec<SyncJob> = self
+            .jobs()
+            .into_iter()
+            .filter(|job| {
+                self.backoff_remaining(job)
+                    .is_none_or(|left| left.is_zero())
+            })
+            .collect();
+        if due.is_empty() {
+            return Ok(());
+        }
+        self.run_jobs(&due).await
+    }
+
+    async fn run_jobs(&self, jobs: &[SyncJob]) -> Result<(), ProviderError> {
+        self.publish(|status| status.syncing = true);
+
+        let mut result = Ok(());
+        for job in jobs {
+            if let Err(error) = self.run_job(job).await {
+                result = Err(error);
+            }
+        }
+
+        self.finish_run(&result);
+        result
+    }
+
+    async fn run_job(&self, job: &SyncJob) -> Result<(), ProviderError> {
+        crate::debug_log!("🔄 [SYNC] Running job: {}", job.name);
+        let result = (job.run)(self).await;
+        if let Err(ref _error) = result {
+            crate::debug_log!("❌ [SYNC] Job {} failed: {}", job.name, _error);
+        }
+        self.record_result(job, &result);
+        result
+    }
+
+    /// Reset a job's failures after a success, or back it off further after a failure
+    fn record_result(&self, job: &SyncJob, result: &Result<(), ProviderError>) {
+        let backoff = self
+            .inner
+            .backoff
+            .lock()
+            .map(|backoff| *backoff)
+            .unwrap_or_default();
+        if let Ok(mut failures) = self.inner.failures.lock() {
+            if result.is_ok() {
+                failures.remove(&job.name);
+                return;
+            }
+            let count = failures.get(&job.name).map_or(0, |failures| failures.count) + 1;
+            failures.insert(
+                job.name.clone(),
+                JobFailures {
+                    count,
+                    retry_at: Instant::now() + backoff.delay(count),
+                },
+            );
+        }
+    }
+
+    /// Time left until a failing job may run again, or None if it isn't failing
+    fn backoff_remaining(&self, job: &SyncJob) -> Option<Duration> {
+        let failures = self.inner.failures.lock().ok()?;
+        let retry_at = failures.get(&job.name)?.retry_at;
+        Some(retry_at.saturating_duration_since(Instant::now()))
+    }
+
+    fn jobs(&self) -> Vec<SyncJob> {
+        self.inner
+            .jobs
+            .lock()
+            .map(|jobs| jobs.clone())
+            .unwrap_or_default()
+    }
+
+    fn follow_network_status(&self) {
+        let mut status = network::subscribe();
+        let scheduler = self.clone();
+        dioxus_core::spawn_forever(async move {
+            while status.changed().await.is_ok() {
+                if *status.borrow_and_update() {
+                    scheduler.notify_reconnect();
+                }
+            }
+        });
+    }
+
+    fn schedule(&self, job: SyncJob) {
+        let Some(interval) = job.interval else {
+            return;
+        };
+        let scheduler = self.clone();
+        dioxus_core::spawn_forever(async move {
+            loop {
+                sleep(scheduler.backoff_remaining(&job).unwrap_or(interval)).await;
+
+                // Another trigger may have failed the job, and pushed its retry back, meanwhile
+                if scheduler
+                    .backoff_remaining(&job)
+                    .is_some_and(|left| !left.is_zero())
+                {
+                    continue;
+                }
+                let _ = scheduler.run_jobs(std::slice::from_ref(&job)).await;
+            }
+        });
+    }
+
+    fn finish_run(&self, result: &Result<(), ProviderError>) {
+        let consecutive_failures = self.inner.failures.lock().map_or(0, |failures| {
+            failures
+                .values()
+                .map(|failures| failures.count)
+                .max()
+                .unwrap_or(0)
+        });
+        self.publish(|status| {
+            status.syncing = false;
+            status.consecutive_failures = consecutive_failures;
+            match result {
+                Ok(()) => {
+                    status.last_sync = Some(now_secs());
+                    if consecutive_failures == 0 {
+                        status.last_error = None;
+                    }
+                }
+                Err(error) => status.last_error = Some(error.to_string()),
+            }
+        });
+    }
+
+    fn pending_work(&self) -> usize {
+        self.inner
+            .jobs
+            .lock()
+            .map(|jobs| jobs.iter().map(SyncJob::pending_work).sum())
+            .unwrap_or(0)
+    }
+
+    fn publish(&self, update: impl FnOnce(&mut SyncStatus)) {
+        if let Ok(mut status) = self.inner.status.lock() {
+            update(&mut status);
+        }
+        self.inner.refresh_registry.trigger_refresh(SYNC_STATUS_KEY);
+    }
+}
+
+/// Get the sync scheduler of the global runtime
+pub fn get_global_sync_scheduler() -> Result<SyncScheduler, GlobalProviderError> {
+    get_global_runtime().map(|runtime| runtime.sync_scheduler().clone())
+}
+
+/// Hook that reports the background sync status
+///
+/// The returned signal updates whenever a sync job starts or settles.
+pub fn use_sync_status() -> Signal<SyncStatus> {
+    let runtime = hook_runtime();
+    let scheduler = runtime.sync_scheduler().clone();
+    let refresh_registry = runtime.refresh_registry().clone();
+
+    let mut status = use_signal(|| scheduler.status());
+    let _status_memo = use_memo(move || {
+        if let Some(reactive_context) = ReactiveContext::current() {
+            refresh_registry.subscribe_to_refresh(SYNC_STATUS_KEY, reactive_context);
+        }
+        let current = scheduler.status();
+        if *status.peek() != current {
+            status.set(current);
+        }
+    });
+
+    status
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicU32;
+
+    #[test]
+    fn backoff_grows_exponentially_up_to_max() {
+        let backoff = SyncBackoff {
+            initial: Duration::from_secs(1),
+            max: Duration::from_secs(10),
+        };
+        assert_eq!(backoff.delay(1), Duration::from_secs(1));
+        assert_eq!(backoff.delay(2), Duration::from_secs(2));
+        assert_eq!(backoff.delay(4), Duration::from_secs(8));
+        assert_eq!(backoff.delay(5), Duration::from_secs(10));
+        assert_eq!(backoff.delay(100), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn sync_now_tracks_status() {
+        let scheduler = SyncScheduler::new(RefreshRegistry::new());
+        let attempts = Arc::new(AtomicU32::new(0));
+        let job_attempts = attempts.clone();
+        scheduler.register(
+            SyncJob::new("flaky", move || {
+                let attempt = job_attempts.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    if attempt == 0 {
+                        Err(ProviderError::Network("offline".to_string()))
+                    } else {
+                        Ok(())
+                    }
+                }
+            })
+            .with_pending(|| 3),
+        );
+
+        assert!(futures::executor::block_on(scheduler.sync_now()).is_err());
+        let status = scheduler.status();
+        assert_eq!(status.consecutive_failures, 1);
+        assert_eq!(status.last_sync, None);
+        assert_eq!(status.pending_work, 3);
+        assert!(status.last_error.is_some());
+
+        assert!(futures::executor::block_on(scheduler.sync_now()).is_ok());
+        let status = scheduler.status();
+        assert_eq!(status.consecutive_failures, 0);
+        assert!(status.last_sync.is_some());
+        assert!(!status.syncing);
+    }
+
+    #[test]
+    fn failing_jobs_back_off_without_delaying_healthy_ones() {
+        let scheduler = SyncScheduler::new(RefreshRegistry::new());
+        scheduler.set_backoff(SyncBackoff {
+            initial: Duration::from_secs(3600),
+            max: Duration::from_secs(3600),
+        });
+        let counter = |name: &'static str, fails: bool| {
+            let runs = Arc::new(AtomicU32::new(0));
+            let job_runs = runs.clone();
+            scheduler.register(SyncJob::new(name, move || {
+                job_runs.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    if fails {
+                        Err(ProviderError::Network("offline".to_string()))
+                    } else {
+                        Ok(())
+                    }
+                }
+            }));
+            runs
+        };
+        let failing = counter("failing", true);
+        let healthy = counter("healthy", false);
+
+        assert!(futures::executor::block_on(scheduler.sync_now()).is_err());
+        assert_eq!(scheduler.status().consecutive_failures, 1);
+
+        // A reconnect skips the job that is backing off, but still runs the healthy one
+        assert!(futures::executor::block_on(scheduler.sync_due()).is_ok());
+        assert_eq!(failing.load(Ordering::SeqCst), 1);
+        assert_eq!(healthy.load(Ordering::SeqCst), 2);
+        assert_eq!(scheduler.status().consecutive_failures, 1);
+
+        // An explicit sync runs every job regardless
+        assert!(futures::executor::block_on(scheduler.sync_now()).is_err());
+        assert_eq!(failing.load(Ordering::SeqCst), 2);
+        assert_eq!(healthy.load(Ordering::SeqCst), 3);
+        assert_eq!(scheduler.status().consecutive_failures, 2);
+    }
+
+    #[test]
+    fn refresh_jobs_invalidate_the_runtime_owning_the_scheduler() {
+        let runtime = crate::global::ProviderConfig::new().build();
+        let provider = crate::hooks::FnProvider::new("sync_refresh_scoped", |_: ()| async {
+            Ok::<u32, ()>(1)
+        });
+        let cache_key = provider.id(&());
+        runtime.cache().set(cache_key.clone(), Ok::<u32, ()>(1));
+
+        let scheduler = runtime.sync_scheduler();
+        scheduler.register(SyncJob::refresh_provider("scoped", provider, ()));
+        assert!(futures::executor::block_on(scheduler.sync_now()).is_ok());
+        assert!(runtime.cache().get::<Result<u32, ()>>(&cache_key).is_none());
+
+        let detached = SyncScheduler::new(RefreshRegistry::new());
+        detached.register(SyncJob::refresh_provider(
+            "detached",
+            crate::hooks::FnProvider::new("sync_refresh_detached", |_: ()| async {
+                Ok::<u32, ()>(1)
+            }),
+            (),
+        ));
+        assert!(futures::executor::block_on(detached.sync_now()).is_err());
+    }
+}
@@ -0,0 +1,391 @@
+//! # Testing Utilities
+//!
+//! Helpers for testing components that use providers with a headless `VirtualDom`. Instead
+//! of pumping the DOM and sleeping a fixed number of times, [`wait_for_state`] drives the
+//! DOM until a provider's state satisfies a predicate, failing after a timeout, and
+//! [`assert_state!`](crate::assert_state) checks a state against a pattern.
+//!
+//! [`FakeTransport`] stands in for the network in tests and examples: providers send their
+//! requests through it to get configurable latency, random failures and scripted responses,
+//! without a global flag per failure mode.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use dioxus_provider::{assert_state, prelude::*, testing::wait_for_state};
+//! use std::time::Duration;
+//!
+//! #[tokio::test]
+//! async fn loads_the_user() {
+//!     init().unwrap();
+//!     let mut vdom = VirtualDom::new(App);
+//!     vdom.rebuild_in_place();
+//!
+//!     // `user` was handed out by the component under test, e.g. through a context
+//!     let state = wait_for_state(&mut vdom, user, |state| state.is_success(), Duration::from_secs(1))
+//!         .await
+//!         .unwrap();
+//!     assert_state!(state, State::Success(user) if user.name == "Ada");
+//! }
+//! ```
+//!
+//! ## Faking the Network
+//!
+//! Register a [`FakeTransport`] as a dependency and send requests through it. Clones share
+//! their settings, so the app can turn failures on and off at runtime:
+//!
+//! ```rust,ignore
+//! use dioxus_provider::{prelude::*, testing::FakeTransport};
+//!
+//! #[provider]
+//! async fn fetch_items() -> Result<Vec<Item>, String> {
+//!     let transport = inject::<FakeTransport>().map_err(|e| e.to_string())?;
+//!     transport
+//!         .request("items", || vec![Item::default()])
+//!         .await
+//!         .map_err(|e| e.to_string())
+//! }
+//!
+//! let transport = FakeTransport::new().with_latency(Duration::from_millis(300));
+//! register_dependency(transport.clone()).unwrap();
+//! transport.script_failure("items", "503 Service Unavailable");
+//! transport.set_failure_rate(0.2);
+//! ```
+
+use std::{
+    any::Any,
+    collections::{HashMap, VecDeque},
+    fmt,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use dioxus::{core::NoOpMutations, prelude::*};
+use futures::{FutureExt, future::select};
+use thiserror::Error;
+
+use crate::{platform::sleep, state::State};
+
+// Platform-specific time imports
+#[cfg(not(target_family = "wasm"))]
+use std::time::Instant;
+#[cfg(target_family = "wasm")]
+use web_time::Instant;
+
+// Send-safe sleep, since provider futures may have to be Send
+#[cfg(not(target_family = "wasm"))]
+use tokio::time::sleep as transport_sleep;
+#[cfg(target_family = "wasm")]
+use wasmtimer::tokio::sleep as transport_sleep;
+
+/// How often [`wait_for_state`] checks the state while the DOM has no work
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Returned by [`wait_for_state`] when the state didn't match in time
+#[derive(Debug, Clone, PartialEq)]
+pub struct WaitTimeout<T, E> {
+    /// The state when the timeout passed
+    pub last: State<T, E>,
+    /// The timeout that passed
+    pub timeout: Duration,
+}
+
+impl<T: fmt::Debug, E: fmt::Debug> fmt::Display for WaitTimeout<T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "state didn't match within {:?}, last state: {:?}",
+            self.timeout, self.last
+        )
+    }
+}
+
+impl<T: fmt::Debug, E: fmt::Debug> std::error::Error for WaitTimeout<T, E> {}
+
+/// Render everything that is ready, without waiting for tasks
+pub fn pump(vdom: &mut VirtualDom) {
+    while vdom.wait_for_work().now_or_never().is_some() {
+        vdom.render_immediate(&mut NoOpMutations);
+    }
+}
+
+/// Drive `vdom` until `state` satisfies `predicate`, returning the matching state
+///
+/// Renders whenever a task of the DOM makes progress, such as a provider resolving, so the
+/// call returns as soon as the state matches. Accepts a state signal or a `ProviderHandle`.
+pub async fn wait_for_state<T, E>(
+    vdom: &mut VirtualDom,
+    state: impl Into<Signal<State<T, E>>>,
+    predicate: impl Fn(&State<T, E>) -> bool,
+    timeout: Duration,
+) -> Result<State<T, E>, WaitTimeout<T, E>>
+where
+    T: Clone + 'static,
+    E: Clone + 'static,
+{
+    let state = state.into();
+    let deadline = Instant::now() + timeout;
+    loop {
+        pump(vdom);
+        let current = vdom.in_runtime(|| state.peek().clone());
+        if predicate(&current) {
+            return Ok(current);
+        }
+
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(WaitTimeout {
+                last: current,
+                timeout,
+            });
+        }
+        // Background refreshes finish outside the DOM's tasks without waking it, so poll too
+        let poll = remaining.min(POLL_INTERVAL);
+        select(Box::pin(vdom.wait_for_work()), Box::pin(sleep(poll))).await;
+    }
+}
+
+/// Assert that a provider state matches a pattern, printing the state if it doesn't
+///
+/// Takes a `State` value, or a reference to one, followed by a pattern with an optional
+/// guard.
+///
+/// ```rust
+/// use dioxus_provider::{assert_state, prelude::State};
+///
+/// let state: State<u32, String> = State::Success(3);
+/// assert_state!(state, State::Success(3));
+/// assert_state!(&state, State::Success(count) if *count > 2);
+/// ```
+#[macro_export]
+macro_rules! assert_state {
+    ($state:expr, $pattern:pat $(if $guard:expr)? $(,)?) => {
+        match $state {
+            $pattern $(if $guard)? => {}
+            ref state => panic!(
+                "state doesn't match `{}`: {:?}",
+                stringify!($pattern $(if $guard)?),
+                state
+            ),
+        }
+    };
+}
+
+/// A request that [`FakeTransport`] failed on purpose
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("request to {endpoint} failed: {message}")]
+pub struct TransportError {
+    /// The endpoint passed to [`FakeTransport::request`]
+    pub endpoint: String,
+    /// The scripted message, or a generic one for random failures
+    pub message: String,
+}
+
+/// A scripted outcome for the next request to an endpoint
+enum Scripted {
+    Respond(Box<dyn Any + Send>),
+    Fail(String),
+}
+
+struct TransportState {
+    latency: Duration,
+    failure_rate: f64,
+    rng: u64,
+    scripts: HashMap<String, VecDeque<Scripted>>,
+    requests: Vec<String>,
+}
+
+impl TransportState {
+    /// Next value in [0, 1) of a xorshift generator, so failures repeat for a given seed
+    fn next_random(&mut self) -> f64 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        (self.rng >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// A fake network for tests and examples
+///
+/// Each [`request`](Self::request) waits for the configured latency, then fails with the
+/// configured probability, returns the next scripted outcome for its endpoint, or falls
+/// back to the given response. Clones share their settings and scripts, so a clone
+/// registered with [`register_dependency`](crate::injection::register_dependency) can be
+/// reconfigured while the app runs.
+#[derive(Clone)]
+pub struct FakeTransport {
+    state: Arc<Mutex<TransportState>>,
+}
+
+impl Default for FakeTransport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for FakeTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.lock();
+        f.debug_struct("FakeTransport")
+            .field("latency", &state.latency)
+            .field("failure_rate", &state.failure_rate)
+            .field("requests", &state.requests.len())
+            .finish()
+    }
+}
+
+impl FakeTransport {
+    /// A transport without latency or failures
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(TransportState {
+                latency: Duration::ZERO,
+                failure_rate: 0.0,
+                rng: 0x2545_f491_4f6c_dd1d,
+                scripts: HashMap::new(),
+                requests: Vec::new(),
+            })),
+        }
+    }
+
+    /// Delay every request by `latency`
+    pub fn with_latency(self, latency: Duration) -> Self {
+        self.set_latency(latency);
+        self
+    }
+
+    /// Fail requests with probability `rate`, clamped to [0, 1]
+    pub fn with_failure_rate(self, rate: f64) -> Self {
+        self.set_failure_rate(rate);
+        self
+    }
+
+    /// Seed the generator deciding random failures, for a different but repeatable sequence
+    pub fn with_seed(self, seed: u64) -> Self {
+        // Xorshift gets stuck at zero
+        self.lock().rng = seed.max(1);
+        self
+    }
+
+    /// Change the latency of later requests
+    pub fn set_latency(&self, latency: Duration) {
+        self.lock().latency = latency;
+    }
+
+    /// Change the failure rate of later requests
+    pub fn set_failure_rate(&self, rate: f64) {
+        self.lock().failure_rate = rate.clamp(0.0, 1.0);
+    }
+
+    /// Respond to the next unscripted request to `endpoint` with `response`
+    ///
+    /// Scripts queue up per endpoint and are used in order. A response whose type doesn't
+    /// match the request's is discarded.
+    pub fn script<T: Send + 'static>(&self, endpoint: impl Into<String>, response: T) {
+        self.push_script(endpoint.into(), Scripted::Respond(Box::new(response)));
+    }
+
+    /// Fail the next unscripted request to `endpoint` with `message`
+    pub fn script_failure(&self, endpoint: impl Into<String>, message: impl Into<String>) {
+        self.push_script(endpoint.into(), Scripted::Fail(message.into()));
+    }
+
+    /// The endpoints requested so far, in order
+    pub fn requests(&self) -> Vec<String> {
+        self.lock().requests.clone()
+    }
+
+    /// Send a request to `endpoint`, answered by `respond` unless scripted or failed
+    pub async fn request<T: Send + 'static>(
+        &self,
+        endpoint: &str,
+        respond: impl FnOnce() -> T,
+    ) -> Result<T, TransportError> {
+        let (latency, scripted, failed) = {
+            let mut state = self.lock();
+            state.requests.push(endpoint.to_string());
+            let scripted = state
+                .scripts
+                .get_mut(endpoint)
+                .and_then(VecDeque::pop_front);
+            let failed = scripted.is_none() && state.next_random() < state.failure_rate;
+            (state.latency, scripted, failed)
+        };
+
+        if !latency.is_zero() {
+            transport_sleep(latency).await;
+        }
+
+        let fail = |message: String| TransportError {
+            endpoint: endpoint.to_string(),
+            message,
+        };
+        match scripted {
+            Some(Scripted::Fail(message)) => Err(fail(message)),
+            Some(Scripted::Respond(response)) => match response.downcast::<T>() {
+                Ok(response) => Ok(*response),
+                Err(_) => Ok(respond()),
+            },
+            None if failed => Err(fail("simulated network failure".to_string())),
+            None => Ok(respond()),
+        }
+    }
+
+    fn push_script(&self, endpoint: String, scripted: Scripted) {
+        self.lock()
+            .scripts
+            .entry(endpoint)
+            .or_default()
+            .push_back(scripted);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, TransportState> {
+        // Settings stay usable even if a panicking test poisoned the lock
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+
+    #[test]
+    fn fake_transport_follows_scripts_then_falls_back() {
+        let transport = FakeTransport::new();
+        transport.script("items", vec![1, 2]);
+        transport.script_failure("items", "503");
+
+        assert_eq!(
+            block_on(transport.request("items", Vec::new)),
+            Ok(vec![1, 2])
+        );
+        let error = block_on(transport.request("items", Vec::<i32>::new)).unwrap_err();
+        assert_eq!(error.message, "503");
+        assert_eq!(
+            block_on(transport.request("items", || vec![3])),
+            Ok(vec![3])
+        );
+        assert_eq!(transport.requests(), ["items", "items", "items"]);
+    }
+
+    #[test]
+    fn fake_transport_fails_at_the_configured_rate() {
+        let transport = FakeTransport::new().with_failure_rate(1.0);
+        assert!(block_on(transport.request("items", || ())).is_err());
+
+        transport.set_failure_rate(0.0);
+        assert!(block_on(transport.request("items", || ())).is_ok());
+
+        let transport = FakeTransport::new().with_seed(7).with_failure_rate(0.5);
+        let mut failures = 0;
+        for _ in 0..100 {
+            if block_on(transport.request("items", || ())).is_err() {
+                failures += 1;
+            }
+        }
+        assert!((25..75).contains(&failures), "{failures} failures");
+    }
+}
@@ -0,0 +1,64 @@
+//! # Fetch Timeouts
+//!
+//! A provider whose request never resolves would keep its consumers in `State::Loading`
+//! forever. With a [timeout](crate::hooks::Provider::timeout), every run of the provider
+//! that takes longer is dropped and turned into the provider's
+//! [timeout error](crate::hooks::Provider::timeout_error), which is stored and shown like
+//! any other error. A retry policy treats it as a failed attempt and retries it.
+//!
+//! `#[provider]` builds the timeout error for error types implementing
+//! [`TimeoutError`](crate::errors::TimeoutError), which covers `String` and the error types
+//! in [`errors`](crate::errors); other error types give it with `timeout_error`, and fail to
+//! compile without one.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use dioxus_provider::prelude::*;
+//!
+//! #[provider(timeout = "10s")]
+//! async fn fetch_report(id: u32) -> Result<Report, ProviderError> {
+//!     api::report(id).await
+//! }
+//!
+//! #[provider(timeout = "5s", timeout_error = |_| ReportError::Unavailable)]
+//! async fn fetch_summary(id: u32) -> Result<Summary, ReportError> {
+//!     api::summary(id).await
+//! }
+//! ```
+
+use futures::future::{Either, select};
+
+use crate::{hooks::Provider, platform::sleep, types::ProviderParamBounds};
+
+/// Run a provider, giving up with its timeout error once its timeout has passed
+pub(crate) async fn run<P, Param>(provider: &P, param: Param) -> Result<P::Output, P::Error>
+where
+    P: Provider<Param>,
+    Param: ProviderParamBounds,
+{
+    let Some(timeout) = provider.timeout() else {
+        return provider.run(param).await;
+    };
+    let Some(error) = provider.timeout_error(timeout) else {
+        panic!(
+            "{} sets a timeout of {timeout:?} but no timeout error. Implement \
+             `Provider::timeout_error`, or `TimeoutError` for its error type.",
+            std::any::type_name::<P>()
+        );
+    };
+
+    let run = std::pin::pin!(provider.run(param));
+    let expired = std::pin::pin!(sleep(timeout));
+    match select(run, expired).await {
+        Either::Left((result, _)) => result,
+        Either::Right(_) => {
+            crate::debug_log!(
+                "⏱️ [TIMEOUT] {} didn't resolve within {:?}",
+                std::any::type_name::<P>(),
+                timeout
+            );
+            Err(error)
+        }
+    }
+}
@@ -114,3 +114,81 @@ impl<T> ProviderOutputBounds for T where T: Clone + PartialEq + Send + Sync + 's
 /// ```
 pub trait ProviderErrorBounds: Clone + PartialEq + Send + Sync + 'static {}
 impl<T> ProviderErrorBounds for T where T: Clone + PartialEq + Send + Sync + 'static {}
+
+/// Targeted bound checks emitted by the `#[provider]` and `#[mutation]` macros
+///
+/// Each trait wraps one of the bounds required of provider outputs and errors, so a type
+/// missing one gets an error naming the type and the bound instead of an unsatisfied
+/// `ProviderOutputBounds` deep inside the generated impl.
+#[doc(hidden)]
+pub mod __bounds {
+    #[diagnostic::on_unimplemented(
+        message = "provider output `{Self}` must implement `Clone`",
+        label = "this output type is not `Clone`",
+        note = "results are cached and handed to every consumer, so they must be cloneable"
+    )]
+    pub trait OutputClone {}
+    impl<T: Clone> OutputClone for T {}
+
+    #[diagnostic::on_unimplemented(
+        message = "provider output `{Self}` must implement `PartialEq`",
+        label = "this output type is not `PartialEq`",
+        note = "results are compared to skip re-renders when a refetch returns the same data"
+    )]
+    pub trait OutputPartialEq {}
+    impl<T: PartialEq> OutputPartialEq for T {}
+
+    #[diagnostic::on_unimplemented(
+        message = "provider output `{Self}` must implement `Send`",
+        label = "this output type is not `Send`",
+        note = "results are shared through a cache that background tasks access"
+    )]
+    pub trait OutputSend {}
+    impl<T: Send> OutputSend for T {}
+
+    #[diagnostic::on_unimplemented(
+        message = "provider output `{Self}` must implement `Sync`",
+        label = "this output type is not `Sync`",
+        note = "results are shared through a cache that background tasks access"
+    )]
+    pub trait OutputSync {}
+    impl<T: Sync> OutputSync for T {}
+
+    #[diagnostic::on_unimplemented(
+        message = "provider error `{Self}` must implement `Clone`",
+        label = "this error type is not `Clone`",
+        note = "errors are cached and handed to every consumer, so they must be cloneable"
+    )]
+    pub trait ErrorClone {}
+    impl<T: Clone> ErrorClone for T {}
+
+    #[diagnostic::on_unimplemented(
+        message = "provider error `{Self}` must implement `PartialEq`",
+        label = "this error type is not `PartialEq`",
+        note = "errors are compared to skip re-renders when a refetch fails the same way"
+    )]
+    pub trait ErrorPartialEq {}
+    impl<T: PartialEq> ErrorPartialEq for T {}
+
+    #[diagnostic::on_unimplemented(
+        message = "provider error `{Self}` must implement `Send`",
+        label = "this error type is not `Send`",
+        note = "errors are shared through a cache that background tasks access"
+    )]
+    pub trait ErrorSend {}
+    impl<T: Send> ErrorSend for T {}
+
+    #[diagnostic::on_unimplemented(
+        message = "provider error `{Self}` must implement `Sync`",
+        label = "this error type is not `Sync`",
+        note = "errors are shared through a cache that background tasks access"
+    )]
+    pub trait ErrorSync {}
+    impl<T: Sync> ErrorSync for T {}
+
+    /// Requires `T` to be usable as a provider output
+    pub fn assert_output<T: OutputClone + OutputPartialEq + OutputSend + OutputSync>() {}
+
+    /// Requires `T` to be usable as a provider error
+    pub fn assert_error<T: ErrorClone + ErrorPartialEq + ErrorSend + ErrorSync>() {}
+}
@@ -0,0 +1,94 @@
+//! # Warm Sets
+//!
+//! Usage analytics usually show that most sessions open the same few screens. A [`WarmSet`]
+//! lists the providers behind them; [`use_warm_set`], called in the root component, pins
+//! their cache keys so maintenance never evicts them or removes them as unused, and
+//! prefetches them on the low-priority lane as soon as the app starts. The hot screens then
+//! render from the cache the first time they are opened and every time after.
+//!
+//! Pinned keys can also be managed directly with
+//! [`ProviderCache::pin_keys`](crate::cache::ProviderCache::pin_keys) and
+//! [`ProviderCache::unpin_keys`](crate::cache::ProviderCache::unpin_keys), for example when
+//! updated analytics arrive.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use dioxus_provider::{prelude::*, warm::{WarmSet, use_warm_set}};
+//!
+//! fn hot_screens() -> WarmSet {
+//!     analytics::top_projects()
+//!         .into_iter()
+//!         .fold(WarmSet::new().provider(fetch_dashboard(), ()), |set, id| {
+//!             set.provider(fetch_project(), id)
+//!         })
+//! }
+//!
+//! #[component]
+//! fn App() -> Element {
+//!     use_warm_set(hot_screens);
+//!     rsx! { Router::<Route> {} }
+//! }
+//! ```
+
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+use crate::{
+    global::hook_runtime, hooks::Provider, param_utils::IntoProviderParam, runtime::ProviderRuntime,
+};
+
+type PrefetchFn = Rc<dyn Fn(&ProviderRuntime)>;
+
+/// Hot providers to keep cached, pinned and prefetched at startup
+#[derive(Clone, Default)]
+pub struct WarmSet {
+    keys: Vec<String>,
+    prefetches: Vec<PrefetchFn>,
+}
+
+impl WarmSet {
+    /// Create a warm set without any providers
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a provider to pin and prefetch
+    pub fn provider<P, Args>(mut self, provider: P, args: Args) -> Self
+    where
+        P: Provider<Args::Param> + Send + Clone,
+        Args: IntoProviderParam,
+    {
+        let param = args.into_param();
+        self.keys.push(provider.id(&param));
+        self.prefetches
+            .push(Rc::new(move |runtime: &ProviderRuntime| {
+                runtime.prefetch_low_priority(provider.clone(), param.clone());
+            }));
+        self
+    }
+
+    /// Cache keys of the providers in this set
+    pub fn keys(&self) -> &[String] {
+        &self.keys
+    }
+
+    /// Pin every key and prefetch the providers that aren't cached or already loading
+    ///
+    /// Must be called while the Dioxus runtime is running.
+    pub fn warm(&self, runtime: &ProviderRuntime) {
+        crate::debug_log!("🔥 [WARM] Pinning and warming {} keys", self.keys.len());
+        runtime.cache().pin_keys(&self.keys);
+        for prefetch in &self.prefetches {
+            prefetch(runtime);
+        }
+    }
+}
+
+/// Hook that warms the set built by `warm_set` once, when the component first renders
+///
+/// Call it in the root component so the prefetches start with the app.
+pub fn use_warm_set(warm_set: impl FnOnce() -> WarmSet) {
+    let runtime = hook_runtime();
+    use_hook(move || warm_set().warm(&runtime));
+}
@@ -0,0 +1,69 @@
+//! Lazy initialization with `ProviderConfig::auto_init`
+//!
+//! Kept in their own test binary, since every other test initializes the global providers.
+
+use dioxus::prelude::*;
+use dioxus_provider::assert_state;
+use dioxus_provider::cache::InvalidationMode;
+use dioxus_provider::global;
+use dioxus_provider::prelude::{ProviderHandle, State, provider, use_provider};
+use dioxus_provider::testing::wait_for_state;
+use std::rc::Rc;
+use std::time::Duration;
+
+#[provider]
+async fn motd() -> Result<String, String> {
+    Ok("hello".to_string())
+}
+
+type MotdSlot = Rc<std::cell::RefCell<Option<ProviderHandle<String, String>>>>;
+
+#[derive(Props, Clone, PartialEq)]
+struct BannerProps {
+    handle: MotdSlot,
+}
+
+#[allow(non_snake_case)]
+fn Banner(props: BannerProps) -> Element {
+    *props.handle.borrow_mut() = Some(use_provider(motd(), ()));
+    rsx!(div {})
+}
+
+#[test]
+fn registered_configurations_initialize_on_first_hook_use() {
+    tokio::runtime::Runtime::new()
+        .expect("tokio runtime")
+        .block_on(async {
+            global::ProviderConfig::new()
+                .with_invalidation_mode(InvalidationMode::Remove)
+                .auto_init();
+            global::ProviderConfig::new().auto_init();
+            assert!(!global::is_initialized(), "registering is lazy");
+
+            let slot = MotdSlot::default();
+            let mut vdom = VirtualDom::new_with_props(
+                Banner,
+                BannerProps {
+                    handle: slot.clone(),
+                },
+            );
+            vdom.rebuild_in_place();
+            assert!(global::is_initialized());
+
+            let state = slot.borrow().expect("banner should have rendered");
+            let state = wait_for_state(
+                &mut vdom,
+                state,
+                |state| state.is_success(),
+                Duration::from_secs(1),
+            )
+            .await
+            .unwrap();
+            assert_state!(state, State::Success(text) if text == "hello");
+            assert_eq!(
+                global::get_global_runtime().unwrap().invalidation_mode(),
+                InvalidationMode::Remove,
+                "the first registered configuration wins"
+            );
+        });
+}
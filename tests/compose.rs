@@ -0,0 +1,119 @@
+use dioxus_provider::prelude::*;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Member {
+    pub user_id: u32,
+    pub org_id: String,
+}
+
+#[provider]
+async fn fetch_member(user_id: u32, org_id: String) -> Result<Member, String> {
+    Ok(Member { user_id, org_id })
+}
+
+#[provider]
+async fn fetch_permissions(user_id: u32) -> Result<Vec<String>, String> {
+    Ok(vec![format!("read:{user_id}")])
+}
+
+#[provider]
+async fn fetch_org_name(org_id: String) -> Result<String, String> {
+    Ok(org_id.to_uppercase())
+}
+
+#[provider]
+async fn fetch_default_org() -> Result<String, String> {
+    Ok("acme".to_string())
+}
+
+#[provider(compose = [
+    fetch_member,
+    fetch_permissions(user_id),
+    fetch_org_name(org_id),
+    fetch_default_org(),
+])]
+async fn fetch_membership(
+    user_id: u32,
+    org_id: String,
+) -> Result<(Member, Vec<String>, String, String), String> {
+    Ok((
+        __dioxus_composed_fetch_member_result?,
+        __dioxus_composed_fetch_permissions_result?,
+        __dioxus_composed_fetch_org_name_result?,
+        __dioxus_composed_fetch_default_org_result?,
+    ))
+}
+
+#[test]
+fn composes_providers_with_mapped_parameters() {
+    let result = futures::executor::block_on(FetchMembership::call(7, "ops".to_string()));
+    assert_eq!(
+        result,
+        Ok((
+            Member {
+                user_id: 7,
+                org_id: "ops".to_string()
+            },
+            vec!["read:7".to_string()],
+            "OPS".to_string(),
+            "acme".to_string(),
+        ))
+    );
+}
+
+#[provider]
+async fn fetch_missing(user_id: u32) -> Result<String, String> {
+    Err(format!("user {user_id} not found"))
+}
+
+#[provider]
+async fn fetch_never(user_id: u32) -> Result<String, String> {
+    let _ = user_id;
+    std::future::pending().await
+}
+
+#[provider(compose = [fetch_permissions, fetch_missing], compose_policy = "all")]
+async fn fetch_strict_profile(user_id: u32) -> Result<usize, String> {
+    let _ = user_id;
+    let permissions: Vec<String> = __dioxus_composed_fetch_permissions_result;
+    Ok(permissions.len() + __dioxus_composed_fetch_missing_result.len())
+}
+
+#[provider(compose = [fetch_permissions, fetch_missing], compose_policy = "best_effort")]
+async fn fetch_partial_profile(user_id: u32) -> Result<(bool, bool), String> {
+    let _ = user_id;
+    Ok((
+        __dioxus_composed_fetch_permissions_result.is_some(),
+        __dioxus_composed_fetch_missing_result.is_some(),
+    ))
+}
+
+#[provider(compose = [fetch_missing, fetch_never, fetch_org_name(user_id.to_string())], compose_policy = "any")]
+async fn fetch_any_name(user_id: u32) -> Result<String, String> {
+    let _ = user_id;
+    assert!(__dioxus_composed_fetch_missing_result.is_none());
+    assert!(__dioxus_composed_fetch_never_result.is_none());
+    Ok(__dioxus_composed_fetch_org_name_result.unwrap_or_default())
+}
+
+#[provider(compose = [fetch_missing], compose_policy = "any")]
+async fn fetch_any_missing(user_id: u32) -> Result<String, String> {
+    let _ = user_id;
+    Ok(__dioxus_composed_fetch_missing_result.unwrap_or_default())
+}
+
+#[test]
+fn compose_policies_handle_failed_dependencies() {
+    use futures::executor::block_on;
+
+    assert_eq!(
+        block_on(FetchStrictProfile::call(1)),
+        Err("user 1 not found".to_string())
+    );
+    assert_eq!(block_on(FetchPartialProfile::call(1)), Ok((true, false)));
+    assert_eq!(block_on(FetchAnyName::call(42)), Ok("42".to_string()));
+    assert_eq!(
+        block_on(FetchAnyMissing::call(3)),
+        Err("user 3 not found".to_string())
+    );
+}
@@ -0,0 +1,98 @@
+//! Hooks used without `init()`
+//!
+//! Kept in their own test binary, since every other test initializes the global providers.
+
+#![cfg(not(feature = "auto-init"))]
+
+use dioxus::prelude::*;
+use dioxus_provider::ProviderRuntime;
+use dioxus_provider::global;
+use dioxus_provider::prelude::{ProviderScope, State, provider, use_provider};
+use dioxus_provider::testing;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+use tokio::time::sleep;
+
+type RenderLog = Rc<std::cell::RefCell<Vec<String>>>;
+
+static GREETING_CALLS: AtomicU32 = AtomicU32::new(0);
+
+#[provider]
+async fn greeting() -> Result<String, String> {
+    GREETING_CALLS.fetch_add(1, Ordering::SeqCst);
+    Ok("hello".to_string())
+}
+
+#[derive(Props, Clone, PartialEq)]
+struct AppProps {
+    runtime: ProviderRuntime,
+    renders: RenderLog,
+}
+
+#[allow(non_snake_case)]
+fn App(props: AppProps) -> Element {
+    let errors = props.renders.clone();
+    rsx! {
+        ErrorBoundary {
+            handle_error: move |context: ErrorContext| {
+                if let Some(error) = context.error() {
+                    errors.borrow_mut().push(format!("boundary: {error}"));
+                }
+                rsx!(div {})
+            },
+            Welcome { renders: props.renders.clone() }
+        }
+        ProviderScope { runtime: props.runtime,
+            Welcome { renders: props.renders }
+        }
+    }
+}
+
+#[component]
+fn Welcome(renders: RenderLog) -> Element {
+    let greeting = use_provider(greeting(), ());
+    if let State::Success(text) = &*greeting.read() {
+        renders.borrow_mut().push(format!("scoped: {text}"));
+    }
+    rsx!(div {})
+}
+
+#[test]
+fn missing_init_reaches_the_error_boundary_and_scopes_still_work() {
+    tokio::runtime::Runtime::new()
+        .expect("tokio runtime")
+        .block_on(async {
+            let renders = RenderLog::default();
+            let mut vdom = VirtualDom::new_with_props(
+                App,
+                AppProps {
+                    runtime: global::ProviderConfig::new().build(),
+                    renders: renders.clone(),
+                },
+            );
+            vdom.rebuild_in_place();
+            for _ in 0..3 {
+                testing::pump(&mut vdom);
+                sleep(Duration::from_millis(20)).await;
+            }
+
+            let renders = renders.borrow();
+            assert!(!global::is_initialized());
+            assert!(
+                renders
+                    .iter()
+                    .any(|render| render.starts_with("boundary:") && render.contains("init()")),
+                "{renders:?}"
+            );
+            assert!(
+                renders.contains(&"scoped: hello".to_string()),
+                "{renders:?}"
+            );
+            assert_eq!(
+                GREETING_CALLS.load(Ordering::SeqCst),
+                1,
+                "only the scoped consumer fetches"
+            );
+        });
+}
@@ -0,0 +1,514 @@
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+use dioxus_provider::ProviderRuntime;
+use dioxus_provider::global;
+use dioxus_provider::journal::{MemoryJournalStore, MutationJournal};
+use dioxus_provider::platform::network;
+use dioxus_provider::prelude::{
+    Mutation, MutationState, MutationSuspenseExt, PendingBoundary, ProviderScope, State, mutation,
+    provider, use_mutation, use_mutation_silent, use_provider,
+};
+use dioxus_provider::sync::SyncJob;
+use futures::FutureExt;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[derive(Clone, PartialEq)]
+struct SubmitOrder;
+
+impl Mutation<()> for SubmitOrder {
+    type Output = u32;
+    type Error = ();
+
+    async fn mutate(&self, _input: ()) -> Result<Self::Output, Self::Error> {
+        sleep(Duration::from_millis(10)).await;
+        Ok(7)
+    }
+}
+
+type Recorder = Rc<RefCell<Vec<&'static str>>>;
+type Submit = Rc<RefCell<Option<Rc<dyn Fn(())>>>>;
+
+#[derive(Props, Clone)]
+struct CheckoutProps {
+    recorder: Recorder,
+    submit: Submit,
+}
+
+impl PartialEq for CheckoutProps {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+#[allow(non_snake_case)]
+fn Checkout(props: CheckoutProps) -> Element {
+    let (order, submit) = use_mutation(SubmitOrder);
+    *props.submit.borrow_mut() = Some(Rc::new(submit));
+    let fallback_recorder = props.recorder.clone();
+
+    rsx! {
+        PendingBoundary {
+            fallback: rsx! {
+                Fallback { recorder: fallback_recorder }
+            },
+            CheckoutForm { order, recorder: props.recorder.clone() }
+        }
+    }
+}
+
+#[derive(Props, Clone)]
+struct FallbackProps {
+    recorder: Recorder,
+}
+
+impl PartialEq for FallbackProps {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+#[allow(non_snake_case)]
+fn Fallback(props: FallbackProps) -> Element {
+    props.recorder.borrow_mut().push("fallback");
+    rsx!(div {})
+}
+
+#[derive(Props, Clone)]
+struct CheckoutFormProps {
+    order: Signal<MutationState<u32, ()>>,
+    recorder: Recorder,
+}
+
+impl PartialEq for CheckoutFormProps {
+    fn eq(&self, other: &Self) -> bool {
+        self.order == other.order
+    }
+}
+
+#[allow(non_snake_case)]
+fn CheckoutForm(props: CheckoutFormProps) -> Element {
+    if let Err(suspended) = props.order.suspend_while_loading() {
+        props.recorder.borrow_mut().push("suspended");
+        return Err(suspended.into());
+    }
+    props.recorder.borrow_mut().push(match *props.order.read() {
+        MutationState::Success(_) => "success",
+        _ => "idle",
+    });
+    rsx!(div {})
+}
+
+#[test]
+fn pending_boundary_shows_fallback_while_mutating() {
+    tokio::runtime::Runtime::new()
+        .expect("tokio runtime")
+        .block_on(async {
+            let _ = global::init();
+            let recorder = Recorder::default();
+            let submit = Submit::default();
+
+            let mut vdom = VirtualDom::new_with_props(
+                Checkout,
+                CheckoutProps {
+                    recorder: recorder.clone(),
+                    submit: submit.clone(),
+                },
+            );
+            vdom.rebuild_in_place();
+            assert_eq!(*recorder.borrow(), ["idle"]);
+
+            let submit = submit
+                .borrow()
+                .clone()
+                .expect("checkout should have rendered");
+            vdom.runtime().in_scope(ScopeId::APP, || submit(()));
+
+            let mut mutations = NoOpMutations;
+            for _ in 0..3 {
+                while vdom.wait_for_work().now_or_never().is_some() {
+                    vdom.render_immediate(&mut mutations);
+                }
+                sleep(Duration::from_millis(20)).await;
+            }
+
+            let renders = recorder.borrow();
+            assert_eq!(renders.first(), Some(&"idle"));
+            let suspended = renders.iter().position(|render| *render == "suspended");
+            let fallback = renders.iter().position(|render| *render == "fallback");
+            assert!(suspended.is_some(), "the form should suspend: {renders:?}");
+            assert!(
+                fallback > suspended,
+                "the fallback should show: {renders:?}"
+            );
+            assert_eq!(renders.last(), Some(&"success"), "{renders:?}");
+        });
+}
+
+static SAVE_ATTEMPTS: AtomicU32 = AtomicU32::new(0);
+
+#[mutation(retry = 2, retry_delay = "10ms")]
+async fn save_draft(text: String) -> Result<usize, String> {
+    if SAVE_ATTEMPTS.fetch_add(1, Ordering::SeqCst) < 2 {
+        return Err("connection reset".to_string());
+    }
+    Ok(text.len())
+}
+
+type SaveState = Rc<RefCell<Option<Signal<MutationState<usize, String>>>>>;
+type Save = Rc<RefCell<Option<Rc<dyn Fn(String)>>>>;
+
+#[derive(Props, Clone)]
+struct EditorProps {
+    state: SaveState,
+    save: Save,
+}
+
+impl PartialEq for EditorProps {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+#[allow(non_snake_case)]
+fn Editor(props: EditorProps) -> Element {
+    let (state, save) = use_mutation(save_draft());
+    *props.state.borrow_mut() = Some(state);
+    *props.save.borrow_mut() = Some(Rc::new(save));
+    rsx!(div {})
+}
+
+#[test]
+fn failed_mutations_are_retried_before_reporting_errors() {
+    tokio::runtime::Runtime::new()
+        .expect("tokio runtime")
+        .block_on(async {
+            let _ = global::init();
+            let state = SaveState::default();
+            let save = Save::default();
+
+            let mut vdom = VirtualDom::new_with_props(
+                Editor,
+                EditorProps {
+                    state: state.clone(),
+                    save: save.clone(),
+                },
+            );
+            vdom.rebuild_in_place();
+
+            let save = save.borrow().clone().expect("editor should have rendered");
+            vdom.runtime()
+                .in_scope(ScopeId::APP, || save("hello".to_string()));
+
+            let mut mutations = NoOpMutations;
+            for _ in 0..10 {
+                while vdom.wait_for_work().now_or_never().is_some() {
+                    vdom.render_immediate(&mut mutations);
+                }
+                sleep(Duration::from_millis(10)).await;
+            }
+
+            let state = state.borrow().expect("editor should have rendered");
+            let state = vdom.in_runtime(|| state.peek().clone());
+            assert!(state == MutationState::Success(5), "{:?}", state.error());
+            assert_eq!(SAVE_ATTEMPTS.load(Ordering::SeqCst), 3);
+        });
+}
+
+static SHELF_FETCHES: AtomicU32 = AtomicU32::new(0);
+
+#[provider]
+async fn shelf_items() -> Result<Vec<u32>, String> {
+    let fetch = SHELF_FETCHES.fetch_add(1, Ordering::SeqCst);
+    Ok(if fetch == 0 { vec![1, 2] } else { vec![2] })
+}
+
+#[mutation(invalidates = [shelf_items])]
+async fn archive_shelf_item(id: u32) -> Result<(), String> {
+    let _ = id;
+    sleep(Duration::from_millis(5)).await;
+    Ok(())
+}
+
+type ShelfState = Rc<
+    RefCell<
+        Option<(
+            Signal<State<Vec<u32>, String>>,
+            Signal<MutationState<(), String>>,
+        )>,
+    >,
+>;
+type Archive = Rc<RefCell<Option<Rc<dyn Fn(u32)>>>>;
+
+#[derive(Props, Clone)]
+struct ShelfProps {
+    state: ShelfState,
+    archive: Archive,
+}
+
+impl PartialEq for ShelfProps {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+#[allow(non_snake_case)]
+fn Shelf(props: ShelfProps) -> Element {
+    let items = use_provider(shelf_items(), ());
+    let (archived, archive) = use_mutation(archive_shelf_item());
+    *props.state.borrow_mut() = Some((*items, archived));
+    *props.archive.borrow_mut() = Some(Rc::new(archive));
+    rsx!(div {})
+}
+
+#[test]
+fn unit_mutations_invalidate_without_a_payload() {
+    tokio::runtime::Runtime::new()
+        .expect("tokio runtime")
+        .block_on(async {
+            let _ = global::init();
+            let state = ShelfState::default();
+            let archive = Archive::default();
+
+            let mut vdom = VirtualDom::new_with_props(
+                Shelf,
+                ShelfProps {
+                    state: state.clone(),
+                    archive: archive.clone(),
+                },
+            );
+            vdom.rebuild_in_place();
+            let mut mutations = NoOpMutations;
+            let mut settle = async |vdom: &mut VirtualDom| {
+                for _ in 0..3 {
+                    while vdom.wait_for_work().now_or_never().is_some() {
+                        vdom.render_immediate(&mut mutations);
+                    }
+                    sleep(Duration::from_millis(20)).await;
+                }
+            };
+            settle(&mut vdom).await;
+
+            let archive = archive
+                .borrow()
+                .clone()
+                .expect("shelf should have rendered");
+            vdom.runtime().in_scope(ScopeId::APP, || archive(1));
+            settle(&mut vdom).await;
+
+            let (items, archived) = state.borrow().expect("shelf should have rendered");
+            let (items, archived) =
+                vdom.in_runtime(|| (items.peek().clone(), archived.peek().clone()));
+            assert!(
+                archived == MutationState::Success(()),
+                "{:?}",
+                archived.error()
+            );
+            assert!(items == State::Success(vec![2]), "the shelf should refetch");
+            assert_eq!(SHELF_FETCHES.load(Ordering::SeqCst), 2);
+        });
+}
+
+static TRACKED_EVENTS: AtomicU32 = AtomicU32::new(0);
+
+#[mutation]
+async fn track_event(name: String) -> Result<(), String> {
+    let _ = name;
+    sleep(Duration::from_millis(10)).await;
+    TRACKED_EVENTS.fetch_add(1, Ordering::SeqCst);
+    Ok(())
+}
+
+type Track = Rc<RefCell<Option<Rc<dyn Fn(String)>>>>;
+
+#[derive(Props, Clone)]
+struct TrackedButtonProps {
+    track: Track,
+}
+
+impl PartialEq for TrackedButtonProps {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+#[allow(non_snake_case)]
+fn TrackedButton(props: TrackedButtonProps) -> Element {
+    let track = use_mutation_silent(track_event());
+    *props.track.borrow_mut() = Some(Rc::new(track));
+    rsx!(div {})
+}
+
+#[test]
+fn silent_mutations_run_every_call() {
+    tokio::runtime::Runtime::new()
+        .expect("tokio runtime")
+        .block_on(async {
+            let _ = global::init();
+            let track = Track::default();
+
+            let mut vdom = VirtualDom::new_with_props(
+                TrackedButton,
+                TrackedButtonProps {
+                    track: track.clone(),
+                },
+            );
+            vdom.rebuild_in_place();
+
+            let track = track.borrow().clone().expect("button should have rendered");
+            vdom.runtime().in_scope(ScopeId::APP, || {
+                for event in ["open", "scroll", "buy"] {
+                    track(event.to_string());
+                }
+            });
+
+            let mut mutations = NoOpMutations;
+            for _ in 0..3 {
+                while vdom.wait_for_work().now_or_never().is_some() {
+                    vdom.render_immediate(&mut mutations);
+                }
+                sleep(Duration::from_millis(20)).await;
+            }
+
+            assert_eq!(
+                TRACKED_EVENTS.load(Ordering::SeqCst),
+                3,
+                "calls made while another is running aren't skipped"
+            );
+        });
+}
+
+#[mutation(journal)]
+async fn save_note(text: String) -> Result<(), String> {
+    sleep(Duration::from_millis(10)).await;
+    if text == "offline" {
+        return Err("no connection".to_string());
+    }
+    Ok(())
+}
+
+#[derive(Props, Clone)]
+struct NotesProps {
+    runtime: ProviderRuntime,
+    save: Track,
+}
+
+impl PartialEq for NotesProps {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+#[allow(non_snake_case)]
+fn Notes(props: NotesProps) -> Element {
+    rsx! {
+        ProviderScope { runtime: props.runtime,
+            NoteEditor { track: props.save }
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+fn NoteEditor(props: TrackedButtonProps) -> Element {
+    let save = use_mutation_silent(save_note());
+    *props.track.borrow_mut() = Some(Rc::new(save));
+    rsx!(div {})
+}
+
+#[test]
+fn journaled_mutations_stay_recorded_until_they_succeed() {
+    tokio::runtime::Runtime::new()
+        .expect("tokio runtime")
+        .block_on(async {
+            let journal = MutationJournal::new(MemoryJournalStore::new());
+            let runtime = global::ProviderConfig::new()
+                .with_mutation_journal(journal.clone())
+                .build();
+            let save = Track::default();
+
+            let mut vdom = VirtualDom::new_with_props(
+                Notes,
+                NotesProps {
+                    runtime,
+                    save: save.clone(),
+                },
+            );
+            vdom.rebuild_in_place();
+
+            let save = save.borrow().clone().expect("editor should have rendered");
+            vdom.runtime().in_scope(ScopeId::APP, || {
+                save("draft".to_string());
+                save("offline".to_string());
+            });
+            assert_eq!(
+                journal.pending().unwrap().len(),
+                2,
+                "recorded when accepted"
+            );
+
+            let mut mutations = NoOpMutations;
+            for _ in 0..3 {
+                while vdom.wait_for_work().now_or_never().is_some() {
+                    vdom.render_immediate(&mut mutations);
+                }
+                sleep(Duration::from_millis(20)).await;
+            }
+
+            let pending: Vec<String> = journal
+                .pending_inputs(&save_note())
+                .unwrap()
+                .into_iter()
+                .map(|(_, text)| text)
+                .collect();
+            assert_eq!(pending, ["offline"]);
+        });
+}
+
+#[allow(non_snake_case)]
+fn Offline() -> Element {
+    rsx!(div {})
+}
+
+#[test]
+fn started_schedulers_replay_the_journal_when_back_online() {
+    tokio::runtime::Runtime::new()
+        .expect("tokio runtime")
+        .block_on(async {
+            let journal = MutationJournal::new(MemoryJournalStore::new());
+            journal
+                .record(&save_note().id(), &"draft".to_string())
+                .unwrap();
+            let runtime = global::ProviderConfig::new().build();
+            let scheduler = runtime.sync_scheduler().clone();
+            scheduler.register(SyncJob::replay_journal(
+                "notes",
+                journal.clone(),
+                save_note(),
+            ));
+
+            network::set_online(false);
+            let mut vdom = VirtualDom::new(Offline);
+            vdom.rebuild_in_place();
+            vdom.runtime().in_scope(ScopeId::APP, || scheduler.start());
+
+            let mut mutations = NoOpMutations;
+            let mut pump = async |vdom: &mut VirtualDom| {
+                for _ in 0..3 {
+                    while vdom.wait_for_work().now_or_never().is_some() {
+                        vdom.render_immediate(&mut mutations);
+                    }
+                    sleep(Duration::from_millis(20)).await;
+                }
+            };
+            pump(&mut vdom).await;
+            assert_eq!(journal.pending().unwrap().len(), 1, "offline, nothing ran");
+
+            network::set_online(true);
+            pump(&mut vdom).await;
+            assert!(journal.pending().unwrap().is_empty());
+            assert!(scheduler.status().last_sync.is_some());
+        });
+}
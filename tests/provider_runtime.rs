@@ -1,14 +1,37 @@
 use dioxus::prelude::*;
 use dioxus_core::NoOpMutations;
+use dioxus_provider::ProviderRuntime;
+use dioxus_provider::assert_state;
+use dioxus_provider::cache::ProviderCache;
+use dioxus_provider::chaos::ChaosSettings;
+use dioxus_provider::demo::{CacheSnapshot, SnapshotRecorder};
+use dioxus_provider::dynamic::use_dynamic_provider;
+use dioxus_provider::entity::{Entity, use_entity};
+use dioxus_provider::errors::ProviderError;
 use dioxus_provider::global;
-use dioxus_provider::hooks::Provider;
-use dioxus_provider::prelude::{State, use_provider};
+use dioxus_provider::hooks::{EditHandle, FnProvider, Provider, ProviderCacheUpdater};
+use dioxus_provider::infinite::{InfiniteQuery, use_infinite_provider};
+use dioxus_provider::pause::{PauseHandle, use_pause_provider};
+use dioxus_provider::persist::{HydrateFuture, HydrationSource, LazyHydration};
+use dioxus_provider::platform::network;
+use dioxus_provider::prelude::{
+    ProviderContext, ProviderHandle, ProviderScope, ProviderStatus, State, mutation, provider,
+    use_chained_provider, use_mutation_silent, use_provider, use_provider_by_key,
+    use_provider_editable, use_provider_in, use_provider_map, use_provider_readonly,
+    use_provider_select, use_provider_suspense, use_update_provider_cache,
+};
+use dioxus_provider::route::{RouteLoader, use_route_data, use_route_loader};
+use dioxus_provider::settings::RuntimeSettings;
+use dioxus_provider::startup::{PrefetchPlan, PrefetchPriority, PrefetchProgress};
+use dioxus_provider::storage::FileStorage;
+use dioxus_provider::testing::{self, wait_for_state};
+use dioxus_provider::warm::{WarmSet, use_warm_set};
 use futures::FutureExt;
 use std::future::Future;
 use std::rc::Rc;
 use std::sync::{
     Arc,
-    atomic::{AtomicU32, Ordering},
+    atomic::{AtomicBool, AtomicU32, Ordering},
 };
 use std::time::Duration;
 use tokio::{task::yield_now, time::sleep};
@@ -128,3 +151,2773 @@ fn dedupes_parallel_consumers() {
         );
     });
 }
+
+#[derive(Clone)]
+struct SoftInvalidationProvider {
+    calls: Arc<AtomicU32>,
+}
+
+impl PartialEq for SoftInvalidationProvider {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Provider<()> for SoftInvalidationProvider {
+    type Output = u32;
+    type Error = ();
+
+    fn run(
+        &self,
+        _param: (),
+    ) -> impl std::future::Future<Output = Result<Self::Output, Self::Error>> {
+        let calls = self.calls.clone();
+        async move {
+            let value = calls.fetch_add(1, Ordering::SeqCst) + 1;
+            sleep(Duration::from_millis(10)).await;
+            Ok(value)
+        }
+    }
+}
+
+#[derive(Props, Clone, PartialEq)]
+struct SoftConsumerProps {
+    provider: SoftInvalidationProvider,
+    recorder: Rc<std::cell::RefCell<Vec<State<u32, ()>>>>,
+}
+
+#[allow(non_snake_case)]
+fn SoftConsumer(props: SoftConsumerProps) -> Element {
+    let state = use_provider(props.provider.clone(), ());
+    let record = props.recorder.clone();
+    use_effect(move || {
+        record.borrow_mut().push(state.read().clone());
+    });
+    rsx!(div {})
+}
+
+#[test]
+fn soft_invalidation_keeps_data_while_refetching() {
+    block_on_test(async {
+        let _ = global::init();
+        let calls = Arc::new(AtomicU32::new(0));
+        let provider = SoftInvalidationProvider {
+            calls: calls.clone(),
+        };
+        let cache_key = provider.id(&());
+        let recorder = Rc::new(std::cell::RefCell::new(Vec::new()));
+
+        let mut vdom = VirtualDom::new_with_props(
+            SoftConsumer,
+            SoftConsumerProps {
+                provider,
+                recorder: recorder.clone(),
+            },
+        );
+        vdom.rebuild_in_place();
+        let mut mutations = NoOpMutations;
+        let mut pump = async |vdom: &mut VirtualDom| {
+            for _ in 0..3 {
+                while vdom.wait_for_work().now_or_never().is_some() {
+                    vdom.render_immediate(&mut mutations);
+                }
+                sleep(Duration::from_millis(20)).await;
+            }
+        };
+        pump(&mut vdom).await;
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let handles = global::get_global_runtime_handles().unwrap();
+        assert!(handles.cache.mark_stale(&cache_key));
+        handles.refresh_registry.trigger_refresh(&cache_key);
+        pump(&mut vdom).await;
+
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        let states = recorder.borrow();
+        let first_success = states
+            .iter()
+            .position(|state| matches!(state, State::Success(_)))
+            .expect("initial fetch should succeed");
+        assert!(
+            states[first_success..]
+                .iter()
+                .all(|state| matches!(state, State::Success(_))),
+            "soft invalidation should never show a loading state"
+        );
+        assert!(matches!(states.last(), Some(State::Success(2))));
+    });
+}
+
+static RELOAD_CALLS: AtomicU32 = AtomicU32::new(0);
+
+#[provider]
+async fn reloaded_count() -> Result<u32, ()> {
+    let value = RELOAD_CALLS.fetch_add(1, Ordering::SeqCst) + 1;
+    sleep(Duration::from_millis(10)).await;
+    Ok(value)
+}
+
+#[derive(Props, Clone, PartialEq)]
+struct ReloadConsumerProps {
+    recorder: Rc<std::cell::RefCell<Vec<State<u32, ()>>>>,
+}
+
+#[allow(non_snake_case)]
+fn ReloadConsumer(props: ReloadConsumerProps) -> Element {
+    let state = use_provider(reloaded_count(), ());
+    let record = props.recorder.clone();
+    use_effect(move || {
+        record.borrow_mut().push(state.read().clone());
+    });
+    rsx!(div {})
+}
+
+#[test]
+fn invalidation_reloads_with_previous_data() {
+    block_on_test(async {
+        let _ = global::init();
+        let recorder = Rc::new(std::cell::RefCell::new(Vec::new()));
+        let mut vdom = VirtualDom::new_with_props(
+            ReloadConsumer,
+            ReloadConsumerProps {
+                recorder: recorder.clone(),
+            },
+        );
+        vdom.rebuild_in_place();
+        let mut mutations = NoOpMutations;
+        let mut pump = async |vdom: &mut VirtualDom| {
+            for _ in 0..3 {
+                while vdom.wait_for_work().now_or_never().is_some() {
+                    vdom.render_immediate(&mut mutations);
+                }
+                sleep(Duration::from_millis(20)).await;
+            }
+        };
+        pump(&mut vdom).await;
+        assert!(matches!(recorder.borrow().last(), Some(State::Success(1))));
+
+        let handles = global::get_global_runtime_handles().unwrap();
+        let cache_key = reloaded_count().id(&());
+        handles.cache.invalidate(&cache_key);
+        handles.refresh_registry.trigger_refresh(&cache_key);
+        pump(&mut vdom).await;
+
+        let states = recorder.borrow();
+        let first_success = states
+            .iter()
+            .position(|state| matches!(state, State::Success(1)))
+            .unwrap();
+        let after = &states[first_success + 1..];
+        assert!(
+            after
+                .iter()
+                .any(|state| matches!(state, State::Reloading { previous: 1, .. })),
+            "the refetch should keep showing the previous data"
+        );
+        assert!(!after.iter().any(State::is_loading));
+        assert!(matches!(states.last(), Some(State::Success(2))));
+    });
+}
+
+#[derive(Clone, PartialEq)]
+struct SeedProvider;
+
+impl Provider<()> for SeedProvider {
+    type Output = u32;
+    type Error = ();
+
+    async fn run(&self, _param: ()) -> Result<Self::Output, Self::Error> {
+        sleep(Duration::from_millis(10)).await;
+        Ok(3)
+    }
+}
+
+#[derive(Clone, PartialEq)]
+struct ScaleProvider;
+
+impl Provider<u32> for ScaleProvider {
+    type Output = u32;
+    type Error = ();
+
+    async fn run(&self, value: u32) -> Result<Self::Output, Self::Error> {
+        sleep(Duration::from_millis(10)).await;
+        Ok(value * 10)
+    }
+}
+
+#[derive(Props, Clone, PartialEq)]
+struct ChainedConsumerProps {
+    recorder: Rc<std::cell::RefCell<Vec<State<u32, ()>>>>,
+}
+
+#[allow(non_snake_case)]
+fn ChainedConsumer(props: ChainedConsumerProps) -> Element {
+    let state = use_chained_provider(SeedProvider, (), |seed: &u32| (ScaleProvider, *seed));
+    let record = props.recorder.clone();
+    use_effect(move || {
+        record.borrow_mut().push(state.read().clone());
+    });
+    rsx!(div {})
+}
+
+#[test]
+fn chained_provider_waits_for_first_output() {
+    block_on_test(async {
+        let _ = global::init();
+        let recorder = Rc::new(std::cell::RefCell::new(Vec::new()));
+
+        let mut vdom = VirtualDom::new_with_props(
+            ChainedConsumer,
+            ChainedConsumerProps {
+                recorder: recorder.clone(),
+            },
+        );
+        vdom.rebuild_in_place();
+        let mut mutations = NoOpMutations;
+        for _ in 0..4 {
+            while vdom.wait_for_work().now_or_never().is_some() {
+                vdom.render_immediate(&mut mutations);
+            }
+            sleep(Duration::from_millis(20)).await;
+        }
+
+        let states = recorder.borrow();
+        assert!(matches!(states.first(), Some(State::Loading { .. })));
+        assert!(
+            states
+                .iter()
+                .all(|state| matches!(state, State::Loading { .. } | State::Success(30))),
+            "the first provider's output should never leak through"
+        );
+        assert!(matches!(states.last(), Some(State::Success(30))));
+    });
+}
+
+#[derive(Clone, PartialEq)]
+struct TelemetryProvider;
+
+impl Provider<()> for TelemetryProvider {
+    type Output = u32;
+    type Error = ();
+
+    async fn run(&self, _param: ()) -> Result<Self::Output, Self::Error> {
+        Ok(7)
+    }
+}
+
+#[allow(non_snake_case)]
+fn TelemetryConsumer() -> Element {
+    let _state = use_provider_in("telemetry", TelemetryProvider, ());
+    rsx!(div {})
+}
+
+#[test]
+fn named_runtimes_keep_separate_caches() {
+    block_on_test(async {
+        let _ = global::init();
+        global::init_named("telemetry", global::ProviderConfig::new()).unwrap();
+
+        let mut vdom = VirtualDom::new(TelemetryConsumer);
+        vdom.rebuild_in_place();
+        let mut mutations = NoOpMutations;
+        for _ in 0..3 {
+            while vdom.wait_for_work().now_or_never().is_some() {
+                vdom.render_immediate(&mut mutations);
+            }
+            sleep(Duration::from_millis(10)).await;
+        }
+
+        let cache_key = TelemetryProvider.id(&());
+        let named = global::get_named_runtime("telemetry").unwrap();
+        assert_eq!(
+            named.cache().get::<Result<u32, ()>>(&cache_key),
+            Some(Ok(7))
+        );
+        let global_cache = global::get_global_cache().unwrap();
+        assert_eq!(global_cache.get::<Result<u32, ()>>(&cache_key), None);
+    });
+}
+
+#[derive(Clone)]
+struct HandleProvider {
+    calls: Arc<AtomicU32>,
+}
+
+impl PartialEq for HandleProvider {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Provider<()> for HandleProvider {
+    type Output = u32;
+    type Error = ();
+
+    async fn run(&self, _param: ()) -> Result<Self::Output, Self::Error> {
+        let value = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+        sleep(Duration::from_millis(10)).await;
+        Ok(value)
+    }
+}
+
+#[derive(Props, Clone, PartialEq)]
+struct HandleConsumerProps {
+    provider: HandleProvider,
+    handle: Rc<std::cell::RefCell<Option<ProviderHandle<u32, ()>>>>,
+}
+
+#[allow(non_snake_case)]
+fn HandleConsumer(props: HandleConsumerProps) -> Element {
+    let handle = use_provider(props.provider.clone(), ());
+    *props.handle.borrow_mut() = Some(handle);
+    rsx!(div {})
+}
+
+#[test]
+fn provider_handle_refetches_in_place() {
+    block_on_test(async {
+        let _ = global::init();
+        let calls = Arc::new(AtomicU32::new(0));
+        let provider = HandleProvider {
+            calls: calls.clone(),
+        };
+        let slot = Rc::new(std::cell::RefCell::new(None));
+
+        let mut vdom = VirtualDom::new_with_props(
+            HandleConsumer,
+            HandleConsumerProps {
+                provider,
+                handle: slot.clone(),
+            },
+        );
+        vdom.rebuild_in_place();
+        testing::pump(&mut vdom);
+
+        let handle = slot.borrow().expect("consumer should have rendered");
+        let timeout = Duration::from_secs(1);
+        wait_for_state(&mut vdom, handle, |state| state.is_success(), timeout)
+            .await
+            .unwrap();
+        vdom.in_runtime(|| {
+            assert_eq!(handle.status(), ProviderStatus::Success);
+            handle.refetch();
+        });
+
+        let state = wait_for_state(&mut vdom, handle, |state| state.data() == Some(&2), timeout)
+            .await
+            .unwrap();
+        assert_state!(state, State::Success(2));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    });
+}
+
+type EntryRecorder = Rc<std::cell::RefCell<Vec<Option<Result<u32, ()>>>>>;
+
+#[derive(Props, Clone, PartialEq)]
+struct KeyObserverProps {
+    cache_key: String,
+    recorder: EntryRecorder,
+}
+
+#[allow(non_snake_case)]
+fn KeyObserver(props: KeyObserverProps) -> Element {
+    let entry = use_provider_by_key::<u32, ()>(props.cache_key.clone());
+    let record = props.recorder.clone();
+    use_effect(move || {
+        record.borrow_mut().push(*entry.read());
+    });
+    rsx!(div {})
+}
+
+#[test]
+fn observes_cache_entries_by_key() {
+    block_on_test(async {
+        let _ = global::init();
+        let cache_key = "observed-by-key".to_string();
+        let recorder = Rc::new(std::cell::RefCell::new(Vec::new()));
+
+        let mut vdom = VirtualDom::new_with_props(
+            KeyObserver,
+            KeyObserverProps {
+                cache_key: cache_key.clone(),
+                recorder: recorder.clone(),
+            },
+        );
+        vdom.rebuild_in_place();
+        let mut mutations = NoOpMutations;
+        let mut pump = async |vdom: &mut VirtualDom| {
+            for _ in 0..3 {
+                while vdom.wait_for_work().now_or_never().is_some() {
+                    vdom.render_immediate(&mut mutations);
+                }
+                yield_now().await;
+            }
+        };
+        pump(&mut vdom).await;
+
+        let handles = global::get_global_runtime_handles().unwrap();
+        handles.cache.set(cache_key.clone(), Ok::<u32, ()>(5));
+        handles.refresh_registry.trigger_refresh(&cache_key);
+        pump(&mut vdom).await;
+
+        assert_eq!(*recorder.borrow(), [None, Some(Ok(5))]);
+    });
+}
+
+#[derive(Clone)]
+struct ServedUserProvider {
+    calls: Arc<AtomicU32>,
+}
+
+impl PartialEq for ServedUserProvider {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Provider<u32> for ServedUserProvider {
+    type Output = u32;
+    type Error = ();
+
+    async fn run(&self, id: u32) -> Result<Self::Output, Self::Error> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        Ok(id)
+    }
+
+    fn serves(&self) -> &'static [&'static str] {
+        &["served-user"]
+    }
+}
+
+#[derive(Props, Clone, PartialEq)]
+struct ServedUserConsumerProps {
+    provider: ServedUserProvider,
+}
+
+#[allow(non_snake_case)]
+fn ServedUserConsumer(props: ServedUserConsumerProps) -> Element {
+    let _user = use_provider(props.provider.clone(), (42,));
+    rsx!(div {})
+}
+
+#[test]
+fn invalidates_providers_serving_an_entity() {
+    block_on_test(async {
+        let _ = global::init();
+        let calls = Arc::new(AtomicU32::new(0));
+        let provider = ServedUserProvider {
+            calls: calls.clone(),
+        };
+
+        let mut vdom =
+            VirtualDom::new_with_props(ServedUserConsumer, ServedUserConsumerProps { provider });
+        vdom.rebuild_in_place();
+        let mut mutations = NoOpMutations;
+        let mut pump = async |vdom: &mut VirtualDom| {
+            for _ in 0..3 {
+                while vdom.wait_for_work().now_or_never().is_some() {
+                    vdom.render_immediate(&mut mutations);
+                }
+                sleep(Duration::from_millis(10)).await;
+            }
+        };
+        pump(&mut vdom).await;
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let runtime = global::get_global_runtime().unwrap();
+        assert!(
+            runtime
+                .invalidate_entity("served-user", Some(&7))
+                .is_empty()
+        );
+        assert_eq!(runtime.invalidate_entity("served-user", Some(&42)).len(), 1);
+        pump(&mut vdom).await;
+
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    });
+}
+
+#[derive(Clone, PartialEq)]
+struct ObservedProvider;
+
+impl Provider<()> for ObservedProvider {
+    type Output = u32;
+    type Error = ();
+
+    async fn run(&self, _param: ()) -> Result<Self::Output, Self::Error> {
+        Ok(1)
+    }
+}
+
+#[allow(non_snake_case)]
+fn ObservedConsumer() -> Element {
+    let _value = use_provider(ObservedProvider, ());
+    rsx!(div {})
+}
+
+#[allow(non_snake_case)]
+fn ObservedApp() -> Element {
+    rsx! {
+        ObservedConsumer {}
+        ObservedConsumer {}
+    }
+}
+
+#[test]
+fn cache_stats_count_observers_per_key() {
+    block_on_test(async {
+        let _ = global::init();
+        let runtime = global::get_global_runtime().unwrap();
+        let cache_key = ObservedProvider.id(&());
+        runtime.cache().set("unobserved-entry".to_string(), 0u32);
+
+        let mut vdom = VirtualDom::new(ObservedApp);
+        vdom.rebuild_in_place();
+        let mut mutations = NoOpMutations;
+        for _ in 0..3 {
+            while vdom.wait_for_work().now_or_never().is_some() {
+                vdom.render_immediate(&mut mutations);
+            }
+            yield_now().await;
+        }
+
+        let stats = runtime.cache_stats();
+        assert_eq!(stats.observers.get(&cache_key), Some(&2));
+        assert_eq!(stats.observers.get("unobserved-entry"), Some(&0));
+        assert!(stats.unobserved_entries() >= 1);
+    });
+}
+
+#[derive(Clone)]
+struct PersistedProvider {
+    calls: Arc<AtomicU32>,
+}
+
+impl PartialEq for PersistedProvider {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Provider<()> for PersistedProvider {
+    type Output = u32;
+    type Error = ();
+
+    async fn run(&self, _param: ()) -> Result<Self::Output, Self::Error> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        Ok(1)
+    }
+}
+
+struct PersistedStorage {
+    key: String,
+}
+
+impl HydrationSource for PersistedStorage {
+    fn keys(&self) -> Result<Vec<String>, ProviderError> {
+        Ok(vec![self.key.clone()])
+    }
+
+    fn hydrate(&self, key: &str, cache: ProviderCache) -> HydrateFuture {
+        let key = key.to_string();
+        Box::pin(async move {
+            sleep(Duration::from_millis(10)).await;
+            Ok(cache.set(key, Ok::<u32, ()>(99)))
+        })
+    }
+}
+
+type HydrationRecorder = Rc<std::cell::RefCell<Vec<(State<u32, ()>, bool)>>>;
+
+#[derive(Props, Clone, PartialEq)]
+struct PersistedConsumerProps {
+    provider: PersistedProvider,
+    recorder: HydrationRecorder,
+}
+
+#[allow(non_snake_case)]
+fn PersistedConsumer(props: PersistedConsumerProps) -> Element {
+    let state = use_provider_in("hydrated", props.provider.clone(), ());
+    let record = props.recorder.clone();
+    use_effect(move || {
+        record
+            .borrow_mut()
+            .push((state.read().clone(), state.is_hydrating()));
+    });
+    rsx!(div {})
+}
+
+#[test]
+fn hydrates_persisted_entries_on_first_use() {
+    block_on_test(async {
+        let calls = Arc::new(AtomicU32::new(0));
+        let provider = PersistedProvider {
+            calls: calls.clone(),
+        };
+        let storage = PersistedStorage {
+            key: provider.id(&()),
+        };
+        global::init_named(
+            "hydrated",
+            global::ProviderConfig::new().with_lazy_hydration(LazyHydration::new(storage)),
+        )
+        .unwrap();
+        let recorder = HydrationRecorder::default();
+
+        let mut vdom = VirtualDom::new_with_props(
+            PersistedConsumer,
+            PersistedConsumerProps {
+                provider,
+                recorder: recorder.clone(),
+            },
+        );
+        vdom.rebuild_in_place();
+        let mut mutations = NoOpMutations;
+        for _ in 0..3 {
+            while vdom.wait_for_work().now_or_never().is_some() {
+                vdom.render_immediate(&mut mutations);
+            }
+            sleep(Duration::from_millis(20)).await;
+        }
+
+        assert_eq!(
+            calls.load(Ordering::SeqCst),
+            0,
+            "hydrated data needs no fetch"
+        );
+        let states = recorder.borrow();
+        assert!(
+            states
+                .iter()
+                .any(|(state, hydrating)| state.is_loading() && *hydrating)
+        );
+        assert!(matches!(states.last(), Some((State::Success(99), false))));
+    });
+}
+
+#[derive(Clone)]
+struct ChaosProvider {
+    calls: Arc<AtomicU32>,
+}
+
+impl PartialEq for ChaosProvider {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Provider<()> for ChaosProvider {
+    type Output = u32;
+    type Error = ();
+
+    async fn run(&self, _param: ()) -> Result<Self::Output, Self::Error> {
+        Ok(self.calls.fetch_add(1, Ordering::SeqCst) + 1)
+    }
+}
+
+#[derive(Props, Clone, PartialEq)]
+struct ChaosConsumerProps {
+    provider: ChaosProvider,
+    handle: Rc<std::cell::RefCell<Option<ProviderHandle<u32, ()>>>>,
+}
+
+#[allow(non_snake_case)]
+fn ChaosConsumer(props: ChaosConsumerProps) -> Element {
+    let handle = use_provider_in("chaos", props.provider.clone(), ());
+    *props.handle.borrow_mut() = Some(handle);
+    rsx!(div {})
+}
+
+#[test]
+fn chaos_drops_and_duplicates_responses() {
+    block_on_test(async {
+        let dropping = ChaosSettings::new().with_drop_rate(1.0);
+        global::ProviderConfig::new()
+            .with_settings(RuntimeSettings::new().with_chaos(dropping))
+            .init_named("chaos")
+            .unwrap();
+        let runtime = global::get_named_runtime("chaos").unwrap();
+        let calls = Arc::new(AtomicU32::new(0));
+        let provider = ChaosProvider {
+            calls: calls.clone(),
+        };
+        let cache_key = provider.id(&());
+        let slot = Rc::new(std::cell::RefCell::new(None));
+
+        let mut vdom = VirtualDom::new_with_props(
+            ChaosConsumer,
+            ChaosConsumerProps {
+                provider,
+                handle: slot.clone(),
+            },
+        );
+        vdom.rebuild_in_place();
+        testing::pump(&mut vdom);
+        let handle = slot.borrow().expect("consumer should have rendered");
+
+        // The response is lost, so the component keeps loading
+        let timeout = Duration::from_millis(100);
+        let stuck = wait_for_state(&mut vdom, handle, |state| state.is_success(), timeout).await;
+        assert_state!(stuck.unwrap_err().last, State::Loading { .. });
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // A duplicated response overwrites newer data once it arrives
+        let duplicating = ChaosSettings::new()
+            .with_duplicate_rate(1.0)
+            .with_max_delay(Duration::from_millis(100));
+        runtime.reconfigure(runtime.settings().with_chaos(duplicating));
+        vdom.in_runtime(|| handle.refetch());
+        let timeout = Duration::from_secs(1);
+        wait_for_state(&mut vdom, handle, |state| state.data() == Some(&2), timeout)
+            .await
+            .unwrap();
+        runtime.cache().set(cache_key.clone(), Ok::<u32, ()>(99));
+        runtime.refresh_registry().trigger_refresh(&cache_key);
+        wait_for_state(
+            &mut vdom,
+            handle,
+            |state| state.data() == Some(&99),
+            timeout,
+        )
+        .await
+        .unwrap();
+        wait_for_state(&mut vdom, handle, |state| state.data() == Some(&2), timeout)
+            .await
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    });
+}
+
+static NEVER_CACHED_CALLS: AtomicU32 = AtomicU32::new(0);
+static SAME_CACHED_CALLS: AtomicU32 = AtomicU32::new(0);
+
+/// Fails on the first call only
+fn flaky(calls: &AtomicU32) -> Result<u32, String> {
+    match calls.fetch_add(1, Ordering::SeqCst) {
+        0 => Err("backend hiccup".to_string()),
+        call => Ok(call),
+    }
+}
+
+#[provider(cache_errors = "never")]
+async fn fetch_never_cached() -> Result<u32, String> {
+    flaky(&NEVER_CACHED_CALLS)
+}
+
+#[provider]
+async fn fetch_same_cached() -> Result<u32, String> {
+    flaky(&SAME_CACHED_CALLS)
+}
+
+#[derive(Props, Clone, PartialEq)]
+struct FlakyConsumerProps {
+    never_cached: bool,
+    handle: Rc<std::cell::RefCell<Option<ProviderHandle<u32, String>>>>,
+}
+
+#[allow(non_snake_case)]
+fn FlakyConsumer(props: FlakyConsumerProps) -> Element {
+    let handle = if props.never_cached {
+        use_provider(fetch_never_cached(), ())
+    } else {
+        use_provider(fetch_same_cached(), ())
+    };
+    *props.handle.borrow_mut() = Some(handle);
+    rsx!(div {})
+}
+
+async fn mount_flaky_consumer(never_cached: bool) -> (VirtualDom, State<u32, String>) {
+    let slot = Rc::new(std::cell::RefCell::new(None));
+    let mut vdom = VirtualDom::new_with_props(
+        FlakyConsumer,
+        FlakyConsumerProps {
+            never_cached,
+            handle: slot.clone(),
+        },
+    );
+    vdom.rebuild_in_place();
+    testing::pump(&mut vdom);
+    let handle = slot.borrow().expect("consumer should have rendered");
+    let state = wait_for_state(
+        &mut vdom,
+        handle,
+        |state| !state.is_loading(),
+        Duration::from_secs(1),
+    )
+    .await
+    .unwrap();
+    (vdom, state)
+}
+
+#[test]
+fn errors_that_are_never_cached_refetch_for_new_consumers() {
+    block_on_test(async {
+        let _ = global::init();
+
+        let (_first, state) = mount_flaky_consumer(true).await;
+        assert_state!(state, State::Error(_));
+        let (_second, state) = mount_flaky_consumer(true).await;
+        assert_state!(state, State::Success(1));
+        assert_eq!(NEVER_CACHED_CALLS.load(Ordering::SeqCst), 2);
+
+        let (_first, state) = mount_flaky_consumer(false).await;
+        assert_state!(state, State::Error(_));
+        let (_second, state) = mount_flaky_consumer(false).await;
+        assert_state!(state, State::Error(_));
+        assert_eq!(SAME_CACHED_CALLS.load(Ordering::SeqCst), 1);
+    });
+}
+
+static LOOKUP_CALLS: AtomicU32 = AtomicU32::new(0);
+
+#[provider(
+    cache_errors = "never",
+    cache_not_found = "1h",
+    not_found = |error: &String| error.starts_with("missing")
+)]
+async fn lookup_account(id: u32) -> Result<u32, String> {
+    LOOKUP_CALLS.fetch_add(1, Ordering::SeqCst);
+    match id {
+        0 => Err("missing account 0".to_string()),
+        _ => Err("backend hiccup".to_string()),
+    }
+}
+
+#[derive(Props, Clone, PartialEq)]
+struct LookupConsumerProps {
+    id: u32,
+    handle: Rc<std::cell::RefCell<Option<ProviderHandle<u32, String>>>>,
+}
+
+#[allow(non_snake_case)]
+fn LookupConsumer(props: LookupConsumerProps) -> Element {
+    let handle = use_provider(lookup_account(), props.id);
+    *props.handle.borrow_mut() = Some(handle);
+    rsx!(div {})
+}
+
+#[test]
+fn not_found_errors_are_cached_separately() {
+    block_on_test(async {
+        let _ = global::init();
+        let lookup = async |id| {
+            let slot = Rc::new(std::cell::RefCell::new(None));
+            let mut vdom = VirtualDom::new_with_props(
+                LookupConsumer,
+                LookupConsumerProps {
+                    id,
+                    handle: slot.clone(),
+                },
+            );
+            vdom.rebuild_in_place();
+            testing::pump(&mut vdom);
+            let handle = slot.borrow().expect("consumer should have rendered");
+            let timeout = Duration::from_secs(1);
+            wait_for_state(&mut vdom, handle, |state| state.is_error(), timeout)
+                .await
+                .unwrap();
+        };
+
+        lookup(0).await;
+        lookup(0).await;
+        assert_eq!(LOOKUP_CALLS.load(Ordering::SeqCst), 1);
+
+        lookup(1).await;
+        lookup(1).await;
+        assert_eq!(LOOKUP_CALLS.load(Ordering::SeqCst), 3);
+    });
+}
+
+static TICKER_CALLS: AtomicU32 = AtomicU32::new(0);
+
+#[provider(interval = "300ms")]
+async fn ticker() -> Result<u32, ()> {
+    Ok(TICKER_CALLS.fetch_add(1, Ordering::SeqCst) + 1)
+}
+
+type PausedHandles = (ProviderHandle<u32, ()>, PauseHandle);
+
+#[derive(Props, Clone, PartialEq)]
+struct PausedConsumerProps {
+    handles: Rc<std::cell::RefCell<Option<PausedHandles>>>,
+}
+
+#[allow(non_snake_case)]
+fn PausedConsumer(props: PausedConsumerProps) -> Element {
+    let state = use_provider(ticker(), ());
+    let freeze = use_pause_provider(ticker(), ());
+    *props.handles.borrow_mut() = Some((state, freeze));
+    rsx!(div {})
+}
+
+#[test]
+fn paused_providers_skip_intervals_and_revalidate_on_resume() {
+    block_on_test(async {
+        let _ = global::init();
+        let slot = Rc::new(std::cell::RefCell::new(None));
+        let mut vdom = VirtualDom::new_with_props(
+            PausedConsumer,
+            PausedConsumerProps {
+                handles: slot.clone(),
+            },
+        );
+        vdom.rebuild_in_place();
+        testing::pump(&mut vdom);
+        let (state, freeze) = slot.borrow().expect("consumer should have rendered");
+        let timeout = Duration::from_secs(1);
+        wait_for_state(&mut vdom, state, |state| state.is_success(), timeout)
+            .await
+            .unwrap();
+
+        vdom.in_runtime(|| freeze.pause());
+        let paused_for = Duration::from_millis(700);
+        let _ = wait_for_state(&mut vdom, state, |_| false, paused_for).await;
+        assert_eq!(TICKER_CALLS.load(Ordering::SeqCst), 1);
+        assert!(vdom.in_runtime(|| freeze.is_paused()));
+
+        vdom.in_runtime(|| freeze.resume());
+        let state = wait_for_state(&mut vdom, state, |state| state.data() == Some(&2), timeout)
+            .await
+            .unwrap();
+        assert_state!(state, State::Success(2));
+        assert_eq!(TICKER_CALLS.load(Ordering::SeqCst), 2);
+    });
+}
+
+static INBOX_CALLS: AtomicU32 = AtomicU32::new(0);
+
+#[provider(refetch_on_focus = true)]
+async fn unread_count() -> Result<u32, ()> {
+    Ok(INBOX_CALLS.fetch_add(1, Ordering::SeqCst) + 1)
+}
+
+#[derive(Props, Clone, PartialEq)]
+struct InboxConsumerProps {
+    handle: Rc<std::cell::RefCell<Option<ProviderHandle<u32, ()>>>>,
+}
+
+#[allow(non_snake_case)]
+fn InboxConsumer(props: InboxConsumerProps) -> Element {
+    let state = use_provider(unread_count(), ());
+    *props.handle.borrow_mut() = Some(state);
+    rsx!(div {})
+}
+
+#[test]
+fn focus_revalidates_providers_marked_refetch_on_focus() {
+    block_on_test(async {
+        let _ = global::init();
+        let slot = Rc::new(std::cell::RefCell::new(None));
+        let mut vdom = VirtualDom::new_with_props(
+            InboxConsumer,
+            InboxConsumerProps {
+                handle: slot.clone(),
+            },
+        );
+        vdom.rebuild_in_place();
+        testing::pump(&mut vdom);
+        let state = slot.borrow().expect("consumer should have rendered");
+        let timeout = Duration::from_secs(1);
+        let state = wait_for_state(&mut vdom, state, |state| state.is_success(), timeout)
+            .await
+            .unwrap();
+        assert_state!(state, State::Success(1));
+
+        global::get_global_refresh_registry()
+            .unwrap()
+            .notify_focus();
+        let state = slot.borrow().expect("consumer should have rendered");
+        let state = wait_for_state(&mut vdom, state, |state| state.data() == Some(&2), timeout)
+            .await
+            .unwrap();
+        assert_state!(state, State::Success(2));
+        assert_eq!(INBOX_CALLS.load(Ordering::SeqCst), 2);
+    });
+}
+
+static BACKEND_REACHABLE: AtomicBool = AtomicBool::new(false);
+
+#[provider(refetch_on_reconnect)]
+async fn account_balance() -> Result<u32, String> {
+    if BACKEND_REACHABLE.load(Ordering::SeqCst) {
+        Ok(120)
+    } else {
+        Err("network unreachable".to_string())
+    }
+}
+
+#[derive(Props, Clone, PartialEq)]
+struct BalanceConsumerProps {
+    handle: Rc<std::cell::RefCell<Option<ProviderHandle<u32, String>>>>,
+}
+
+#[allow(non_snake_case)]
+fn BalanceConsumer(props: BalanceConsumerProps) -> Element {
+    let state = use_provider(account_balance(), ());
+    *props.handle.borrow_mut() = Some(state);
+    rsx!(div {})
+}
+
+#[test]
+fn reconnecting_refetches_failed_providers_marked_refetch_on_reconnect() {
+    block_on_test(async {
+        let _ = global::init();
+        network::set_online(false);
+        let slot = Rc::new(std::cell::RefCell::new(None));
+        let mut vdom = VirtualDom::new_with_props(
+            BalanceConsumer,
+            BalanceConsumerProps {
+                handle: slot.clone(),
+            },
+        );
+        vdom.rebuild_in_place();
+        testing::pump(&mut vdom);
+        let state = slot.borrow().expect("consumer should have rendered");
+        let timeout = Duration::from_secs(1);
+        let state = wait_for_state(&mut vdom, state, |state| state.is_error(), timeout)
+            .await
+            .unwrap();
+        assert_state!(state, State::Error(_));
+
+        BACKEND_REACHABLE.store(true, Ordering::SeqCst);
+        network::set_online(true);
+        let state = slot.borrow().expect("consumer should have rendered");
+        let state = wait_for_state(&mut vdom, state, |state| state.is_success(), timeout)
+            .await
+            .unwrap();
+        assert_state!(state, State::Success(120));
+    });
+}
+
+static GREETING_CALLS: AtomicU32 = AtomicU32::new(0);
+
+#[provider(persist)]
+async fn persisted_greeting() -> Result<String, String> {
+    GREETING_CALLS.fetch_add(1, Ordering::SeqCst);
+    Ok("hello".to_string())
+}
+
+#[derive(Props, Clone, PartialEq)]
+struct GreetingConsumerProps {
+    runtime: &'static str,
+    handle: Rc<std::cell::RefCell<Option<ProviderHandle<String, String>>>>,
+}
+
+#[allow(non_snake_case)]
+fn GreetingConsumer(props: GreetingConsumerProps) -> Element {
+    let state = use_provider_in(props.runtime, persisted_greeting(), ());
+    *props.handle.borrow_mut() = Some(state);
+    rsx!(div {})
+}
+
+async fn mount_greeting(runtime: &'static str) -> State<String, String> {
+    let slot = Rc::new(std::cell::RefCell::new(None));
+    let mut vdom = VirtualDom::new_with_props(
+        GreetingConsumer,
+        GreetingConsumerProps {
+            runtime,
+            handle: slot.clone(),
+        },
+    );
+    vdom.rebuild_in_place();
+    testing::pump(&mut vdom);
+    let state = slot.borrow().expect("consumer should have rendered");
+    wait_for_state(
+        &mut vdom,
+        state,
+        |state| state.is_success(),
+        Duration::from_secs(1),
+    )
+    .await
+    .unwrap()
+}
+
+#[test]
+fn persisted_providers_restore_from_storage_in_a_new_runtime() {
+    block_on_test(async {
+        let dir = std::env::temp_dir().join(format!("provider-persist-{}", std::process::id()));
+        for runtime in ["persist-first", "persist-second"] {
+            global::ProviderConfig::new()
+                .with_storage(FileStorage::new(&dir))
+                .init_named(runtime)
+                .unwrap();
+        }
+
+        let state = mount_greeting("persist-first").await;
+        assert_state!(state, State::Success(ref greeting) if greeting == "hello");
+        assert_eq!(GREETING_CALLS.load(Ordering::SeqCst), 1);
+        sleep(Duration::from_millis(20)).await;
+
+        let state = mount_greeting("persist-second").await;
+        assert_state!(state, State::Success(ref greeting) if greeting == "hello");
+        assert_eq!(
+            GREETING_CALLS.load(Ordering::SeqCst),
+            1,
+            "restored entries need no fetch"
+        );
+        let _ = std::fs::remove_dir_all(dir);
+    });
+}
+
+static HEADLINE_CALLS: AtomicU32 = AtomicU32::new(0);
+
+#[provider(persist)]
+async fn demo_headline() -> Result<String, String> {
+    HEADLINE_CALLS.fetch_add(1, Ordering::SeqCst);
+    Ok("breaking news".to_string())
+}
+
+#[derive(Props, Clone, PartialEq)]
+struct HeadlineConsumerProps {
+    runtime: &'static str,
+    handle: Rc<std::cell::RefCell<Option<ProviderHandle<String, String>>>>,
+}
+
+#[allow(non_snake_case)]
+fn HeadlineConsumer(props: HeadlineConsumerProps) -> Element {
+    let state = use_provider_in(props.runtime, demo_headline(), ());
+    *props.handle.borrow_mut() = Some(state);
+    rsx!(div {})
+}
+
+async fn mount_headline(runtime: &'static str) -> State<String, String> {
+    let slot = Rc::new(std::cell::RefCell::new(None));
+    let mut vdom = VirtualDom::new_with_props(
+        HeadlineConsumer,
+        HeadlineConsumerProps {
+            runtime,
+            handle: slot.clone(),
+        },
+    );
+    vdom.rebuild_in_place();
+    testing::pump(&mut vdom);
+    let state = slot.borrow().expect("consumer should have rendered");
+    wait_for_state(
+        &mut vdom,
+        state,
+        |state| state.is_success(),
+        Duration::from_secs(1),
+    )
+    .await
+    .unwrap()
+}
+
+#[test]
+fn demo_mode_replays_recorded_snapshots_without_running_providers() {
+    block_on_test(async {
+        let recorder = SnapshotRecorder::new();
+        global::ProviderConfig::new()
+            .with_storage(recorder.clone())
+            .init_named("demo-record")
+            .unwrap();
+
+        let state = mount_headline("demo-record").await;
+        assert_state!(state, State::Success(ref headline) if headline == "breaking news");
+        sleep(Duration::from_millis(20)).await;
+        let snapshot = CacheSnapshot::from_json(&recorder.snapshot().to_json()).unwrap();
+        assert_eq!(snapshot.len(), 1);
+
+        global::ProviderConfig::new()
+            .with_demo_snapshot(snapshot)
+            .init_named("demo-replay")
+            .unwrap();
+        let state = mount_headline("demo-replay").await;
+        assert_state!(state, State::Success(ref headline) if headline == "breaking news");
+        assert_eq!(
+            HEADLINE_CALLS.load(Ordering::SeqCst),
+            1,
+            "demo mode never runs providers"
+        );
+    });
+}
+
+static HEARTBEAT_CALLS: AtomicU32 = AtomicU32::new(0);
+
+#[provider(interval = "300ms")]
+async fn heartbeat() -> Result<u32, ()> {
+    Ok(HEARTBEAT_CALLS.fetch_add(1, Ordering::SeqCst) + 1)
+}
+
+#[derive(Props, Clone, PartialEq)]
+struct HeartbeatConsumerProps {
+    handle: Rc<std::cell::RefCell<Option<ProviderHandle<u32, ()>>>>,
+}
+
+#[allow(non_snake_case)]
+fn HeartbeatConsumer(props: HeartbeatConsumerProps) -> Element {
+    let state = use_provider_in("background", heartbeat(), ());
+    *props.handle.borrow_mut() = Some(state);
+    rsx!(div {})
+}
+
+#[test]
+fn paused_background_skips_interval_refreshes_until_resumed() {
+    block_on_test(async {
+        global::init_named("background", global::ProviderConfig::new()).unwrap();
+        let runtime = global::get_named_runtime("background").unwrap();
+        let slot = Rc::new(std::cell::RefCell::new(None));
+        let mut vdom = VirtualDom::new_with_props(
+            HeartbeatConsumer,
+            HeartbeatConsumerProps {
+                handle: slot.clone(),
+            },
+        );
+        vdom.rebuild_in_place();
+        testing::pump(&mut vdom);
+        let state = slot.borrow().expect("consumer should have rendered");
+        let timeout = Duration::from_secs(1);
+        wait_for_state(&mut vdom, state, |state| state.is_success(), timeout)
+            .await
+            .unwrap();
+
+        runtime.pause_background();
+        let _ = wait_for_state(&mut vdom, state, |_| false, Duration::from_millis(700)).await;
+        assert_eq!(HEARTBEAT_CALLS.load(Ordering::SeqCst), 1);
+        assert!(runtime.is_background_paused());
+
+        runtime.resume_background();
+        let state = wait_for_state(&mut vdom, state, |state| state.data() == Some(&2), timeout)
+            .await
+            .unwrap();
+        assert_state!(state, State::Success(2));
+    });
+}
+
+static FLAKY_CALLS: AtomicU32 = AtomicU32::new(0);
+
+#[provider(retry = 2, retry_delay = "10ms", retry_backoff = "exponential")]
+async fn flaky_feed() -> Result<u32, String> {
+    match FLAKY_CALLS.fetch_add(1, Ordering::SeqCst) + 1 {
+        calls @ 1..=2 => Err(format!("timeout #{calls}")),
+        calls => Ok(calls),
+    }
+}
+
+#[derive(Props, Clone, PartialEq)]
+struct RetriedConsumerProps {
+    handle: Rc<std::cell::RefCell<Option<ProviderHandle<u32, String>>>>,
+}
+
+#[allow(non_snake_case)]
+fn RetriedConsumer(props: RetriedConsumerProps) -> Element {
+    let state = use_provider(flaky_feed(), ());
+    *props.handle.borrow_mut() = Some(state);
+    rsx!(div {})
+}
+
+#[test]
+fn failed_fetches_are_retried_before_surfacing_errors() {
+    block_on_test(async {
+        let _ = global::init();
+        let slot = Rc::new(std::cell::RefCell::new(None));
+        let mut vdom = VirtualDom::new_with_props(
+            RetriedConsumer,
+            RetriedConsumerProps {
+                handle: slot.clone(),
+            },
+        );
+        vdom.rebuild_in_place();
+        testing::pump(&mut vdom);
+        let state = slot.borrow().expect("consumer should have rendered");
+
+        let state = wait_for_state(
+            &mut vdom,
+            state,
+            |state| !state.is_loading(),
+            Duration::from_secs(1),
+        )
+        .await
+        .unwrap();
+        assert_state!(state, State::Success(3));
+        assert_eq!(FLAKY_CALLS.load(Ordering::SeqCst), 3);
+    });
+}
+
+static STREAM_OPENS: AtomicU32 = AtomicU32::new(0);
+
+#[dioxus_provider::prelude::stream_provider]
+async fn counting_stream(
+    limit: u32,
+) -> impl dioxus_provider::stream::Stream<Item = Result<u32, String>> {
+    STREAM_OPENS.fetch_add(1, Ordering::SeqCst);
+    futures::stream::iter((1..=limit).map(Ok))
+}
+
+type StreamSlot = Rc<std::cell::RefCell<Option<ReadSignal<State<u32, String>>>>>;
+
+#[derive(Props, Clone, PartialEq)]
+struct StreamConsumerProps {
+    handle: StreamSlot,
+}
+
+#[allow(non_snake_case)]
+fn StreamConsumer(props: StreamConsumerProps) -> Element {
+    let state = dioxus_provider::stream::use_stream_provider(counting_stream(), 3u32);
+    *props.handle.borrow_mut() = Some(state);
+    rsx!(div {})
+}
+
+#[test]
+fn stream_providers_update_state_and_cache_on_every_item() {
+    block_on_test(async {
+        let _ = global::init();
+        let slot = Rc::new(std::cell::RefCell::new(None));
+        let mut vdom = VirtualDom::new_with_props(
+            StreamConsumer,
+            StreamConsumerProps {
+                handle: slot.clone(),
+            },
+        );
+        vdom.rebuild_in_place();
+        testing::pump(&mut vdom);
+        let state = slot.borrow().expect("consumer should have rendered");
+
+        let deadline = std::time::Instant::now() + Duration::from_secs(1);
+        let last = loop {
+            testing::pump(&mut vdom);
+            let current = vdom.in_runtime(|| state.peek().clone());
+            if current.data() == Some(&3) || std::time::Instant::now() > deadline {
+                break current;
+            }
+            yield_now().await;
+        };
+        assert_state!(last, State::Success(3));
+        assert_eq!(STREAM_OPENS.load(Ordering::SeqCst), 1);
+
+        let cache_key = dioxus_provider::stream::StreamProvider::id(&counting_stream(), &3);
+        let cached = global::get_global_runtime()
+            .unwrap()
+            .cache()
+            .get::<Result<u32, String>>(&cache_key);
+        assert_eq!(cached, Some(Ok(3)));
+    });
+}
+
+#[provider(paginated)]
+async fn numbered_feed(
+    topic: String,
+    cursor: Option<u32>,
+) -> Result<(Vec<String>, Option<u32>), String> {
+    let page = cursor.unwrap_or(0);
+    sleep(Duration::from_millis(10)).await;
+    let items = (0..2)
+        .map(|n| format!("{topic}-{}", page * 2 + n))
+        .collect();
+    Ok((items, (page < 1).then_some(page + 1)))
+}
+
+type FeedQuery = InfiniteQuery<NumberedFeed, String, String, u32>;
+
+#[derive(Props, Clone)]
+struct FeedConsumerProps {
+    handle: Rc<std::cell::RefCell<Option<FeedQuery>>>,
+}
+
+impl PartialEq for FeedConsumerProps {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.handle, &other.handle)
+    }
+}
+
+#[allow(non_snake_case)]
+fn FeedConsumer(props: FeedConsumerProps) -> Element {
+    let feed = use_infinite_provider(numbered_feed(), "news".to_string());
+    *props.handle.borrow_mut() = Some(feed);
+    rsx!(div {})
+}
+
+#[test]
+fn paginated_providers_load_pages_on_demand() {
+    block_on_test(async {
+        let _ = global::init();
+        let slot = Rc::new(std::cell::RefCell::new(None));
+        let mut vdom = VirtualDom::new_with_props(
+            FeedConsumer,
+            FeedConsumerProps {
+                handle: slot.clone(),
+            },
+        );
+        vdom.rebuild_in_place();
+        testing::pump(&mut vdom);
+        let feed = slot
+            .borrow()
+            .clone()
+            .expect("consumer should have rendered");
+
+        let first = wait_for_state(
+            &mut vdom,
+            feed.state(),
+            |state| state.is_success(),
+            Duration::from_secs(1),
+        )
+        .await
+        .unwrap();
+        let pages = first.data().unwrap();
+        assert_eq!(pages.items().collect::<Vec<_>>(), ["news-0", "news-1"]);
+        assert!(vdom.in_runtime(|| feed.has_next_page()));
+
+        vdom.in_scope(ScopeId::ROOT, || feed.fetch_next_page());
+        assert!(vdom.in_runtime(|| feed.is_fetching_next_page()));
+        let second = wait_for_state(
+            &mut vdom,
+            feed.state(),
+            |state| state.data().is_some_and(|pages| pages.pages().len() == 2),
+            Duration::from_secs(1),
+        )
+        .await
+        .unwrap();
+        assert_eq!(second.data().unwrap().items().count(), 4);
+        assert!(!vdom.in_runtime(|| feed.is_fetching_next_page()));
+        assert!(!vdom.in_runtime(|| feed.has_next_page()));
+    });
+}
+
+#[derive(Props, Clone, PartialEq)]
+struct DynamicConsumerProps {
+    handle: Rc<std::cell::RefCell<Option<ProviderHandle<u32, String>>>>,
+}
+
+#[allow(non_snake_case)]
+fn DynamicConsumer(props: DynamicConsumerProps) -> Element {
+    let state = use_dynamic_provider::<u32, String, _>("test.dynamic_length", "plugin".to_string());
+    *props.handle.borrow_mut() = Some(state);
+    rsx!(div {})
+}
+
+#[test]
+fn dynamic_providers_resolve_when_registered() {
+    block_on_test(async {
+        let _ = global::init();
+        let runtime = global::get_global_runtime().unwrap();
+        let slot = Rc::new(std::cell::RefCell::new(None));
+        let mut vdom = VirtualDom::new_with_props(
+            DynamicConsumer,
+            DynamicConsumerProps {
+                handle: slot.clone(),
+            },
+        );
+        vdom.rebuild_in_place();
+        testing::pump(&mut vdom);
+        let state = slot.borrow().expect("consumer should have rendered");
+
+        let unregistered = wait_for_state(
+            &mut vdom,
+            state,
+            |state| !state.is_loading(),
+            Duration::from_secs(1),
+        )
+        .await
+        .unwrap();
+        assert!(unregistered.is_error());
+
+        runtime.register_provider(
+            "test.dynamic_length",
+            FnProvider::new("length", |text: String| async move {
+                Ok::<_, String>(text.len() as u32)
+            }),
+        );
+        let registered = wait_for_state(
+            &mut vdom,
+            state,
+            |state| state.is_success(),
+            Duration::from_secs(1),
+        )
+        .await
+        .unwrap();
+        assert_state!(registered, State::Success(6));
+        assert!(runtime.unregister_provider("test.dynamic_length"));
+    });
+}
+
+static FINISHED_PROFILES: AtomicU32 = AtomicU32::new(0);
+
+#[provider]
+async fn slow_profile(id: u32) -> Result<String, String> {
+    let context = ProviderContext::current().expect("fetches run with a context");
+    assert_eq!(context.cache_key(), slow_profile().id(&id));
+    sleep(Duration::from_millis(50)).await;
+    FINISHED_PROFILES.fetch_add(1, Ordering::SeqCst);
+    Ok(format!("profile {id}"))
+}
+
+type ProfileSlot = Rc<std::cell::RefCell<Option<(Signal<u32>, ProviderHandle<String, String>)>>>;
+
+#[derive(Props, Clone, PartialEq)]
+struct ProfileConsumerProps {
+    handle: ProfileSlot,
+}
+
+#[allow(non_snake_case)]
+fn ProfileConsumer(props: ProfileConsumerProps) -> Element {
+    let id = use_signal(|| 1);
+    let handle = use_provider(slow_profile(), id());
+    *props.handle.borrow_mut() = Some((id, handle));
+    rsx!(div {})
+}
+
+#[test]
+fn switching_parameters_cancels_the_fetch_in_flight() {
+    block_on_test(async {
+        let _ = global::init();
+        let runtime = global::get_global_runtime().unwrap();
+        let slot = Rc::new(std::cell::RefCell::new(None));
+        let mut vdom = VirtualDom::new_with_props(
+            ProfileConsumer,
+            ProfileConsumerProps {
+                handle: slot.clone(),
+            },
+        );
+        vdom.rebuild_in_place();
+        testing::pump(&mut vdom);
+        let (mut id, state) = slot.borrow().expect("consumer should have rendered");
+
+        vdom.in_runtime(|| id.set(2));
+        testing::pump(&mut vdom);
+        let loaded = wait_for_state(
+            &mut vdom,
+            state,
+            |state| state.is_success(),
+            Duration::from_secs(1),
+        )
+        .await
+        .unwrap();
+        assert_state!(loaded, State::Success(ref profile) if profile == "profile 2");
+
+        sleep(Duration::from_millis(100)).await;
+        testing::pump(&mut vdom);
+        assert_eq!(FINISHED_PROFILES.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            runtime
+                .cache()
+                .get::<Result<String, String>>(&slow_profile().id(&1)),
+            None
+        );
+        assert!(vdom.in_runtime(|| state.read().is_success()));
+    });
+}
+
+static REPORT_CALLS: AtomicU32 = AtomicU32::new(0);
+
+#[provider(persist)]
+async fn tiered_report() -> Result<String, String> {
+    REPORT_CALLS.fetch_add(1, Ordering::SeqCst);
+    Ok("quarterly".to_string())
+}
+
+#[derive(Props, Clone, PartialEq)]
+struct ReportConsumerProps {
+    handle: Rc<std::cell::RefCell<Option<ProviderHandle<String, String>>>>,
+}
+
+#[allow(non_snake_case)]
+fn ReportConsumer(props: ReportConsumerProps) -> Element {
+    let state = use_provider_in("tiered", tiered_report(), ());
+    *props.handle.borrow_mut() = Some(state);
+    rsx!(div {})
+}
+
+#[test]
+fn tiered_storage_promotes_evicted_entries_without_refetching() {
+    block_on_test(async {
+        global::ProviderConfig::new()
+            .with_tiered_storage(SnapshotRecorder::new())
+            .init_named("tiered")
+            .unwrap();
+        let runtime = global::get_named_runtime("tiered").unwrap();
+        let mount = async || {
+            let slot = Rc::new(std::cell::RefCell::new(None));
+            let mut vdom = VirtualDom::new_with_props(
+                ReportConsumer,
+                ReportConsumerProps {
+                    handle: slot.clone(),
+                },
+            );
+            vdom.rebuild_in_place();
+            testing::pump(&mut vdom);
+            let state = slot.borrow().expect("consumer should have rendered");
+            wait_for_state(
+                &mut vdom,
+                state,
+                |state| state.is_success(),
+                Duration::from_secs(1),
+            )
+            .await
+            .unwrap()
+        };
+
+        let state = mount().await;
+        assert_state!(state, State::Success(ref report) if report == "quarterly");
+        assert_eq!(runtime.cache().evict_lru_entries(0), 1);
+        assert_eq!(runtime.cache().size(), 0);
+
+        let state = mount().await;
+        assert_state!(state, State::Success(ref report) if report == "quarterly");
+        assert_eq!(
+            REPORT_CALLS.load(Ordering::SeqCst),
+            1,
+            "evicted entries are restored from storage"
+        );
+    });
+}
+
+static QUOTE_CALLS: AtomicU32 = AtomicU32::new(0);
+
+#[provider]
+async fn daily_quote() -> Result<u32, ()> {
+    sleep(Duration::from_millis(10)).await;
+    Ok(QUOTE_CALLS.fetch_add(1, Ordering::SeqCst) + 1)
+}
+
+type RenderLog = Rc<std::cell::RefCell<Vec<String>>>;
+
+#[derive(Props, Clone, PartialEq)]
+struct QuoteBoundaryProps {
+    renders: RenderLog,
+}
+
+#[allow(non_snake_case)]
+fn QuoteBoundary(props: QuoteBoundaryProps) -> Element {
+    let fallback_renders = props.renders.clone();
+    rsx! {
+        SuspenseBoundary {
+            fallback: move |_| {
+                fallback_renders.borrow_mut().push("fallback".to_string());
+                rsx!(div {})
+            },
+            QuoteCard { renders: props.renders.clone() }
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+#[component]
+fn QuoteCard(renders: RenderLog) -> Element {
+    match use_provider_suspense(daily_quote(), ()) {
+        Ok(quote) => renders.borrow_mut().push(format!("{quote:?}")),
+        Err(suspended) => {
+            renders.borrow_mut().push("suspended".to_string());
+            return Err(suspended.into());
+        }
+    }
+    rsx!(div {})
+}
+
+#[test]
+fn suspense_hook_suspends_once_and_revalidates_in_the_background() {
+    block_on_test(async {
+        let _ = global::init();
+        let renders = RenderLog::default();
+        let mut vdom = VirtualDom::new_with_props(
+            QuoteBoundary,
+            QuoteBoundaryProps {
+                renders: renders.clone(),
+            },
+        );
+        vdom.rebuild_in_place();
+        let settle = async |vdom: &mut VirtualDom| {
+            for _ in 0..3 {
+                testing::pump(vdom);
+                sleep(Duration::from_millis(20)).await;
+            }
+            testing::pump(vdom);
+        };
+        settle(&mut vdom).await;
+        assert_eq!(renders.borrow().last().map(String::as_str), Some("Ok(1)"));
+        assert!(renders.borrow().iter().any(|render| render == "fallback"));
+
+        let handles = global::get_global_runtime_handles().unwrap();
+        let cache_key = daily_quote().id(&());
+        let loaded = renders.borrow().len();
+        handles.cache.invalidate(&cache_key);
+        handles.refresh_registry.trigger_refresh(&cache_key);
+        settle(&mut vdom).await;
+
+        let renders = renders.borrow();
+        let after = &renders[loaded..];
+        assert!(
+            !after
+                .iter()
+                .any(|render| render == "suspended" || render == "fallback"),
+            "revalidation shouldn't suspend again: {after:?}"
+        );
+        assert_eq!(after.last().map(String::as_str), Some("Ok(2)"));
+    });
+}
+
+static DASHBOARD_CALLS: AtomicU32 = AtomicU32::new(0);
+
+#[provider]
+async fn hot_dashboard(team: u32) -> Result<String, String> {
+    DASHBOARD_CALLS.fetch_add(1, Ordering::SeqCst);
+    Ok(format!("dashboard {team}"))
+}
+
+#[allow(non_snake_case)]
+fn WarmApp() -> Element {
+    use_warm_set(|| WarmSet::new().provider(hot_dashboard(), 7));
+    rsx!(div {})
+}
+
+#[test]
+fn warm_sets_pin_and_prefetch_hot_keys_at_startup() {
+    block_on_test(async {
+        global::init().unwrap();
+        let runtime = global::get_global_runtime().unwrap();
+        let key = hot_dashboard().id(&7);
+
+        let mut vdom = VirtualDom::new(WarmApp);
+        vdom.rebuild_in_place();
+        assert!(runtime.cache().is_pinned(&key));
+        for _ in 0..100 {
+            testing::pump(&mut vdom);
+            if runtime
+                .cache()
+                .get::<Result<String, String>>(&key)
+                .is_some()
+            {
+                break;
+            }
+            sleep(Duration::from_millis(5)).await;
+        }
+
+        assert_eq!(
+            runtime.cache().get::<Result<String, String>>(&key),
+            Some(Ok("dashboard 7".to_string()))
+        );
+        testing::pump(&mut vdom);
+        assert_eq!(DASHBOARD_CALLS.load(Ordering::SeqCst), 1);
+    });
+}
+
+static ARTICLE_CALLS: AtomicU32 = AtomicU32::new(0);
+
+#[provider(persist)]
+async fn ssr_article(id: u32) -> Result<String, String> {
+    ARTICLE_CALLS.fetch_add(1, Ordering::SeqCst);
+    Ok(format!("article {id}"))
+}
+
+#[derive(Props, Clone, PartialEq)]
+struct ArticleConsumerProps {
+    runtime: &'static str,
+    handle: Rc<std::cell::RefCell<Option<ProviderHandle<String, String>>>>,
+}
+
+#[allow(non_snake_case)]
+fn ArticleConsumer(props: ArticleConsumerProps) -> Element {
+    let state = use_provider_in(props.runtime, ssr_article(), 3);
+    *props.handle.borrow_mut() = Some(state);
+    rsx!(div {})
+}
+
+#[test]
+fn serialized_server_entries_hydrate_the_first_client_render() {
+    block_on_test(async {
+        global::ProviderConfig::new()
+            .init_named("ssr-server")
+            .unwrap();
+        global::ProviderConfig::new()
+            .init_named("ssr-client")
+            .unwrap();
+        let mount = |runtime| {
+            let slot = Rc::new(std::cell::RefCell::new(None));
+            let mut vdom = VirtualDom::new_with_props(
+                ArticleConsumer,
+                ArticleConsumerProps {
+                    runtime,
+                    handle: slot.clone(),
+                },
+            );
+            vdom.rebuild_in_place();
+            let state = slot.borrow().expect("consumer should have rendered");
+            (vdom, state)
+        };
+
+        let (mut server, state) = mount("ssr-server");
+        let state = wait_for_state(
+            &mut server,
+            state,
+            |state| state.is_success(),
+            Duration::from_secs(1),
+        )
+        .await
+        .unwrap();
+        assert_state!(state, State::Success(ref article) if article == "article 3");
+        let snapshot = global::get_named_runtime("ssr-server")
+            .unwrap()
+            .cache()
+            .serialize();
+        assert_eq!(snapshot.get(&ssr_article().id(&3)), Some("\"article 3\""));
+
+        global::get_named_runtime("ssr-client")
+            .unwrap()
+            .cache()
+            .hydrate(&snapshot);
+        let (client, state) = mount("ssr-client");
+        let first_render = client.in_runtime(|| state.read().clone());
+        assert_state!(first_render, State::Success(ref article) if article == "article 3");
+        assert_eq!(ARTICLE_CALLS.load(Ordering::SeqCst), 1);
+    });
+}
+
+static PLAN_LOG: std::sync::Mutex<Vec<String>> = std::sync::Mutex::new(Vec::new());
+
+#[provider]
+async fn plan_screen(name: String) -> Result<String, String> {
+    PLAN_LOG.lock().unwrap().push(name.clone());
+    sleep(Duration::from_millis(20)).await;
+    Ok(name)
+}
+
+#[allow(non_snake_case)]
+fn PlanApp() -> Element {
+    use_provider_in("startup", plan_screen(), "home".to_string());
+    rsx!(div {})
+}
+
+async fn wait_for_plan(
+    vdom: &mut VirtualDom,
+    progress: impl Fn() -> PrefetchProgress,
+) -> PrefetchProgress {
+    for _ in 0..200 {
+        testing::pump(vdom);
+        if progress().finished {
+            break;
+        }
+        sleep(Duration::from_millis(5)).await;
+    }
+    progress()
+}
+
+#[test]
+fn prefetch_plans_run_by_priority_within_their_budget() {
+    block_on_test(async {
+        global::ProviderConfig::new()
+            .with_prefetch_plan(
+                PrefetchPlan::new()
+                    .provider(plan_screen(), "inbox".to_string())
+                    .provider_with_priority(
+                        PrefetchPriority::Low,
+                        plan_screen(),
+                        "contacts".to_string(),
+                    )
+                    .provider_with_priority(
+                        PrefetchPriority::High,
+                        plan_screen(),
+                        "session".to_string(),
+                    )
+                    .with_max_concurrency(1),
+            )
+            .init_named("startup")
+            .unwrap();
+        let runtime = global::get_named_runtime("startup").unwrap();
+        assert!(runtime.prefetch_progress().finished, "nothing ran yet");
+
+        let mut vdom = VirtualDom::new(PlanApp);
+        vdom.rebuild_in_place();
+        let progress = wait_for_plan(&mut vdom, || runtime.prefetch_progress()).await;
+        assert_eq!(
+            progress,
+            PrefetchProgress {
+                total: 3,
+                completed: 3,
+                skipped: 0,
+                finished: true,
+            }
+        );
+        let planned: Vec<String> = PLAN_LOG
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|name| *name != "home")
+            .cloned()
+            .collect();
+        assert_eq!(planned, ["session", "inbox", "contacts"]);
+
+        let over_budget = PrefetchPlan::new()
+            .provider(plan_screen(), "settings".to_string())
+            .provider(plan_screen(), "billing".to_string())
+            .provider(plan_screen(), "reports".to_string())
+            .with_max_concurrency(1)
+            .with_time_budget(Duration::from_millis(5));
+        vdom.in_runtime(|| runtime.run_prefetch_plan(over_budget));
+        let progress = wait_for_plan(&mut vdom, || runtime.prefetch_progress()).await;
+        assert!(progress.finished);
+        assert_eq!(progress.skipped, 2);
+        assert_eq!(PLAN_LOG.lock().unwrap().len(), 5);
+    });
+}
+
+static PROFILE_CALLS: AtomicU32 = AtomicU32::new(0);
+
+#[provider]
+async fn selected_profile(id: u32) -> Result<(String, u32), String> {
+    sleep(Duration::from_millis(10)).await;
+    Ok((
+        format!("user {id}"),
+        PROFILE_CALLS.fetch_add(1, Ordering::SeqCst),
+    ))
+}
+
+#[derive(Props, Clone, PartialEq)]
+struct ProfileNameProps {
+    renders: RenderLog,
+}
+
+#[allow(non_snake_case)]
+fn ProfileName(props: ProfileNameProps) -> Element {
+    let name = use_provider_select(selected_profile(), 5, |profile: &(String, u32)| {
+        profile.0.clone()
+    });
+    props
+        .renders
+        .borrow_mut()
+        .push(format!("{:?}", name.read()));
+    rsx!(div {})
+}
+
+#[test]
+fn selecting_a_slice_skips_renders_when_it_is_unchanged() {
+    block_on_test(async {
+        let _ = global::init();
+        let renders = RenderLog::default();
+        let mut vdom = VirtualDom::new_with_props(
+            ProfileName,
+            ProfileNameProps {
+                renders: renders.clone(),
+            },
+        );
+        vdom.rebuild_in_place();
+        let settle = async |vdom: &mut VirtualDom| {
+            for _ in 0..3 {
+                testing::pump(vdom);
+                sleep(Duration::from_millis(20)).await;
+            }
+            testing::pump(vdom);
+        };
+        settle(&mut vdom).await;
+        assert_eq!(
+            renders.borrow().last().map(String::as_str),
+            Some(r#"Some(Ok("user 5"))"#)
+        );
+
+        let handles = global::get_global_runtime_handles().unwrap();
+        let cache_key = selected_profile().id(&5);
+        let calls = PROFILE_CALLS.load(Ordering::SeqCst);
+        let loaded = renders.borrow().len();
+        handles.cache.invalidate(&cache_key);
+        handles.refresh_registry.trigger_refresh(&cache_key);
+        settle(&mut vdom).await;
+
+        assert!(PROFILE_CALLS.load(Ordering::SeqCst) > calls);
+        assert_eq!(
+            renders.borrow().len(),
+            loaded,
+            "refetching an unchanged slice shouldn't re-render: {:?}",
+            renders.borrow()
+        );
+    });
+}
+
+static CACHED_GREETING_CALLS: AtomicU32 = AtomicU32::new(0);
+
+#[provider]
+async fn cached_greeting(name: String) -> Result<String, String> {
+    CACHED_GREETING_CALLS.fetch_add(1, Ordering::SeqCst);
+    Ok(format!("fetched {name}"))
+}
+
+/// Storage holding an older greeting than the one already cached
+struct GreetingStorage {
+    key: String,
+}
+
+impl HydrationSource for GreetingStorage {
+    fn keys(&self) -> Result<Vec<String>, ProviderError> {
+        Ok(vec![self.key.clone()])
+    }
+
+    fn hydrate(&self, key: &str, cache: ProviderCache) -> HydrateFuture {
+        let key = key.to_string();
+        Box::pin(async move { Ok(cache.set(key, Ok::<String, String>("stored ada".to_string()))) })
+    }
+}
+
+#[derive(Props, Clone, PartialEq)]
+struct GreetingProps {
+    renders: RenderLog,
+}
+
+#[allow(non_snake_case)]
+fn Greeting(props: GreetingProps) -> Element {
+    let greeting = use_provider_in("first-render", cached_greeting(), "ada".to_string());
+    props.renders.borrow_mut().push(match &*greeting.read() {
+        State::Success(text) => text.clone(),
+        state if state.is_loading() => "loading".to_string(),
+        state => format!("{state:?}"),
+    });
+    rsx!(div {})
+}
+
+#[test]
+fn cached_data_renders_on_the_first_render() {
+    block_on_test(async {
+        let cache_key = cached_greeting().id(&"ada".to_string());
+        let storage = GreetingStorage {
+            key: cache_key.clone(),
+        };
+        global::ProviderConfig::new()
+            .with_lazy_hydration(LazyHydration::new(storage))
+            .init_named("first-render")
+            .unwrap();
+        global::get_named_runtime("first-render")
+            .unwrap()
+            .cache()
+            .set(cache_key, Ok::<String, String>("cached ada".to_string()));
+
+        let renders = RenderLog::default();
+        let mut vdom = VirtualDom::new_with_props(
+            Greeting,
+            GreetingProps {
+                renders: renders.clone(),
+            },
+        );
+        vdom.rebuild_in_place();
+        assert_eq!(*renders.borrow(), ["cached ada"]);
+
+        testing::pump(&mut vdom);
+        sleep(Duration::from_millis(20)).await;
+        testing::pump(&mut vdom);
+        assert!(
+            renders.borrow().iter().all(|render| render == "cached ada"),
+            "cached data shouldn't flash a loading state: {:?}",
+            renders.borrow()
+        );
+        assert_eq!(CACHED_GREETING_CALLS.load(Ordering::SeqCst), 0);
+    });
+}
+
+#[provider(timeout = "20ms")]
+async fn stalled_report() -> Result<String, String> {
+    std::future::pending::<()>().await;
+    Ok("report".to_string())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SummaryError {
+    Unavailable,
+}
+
+static SUMMARY_ATTEMPTS: AtomicU32 = AtomicU32::new(0);
+
+#[provider(
+    timeout = "20ms",
+    timeout_error = |_| SummaryError::Unavailable,
+    retry = 1,
+    retry_delay = "1ms"
+)]
+async fn stalled_summary() -> Result<String, SummaryError> {
+    SUMMARY_ATTEMPTS.fetch_add(1, Ordering::SeqCst);
+    std::future::pending::<()>().await;
+    Ok("summary".to_string())
+}
+
+type StalledHandles = Rc<
+    std::cell::RefCell<
+        Option<(
+            ProviderHandle<String, String>,
+            ProviderHandle<String, SummaryError>,
+        )>,
+    >,
+>;
+
+#[derive(Props, Clone, PartialEq)]
+struct StalledProps {
+    handles: StalledHandles,
+}
+
+#[allow(non_snake_case)]
+fn Stalled(props: StalledProps) -> Element {
+    let report = use_provider(stalled_report(), ());
+    let summary = use_provider(stalled_summary(), ());
+    *props.handles.borrow_mut() = Some((report, summary));
+    rsx!(div {})
+}
+
+#[test]
+fn providers_fail_with_their_timeout_error_instead_of_loading_forever() {
+    block_on_test(async {
+        let _ = global::init();
+        let handles = StalledHandles::default();
+        let mut vdom = VirtualDom::new_with_props(
+            Stalled,
+            StalledProps {
+                handles: handles.clone(),
+            },
+        );
+        vdom.rebuild_in_place();
+        testing::pump(&mut vdom);
+        let (report, summary) = handles.borrow().expect("consumer should have rendered");
+
+        let timeout = Duration::from_secs(1);
+        let state = wait_for_state(&mut vdom, report, |state| state.is_error(), timeout)
+            .await
+            .unwrap();
+        assert_state!(state, State::Error(ref error) if error.contains("no result within 20ms"));
+
+        let state = wait_for_state(&mut vdom, summary, |state| state.is_error(), timeout)
+            .await
+            .unwrap();
+        assert_state!(state, State::Error(SummaryError::Unavailable));
+        assert_eq!(
+            SUMMARY_ATTEMPTS.load(Ordering::SeqCst),
+            2,
+            "timed-out attempts are retried"
+        );
+    });
+}
+
+static WINDOW_TITLE_CALLS: AtomicU32 = AtomicU32::new(0);
+
+#[provider]
+async fn window_title() -> Result<u32, ()> {
+    Ok(WINDOW_TITLE_CALLS.fetch_add(1, Ordering::SeqCst) + 1)
+}
+
+#[allow(non_snake_case)]
+fn TitleBar() -> Element {
+    let _title = use_provider(window_title(), ());
+    rsx!(div {})
+}
+
+#[derive(Props, Clone, PartialEq)]
+struct WindowsProps {
+    first: ProviderRuntime,
+    second: ProviderRuntime,
+}
+
+#[allow(non_snake_case)]
+fn Windows(props: WindowsProps) -> Element {
+    rsx! {
+        ProviderScope { runtime: props.first, TitleBar {} }
+        ProviderScope { runtime: props.second, TitleBar {} }
+        TitleBar {}
+    }
+}
+
+#[test]
+fn provider_scopes_isolate_their_caches_from_the_global_one() {
+    block_on_test(async {
+        let _ = global::init();
+        let first = global::ProviderConfig::new().build();
+        let second = global::ProviderConfig::new().build();
+        let mut vdom = VirtualDom::new_with_props(
+            Windows,
+            WindowsProps {
+                first: first.clone(),
+                second: second.clone(),
+            },
+        );
+        vdom.rebuild_in_place();
+        for _ in 0..3 {
+            testing::pump(&mut vdom);
+            sleep(Duration::from_millis(10)).await;
+        }
+
+        let cache_key = window_title().id(&());
+        let titles = [
+            first.cache(),
+            second.cache(),
+            global::get_global_cache().unwrap(),
+        ]
+        .map(|cache| cache.get::<Result<u32, ()>>(&cache_key));
+        assert_eq!(WINDOW_TITLE_CALLS.load(Ordering::SeqCst), 3);
+        assert!(titles.iter().all(Option::is_some), "{titles:?}");
+        assert!(
+            titles[0] != titles[1] && titles[1] != titles[2] && titles[0] != titles[2],
+            "each runtime fetches for itself: {titles:?}"
+        );
+    });
+}
+
+static ROOM_MESSAGE_CALLS: AtomicU32 = AtomicU32::new(0);
+
+#[provider]
+async fn room_messages(room: u32) -> Result<Vec<String>, String> {
+    ROOM_MESSAGE_CALLS.fetch_add(1, Ordering::SeqCst);
+    sleep(Duration::from_millis(10)).await;
+    Ok(vec![format!("welcome to {room}")])
+}
+
+type RoomSlot = Rc<
+    std::cell::RefCell<
+        Option<(
+            ProviderHandle<Vec<String>, String>,
+            ProviderCacheUpdater<RoomMessages, u32>,
+        )>,
+    >,
+>;
+
+#[derive(Props, Clone)]
+struct RoomProps {
+    runtime: ProviderRuntime,
+    handle: RoomSlot,
+}
+
+impl PartialEq for RoomProps {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+#[allow(non_snake_case)]
+fn Room(props: RoomProps) -> Element {
+    rsx! {
+        ProviderScope { runtime: props.runtime.clone(),
+            RoomFeed { ..props }
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+fn RoomFeed(props: RoomProps) -> Element {
+    let state = use_provider(room_messages(), 7);
+    let updater = use_update_provider_cache(room_messages(), 7);
+    *props.handle.borrow_mut() = Some((state, updater));
+    rsx!(div {})
+}
+
+#[test]
+fn cache_updates_patch_data_in_place_without_refetching() {
+    block_on_test(async {
+        let runtime = global::ProviderConfig::new().build();
+        let slot = RoomSlot::default();
+        let mut vdom = VirtualDom::new_with_props(
+            Room,
+            RoomProps {
+                runtime,
+                handle: slot.clone(),
+            },
+        );
+        vdom.rebuild_in_place();
+        let (state, updater) = slot.borrow().clone().expect("feed should have rendered");
+        assert!(
+            !updater.update(|messages| messages.push("too early".to_string())),
+            "nothing is cached before the first fetch resolves"
+        );
+
+        let timeout = Duration::from_secs(1);
+        wait_for_state(&mut vdom, state, |state| state.is_success(), timeout)
+            .await
+            .unwrap();
+        assert!(!updater.update(|_| {}), "unchanged data isn't an update");
+        assert!(vdom.in_runtime(|| updater.update(|messages| messages.push("hi".to_string()))));
+
+        let state = wait_for_state(
+            &mut vdom,
+            state,
+            |state| state.data().is_some_and(|m| m.len() == 2),
+            timeout,
+        )
+        .await
+        .unwrap();
+        assert_state!(state, State::Success(messages) if messages[1] == "hi");
+        assert_eq!(ROOM_MESSAGE_CALLS.load(Ordering::SeqCst), 1);
+    });
+}
+
+#[provider]
+async fn team_todos(team: u32) -> Result<Vec<(String, bool)>, String> {
+    sleep(Duration::from_millis(10)).await;
+    if team == 0 {
+        return Err("no team".to_string());
+    }
+    Ok(vec![
+        ("write docs".to_string(), true),
+        ("ship".to_string(), false),
+    ])
+}
+
+static TODO_MAPS: AtomicU32 = AtomicU32::new(0);
+
+type TodoStates = Rc<std::cell::RefCell<Vec<Signal<State<Vec<(String, bool)>, String>>>>>;
+
+#[derive(Props, Clone, PartialEq)]
+struct TodoBoardProps {
+    runtime: ProviderRuntime,
+    renders: RenderLog,
+    states: TodoStates,
+}
+
+#[allow(non_snake_case)]
+fn TodoBoard(props: TodoBoardProps) -> Element {
+    rsx! {
+        ProviderScope { runtime: props.runtime,
+            TodoWatcher { states: props.states }
+            TodoStats { team: 1, renders: props.renders.clone() }
+            TodoStats { team: 0, renders: props.renders }
+        }
+    }
+}
+
+#[derive(Props, Clone)]
+struct TodoWatcherProps {
+    states: TodoStates,
+}
+
+impl PartialEq for TodoWatcherProps {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+#[allow(non_snake_case)]
+fn TodoWatcher(props: TodoWatcherProps) -> Element {
+    let teams = [use_provider(team_todos(), 1), use_provider(team_todos(), 0)];
+    *props.states.borrow_mut() = teams.iter().map(|team| team.signal()).collect();
+    rsx!(div {})
+}
+
+#[component]
+fn TodoStats(team: u32, renders: RenderLog) -> Element {
+    let done = use_provider_map(team_todos(), team, |todos: &Vec<(String, bool)>| {
+        TODO_MAPS.fetch_add(1, Ordering::SeqCst);
+        todos.iter().filter(|(_, completed)| *completed).count()
+    });
+    if !done.read().is_loading() {
+        renders
+            .borrow_mut()
+            .push(format!("{team}: {:?}", done.read()));
+    }
+    rsx!(div {})
+}
+
+#[test]
+fn mapped_providers_derive_data_and_pass_errors_through() {
+    block_on_test(async {
+        let runtime = global::ProviderConfig::new().build();
+        let renders = RenderLog::default();
+        let states = TodoStates::default();
+        let mut vdom = VirtualDom::new_with_props(
+            TodoBoard,
+            TodoBoardProps {
+                runtime: runtime.clone(),
+                renders: renders.clone(),
+                states: states.clone(),
+            },
+        );
+        vdom.rebuild_in_place();
+        let timeout = Duration::from_secs(1);
+        let [team, no_team] = <[_; 2]>::try_from(states.borrow().clone()).unwrap();
+        wait_for_state(&mut vdom, team, |state| state.is_success(), timeout)
+            .await
+            .unwrap();
+        wait_for_state(&mut vdom, no_team, |state| state.is_error(), timeout)
+            .await
+            .unwrap();
+        testing::pump(&mut vdom);
+
+        let mut logged = renders.borrow().clone();
+        logged.sort();
+        assert_eq!(logged, [r#"0: Error("no team")"#, "1: Success(1)"]);
+        let maps = TODO_MAPS.load(Ordering::SeqCst);
+
+        // New data with the same number of completed todos is mapped again, but the mapped
+        // value is unchanged, so the consumer doesn't re-render
+        let cache_key = team_todos().id(&1);
+        let todos = vec![
+            ("write docs".to_string(), true),
+            ("ship".to_string(), false),
+            ("celebrate".to_string(), false),
+        ];
+        runtime
+            .cache()
+            .set(cache_key.clone(), Ok::<_, String>(todos.clone()));
+        runtime.refresh_registry().trigger_refresh(&cache_key);
+        wait_for_state(
+            &mut vdom,
+            team,
+            |state| state.data().is_some_and(|todos| todos.len() == 3),
+            timeout,
+        )
+        .await
+        .unwrap();
+        testing::pump(&mut vdom);
+        assert_eq!(TODO_MAPS.load(Ordering::SeqCst), maps + 1);
+        assert_eq!(renders.borrow().len(), 2);
+
+        // Storing the same data again doesn't map it at all
+        runtime.cache().set(cache_key, Ok::<_, String>(todos));
+        testing::pump(&mut vdom);
+        assert_eq!(TODO_MAPS.load(Ordering::SeqCst), maps + 1);
+        assert_eq!(renders.borrow().len(), 2);
+    });
+}
+
+static THEME_CALLS: AtomicU32 = AtomicU32::new(0);
+
+#[provider]
+async fn theme_revision() -> Result<u32, String> {
+    sleep(Duration::from_millis(10)).await;
+    Ok(THEME_CALLS.fetch_add(1, Ordering::SeqCst) + 1)
+}
+
+type ThemeSlot = Rc<std::cell::RefCell<Option<ReadSignal<State<u32, String>>>>>;
+
+#[derive(Props, Clone, PartialEq)]
+struct ThemeProps {
+    runtime: ProviderRuntime,
+    handle: ThemeSlot,
+}
+
+#[allow(non_snake_case)]
+fn Themed(props: ThemeProps) -> Element {
+    rsx! {
+        ProviderScope { runtime: props.runtime.clone(),
+            ThemeReader { ..props }
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+fn ThemeReader(props: ThemeProps) -> Element {
+    *props.handle.borrow_mut() = Some(use_provider_readonly(theme_revision(), ()));
+    rsx!(div {})
+}
+
+#[test]
+fn read_only_signals_follow_fetches_and_refetches() {
+    block_on_test(async {
+        let runtime = global::ProviderConfig::new().build();
+        let slot = ThemeSlot::default();
+        let mut vdom = VirtualDom::new_with_props(
+            Themed,
+            ThemeProps {
+                runtime: runtime.clone(),
+                handle: slot.clone(),
+            },
+        );
+        vdom.rebuild_in_place();
+        let settle = async |vdom: &mut VirtualDom| {
+            for _ in 0..3 {
+                testing::pump(vdom);
+                sleep(Duration::from_millis(20)).await;
+            }
+        };
+        let theme = slot.borrow().expect("reader should have rendered");
+        assert!(vdom.in_runtime(|| theme.peek().is_loading()));
+
+        settle(&mut vdom).await;
+        assert_state!(vdom.in_runtime(|| theme.peek().clone()), State::Success(1));
+
+        let cache_key = theme_revision().id(&());
+        runtime.cache().invalidate(&cache_key);
+        runtime.refresh_registry().trigger_refresh(&cache_key);
+        settle(&mut vdom).await;
+        assert_state!(vdom.in_runtime(|| theme.peek().clone()), State::Success(2));
+    });
+}
+
+static DISPLAY_NAME_FETCHES: AtomicU32 = AtomicU32::new(0);
+
+#[provider]
+async fn display_name(id: u32) -> Result<String, String> {
+    sleep(Duration::from_millis(10)).await;
+    let fetch = DISPLAY_NAME_FETCHES.fetch_add(1, Ordering::SeqCst) + 1;
+    Ok(format!("user {id} (fetch {fetch})"))
+}
+
+#[mutation]
+async fn save_display_name(name: String) -> Result<String, String> {
+    if name.is_empty() {
+        return Err("name can't be empty".to_string());
+    }
+    Ok(name)
+}
+
+type NameEditorSlot =
+    Rc<std::cell::RefCell<Option<(Signal<State<String, String>>, EditHandle<DisplayName, u32>)>>>;
+
+#[derive(Props, Clone)]
+struct NameFormProps {
+    runtime: ProviderRuntime,
+    handle: NameEditorSlot,
+}
+
+impl PartialEq for NameFormProps {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+#[allow(non_snake_case)]
+fn NameForm(props: NameFormProps) -> Element {
+    rsx! {
+        ProviderScope { runtime: props.runtime.clone(),
+            NameEditor { ..props }
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+fn NameEditor(props: NameFormProps) -> Element {
+    *props.handle.borrow_mut() = Some(use_provider_editable(display_name(), 3));
+    rsx!(div {})
+}
+
+#[test]
+fn editable_drafts_survive_failed_commits_and_refetch_after_successful_ones() {
+    block_on_test(async {
+        let slot = NameEditorSlot::default();
+        let mut vdom = VirtualDom::new_with_props(
+            NameForm,
+            NameFormProps {
+                runtime: global::ProviderConfig::new().build(),
+                handle: slot.clone(),
+            },
+        );
+        vdom.rebuild_in_place();
+        let (state, editor) = slot.borrow().clone().expect("editor should have rendered");
+        let timeout = Duration::from_secs(1);
+        wait_for_state(&mut vdom, state, |state| state.is_success(), timeout)
+            .await
+            .unwrap();
+        assert!(!editor.is_dirty());
+
+        assert!(vdom.in_runtime(|| editor.edit(|name| *name = "Ada".to_string())));
+        assert!(editor.is_dirty());
+        let draft = wait_for_state(
+            &mut vdom,
+            state,
+            |state| state.data().is_some_and(|name| name == "Ada"),
+            timeout,
+        )
+        .await
+        .unwrap();
+        assert_state!(draft, State::Success(_));
+
+        let failed = editor.commit(&save_display_name(), String::new()).await;
+        assert_eq!(failed, Err("name can't be empty".to_string()));
+        assert!(editor.is_dirty(), "failed commits keep the draft");
+        assert_eq!(editor.draft().as_deref(), Some("Ada"));
+
+        let committed = editor.commit(&save_display_name(), "Ada".to_string()).await;
+        assert_eq!(committed, Ok("Ada".to_string()));
+        assert!(!editor.is_dirty());
+        let refetched = wait_for_state(
+            &mut vdom,
+            state,
+            |state| state.data().is_some_and(|name| name.ends_with("(fetch 2)")),
+            timeout,
+        )
+        .await
+        .unwrap();
+        assert_state!(refetched, State::Success(name) if name == "user 3 (fetch 2)");
+    });
+}
+
+static ROUTE_USER_CALLS: AtomicU32 = AtomicU32::new(0);
+
+#[provider]
+async fn route_user(id: u32) -> Result<String, String> {
+    ROUTE_USER_CALLS.fetch_add(1, Ordering::SeqCst);
+    sleep(Duration::from_millis(10)).await;
+    Ok(format!("user {id}"))
+}
+
+#[derive(Clone, PartialEq)]
+enum Page {
+    Home,
+    User(u32),
+}
+
+fn page_loader(page: &Page) -> RouteLoader {
+    match page {
+        Page::User(id) => RouteLoader::new().provider(route_user(), *id),
+        Page::Home => RouteLoader::new(),
+    }
+}
+
+type PageSlot = Rc<std::cell::RefCell<Option<Signal<Page>>>>;
+
+#[derive(Props, Clone, PartialEq)]
+struct RoutedAppProps {
+    runtime: ProviderRuntime,
+    page: PageSlot,
+    renders: RenderLog,
+}
+
+#[allow(non_snake_case)]
+fn RoutedApp(props: RoutedAppProps) -> Element {
+    rsx! {
+        ProviderScope { runtime: props.runtime.clone(),
+            RouteLayout { ..props }
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+fn RouteLayout(props: RoutedAppProps) -> Element {
+    let page = use_signal(|| Page::User(1));
+    *props.page.borrow_mut() = Some(page);
+    use_route_loader(page(), page_loader);
+    match page() {
+        Page::User(id) => rsx! {
+            UserPage { id, renders: props.renders }
+        },
+        Page::Home => rsx!(div {}),
+    }
+}
+
+#[component]
+fn UserPage(id: u32, renders: RenderLog) -> Element {
+    let user = use_route_data(route_user(), id);
+    if let State::Success(name) = &*user.read() {
+        renders.borrow_mut().push(name.clone());
+    }
+    rsx!(div {})
+}
+
+#[test]
+fn route_loaders_prefetch_on_match_and_routed_components_join_the_request() {
+    block_on_test(async {
+        let runtime = global::ProviderConfig::new().build();
+        let page = PageSlot::default();
+        let renders = RenderLog::default();
+        let mut vdom = VirtualDom::new_with_props(
+            RoutedApp,
+            RoutedAppProps {
+                runtime: runtime.clone(),
+                page: page.clone(),
+                renders: renders.clone(),
+            },
+        );
+        vdom.rebuild_in_place();
+        let settle = async |vdom: &mut VirtualDom| {
+            for _ in 0..3 {
+                testing::pump(vdom);
+                sleep(Duration::from_millis(20)).await;
+            }
+        };
+        settle(&mut vdom).await;
+        assert_eq!(*renders.borrow(), ["user 1"]);
+        assert_eq!(
+            ROUTE_USER_CALLS.load(Ordering::SeqCst),
+            1,
+            "the routed component joins the loader's request"
+        );
+
+        let mut page = page.borrow().expect("layout should have rendered");
+        vdom.in_runtime(|| page.set(Page::Home));
+        settle(&mut vdom).await;
+        assert_eq!(ROUTE_USER_CALLS.load(Ordering::SeqCst), 1);
+
+        vdom.in_runtime(|| page.set(Page::User(2)));
+        testing::pump(&mut vdom);
+        assert_ne!(
+            runtime.pending_request_count(&route_user().id(&2)),
+            0,
+            "matching the route starts the fetch"
+        );
+        settle(&mut vdom).await;
+        assert_eq!(*renders.borrow(), ["user 1", "user 2"]);
+        assert_eq!(ROUTE_USER_CALLS.load(Ordering::SeqCst), 2);
+    });
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Member {
+    pub id: u32,
+    pub name: String,
+}
+
+impl Entity for Member {
+    type Id = u32;
+
+    fn entity_id(&self) -> u32 {
+        self.id
+    }
+}
+
+static MEMBER_FETCHES: AtomicU32 = AtomicU32::new(0);
+
+#[provider(normalize)]
+async fn team_members() -> Result<Vec<Member>, String> {
+    MEMBER_FETCHES.fetch_add(1, Ordering::SeqCst);
+    Ok(vec![Member {
+        id: 1,
+        name: "Ada".to_string(),
+    }])
+}
+
+#[mutation(normalize)]
+async fn rename_member(id: u32, name: String) -> Result<Member, String> {
+    sleep(Duration::from_millis(10)).await;
+    if name.is_empty() {
+        return Err("name can't be empty".to_string());
+    }
+    Ok(Member { id, name })
+}
+
+type MemberSlot =
+    Rc<std::cell::RefCell<Option<(Signal<Option<Member>>, Rc<dyn Fn((u32, String))>)>>>;
+
+#[derive(Props, Clone)]
+struct MemberCardProps {
+    handle: MemberSlot,
+}
+
+impl PartialEq for MemberCardProps {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+#[allow(non_snake_case)]
+fn MemberCard(props: MemberCardProps) -> Element {
+    let _members = use_provider(team_members(), ());
+    let member = use_entity::<Member>(1);
+    let rename = use_mutation_silent(rename_member());
+    *props.handle.borrow_mut() = Some((member, Rc::new(rename)));
+    rsx!(div {})
+}
+
+#[test]
+fn normalizing_mutations_update_entities_without_refetching() {
+    block_on_test(async {
+        let _ = global::init();
+        let slot = MemberSlot::default();
+        let mut vdom = VirtualDom::new_with_props(
+            MemberCard,
+            MemberCardProps {
+                handle: slot.clone(),
+            },
+        );
+        vdom.rebuild_in_place();
+        let settle = async |vdom: &mut VirtualDom| {
+            for _ in 0..3 {
+                testing::pump(vdom);
+                sleep(Duration::from_millis(20)).await;
+            }
+        };
+        settle(&mut vdom).await;
+        let (member, rename) = slot.borrow().clone().expect("card should have rendered");
+        let name = |vdom: &VirtualDom| {
+            vdom.in_runtime(|| member.peek().as_ref().map(|member| member.name.clone()))
+        };
+        assert_eq!(name(&vdom).as_deref(), Some("Ada"));
+
+        vdom.in_runtime(|| rename((1, "Grace".to_string())));
+        settle(&mut vdom).await;
+        assert_eq!(name(&vdom).as_deref(), Some("Grace"));
+
+        vdom.in_runtime(|| rename((1, String::new())));
+        settle(&mut vdom).await;
+        assert_eq!(
+            name(&vdom).as_deref(),
+            Some("Grace"),
+            "failed mutations leave the entity alone"
+        );
+        assert_eq!(MEMBER_FETCHES.load(Ordering::SeqCst), 1);
+    });
+}
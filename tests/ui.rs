@@ -0,0 +1,12 @@
+//! Trybuild tests for the provider macros
+//!
+//! `tests/ui/*.rs` must fail to compile with the diagnostics in their `.stderr` files, and
+//! `tests/ui/pass/*.rs` must compile and run. Run with `TRYBUILD=overwrite cargo test --test ui`
+//! to update the expected output after an intended change to a diagnostic.
+
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+    t.pass("tests/ui/pass/*.rs");
+}
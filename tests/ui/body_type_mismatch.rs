@@ -0,0 +1,9 @@
+use dioxus_provider::prelude::*;
+
+#[provider]
+async fn fetch_user_name(id: u32) -> Result<String, String> {
+    let name = id * 2;
+    Ok(name)
+}
+
+fn main() {}
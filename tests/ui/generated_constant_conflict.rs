@@ -0,0 +1,10 @@
+use dioxus_provider::prelude::*;
+
+const FETCH_INBOX: usize = 20;
+
+#[provider]
+async fn fetch_inbox() -> Result<Vec<String>, String> {
+    Ok(Vec::new())
+}
+
+fn main() {}
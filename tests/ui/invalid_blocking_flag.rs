@@ -0,0 +1,8 @@
+use dioxus_provider::prelude::*;
+
+#[provider(blocking = "yes")]
+fn read_config() -> Result<String, String> {
+    Ok(String::new())
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use dioxus_provider::prelude::*;
+
+#[provider]
+async fn fetch_name(id: u32) -> Result<String, String> {
+    Ok(id.to_string())
+}
+
+#[provider(compose = [fetch_name], compose_policy = "first")]
+async fn fetch_label(id: u32) -> Result<String, String> {
+    let _ = id;
+    __dioxus_composed_fetch_name_result
+}
+
+fn main() {}
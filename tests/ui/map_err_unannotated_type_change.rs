@@ -0,0 +1,13 @@
+use dioxus_provider::prelude::*;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AppError {
+    Backend(String),
+}
+
+#[provider(map_err = |error: String| AppError::Backend(error))]
+async fn fetch_name() -> Result<String, String> {
+    Ok(String::new())
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use dioxus_provider::prelude::*;
+
+#[provider(map_ok = str::len)]
+async fn fetch_name() -> Result<String, String> {
+    Ok(String::new())
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use dioxus_provider::prelude::*;
+
+#[provider(map_param = |id: u32, page: u32| (id, page))]
+async fn fetch_page(id: u32, page: u32) -> Result<String, String> {
+    Ok(format!("{id}:{page}"))
+}
+
+fn main() {}
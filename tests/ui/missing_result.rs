@@ -0,0 +1,8 @@
+use dioxus_provider::prelude::*;
+
+#[provider]
+async fn fetch_count() -> u32 {
+    42
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use dioxus_provider::prelude::*;
+
+#[provider]
+async fn search(query: &mut String) -> Result<String, String> {
+    Ok(query.clone())
+}
+
+fn main() {}
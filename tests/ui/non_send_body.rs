@@ -0,0 +1,15 @@
+use dioxus_provider::prelude::*;
+use std::rc::Rc;
+
+async fn load() -> u32 {
+    1
+}
+
+#[provider]
+async fn fetch_total() -> Result<u32, String> {
+    let cached = Rc::new(2);
+    let loaded = load().await;
+    Ok(*cached + loaded)
+}
+
+fn main() {}
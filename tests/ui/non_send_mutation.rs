@@ -0,0 +1,15 @@
+use dioxus_provider::prelude::*;
+use std::rc::Rc;
+
+async fn save(value: u32) -> u32 {
+    value
+}
+
+#[mutation]
+async fn save_total(total: u32) -> Result<u32, String> {
+    let pending = Rc::new(total);
+    let saved = save(total).await;
+    Ok(*pending + saved)
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use dioxus_provider::prelude::*;
+
+#[derive(PartialEq)]
+pub struct Report {
+    pub rows: Vec<u32>,
+}
+
+#[provider]
+async fn fetch_report() -> Result<Report, String> {
+    Ok(Report { rows: vec![1, 2, 3] })
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use dioxus_provider::{hooks::Provider, prelude::*};
+
+#[provider(blocking)]
+fn read_config(name: String) -> Result<String, String> {
+    let thread = std::thread::current();
+    Ok(format!("{name} on {}", thread.name().unwrap_or("unnamed")))
+}
+
+fn main() {
+    let loaded = futures::executor::block_on(read_config().run("app".to_string())).unwrap();
+    assert!(loaded.starts_with("app on "));
+    assert_ne!(loaded, "app on main", "the body runs off the calling thread");
+}
@@ -0,0 +1,25 @@
+use dioxus_provider::prelude::*;
+
+#[provider]
+async fn fetch_inbox() -> Result<Vec<String>, String> {
+    Ok(Vec::new())
+}
+
+#[mutation]
+async fn archive_message(id: u32) -> Result<u32, String> {
+    Ok(id)
+}
+
+fn label(provider: FetchInbox) -> &'static str {
+    match provider {
+        FETCH_INBOX => "inbox",
+    }
+}
+
+const ARCHIVE: ArchiveMessage = ARCHIVE_MESSAGE;
+
+fn main() {
+    assert_eq!(label(fetch_inbox()), "inbox");
+    assert!(FETCH_INBOX == fetch_inbox());
+    assert!(ARCHIVE == archive_message());
+}
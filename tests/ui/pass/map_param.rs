@@ -0,0 +1,24 @@
+use dioxus_provider::{hooks::Provider, prelude::*};
+
+#[provider(map_param = |query: String| query.trim().to_lowercase())]
+async fn search(query: String) -> Result<String, String> {
+    Ok(query)
+}
+
+#[provider(map_param = |(id, page): (u64, u64)| (id as u32, page.max(1) as u32))]
+async fn fetch_page(id: u32, page: u32) -> Result<String, String> {
+    Ok(format!("{id}:{page}"))
+}
+
+fn main() {
+    assert_eq!(
+        search().id(&" Rust ".to_string()),
+        search().id(&"rust".to_string())
+    );
+    let found = futures::executor::block_on(search().run(" Rust ".to_string())).unwrap();
+    assert_eq!(found, "rust");
+
+    assert_eq!(fetch_page().id(&(7, 0)), fetch_page().id(&(7, 1)));
+    let page = futures::executor::block_on(fetch_page().run((7, 0))).unwrap();
+    assert_eq!(page, "7:1");
+}
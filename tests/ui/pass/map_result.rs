@@ -0,0 +1,39 @@
+use dioxus_provider::{hooks::Provider, prelude::*};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Envelope {
+    pub data: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AppError {
+    Backend(String),
+}
+
+#[provider(
+    map_ok = |envelope: Envelope| -> String { envelope.data },
+    map_err = |error: String| -> AppError { AppError::Backend(error) }
+)]
+async fn fetch_name(id: u32) -> Result<Envelope, String> {
+    if id == 0 {
+        return Err("missing".to_string());
+    }
+    Ok(Envelope {
+        data: format!("user {id}"),
+    })
+}
+
+#[provider(map_ok = |name: String| name.to_uppercase())]
+async fn shout(name: String) -> Result<String, String> {
+    Ok(name)
+}
+
+fn main() {
+    let found: Result<String, AppError> = futures::executor::block_on(fetch_name().run(7));
+    assert_eq!(found, Ok("user 7".to_string()));
+    let missing = futures::executor::block_on(fetch_name().run(0));
+    assert_eq!(missing, Err(AppError::Backend("missing".to_string())));
+
+    let loud = futures::executor::block_on(shout().run("hi".to_string()));
+    assert_eq!(loud, Ok("HI".to_string()));
+}
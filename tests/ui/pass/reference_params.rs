@@ -0,0 +1,22 @@
+use dioxus_provider::{hooks::Provider, prelude::*};
+
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub struct Filter {
+    pub unread: bool,
+}
+
+#[provider]
+async fn search(query: &str, tags: &[u32], filter: &Filter) -> Result<String, String> {
+    Ok(format!("{query} {tags:?} {}", filter.unread))
+}
+
+fn main() {
+    let param = (
+        "inbox".to_string(),
+        vec![1, 2],
+        Filter { unread: true },
+    );
+    assert_eq!(search().id(&param), search().id(&param.clone()));
+    let result = futures::executor::block_on(search().run(param)).unwrap();
+    assert_eq!(result, "inbox [1, 2] true");
+}
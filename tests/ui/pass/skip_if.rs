@@ -0,0 +1,21 @@
+use dioxus_provider::{hooks::Provider, prelude::*};
+
+#[provider(skip_if = |query: &String| query.is_empty())]
+async fn search(query: String) -> Result<Vec<String>, String> {
+    Ok(vec![query])
+}
+
+#[provider(skip_if = |query: &String| query.trim().is_empty(), skip_value = vec!["recent".to_string()])]
+async fn suggestions(query: String) -> Result<Vec<String>, String> {
+    Ok(vec![query])
+}
+
+fn main() {
+    assert_eq!(search().skip(&String::new()), Some(Vec::new()));
+    assert_eq!(search().skip(&"dioxus".to_string()), None);
+    assert_eq!(
+        suggestions().skip(&"  ".to_string()),
+        Some(vec!["recent".to_string()])
+    );
+    assert_eq!(suggestions().skip(&"rust".to_string()), None);
+}
@@ -0,0 +1,8 @@
+use dioxus_provider::prelude::*;
+
+#[provider(schedule = "0 61 * * * *")]
+async fn hourly_report() -> Result<String, String> {
+    Ok(String::new())
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use dioxus_provider::prelude::*;
+
+fn is_blank(query: &String) -> bool {
+    query.is_empty()
+}
+
+#[provider(skip_if = is_blank)]
+async fn search(query: String) -> Result<Vec<String>, String> {
+    Ok(vec![query])
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use dioxus_provider::prelude::*;
+
+#[provider(skip_if = |_: &()| true)]
+async fn fetch_feed() -> Result<Vec<String>, String> {
+    Ok(Vec::new())
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use dioxus_provider::prelude::*;
+
+#[provider(skip_value = Vec::new())]
+async fn search(query: String) -> Result<Vec<String>, String> {
+    Ok(vec![query])
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use dioxus_provider::prelude::*;
+
+#[provider]
+async fn fetch_limit() -> Result<u32, String> {
+    100
+}
+
+fn main() {}
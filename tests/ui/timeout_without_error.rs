@@ -0,0 +1,13 @@
+use dioxus_provider::prelude::*;
+
+#[derive(Debug, Clone, PartialEq)]
+enum ReportError {
+    Unavailable,
+}
+
+#[provider(timeout = "10s")]
+async fn fetch_report() -> Result<String, ReportError> {
+    Err(ReportError::Unavailable)
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use dioxus_provider::prelude::*;
+
+#[provider]
+async fn fetch_items() -> Result<Vec<u32>, String> {
+    Ok(vec![1, 2, 3])
+}
+
+#[mutation(
+    invalidates = [fetch_items],
+    optimistic = |items: &mut Vec<u32>, id: &u32| items.retain(|item| item != id)
+)]
+async fn delete_item(id: u32) -> Result<(), String> {
+    let _ = id;
+    Ok(())
+}
+
+fn main() {}
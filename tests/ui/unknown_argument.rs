@@ -0,0 +1,8 @@
+use dioxus_provider::prelude::*;
+
+#[provider(refresh = "5s")]
+async fn fetch_feed() -> Result<Vec<String>, String> {
+    Ok(Vec::new())
+}
+
+fn main() {}